@@ -1,9 +1,149 @@
-use std::time::Duration;
+use std::fmt;
+use std::thread;
+use std::time::{SystemTime, Duration};
 
-/// stores sequence and prev_time for a generator
+use snowcloud_core::display::UtcDisplay;
+use snowcloud_core::traits::{FromIdGenerator, FlakeParts};
+
+use crate::EpochPolicy;
+use crate::error;
+
+/// stores sequence, prev_time, and the last generated id for a generator
 #[derive(Clone)]
-pub struct Counts {
+pub struct Counts<T> {
     pub sequence: u64,
     pub prev_time: Duration,
+    pub last: Option<T>,
+    /// the last tick that ran out of sequence values
+    pub exhausted: Option<u64>,
+}
+
+impl<T> Counts<T> {
+    /// records that the sequence ran out on the given tick. returns true if
+    /// this is the first time it has run out on that tick
+    pub fn exhaust(&mut self, tick: u64) -> bool {
+        if self.exhausted == Some(tick) {
+            false
+        } else {
+            self.exhausted = Some(tick);
+            true
+        }
+    }
+}
+
+/// displays a SystemTime as milliseconds from UNIX_EPOCH along with a UTC
+/// timestamp. `1679587200000 (2023-03-23T16:00:00.000Z)`
+pub struct EpochDisplay<'a>(pub &'a SystemTime);
+
+impl<'a> fmt::Display for EpochDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Ok(dur) = self.0.duration_since(SystemTime::UNIX_EPOCH) else {
+            return write!(f, "{:?}", self.0);
+        };
+
+        write!(f, "{} ({})", dur.as_millis(), UtcDisplay(dur))
+    }
 }
 
+/// name of the given type without the module path
+pub fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let path_end = name.find('<').unwrap_or(name.len());
+
+    match name[..path_end].rfind("::") {
+        Some(index) => &name[(index + 2)..],
+        None => name
+    }
+}
+
+/// sleeps the current thread until the given epoch has been reached
+pub fn wait_for_epoch(ep: &SystemTime) {
+    // the system clock can drift from the clock used by sleep so check again
+    // once awake
+    while let Err(err) = ep.elapsed() {
+        thread::sleep(err.duration());
+    }
+}
+
+/// retrieves the time since the epoch following the given policy
+///
+/// if the time was clamped to zero then the time remaining until the epoch is
+/// also returned
+pub fn elapsed(ep: &SystemTime, policy: EpochPolicy) -> error::Result<(Duration, Option<Duration>)> {
+    match ep.elapsed() {
+        Ok(ts) => Ok((ts, None)),
+        Err(err) => if policy == EpochPolicy::Clamp {
+            Ok((Duration::ZERO, Some(err.duration())))
+        } else {
+            Err(err.into())
+        }
+    }
+}
+
+/// checks that a flake could have been created by a generator with the given
+/// epoch and ids
+pub fn validate<F, T>(ep: &SystemTime, ids: &F::IdSegType, flake: &T) -> Result<(), error::ValidationError>
+where
+    F: FromIdGenerator,
+    F::IdSegType: PartialEq,
+    T: FlakeParts<IdSegType = F::IdSegType>,
+{
+    if flake.id_segs() != *ids {
+        return Err(error::ValidationError::IdSegMismatch);
+    }
+
+    let Ok(ts) = ep.elapsed() else {
+        return Err(error::ValidationError::PreEpoch);
+    };
+
+    if flake.timestamp_ticks() > F::ticks(&ts) {
+        return Err(error::ValidationError::FutureTimestamp);
+    }
+
+    Ok(())
+}
+
+/// flake that uses more bits than are available. the flakes from
+/// snowcloud_flake will not compile with a layout like this so this is used
+/// to check the validation done by the generators
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub struct OverWide<const TOTAL: u32, const MAX: u32>;
+
+#[cfg(test)]
+impl<const TOTAL: u32, const MAX: u32> snowcloud_core::traits::FromIdGenerator for OverWide<TOTAL, MAX> {
+    type IdSegType = i64;
+    type Builder = Self;
+
+    const LAYOUT_BITS: u32 = TOTAL;
+    const MAX_LAYOUT_BITS: u32 = MAX;
+
+    fn valid_id(_: &i64) -> bool {
+        true
+    }
+
+    fn valid_epoch(_: &u64) -> bool {
+        true
+    }
+
+    fn builder(_: &i64) -> Self {
+        OverWide
+    }
+}
+
+#[cfg(test)]
+impl<const TOTAL: u32, const MAX: u32> snowcloud_core::traits::IdBuilder for OverWide<TOTAL, MAX> {
+    type Output = Self;
+
+    fn with_ts(&mut self, _: u64) -> bool {
+        true
+    }
+
+    fn with_seq(&mut self, _: u64) -> bool {
+        true
+    }
+
+    fn build(self) -> Self {
+        self
+    }
+}
@@ -46,6 +46,12 @@ pub enum Error {
     /// a provided epoch is invalid
     EpochInvalid,
 
+    /// the total bits used by the id segments exceed the bits available
+    LayoutInvalid {
+        total_bits: u32,
+        max_bits: u32,
+    },
+
     /// a provided sequence is less than 0 or greater than the max value
     /// specified by a Snowflake
     SequenceInvalid,
@@ -63,7 +69,11 @@ pub enum Error {
     TimestampError,
 
     /// error when attempting to lock a mutex
-    MutexError
+    MutexError,
+
+    /// the file used to share state between processes could not be opened,
+    /// mapped, or locked
+    FileUnavailable(std::io::Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -77,6 +87,9 @@ impl std::fmt::Display for Error {
             Error::EpochInvalid => write!(
                 f, "epoch invalid"
             ),
+            Error::LayoutInvalid { total_bits, max_bits } => write!(
+                f, "layout invalid. {} bits used with {} bits available", total_bits, max_bits
+            ),
             Error::SequenceInvalid => write!(
                 f, "sequence invalid"
             ),
@@ -92,13 +105,19 @@ impl std::fmt::Display for Error {
             Error::MutexError => write!(
                 f, "mutex error"
             ),
+            Error::FileUnavailable(_) => write!(
+                f, "file unavailable"
+            ),
         }
     }
 }
 
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+        match self {
+            Error::FileUnavailable(err) => Some(err),
+            _ => None
+        }
     }
 }
 
@@ -116,3 +135,39 @@ impl traits::NextAvailId for Error {
         }
     }
 }
+
+/// reasons that a snowflake could not have been created by a generator
+///
+/// returned from [`Generator::validate`](crate::Generator::validate) and
+/// [`MutexGenerator::validate`](crate::sync::MutexGenerator::validate)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationError {
+    /// the id segments of the snowflake do not match the generator
+    IdSegMismatch,
+
+    /// the timestamp of the snowflake is ahead of the current time of the
+    /// generator
+    FutureTimestamp,
+
+    /// the current time is before the epoch of the generator so the
+    /// timestamp of the snowflake cannot be checked
+    PreEpoch,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::IdSegMismatch => write!(
+                f, "id seg mismatch"
+            ),
+            ValidationError::FutureTimestamp => write!(
+                f, "future timestamp"
+            ),
+            ValidationError::PreEpoch => write!(
+                f, "pre epoch"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
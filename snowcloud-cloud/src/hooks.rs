@@ -0,0 +1,208 @@
+//! callbacks for observing events from a generator
+//!
+//! hooks can be used to wire counters or logging into id generation without
+//! wrapping every call to next_id.
+//!
+//! ```rust
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use std::time::Duration;
+//!
+//! use snowcloud::cloud::hooks::GeneratorHooks;
+//!
+//! type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+//! type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
+//!
+//! const START_TIME: u64 = 1679587200000;
+//!
+//! #[derive(Default)]
+//! struct Counter {
+//!     generated: AtomicU64,
+//! }
+//!
+//! impl GeneratorHooks<MyFlake> for Counter {
+//!     fn on_generate(&self, _id: &MyFlake) {
+//!         self.generated.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let cloud = MyCloud::new(START_TIME, 1)
+//!     .expect("failed to create MyCloud")
+//!     .with_hooks(Box::new(Counter::default()));
+//!
+//! println!("{:?}", cloud.next_id());
+//! ```
+
+use std::time::Duration;
+
+/// events that can be observed from a generator
+///
+/// every method has an empty default so only the events that are needed
+/// have to be implemented. for the thread safe generators the hooks are
+/// called after any locks have been released.
+pub trait GeneratorHooks<T> {
+    /// called after an id has been successfully generated
+    fn on_generate(&self, _id: &T) {}
+
+    /// called when the max sequence has been reached for the current
+    /// millisecond. provides the estimated duration to the next millisecond
+    ///
+    /// only called for the first caller that runs out on a given tick, any
+    /// other calls that fail on the same tick will not call this again
+    fn on_sequence_exhausted(&self, _dur: Duration) {}
+
+    /// called when the current time is behind the previously recorded time.
+    /// provides the previous time and the current time since the epoch
+    fn on_clock_anomaly(&self, _prev: Duration, _now: Duration) {}
+}
+
+/// hooks that do nothing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopHooks;
+
+impl<T> GeneratorHooks<T> for NoopHooks {}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::thread;
+
+    use snowcloud_flake::i64::SingleIdFlake;
+
+    use super::*;
+    use crate::error;
+
+    const START_TIME: u64 = 1679082337000;
+    const MACHINE_ID: i64 = 1;
+
+    type TestSnowflake = SingleIdFlake<43, 16, 4>;
+
+    #[derive(Default)]
+    struct Counter {
+        generated: AtomicU64,
+        exhausted: AtomicU64,
+    }
+
+    impl GeneratorHooks<TestSnowflake> for Arc<Counter> {
+        fn on_generate(&self, _id: &TestSnowflake) {
+            self.generated.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_sequence_exhausted(&self, _dur: Duration) {
+            self.exhausted.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn generator_hooks() {
+        let counter = Arc::new(Counter::default());
+        let mut cloud = crate::Generator::<TestSnowflake>::new(START_TIME, MACHINE_ID)
+            .unwrap()
+            .with_hooks(Box::new(Arc::clone(&counter)));
+        let total = TestSnowflake::MAX_SEQUENCE as u64 * 3;
+        let mut generated = 0;
+        let mut exhausted_ticks = HashSet::new();
+
+        while generated < total {
+            match cloud.next_id() {
+                Ok(_) => {
+                    generated += 1;
+                },
+                Err(error::Error::SequenceMaxReached(_)) => {
+                    // the sequence ran out on the tick of the last id. keep
+                    // asking without waiting so the same tick fails again
+                    exhausted_ticks.insert(cloud.last_id().unwrap().timestamp());
+                },
+                Err(err) => {
+                    panic!("failed to generate snowflake: {:?}", err);
+                }
+            }
+        }
+
+        assert!(!exhausted_ticks.is_empty());
+        assert_eq!(counter.generated.load(Ordering::Relaxed), generated);
+        assert_eq!(counter.exhausted.load(Ordering::Relaxed), exhausted_ticks.len() as u64);
+    }
+
+    #[test]
+    fn mutex_generator_hooks() {
+        let counter = Arc::new(Counter::default());
+        let cloud = crate::sync::MutexGenerator::<TestSnowflake>::new(START_TIME, MACHINE_ID)
+            .unwrap()
+            .with_hooks(Box::new(Arc::clone(&counter)));
+        let total = TestSnowflake::MAX_SEQUENCE as u64 * 3;
+        let mut generated = 0;
+        let mut exhausted_ticks = HashSet::new();
+
+        while generated < total {
+            match cloud.next_id() {
+                Ok(_) => {
+                    generated += 1;
+                },
+                Err(error::Error::SequenceMaxReached(_)) => {
+                    exhausted_ticks.insert(cloud.last_id().unwrap().timestamp());
+                },
+                Err(err) => {
+                    panic!("failed to generate snowflake: {:?}", err);
+                }
+            }
+        }
+
+        assert!(!exhausted_ticks.is_empty());
+        assert_eq!(counter.generated.load(Ordering::Relaxed), generated);
+        assert_eq!(counter.exhausted.load(Ordering::Relaxed), exhausted_ticks.len() as u64);
+    }
+
+    #[test]
+    fn mutex_generator_hooks_threaded() {
+        let counter = Arc::new(Counter::default());
+        let cloud = crate::sync::MutexGenerator::<TestSnowflake>::new(START_TIME, MACHINE_ID)
+            .unwrap()
+            .with_hooks(Box::new(Arc::clone(&counter)));
+        let total = TestSnowflake::MAX_SEQUENCE as u64 * 50;
+        let mut ticks = HashSet::new();
+        let mut failed = 0;
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let cloud = cloud.clone();
+
+            thread::spawn(move || {
+                let mut generated = 0;
+                let mut ticks = HashSet::new();
+                let mut failed = 0;
+
+                while generated < total {
+                    match cloud.next_id() {
+                        Ok(flake) => {
+                            generated += 1;
+                            ticks.insert(flake.timestamp());
+                        },
+                        Err(error::Error::SequenceMaxReached(_)) => {
+                            failed += 1;
+                        },
+                        Err(err) => {
+                            panic!("failed to generate snowflake: {:?}", err);
+                        }
+                    }
+                }
+
+                (ticks, failed)
+            })
+        }).collect();
+
+        for handle in handles {
+            let (thread_ticks, thread_failed) = handle.join().unwrap();
+
+            ticks.extend(thread_ticks);
+            failed += thread_failed;
+        }
+
+        let exhausted = counter.exhausted.load(Ordering::Relaxed);
+
+        assert!(exhausted > 0);
+        assert!(exhausted <= ticks.len() as u64, "{} exhausted calls for {} ticks", exhausted, ticks.len());
+        assert!(exhausted < failed, "{} exhausted calls for {} failures", exhausted, failed);
+        assert_eq!(counter.generated.load(Ordering::Relaxed), total * 4);
+    }
+}
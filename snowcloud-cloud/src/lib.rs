@@ -1,23 +1,91 @@
+use std::fmt;
+use std::sync::Arc;
 use std::time::{SystemTime, Duration};
 
-use snowcloud_core::traits::{IdGeneratorMut, FromIdGenerator, IdBuilder};
+use snowcloud_core::traits::{IdGeneratorMut, FromIdGenerator, IdBuilder, UnixMillis, FlakeParts, EpochMillis};
 
 pub mod error;
 pub mod wait;
+pub mod hooks;
 mod common;
 pub mod sync;
+mod pool;
+#[cfg(feature = "shared")]
+pub mod shared;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+#[cfg(feature = "stream")]
+pub mod stream;
 
-use common::Counts;
+pub use pool::PoolGenerator;
+
+use common::{Counts, EpochDisplay, short_type_name};
+use hooks::GeneratorHooks;
+
+/// an id along with information about how it was generated
+///
+/// returned from [`Generator::next_id_detailed`] and
+/// [`MutexGenerator::next_id_detailed`](crate::sync::MutexGenerator::next_id_detailed)
+#[derive(Debug, Clone)]
+pub struct Generated<T> {
+    /// the generated id
+    pub id: T,
+
+    /// true if this was the first id generated for the current tick
+    /// (millisecond unless the flake specifies otherwise)
+    pub new_tick: bool,
+
+    /// the sequence number given to the id in the current tick. this is also
+    /// the total number of ids issued for the tick so far
+    pub tick_sequence: u64,
+}
+
+/// how a generator handles an epoch that is ahead of the current time
+///
+/// ```rust
+/// use snowcloud::cloud::EpochPolicy;
+///
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::Generator<MyFlake>;
+///
+/// let now = std::time::SystemTime::now()
+///     .duration_since(std::time::UNIX_EPOCH)
+///     .unwrap();
+/// let next_week = now.as_millis() as u64 + 7 * 24 * 60 * 60 * 1_000;
+///
+/// let mut cloud = MyCloud::new_with_policy(next_week, 1, EpochPolicy::Clamp)
+///     .expect("failed to create MyCloud");
+///
+/// // the timestamp will be 0 until the epoch is reached
+/// println!("{:?}", cloud.next_id());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EpochPolicy {
+    /// the generator cannot be created if the epoch is in the future
+    #[default]
+    Reject,
+
+    /// ids generated before the epoch will have a timestamp of 0. the
+    /// sequence still advances so only the max sequence number of ids can be
+    /// created before the epoch is reached
+    Clamp,
+
+    /// generating an id before the epoch will block the current thread until
+    /// the epoch is reached
+    Wait,
+}
 
 /// simple snowflake generator
 ///
 /// generates a given snowflake with the provided epoch and id value. epoch is
 /// a specified date that can be in the future of
 /// [`UNIX_EPOCH`](std::time::SystemTime::UNIX_EPOCH) but not in the future of
-/// now. the sequence value will always start at 1 when created.
+/// now unless created with
+/// [`new_with_policy`](Generator::new_with_policy). the sequence value
+/// will always start at 1 when created.
 ///
 /// if you want to wait for the next available id without calling the function
-/// again check out [`blocking_next_id_mut`](crate::wait::blocking_next_id_mut)
+/// again check out [`try_next_id_mut`](crate::wait::try_next_id_mut)
 /// or other waiting methods depending on how you want to wait for the next
 /// available id.
 ///
@@ -34,31 +102,72 @@ use common::Counts;
 /// println!("ids: {}", cloud.ids());
 ///
 /// println!("{:?}", cloud.next_id());
+/// println!("{:?}", cloud.last_id());
 /// ```
-#[derive(Clone)]
 pub struct Generator<F>
 where
-    F: FromIdGenerator
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
 {
     ep: SystemTime,
     ids: F::IdSegType,
-    counts: Counts,
+    counts: Counts<<<F as FromIdGenerator>::Builder as IdBuilder>::Output>,
+    policy: EpochPolicy,
+    hooks: Option<Arc<dyn GeneratorHooks<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> + Send + Sync>>,
+}
+
+impl<F> Clone for Generator<F>
+where
+    F: FromIdGenerator,
+    F::IdSegType: Clone,
+    F::Builder: IdBuilder,
+    <<F as FromIdGenerator>::Builder as IdBuilder>::Output: Clone,
+{
+    fn clone(&self) -> Self {
+        Generator {
+            ep: self.ep,
+            ids: self.ids.clone(),
+            counts: self.counts.clone(),
+            policy: self.policy,
+            hooks: self.hooks.clone(),
+        }
+    }
 }
 
 impl<F> Generator<F>
 where
     F: FromIdGenerator,
     F::Builder: IdBuilder,
+    <<F as FromIdGenerator>::Builder as IdBuilder>::Output: Clone,
 {
     /// returns a new Generator
     ///
-    /// will return an error if the primary id is invalid, the timestamp is
+    /// will return an error if the layout of the snowflake uses more bits
+    /// than are available, the primary id is invalid, the timestamp is
     /// invalid, it failes to retrieve the current timestamp, or if the epoch
     /// is ahead of the current timestamp
     pub fn new<I>(epoch: u64, ids: I) -> error::Result<Self>
     where
         I: Into<F::IdSegType>
     {
+        Self::new_with_policy(epoch, ids, EpochPolicy::Reject)
+    }
+
+    /// returns a new Generator with the given policy for future epochs
+    ///
+    /// same as [`new`](Generator::new) but an epoch ahead of the current
+    /// timestamp is only rejected with [`EpochPolicy::Reject`]
+    pub fn new_with_policy<I>(epoch: u64, ids: I, policy: EpochPolicy) -> error::Result<Self>
+    where
+        I: Into<F::IdSegType>
+    {
+        if !F::valid_layout() {
+            return Err(error::Error::LayoutInvalid {
+                total_bits: F::LAYOUT_BITS,
+                max_bits: F::MAX_LAYOUT_BITS,
+            });
+        }
+
         let ids = ids.into();
 
         if !F::valid_id(&ids) {
@@ -72,7 +181,14 @@ where
         let Some(sys_time) = SystemTime::UNIX_EPOCH.checked_add(Duration::from_millis(epoch)) else {
             return Err(error::Error::TimestampError);
         };
-        let prev_time = sys_time.elapsed()?;
+        let prev_time = match sys_time.elapsed() {
+            Ok(ts) => ts,
+            Err(err) => if policy == EpochPolicy::Reject {
+                return Err(err.into());
+            } else {
+                Duration::ZERO
+            }
+        };
 
         Ok(Generator {
             ep: sys_time,
@@ -80,15 +196,83 @@ where
             counts: Counts {
                 sequence: 1,
                 prev_time,
-            }
+                last: None,
+                exhausted: None,
+            },
+            policy,
+            hooks: None,
         })
     }
 
+    /// sets the hooks that will be called when generating ids
+    ///
+    /// see [`GeneratorHooks`](crate::hooks::GeneratorHooks) for the events
+    /// that are available
+    pub fn with_hooks(mut self, hooks: Box<dyn GeneratorHooks<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> + Send + Sync>) -> Self {
+        self.hooks = Some(Arc::from(hooks));
+        self
+    }
+
     /// returns epoch
     pub fn epoch(&self) -> &SystemTime {
         &self.ep
     }
 
+    /// returns the policy used for future epochs
+    pub fn epoch_policy(&self) -> EpochPolicy {
+        self.policy
+    }
+
+    /// returns when the given snowflake was created using the epoch of the
+    /// generator
+    ///
+    /// `None` will be returned if the time cannot be represented
+    pub fn created_at(&self, flake: &<<F as FromIdGenerator>::Builder as IdBuilder>::Output) -> Option<SystemTime>
+    where
+        <<F as FromIdGenerator>::Builder as IdBuilder>::Output: UnixMillis,
+    {
+        let epoch = self.ep.duration_since(SystemTime::UNIX_EPOCH).ok()?;
+
+        flake.created_at(epoch.as_millis() as u64)
+    }
+
+    /// returns how long ago the given snowflake was created using the epoch
+    /// of the generator
+    ///
+    /// `None` will be returned if the snowflake appears to be created in the
+    /// future
+    pub fn age_of(&self, flake: &<<F as FromIdGenerator>::Builder as IdBuilder>::Output) -> Option<Duration>
+    where
+        <<F as FromIdGenerator>::Builder as IdBuilder>::Output: UnixMillis,
+    {
+        SystemTime::now().duration_since(self.created_at(flake)?).ok()
+    }
+
+    /// checks if the given snowflake could have been created by the
+    /// generator
+    ///
+    /// see [`validate`](Generator::validate) for the checks that are performed
+    pub fn owns(&self, flake: &<<F as FromIdGenerator>::Builder as IdBuilder>::Output) -> bool
+    where
+        <<F as FromIdGenerator>::Builder as IdBuilder>::Output: FlakeParts<IdSegType = F::IdSegType>,
+        F::IdSegType: PartialEq,
+    {
+        self.validate(flake).is_ok()
+    }
+
+    /// checks that the id segments of the given snowflake match the
+    /// generator and that its timestamp is not ahead of the current time
+    ///
+    /// the sequence is not checked so a snowflake that matches could still
+    /// be one that was never issued
+    pub fn validate(&self, flake: &<<F as FromIdGenerator>::Builder as IdBuilder>::Output) -> Result<(), error::ValidationError>
+    where
+        <<F as FromIdGenerator>::Builder as IdBuilder>::Output: FlakeParts<IdSegType = F::IdSegType>,
+        F::IdSegType: PartialEq,
+    {
+        common::validate::<F, _>(&self.ep, &self.ids, flake)
+    }
+
     /// returns ids.
     ///
     /// type is determined by the provided snowflake
@@ -96,44 +280,196 @@ where
         &self.ids
     }
 
+    /// returns the last id that was successfully generated
+    ///
+    /// will be None if no id has been generated yet. errors returned from
+    /// next_id will not change this value
+    pub fn last_id(&self) -> Option<&<<F as FromIdGenerator>::Builder as IdBuilder>::Output> {
+        self.counts.last.as_ref()
+    }
+
     /// retrieves the next available id
     ///
     /// if the current timestamp reaches max, the max sequence value is
     /// reached, or if it fails to get the current timestamp this will return
-    /// an error
+    /// an error. if the epoch has not been reached then the
+    /// [`EpochPolicy`] determines the result
     pub fn next_id(&mut self) -> error::Result<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> {
+        self.next_id_detailed().map(|gen| gen.id)
+    }
+
+    /// retrieves the next available id with additional generation details
+    ///
+    /// same as [`next_id`](Generator::next_id) but also indicates if the id
+    /// started a new millisecond and what sequence it was given
+    ///
+    /// ```rust
+    /// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+    /// type MyCloud = snowcloud::Generator<MyFlake>;
+    ///
+    /// const START_TIME: u64 = 1679587200000;
+    ///
+    /// let mut cloud = MyCloud::new(START_TIME, 1)
+    ///     .expect("failed to create MyCloud");
+    /// let generated = cloud.next_id_detailed()
+    ///     .expect("failed to create snowflake");
+    ///
+    /// println!("{} {} {}", generated.id.id(), generated.new_tick, generated.tick_sequence);
+    /// ```
+    pub fn next_id_detailed(&mut self) -> error::Result<Generated<<<F as FromIdGenerator>::Builder as IdBuilder>::Output>> {
         let mut builder = F::builder(&self.ids);
 
-        let ts = self.ep.elapsed()?;
-        let ts_secs = ts.as_secs();
-        let ts_nanos = ts.subsec_nanos();
-        let ts_millis = ts_nanos / 1_000_000;
+        if self.policy == EpochPolicy::Wait {
+            common::wait_for_epoch(&self.ep);
+        }
+
+        let (ts, until_epoch) = common::elapsed(&self.ep, self.policy)?;
+        let ts_tick = F::ticks(&ts);
 
-        if !builder.with_ts(ts_secs * 1_000 + ts_millis as u64) {
+        if !builder.with_ts(ts_tick) {
             return Err(error::Error::TimestampMaxReached);
         }
 
-        let prev_secs = self.counts.prev_time.as_secs();
-        let prev_millis = self.counts.prev_time.subsec_nanos() / 1_000_000;
+        if ts < self.counts.prev_time {
+            if let Some(hooks) = &self.hooks {
+                hooks.on_clock_anomaly(self.counts.prev_time, ts);
+            }
+        }
 
-        if prev_secs == ts_secs && prev_millis == ts_millis {
+        let tick_sequence;
+
+        if F::ticks(&self.counts.prev_time) == ts_tick {
             if !builder.with_seq(self.counts.sequence) {
-                return Err(error::Error::SequenceMaxReached(
-                    Duration::from_nanos((1_000_000 - (ts_nanos % 1_000_000)) as u64)
-                ));
+                // a clamped timestamp will not change until the epoch
+                let dur = until_epoch.unwrap_or_else(|| F::next_tick(&ts));
+
+                if self.counts.exhaust(ts_tick) {
+                    if let Some(hooks) = &self.hooks {
+                        hooks.on_sequence_exhausted(dur);
+                    }
+                }
+
+                return Err(error::Error::SequenceMaxReached(dur));
             }
 
+            tick_sequence = self.counts.sequence;
             self.counts.sequence += 1;
         } else {
             builder.with_seq(1);
 
+            tick_sequence = 1;
             self.counts.prev_time = ts;
             self.counts.sequence = 2;
         }
 
         builder.with_dur(ts);
 
-        Ok(builder.build())
+        let flake = builder.build();
+
+        self.counts.last = Some(flake.clone());
+
+        if let Some(hooks) = &self.hooks {
+            hooks.on_generate(&flake);
+        }
+
+        Ok(Generated {
+            id: flake,
+            new_tick: tick_sequence == 1,
+            tick_sequence,
+        })
+    }
+
+    /// consumes the generator and returns a closure that will call
+    /// [`next_id`](Generator::next_id)
+    ///
+    /// useful for when something expects a factory function instead of a
+    /// generator
+    ///
+    /// ```rust
+    /// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+    /// type MyCloud = snowcloud::Generator<MyFlake>;
+    ///
+    /// const START_TIME: u64 = 1679587200000;
+    ///
+    /// struct Factory {
+    ///     create: Box<dyn FnMut() -> snowcloud::cloud::error::Result<MyFlake> + Send>,
+    /// }
+    ///
+    /// let cloud = MyCloud::new(START_TIME, 1)
+    ///     .expect("failed to create MyCloud");
+    /// let mut factory = Factory {
+    ///     create: Box::new(cloud.into_fn()),
+    /// };
+    ///
+    /// let handle = std::thread::spawn(move || {
+    ///     (factory.create)().expect("failed to create snowflake")
+    /// });
+    ///
+    /// println!("{}", handle.join().unwrap().id());
+    /// ```
+    pub fn into_fn(mut self) -> impl FnMut() -> error::Result<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> {
+        move || self.next_id()
+    }
+
+    /// same as [`into_fn`](Generator::into_fn) but the closure will sleep
+    /// the current thread if the max sequence is reached and try again
+    pub fn into_fn_blocking(mut self) -> impl FnMut() -> error::Result<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> {
+        move || loop {
+            match self.next_id() {
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    std::thread::sleep(dur);
+                },
+                result => {
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+impl<F> fmt::Debug for Generator<F>
+where
+    F: FromIdGenerator,
+    F::IdSegType: fmt::Display,
+    F::Builder: IdBuilder,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Generator")
+            .field("epoch", &format_args!("{}", EpochDisplay(&self.ep)))
+            .field("ids", &format_args!("{}", self.ids))
+            .field("sequence", &self.counts.sequence)
+            .field("prev_time", &self.counts.prev_time)
+            .finish()
+    }
+}
+
+impl<F> fmt::Display for Generator<F>
+where
+    F: FromIdGenerator,
+    F::IdSegType: fmt::Display,
+    F::Builder: IdBuilder,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "epoch={}, ids={}, layout={}",
+            EpochDisplay(&self.ep),
+            self.ids,
+            short_type_name::<F>()
+        )
+    }
+}
+
+impl<F> EpochMillis for Generator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
+{
+    fn epoch_millis(&self) -> u64 {
+        // the epoch is checked to be after UNIX_EPOCH when created
+        self.ep.duration_since(SystemTime::UNIX_EPOCH)
+            .map(|dur| dur.as_millis() as u64)
+            .unwrap_or(0)
     }
 }
 
@@ -141,6 +477,7 @@ impl<F> IdGeneratorMut for Generator<F>
 where
     F: FromIdGenerator,
     F::Builder: IdBuilder,
+    <<F as FromIdGenerator>::Builder as IdBuilder>::Output: Clone,
 {
     type Error = error::Error;
     type Id = <<F as FromIdGenerator>::Builder as IdBuilder>::Output;
@@ -167,7 +504,22 @@ mod test {
     type TestSnowcloud = Generator<TestSnowflake>;
 
     #[test]
-    fn unique_ids() -> () {
+    fn created_at() {
+        let mut cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+
+        let before = SystemTime::now();
+        let flake = cloud.next_id().unwrap();
+        let after = SystemTime::now();
+
+        let created = cloud.created_at(&flake).unwrap();
+
+        // the timestamp is truncated to the millisecond it was created in
+        assert!(created + Duration::from_millis(1) > before, "{:?} is before {:?}", created, before);
+        assert!(created <= after, "{:?} is after {:?}", created, after);
+    }
+
+    #[test]
+    fn unique_ids() {
         let mut cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
         let mut found_dups = false;
         let mut total_found: usize = 0;
@@ -185,20 +537,16 @@ mod test {
             }
         }
 
-        for i in 0..generated.len() {
-            let flake = &generated[i];
+        for (i, flake) in generated.iter().enumerate() {
             let id: i64 = flake.id();
 
             if let Some(dups) = unique_ids.get_mut(&id) {
                 found_dups = true;
                 total_found += 1;
 
-                dups.push((i, flake.clone()));
+                dups.push((i, *flake));
             } else {
-                let mut dups = Vec::with_capacity(1);
-                dups.push((i, flake.clone()));
-
-                unique_ids.insert(id, dups);
+                unique_ids.insert(id, vec![(i, *flake)]);
             }
         }
 
@@ -222,8 +570,6 @@ mod test {
 
             if let Some(dups) = unique_ids.get(&id) {
                 if dups.len() > 1 {
-                    total_found += 1;
-
                     debug_output.write_fmt(format_args!(
                         "flake: {}\n",
                         id,
@@ -248,24 +594,24 @@ mod test {
             }
         }
 
-        debug_output.write(b"\n").unwrap();
+        debug_output.write_all(b"\n").unwrap();
 
-        for index in 0..generated.len() {
+        for (index, flake) in generated.iter().enumerate() {
             let mut is_dup = false;
-            let id = generated[index].id();
+            let id = flake.id();
 
             if let Some(dups) = unique_ids.get(&id) {
                 is_dup = dups.len() > 1;
             }
 
-            let dur = generated[index].duration().unwrap();
+            let dur = flake.duration().unwrap();
 
             debug_output.write_fmt(format_args!(
                 "{:index_width$} {} {} {:seq_width$} | {}.{} {}\n",
                 index,
-                generated[index].timestamp(),
-                generated[index].primary_id(),
-                generated[index].sequence(),
+                flake.timestamp(),
+                flake.primary_id(),
+                flake.sequence(),
                 dur.as_secs(),
                 dur.subsec_nanos(),
                 if is_dup { 'd' } else { ' ' },
@@ -276,4 +622,763 @@ mod test {
 
         panic!("encountered duplidate ids. check Generator_unique_id.debug.txt for details"); 
     }
+
+    #[test]
+    fn last_id() {
+        type SmallSnowflake = SingleIdFlake<43, 16, 4>;
+
+        let mut cloud = Generator::<SmallSnowflake>::new(START_TIME, MACHINE_ID).unwrap();
+        let mut last = None;
+
+        assert!(cloud.last_id().is_none(), "last id present before next_id");
+
+        loop {
+            match cloud.next_id() {
+                Ok(flake) => {
+                    assert_eq!(cloud.last_id(), Some(&flake), "last id does not match generated id");
+
+                    last = Some(flake);
+                },
+                Err(error::Error::SequenceMaxReached(_)) => {
+                    break;
+                },
+                Err(err) => {
+                    panic!("unexpected error: {:?}", err);
+                }
+            }
+        }
+
+        assert!(last.is_some(), "no ids were generated");
+        assert_eq!(cloud.last_id(), last.as_ref(), "last id changed on error");
+    }
+
+    #[test]
+    fn next_id_detailed() {
+        type SmallSnowflake = SingleIdFlake<43, 16, 4>;
+
+        let mut cloud = Generator::<SmallSnowflake>::new(START_TIME, MACHINE_ID).unwrap();
+        let mut generated = Vec::with_capacity(SmallSnowflake::MAX_SEQUENCE as usize * 3);
+        let mut prev_ts = None;
+
+        while generated.len() < generated.capacity() {
+            match cloud.next_id_detailed() {
+                Ok(gen) => {
+                    generated.push(gen);
+                },
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    std::thread::sleep(dur);
+                },
+                Err(err) => {
+                    panic!("unexpected error: {:?}", err);
+                }
+            }
+        }
+
+        for gen in generated {
+            let ts = gen.id.timestamp();
+
+            assert_eq!(gen.new_tick, prev_ts != Some(ts), "new_tick does not match millisecond boundary. {:#?}", gen);
+            assert_eq!(gen.tick_sequence, gen.id.sequence() as u64, "tick_sequence does not match flake sequence. {:#?}", gen);
+
+            prev_ts = Some(ts);
+        }
+    }
+
+    #[test]
+    fn debug_and_display() {
+        // 2023/03/23 16:00:00 UTC
+        let cloud = TestSnowcloud::new(1679587200000, 3).unwrap();
+
+        let debug = format!("{:?}", cloud);
+
+        assert!(debug.contains("ids: (3)"), "debug missing ids: {}", debug);
+        assert!(debug.contains("1679587200000 (2023-03-23T16:00:00.000Z)"), "debug missing epoch: {}", debug);
+
+        let display = cloud.to_string();
+
+        assert_eq!(
+            display,
+            "epoch=1679587200000 (2023-03-23T16:00:00.000Z), ids=(3), layout=SingleIdFlake<43, 8, 12>",
+            "unexpected display output"
+        );
+    }
+
+    #[test]
+    fn layout_validation() {
+        type OverWide = common::OverWide<70, 63>;
+        type OverWideU64 = common::OverWide<65, 64>;
+        type FullU64 = snowcloud_flake::u64::DualIdFlake<44, 4, 4, 12>;
+
+        match Generator::<OverWide>::new(START_TIME, MACHINE_ID) {
+            Err(error::Error::LayoutInvalid { total_bits, max_bits }) => {
+                assert_eq!(total_bits, 70, "invalid total bits");
+                assert_eq!(max_bits, 63, "invalid max bits");
+            },
+            Err(err) => panic!("unexpected error: {:?}", err),
+            Ok(_) => panic!("created generator with an over wide layout"),
+        }
+
+        match Generator::<OverWideU64>::new(START_TIME, MACHINE_ID) {
+            Err(error::Error::LayoutInvalid { total_bits, max_bits }) => {
+                assert_eq!(total_bits, 65, "invalid total bits");
+                assert_eq!(max_bits, 64, "invalid max bits");
+            },
+            Err(err) => panic!("unexpected error: {:?}", err),
+            Ok(_) => panic!("created generator with an over wide layout"),
+        }
+
+        Generator::<FullU64>::new(START_TIME, (1, 1))
+            .expect("failed to create generator with a full layout");
+    }
+
+    fn future_epoch(ahead: u64) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap();
+
+        now.as_millis() as u64 + ahead
+    }
+
+    #[test]
+    fn future_epoch_rejected() {
+        let result = TestSnowcloud::new(future_epoch(300), MACHINE_ID);
+
+        assert!(
+            matches!(result, Err(error::Error::TimestampError)),
+            "created generator with future epoch"
+        );
+    }
+
+    #[test]
+    fn future_epoch_clamp() {
+        let mut cloud = TestSnowcloud::new_with_policy(
+            future_epoch(300),
+            MACHINE_ID,
+            EpochPolicy::Clamp
+        ).unwrap();
+
+        for seq in 1..=3 {
+            let flake = cloud.next_id().expect("failed to generate snowflake");
+
+            assert_eq!(flake.timestamp(), 0, "timestamp was not clamped");
+            assert_eq!(flake.sequence(), seq, "sequence did not advance");
+        }
+
+        let remaining = cloud.epoch()
+            .duration_since(SystemTime::now())
+            .unwrap_or_default();
+        std::thread::sleep(remaining + Duration::from_millis(5));
+
+        let flake = cloud.next_id().expect("failed to generate snowflake");
+
+        assert!(flake.timestamp() > 0, "timestamp still clamped after epoch");
+        assert_eq!(flake.sequence(), 1, "sequence did not reset after epoch");
+    }
+
+    #[test]
+    fn future_epoch_wait() {
+        let mut cloud = TestSnowcloud::new_with_policy(
+            future_epoch(300),
+            MACHINE_ID,
+            EpochPolicy::Wait
+        ).unwrap();
+
+        let flake = cloud.next_id().expect("failed to generate snowflake");
+
+        assert!(cloud.epoch().elapsed().is_ok(), "returned before the epoch");
+        assert_eq!(flake.sequence(), 1);
+    }
+
+    #[test]
+    fn into_fn() {
+        struct Factory {
+            create: Box<dyn FnMut() -> error::Result<TestSnowflake> + Send>,
+        }
+
+        let cloud = Generator::<SingleIdFlake<43, 16, 4>>::new(START_TIME, MACHINE_ID).unwrap();
+        let mut create = cloud.into_fn_blocking();
+
+        let handle = std::thread::spawn(move || {
+            let mut list = Vec::with_capacity(16 * 3);
+
+            while list.len() < list.capacity() {
+                list.push(create().expect("failed to generate snowflake").id());
+            }
+
+            list
+        });
+
+        let list = handle.join().expect("thread paniced");
+        let unique: std::collections::HashSet<i64> = list.iter().copied().collect();
+
+        assert_eq!(unique.len(), list.len(), "duplicate ids generated");
+
+        let mut factory = Factory {
+            create: Box::new(TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap().into_fn()),
+        };
+
+        (factory.create)().expect("failed to generate snowflake");
+    }
+
+    #[test]
+    fn triple_id_segments() {
+        type TripleFlake = snowcloud_flake::i64::TripleIdFlake<43, 4, 4, 4, 8>;
+
+        let mut cloud = Generator::<TripleFlake>::new(START_TIME, (1, 2, 3)).unwrap();
+        let flake = cloud.next_id().expect("failed to generate snowflake");
+
+        assert_eq!(flake.primary_id(), 1);
+        assert_eq!(flake.secondary_id(), 2);
+        assert_eq!(flake.tertiary_id(), 3);
+
+        let and_back: TripleFlake = flake.id().try_into().unwrap();
+
+        assert_eq!(and_back, flake);
+
+        assert!(
+            matches!(Generator::<TripleFlake>::new(START_TIME, (1, 2, 16)), Err(error::Error::IdSegInvalid)),
+            "created generator with an invalid tertiary id"
+        );
+    }
+
+    #[test]
+    fn u64_triple_id_segments() {
+        type TripleFlake = snowcloud_flake::u64::TripleIdFlake<44, 4, 4, 4, 8>;
+
+        let mut cloud = Generator::<TripleFlake>::new(START_TIME, (1u64, 2u64, 3u64)).unwrap();
+        let flake = cloud.next_id().expect("failed to generate snowflake");
+
+        assert_eq!(flake.primary_id(), 1);
+        assert_eq!(flake.secondary_id(), 2);
+        assert_eq!(flake.tertiary_id(), 3);
+
+        let and_back: TripleFlake = flake.id().try_into().unwrap();
+
+        assert_eq!(and_back, flake);
+
+        let cloud = sync::MutexGenerator::<TripleFlake>::new(START_TIME, (1u64, 2u64, 3u64)).unwrap();
+        let flake = cloud.next_id().expect("failed to generate snowflake");
+        let and_back: TripleFlake = flake.id().try_into().unwrap();
+
+        assert_eq!(and_back, flake);
+    }
+
+    #[test]
+    fn u128_unique_ids() {
+        type WideFlake = snowcloud_flake::u128::SingleIdFlake<64, 60, 4>;
+
+        let mut cloud = Generator::<WideFlake>::new(START_TIME, 1u128).unwrap();
+        let total = WideFlake::MAX_SEQUENCE as usize * 4;
+        let mut unique_ids = std::collections::HashSet::with_capacity(total);
+
+        while unique_ids.len() < total {
+            match cloud.next_id() {
+                Ok(flake) => {
+                    assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+                },
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    std::thread::sleep(dur);
+                },
+                Err(err) => {
+                    panic!("failed to generate snowflake: {:?}", err);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn u32_unique_ids() {
+        type SmallFlake = snowcloud_flake::u32::SingleIdFlake<22, 4, 6>;
+
+        // the timestamp only covers a little over an hour so the epoch needs
+        // to be close to now
+        let mut cloud = Generator::<SmallFlake>::new(future_epoch(0) - 1_000, 1u32).unwrap();
+        let total = SmallFlake::MAX_SEQUENCE as usize * 3;
+        let mut unique_ids = std::collections::HashSet::with_capacity(total);
+
+        while unique_ids.len() < total {
+            match cloud.next_id() {
+                Ok(flake) => {
+                    assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+                },
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    std::thread::sleep(dur);
+                },
+                Err(err) => {
+                    panic!("failed to generate snowflake: {:?}", err);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn n_id_segments() {
+        use snowcloud_flake::i64::{NIdFlake, SegmentBits};
+
+        struct ThreeSeg;
+
+        impl SegmentBits<3> for ThreeSeg {
+            const BITS: [u8; 3] = [4, 4, 4];
+        }
+
+        type NFlake = NIdFlake<43, 8, 3, ThreeSeg>;
+
+        let mut cloud = Generator::<NFlake>::new(START_TIME, [1, 2, 3]).unwrap();
+        let flake = cloud.next_id().unwrap();
+
+        assert_eq!(flake.segments(), &[1, 2, 3]);
+        assert_eq!(NFlake::try_from(&flake.id()).unwrap(), flake);
+
+        assert!(matches!(
+            Generator::<NFlake>::new(START_TIME, [1, 16, 3]),
+            Err(error::Error::IdSegInvalid)
+        ));
+    }
+
+    #[test]
+    fn sonyflake_ticks() {
+        use snowcloud_flake::presets::Sonyflake;
+
+        let mut cloud = Generator::<Sonyflake>::new(Sonyflake::EPOCH, 0x0a01u64).unwrap();
+        let first = cloud.next_id().unwrap();
+        let dur = *first.duration().unwrap();
+
+        assert_eq!(first.machine_id(), 0x0a01);
+        assert_eq!(first.timestamp(), dur.as_millis() as u64 / 10);
+
+        let and_back = Sonyflake::try_from(&first.id()).unwrap();
+
+        assert_eq!(and_back, first);
+
+        let dur = loop {
+            match cloud.next_id() {
+                Ok(_) => {},
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    break dur;
+                },
+                Err(err) => {
+                    panic!("failed to generate snowflake: {:?}", err);
+                }
+            }
+        };
+
+        assert!(dur <= Sonyflake::TICK, "estimate is past the next tick: {:?}", dur);
+    }
+
+    #[test]
+    fn uuid7() {
+        use std::collections::HashSet;
+
+        use snowcloud_flake::presets::Uuid7Flake;
+
+        assert!(matches!(Generator::<Uuid7Flake>::new(START_TIME, ()), Err(error::Error::EpochInvalid)));
+
+        let mut cloud = Generator::<Uuid7Flake>::new(0, ()).unwrap();
+        let total = 100_000;
+        let mut seen = HashSet::with_capacity(total);
+        let mut prev: Option<Uuid7Flake> = None;
+
+        while seen.len() < total {
+            let flake = match cloud.next_id() {
+                Ok(flake) => flake,
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    std::thread::sleep(dur);
+                    continue;
+                },
+                Err(err) => {
+                    panic!("failed to generate uuid: {:?}", err);
+                }
+            };
+            let uuid = flake.to_uuid();
+
+            assert_eq!(uuid.get_version_num(), 7);
+            assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+
+            if let Some(prev) = prev {
+                if prev.timestamp() == flake.timestamp() {
+                    assert_eq!(flake.sequence(), prev.sequence() + 1, "sequence skipped within a tick");
+                    assert!(uuid > prev.to_uuid(), "{} is not after {}", uuid, prev.to_uuid());
+                }
+            }
+
+            assert!(seen.insert(uuid), "duplicate uuid {}", uuid);
+
+            prev = Some(flake);
+        }
+    }
+
+    #[test]
+    fn u32_timestamp_max_reached() {
+        type SmallFlake = snowcloud_flake::u32::SingleIdFlake<22, 4, 6>;
+
+        let mut cloud = Generator::<SmallFlake>::new(START_TIME, 1u32).unwrap();
+
+        assert!(
+            matches!(cloud.next_id(), Err(error::Error::TimestampMaxReached)),
+            "generated id past the max timestamp"
+        );
+
+        let cloud = sync::MutexGenerator::<SmallFlake>::new(START_TIME, 1u32).unwrap();
+
+        assert!(
+            matches!(cloud.next_id(), Err(error::Error::TimestampMaxReached)),
+            "generated id past the max timestamp"
+        );
+    }
+
+    #[test]
+    fn seconds_tick() {
+        type SecFlake = snowcloud_flake::i64::SingleIdFlakeSec<32, 10, 21>;
+
+        let mut cloud = Generator::<SecFlake>::new(START_TIME, MACHINE_ID).unwrap();
+        let mut prev: Option<Generated<SecFlake>> = None;
+        let mut crossed_millis = false;
+        let mut crossed_secs = false;
+
+        while !crossed_secs {
+            let gen = cloud.next_id_detailed().expect("failed to generate snowflake");
+            let dur = *gen.id.duration().unwrap();
+
+            if let Some(prev) = prev {
+                let prev_dur = *prev.id.duration().unwrap();
+
+                if prev_dur.as_secs() == dur.as_secs() {
+                    assert!(!gen.new_tick, "new tick within the same second. {:#?}", gen);
+                    assert_eq!(gen.tick_sequence, prev.tick_sequence + 1, "sequence reset within the same second");
+
+                    if prev_dur.as_millis() != dur.as_millis() {
+                        crossed_millis = true;
+                    }
+                } else {
+                    assert!(gen.new_tick, "sequence not reset for a new second. {:#?}", gen);
+                    assert_eq!(gen.tick_sequence, 1, "sequence not reset for a new second");
+
+                    crossed_secs = true;
+                }
+            }
+
+            prev = Some(gen);
+
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        assert!(crossed_millis, "never generated ids across millisecond boundaries");
+    }
+
+    #[test]
+    fn seconds_tick_exhausted() {
+        type SecFlake = snowcloud_flake::i64::SingleIdFlakeSec<32, 27, 4>;
+
+        let mut cloud = Generator::<SecFlake>::new(START_TIME, MACHINE_ID).unwrap();
+
+        let dur = loop {
+            match cloud.next_id() {
+                Ok(_) => {},
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    break dur;
+                },
+                Err(err) => {
+                    panic!("failed to generate snowflake: {:?}", err);
+                }
+            }
+        };
+
+        let last_dur = *cloud.last_id()
+            .expect("no ids generated")
+            .duration()
+            .unwrap();
+
+        assert!(
+            last_dur.subsec_nanos() as u128 + dur.as_nanos() <= 1_000_000_000,
+            "estimate goes past the next second. {:?} {:?}",
+            last_dur,
+            dur
+        );
+
+        std::thread::sleep(dur);
+
+        let next = cloud.next_id_detailed().expect("failed to generate snowflake");
+
+        assert!(next.new_tick, "sequence not reset after waiting. {:#?}", next);
+        assert!(
+            next.id.duration().unwrap().as_secs() > last_dur.as_secs(),
+            "next id is not in the next second"
+        );
+    }
+
+    #[test]
+    fn sort_generated() {
+        let mut cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let mut flakes = Vec::with_capacity(1000);
+
+        while flakes.len() < flakes.capacity() {
+            match cloud.next_id() {
+                Ok(flake) => flakes.push(flake),
+                Err(error::Error::SequenceMaxReached(dur)) => std::thread::sleep(dur),
+                Err(err) => panic!("failed to generate snowflake: {:?}", err),
+            }
+        }
+
+        // deterministic shuffle so a failure can be reproduced
+        let mut state: usize = 17;
+
+        for i in (1..flakes.len()).rev() {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            flakes.swap(i, (state >> 33) % (i + 1));
+        }
+
+        let mut ids: Vec<i64> = flakes.iter().map(|flake| flake.id()).collect();
+
+        flakes.sort();
+        ids.sort();
+
+        assert_eq!(flakes.iter().map(|flake| flake.id()).collect::<Vec<i64>>(), ids);
+    }
+
+    #[test]
+    fn be_bytes_ordered() {
+        let mut cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let mut flakes = Vec::with_capacity(1000);
+
+        // let a few milliseconds pass so the timestamps differ as well
+        while flakes.len() < flakes.capacity() {
+            match cloud.next_id() {
+                Ok(flake) => flakes.push(flake),
+                Err(error::Error::SequenceMaxReached(dur)) => std::thread::sleep(dur),
+                Err(err) => panic!("failed to generate snowflake: {:?}", err),
+            }
+
+            if flakes.len() % 250 == 0 {
+                std::thread::sleep(Duration::from_millis(2));
+            }
+        }
+
+        flakes.sort();
+
+        let mut bytes: Vec<[u8; 8]> = flakes.iter().map(|flake| flake.to_be_bytes()).collect();
+        bytes.sort();
+
+        for (flake, bytes) in flakes.iter().zip(bytes) {
+            assert_eq!(flake.to_be_bytes(), bytes, "byte order differs for {}", flake.id());
+        }
+    }
+
+    #[test]
+    fn nonzero_generated() {
+        let mut cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let flake = cloud.next_id().unwrap();
+
+        let id = flake.id_nonzero().expect("generated id was zero");
+        let back: TestSnowflake = id.try_into().unwrap();
+
+        assert_eq!(back, flake);
+    }
+
+    #[test]
+    fn timestamp_bounds() {
+        let mut cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let mut flakes = Vec::with_capacity(5000);
+
+        while flakes.len() < flakes.capacity() {
+            match cloud.next_id() {
+                Ok(flake) => flakes.push(flake),
+                Err(error::Error::SequenceMaxReached(dur)) => std::thread::sleep(dur),
+                Err(err) => panic!("failed to generate snowflake: {:?}", err),
+            }
+        }
+
+        let tsm = flakes[flakes.len() / 2].timestamp();
+        let first = TestSnowflake::first_for_timestamp(tsm).unwrap();
+        let last = TestSnowflake::last_for_timestamp(tsm).unwrap();
+        let range = first.id()..=last.id();
+
+        for flake in &flakes {
+            assert_eq!(
+                range.contains(&flake.id()),
+                flake.timestamp() == tsm,
+                "{:?} is not properly bounded by {:?}",
+                flake,
+                range
+            );
+        }
+    }
+
+    #[test]
+    fn age_of() {
+        let mut cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let flake = cloud.next_id().unwrap();
+
+        let age = cloud.age_of(&flake).unwrap();
+
+        assert!(age < Duration::from_secs(1), "age is too large: {:?}", age);
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let future = TestSnowflake::from_parts(now - START_TIME as i64 + 60_000, MACHINE_ID, 0).unwrap();
+
+        assert_eq!(cloud.age_of(&future), None);
+    }
+
+    #[test]
+    fn validate() {
+        let mut cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let flake = cloud.next_id().unwrap();
+
+        assert!(cloud.owns(&flake));
+        assert_eq!(cloud.validate(&flake), Ok(()));
+
+        let mut other = TestSnowcloud::new(START_TIME, MACHINE_ID + 1).unwrap();
+        let foreign = other.next_id().unwrap();
+
+        assert!(!cloud.owns(&foreign));
+        assert_eq!(cloud.validate(&foreign), Err(error::ValidationError::IdSegMismatch));
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let future = TestSnowflake::from_parts(now - START_TIME as i64 + 60_000, MACHINE_ID, 1).unwrap();
+
+        assert!(!cloud.owns(&future));
+        assert_eq!(cloud.validate(&future), Err(error::ValidationError::FutureTimestamp));
+
+        let mut clamped = TestSnowcloud::new_with_policy(future_epoch(60_000), MACHINE_ID, EpochPolicy::Clamp).unwrap();
+        let flake = clamped.next_id().unwrap();
+
+        assert_eq!(clamped.validate(&flake), Err(error::ValidationError::PreEpoch));
+    }
+
+    #[test]
+    fn same_tick() {
+        let mut cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+
+        // a tick can pass between the two ids so keep trying until the
+        // sequence shows they were created in the same tick. a new tick
+        // starts the sequence over at 1 so it will not follow the first
+        let (first, second) = loop {
+            let first = cloud.next_id().unwrap();
+            let second = cloud.next_id().unwrap();
+
+            if second.sequence() == first.sequence() + 1 {
+                break (first, second);
+            }
+        };
+
+        assert!(first.same_tick(&second), "{:?} {:?}", first, second);
+
+        type SmallSeqFlake = SingleIdFlake<43, 16, 4>;
+
+        let mut cloud = Generator::<SmallSeqFlake>::new(START_TIME, MACHINE_ID).unwrap();
+        let first = cloud.next_id().unwrap();
+
+        let dur = loop {
+            match cloud.next_id() {
+                Ok(_) => {},
+                Err(error::Error::SequenceMaxReached(dur)) => break dur,
+                Err(err) => panic!("failed to generate snowflake: {:?}", err),
+            }
+        };
+
+        std::thread::sleep(dur);
+
+        let after = cloud.next_id().unwrap();
+
+        assert!(!first.same_tick(&after), "{:?} {:?}", first, after);
+    }
+
+    type MicrosFlake = snowcloud_flake::u64::SingleIdFlakeMicros<52, 4, 8>;
+
+    fn generate_micros(cloud: &mut Generator<MicrosFlake>, amount: usize) -> Vec<MicrosFlake> {
+        let mut generated = Vec::with_capacity(amount);
+
+        // only generate in the loop so the checks do not slow it down
+        while generated.len() < amount {
+            match cloud.next_id() {
+                Ok(flake) => {
+                    generated.push(flake);
+                },
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    assert!(dur <= Duration::from_micros(1), "estimate is past the next microsecond: {:?}", dur);
+                },
+                Err(err) => {
+                    panic!("failed to generate snowflake: {:?}", err);
+                }
+            }
+        }
+
+        generated
+    }
+
+    #[test]
+    fn micros_tick() {
+        let mut cloud = Generator::<MicrosFlake>::new(START_TIME, 1u64).unwrap();
+        let generated = generate_micros(&mut cloud, 10_000);
+        let mut unique_ids = std::collections::HashSet::with_capacity(generated.len());
+
+        for flake in &generated {
+            assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+        }
+    }
+
+    #[test]
+    fn micros_tick_rollover() {
+        // a single id per microsecond so the sequence runs out before the
+        // tick passes even in unoptimized builds
+        type OneSeqFlake = snowcloud_flake::u64::SingleIdFlakeMicros<52, 11, 1>;
+
+        let mut cloud = Generator::<OneSeqFlake>::new(START_TIME, 1u64).unwrap();
+        let mut last = cloud.next_id().unwrap();
+
+        let dur = loop {
+            match cloud.next_id() {
+                Ok(flake) => {
+                    last = flake;
+                },
+                Err(error::Error::SequenceMaxReached(dur)) => break dur,
+                Err(err) => panic!("failed to generate snowflake: {:?}", err),
+            }
+        };
+
+        assert!(dur <= Duration::from_micros(1), "estimate is past the next microsecond: {:?}", dur);
+        assert_eq!(last.sequence(), OneSeqFlake::MAX_SEQUENCE);
+
+        let next = loop {
+            match cloud.next_id() {
+                Ok(flake) => break flake,
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    assert!(dur <= Duration::from_micros(1), "estimate is past the next microsecond: {:?}", dur);
+                },
+                Err(err) => panic!("failed to generate snowflake: {:?}", err),
+            }
+        };
+
+        assert!(next.timestamp() > last.timestamp(), "{:?} {:?}", last, next);
+        assert_eq!(next.sequence(), 1);
+    }
+
+    #[test]
+    fn micros_tick_throughput() {
+        let mut cloud = Generator::<MicrosFlake>::new(START_TIME, 1u64).unwrap();
+        let generated = generate_micros(&mut cloud, 500_000);
+        let mut per_milli: HashMap<u128, usize> = HashMap::new();
+
+        for flake in &generated {
+            *per_milli.entry(flake.duration().unwrap().as_millis()).or_default() += 1;
+        }
+
+        // a 12 bit millisecond sequence would be capped at 4096 ids per
+        // millisecond. unoptimized builds are too slow to reliably call the
+        // generator that often so they only check for more than the 8 bit
+        // sequence would allow with millisecond ticks
+        let expected = if cfg!(debug_assertions) {
+            MicrosFlake::MAX_SEQUENCE as usize
+        } else {
+            4096
+        };
+        let most = per_milli.values().copied().max().unwrap_or(0);
+
+        assert!(most > expected, "did not generate more than {} ids in a millisecond: {}", expected, most);
+    }
 }
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use snowcloud_core::traits::{IdGenerator, FromIdGenerator, IdBuilder};
+
+use crate::error;
+use crate::sync::MutexGenerator;
+
+/// thread safe generator that balances across multiple id segments
+///
+/// holds a [`MutexGenerator`](crate::sync::MutexGenerator) for each of the
+/// provided ids and will round-robin between them when generating a new id.
+/// if a member has reached its max sequence for the current millisecond then
+/// the next member will be tried before returning an error. since each member
+/// has a different id the generated snowflakes will remain unique while
+/// multiplying the total number of ids available per millisecond.
+///
+/// ```rust
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::cloud::PoolGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MyCloud::new(START_TIME, vec![1, 2, 3, 4])
+///     .expect("failed to create MyCloud");
+///
+/// println!("{:?}", cloud.next_id());
+/// ```
+pub struct PoolGenerator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
+{
+    members: Arc<[MutexGenerator<F>]>,
+    next: Arc<AtomicUsize>,
+}
+
+impl<F> Clone for PoolGenerator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
+{
+    fn clone(&self) -> Self {
+        PoolGenerator {
+            members: Arc::clone(&self.members),
+            next: Arc::clone(&self.next),
+        }
+    }
+}
+
+impl<F> PoolGenerator<F>
+where
+    F: FromIdGenerator,
+    F::IdSegType: PartialEq,
+    F::Builder: IdBuilder,
+    <<F as FromIdGenerator>::Builder as IdBuilder>::Output: Clone,
+{
+    /// returns a new PoolGenerator
+    ///
+    /// will return an error if no ids are provided, any of the ids are
+    /// invalid or duplicated, or for the same reasons as
+    /// [`MutexGenerator::new`](crate::sync::MutexGenerator::new)
+    pub fn new<I>(epoch: u64, ids: Vec<I>) -> error::Result<Self>
+    where
+        I: Into<F::IdSegType>
+    {
+        if ids.is_empty() {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        let mut members: Vec<MutexGenerator<F>> = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            let gen = MutexGenerator::new(epoch, id)?;
+
+            if members.iter().any(|member| member.ids() == gen.ids()) {
+                return Err(error::Error::IdSegInvalid);
+            }
+
+            members.push(gen);
+        }
+
+        Ok(PoolGenerator {
+            members: members.into(),
+            next: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+}
+
+impl<F> PoolGenerator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
+    <<F as FromIdGenerator>::Builder as IdBuilder>::Output: Clone,
+{
+    /// returns the generators that make up the pool
+    pub fn members(&self) -> &[MutexGenerator<F>] {
+        &self.members
+    }
+
+    /// retrieves the next available id from the pool
+    ///
+    /// starts with the next member in the pool and will move on to the
+    /// following members if the max sequence has been reached. if every
+    /// member has reached the max sequence then the shortest duration to the
+    /// next millisecond is returned. any other error is returned immediately.
+    pub fn next_id(&self) -> error::Result<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> {
+        let len = self.members.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % len;
+        let mut shortest: Option<Duration> = None;
+
+        for offset in 0..len {
+            match self.members[(start + offset) % len].next_id() {
+                Ok(flake) => {
+                    return Ok(flake);
+                },
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    shortest = Some(match shortest {
+                        Some(prev) => prev.min(dur),
+                        None => dur
+                    });
+                },
+                Err(err) => {
+                    return Err(err);
+                }
+            }
+        }
+
+        Err(error::Error::SequenceMaxReached(shortest.unwrap_or_default()))
+    }
+}
+
+impl<F> IdGenerator for PoolGenerator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
+    <<F as FromIdGenerator>::Builder as IdBuilder>::Output: Clone,
+{
+    type Error = error::Error;
+    type Id = <<F as FromIdGenerator>::Builder as IdBuilder>::Output;
+    type Output = Result<Self::Id, Self::Error>;
+
+    fn next_id(&self) -> Self::Output {
+        PoolGenerator::next_id(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{HashMap, HashSet};
+
+    use snowcloud_flake::i64::SingleIdFlake;
+
+    use super::*;
+
+    const START_TIME: u64 = 1679082337000;
+
+    type TestSnowflake = SingleIdFlake<43, 16, 4>;
+    type TestSnowcloud = PoolGenerator<TestSnowflake>;
+
+    #[test]
+    fn spread_across_members() {
+        let pids = vec![1, 2, 3, 4];
+        let cloud = TestSnowcloud::new(START_TIME, pids.clone()).unwrap();
+        let mut unique_ids = HashSet::new();
+        let mut per_pid: HashMap<i64, usize> = HashMap::new();
+
+        // more than a single member can provide in a millisecond
+        for _ in 0..(TestSnowflake::MAX_SEQUENCE * 3) {
+            let flake = cloud.next_id().expect("failed to generate snowflake");
+
+            assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+
+            *per_pid.entry(flake.primary_id()).or_default() += 1;
+        }
+
+        for pid in pids {
+            assert!(per_pid.contains_key(&pid), "no ids generated for pid {}", pid);
+        }
+    }
+
+    #[test]
+    fn invalid_members() {
+        assert!(TestSnowcloud::new(START_TIME, Vec::<i64>::new()).is_err(), "created pool without members");
+        assert!(TestSnowcloud::new(START_TIME, vec![1, 2, 1]).is_err(), "created pool with duplicate members");
+    }
+}
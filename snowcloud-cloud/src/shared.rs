@@ -0,0 +1,310 @@
+//! generators that share state between processes
+//!
+//! requires the `shared` feature
+
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, Duration};
+
+use fs4::FileExt;
+use memmap2::MmapMut;
+use snowcloud_core::traits::{IdGenerator, FromIdGenerator, IdBuilder, EpochMillis};
+
+use crate::error;
+
+/// byte range of the previous time seconds in the shared file
+const PREV_SECS: std::ops::Range<usize> = 0..8;
+/// byte range of the previous time nanoseconds in the shared file
+const PREV_NANOS: std::ops::Range<usize> = 8..12;
+/// byte range of the sequence in the shared file
+const SEQUENCE: std::ops::Range<usize> = 16..24;
+/// total size of the shared file
+const FILE_LEN: u64 = 24;
+
+/// snowflake generator that shares its counts through a file
+///
+/// the sequence and previous time are stored in a small memory mapped file
+/// that is protected by an advisory file lock (`flock` on unix and
+/// `LockFileEx` on windows). every generator that opens the same file, in the
+/// same process or a different one, will see the same counts so duplicate
+/// ids cannot be created as long as they all use the same epoch and ids.
+///
+/// the api mirrors [`MutexGenerator`](crate::sync::MutexGenerator) with the
+/// addition of the file path. if the file cannot be opened, mapped, or locked
+/// then [`FileUnavailable`](crate::error::Error::FileUnavailable) is returned.
+///
+/// ```rust
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::cloud::shared::FileBackedGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let path = std::env::temp_dir().join("snowcloud_doc_example.counts");
+/// let cloud = MyCloud::new(&path, START_TIME, 1)
+///     .expect("failed to create MyCloud");
+///
+/// println!("{:?}", cloud.next_id());
+/// # std::fs::remove_file(&path).ok();
+/// ```
+pub struct FileBackedGenerator<F>
+where
+    F: FromIdGenerator,
+{
+    ep: SystemTime,
+    ids: F::IdSegType,
+    file: File,
+    map: Mutex<MmapMut>,
+}
+
+impl<F> FileBackedGenerator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
+{
+    /// returns a new FileBackedGenerator
+    ///
+    /// the file will be created if it does not exist. will return an error
+    /// for the same reasons as
+    /// [`MutexGenerator::new`](crate::sync::MutexGenerator::new) or if the
+    /// file cannot be opened, locked, or mapped
+    pub fn new<P, I>(path: P, epoch: u64, ids: I) -> error::Result<Self>
+    where
+        P: AsRef<Path>,
+        I: Into<F::IdSegType>
+    {
+        if !F::valid_layout() {
+            return Err(error::Error::LayoutInvalid {
+                total_bits: F::LAYOUT_BITS,
+                max_bits: F::MAX_LAYOUT_BITS,
+            });
+        }
+
+        let ids = ids.into();
+
+        if !F::valid_id(&ids) {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        if !F::valid_epoch(&epoch) {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        let Some(sys_time) = SystemTime::UNIX_EPOCH.checked_add(Duration::from_millis(epoch)) else {
+            return Err(error::Error::TimestampError);
+        };
+        sys_time.elapsed()?;
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map_err(error::Error::FileUnavailable)?;
+
+        // another process could be creating the file at the same time so the
+        // length has to be checked and set while holding the lock
+        FileExt::lock(&file).map_err(error::Error::FileUnavailable)?;
+
+        let resized = file.metadata().and_then(|meta| {
+            if meta.len() < FILE_LEN {
+                file.set_len(FILE_LEN)
+            } else {
+                Ok(())
+            }
+        });
+
+        FileExt::unlock(&file).map_err(error::Error::FileUnavailable)?;
+        resized.map_err(error::Error::FileUnavailable)?;
+
+        // SAFETY: the file is only modified while the advisory lock is held
+        // and its length is never reduced by a generator
+        let map = unsafe { MmapMut::map_mut(&file) }
+            .map_err(error::Error::FileUnavailable)?;
+
+        Ok(FileBackedGenerator {
+            ep: sys_time,
+            ids,
+            file,
+            map: Mutex::new(map),
+        })
+    }
+
+    /// returns epoch
+    pub fn epoch(&self) -> &SystemTime {
+        &self.ep
+    }
+
+    /// returns ids
+    ///
+    /// type is determined by the provided snowflake
+    pub fn ids(&self) -> &F::IdSegType {
+        &self.ids
+    }
+
+    /// retrieves the next available id
+    ///
+    /// if the current timestamp reaches max, the max sequence value is
+    /// reached, it fails to get the current timestamp, or it fails to lock
+    /// the shared file this will return an error.
+    pub fn next_id(&self) -> error::Result<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> {
+        let mut builder = F::builder(&self.ids);
+
+        // the file lock is held by the open file and not the thread so the
+        // mutex is needed to keep threads using this generator in line
+        let Ok(mut map) = self.map.lock() else {
+            return Err(error::Error::MutexError);
+        };
+
+        FileExt::lock(&self.file).map_err(error::Error::FileUnavailable)?;
+
+        let result = self.update_counts(&mut map, &mut builder);
+        let unlocked = FileExt::unlock(&self.file);
+
+        let ts = result?;
+        unlocked.map_err(error::Error::FileUnavailable)?;
+
+        builder.with_dur(ts);
+
+        Ok(builder.build())
+    }
+
+    /// performs the same logic as a MutexGenerator but reads and writes the
+    /// counts from the mapped file
+    fn update_counts(&self, map: &mut MmapMut, builder: &mut F::Builder) -> error::Result<Duration> {
+        let ts = self.ep.elapsed()?;
+        let ts_tick = F::ticks(&ts);
+
+        if !builder.with_ts(ts_tick) {
+            return Err(error::Error::TimestampMaxReached);
+        }
+
+        let prev_time = Duration::new(read_u64(&map[PREV_SECS]), read_u32(&map[PREV_NANOS]));
+        let sequence = read_u64(&map[SEQUENCE]).max(1);
+
+        if F::ticks(&prev_time) == ts_tick {
+            if !builder.with_seq(sequence) {
+                return Err(error::Error::SequenceMaxReached(F::next_tick(&ts)));
+            }
+
+            map[SEQUENCE].copy_from_slice(&(sequence + 1).to_le_bytes());
+        } else {
+            builder.with_seq(1);
+
+            map[PREV_SECS].copy_from_slice(&ts.as_secs().to_le_bytes());
+            map[PREV_NANOS].copy_from_slice(&ts.subsec_nanos().to_le_bytes());
+            map[SEQUENCE].copy_from_slice(&2u64.to_le_bytes());
+        }
+
+        Ok(ts)
+    }
+}
+
+impl<F> EpochMillis for FileBackedGenerator<F>
+where
+    F: FromIdGenerator,
+{
+    fn epoch_millis(&self) -> u64 {
+        // the epoch is checked to be after UNIX_EPOCH when created
+        self.ep.duration_since(SystemTime::UNIX_EPOCH)
+            .map(|dur| dur.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+impl<F> IdGenerator for FileBackedGenerator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder
+{
+    type Error = error::Error;
+    type Id = <<F as FromIdGenerator>::Builder as IdBuilder>::Output;
+    type Output = Result<Self::Id, Self::Error>;
+
+    fn next_id(&self) -> Self::Output {
+        FileBackedGenerator::next_id(self)
+    }
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    let mut array = [0u8; 8];
+    array.copy_from_slice(bytes);
+
+    u64::from_le_bytes(array)
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    let mut array = [0u8; 4];
+    array.copy_from_slice(bytes);
+
+    u32::from_le_bytes(array)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    use snowcloud_flake::i64::SingleIdFlake;
+
+    use super::*;
+
+    const START_TIME: u64 = 1679082337000;
+    const MACHINE_ID: i64 = 1;
+
+    type TestSnowflake = SingleIdFlake<43, 16, 4>;
+    type TestSnowcloud = FileBackedGenerator<TestSnowflake>;
+
+    #[test]
+    fn unique_ids_across_files() {
+        let path = std::env::temp_dir().join(format!(
+            "snowcloud_unique_ids_across_files_{}.counts",
+            std::process::id()
+        ));
+        let barrier = Arc::new(Barrier::new(3));
+        let mut handles = Vec::with_capacity(3);
+
+        for _ in 0..handles.capacity() {
+            let b = Arc::clone(&barrier);
+            let p = path.clone();
+
+            handles.push(thread::spawn(move || {
+                // each thread opens the file separately to act like another
+                // process would
+                let cloud = TestSnowcloud::new(&p, START_TIME, MACHINE_ID)
+                    .expect("failed to create generator");
+                let mut id_list = Vec::with_capacity(TestSnowflake::MAX_SEQUENCE as usize * 3);
+
+                b.wait();
+
+                while id_list.len() < id_list.capacity() {
+                    match cloud.next_id() {
+                        Ok(flake) => {
+                            id_list.push(flake);
+                        },
+                        Err(error::Error::SequenceMaxReached(dur)) => {
+                            thread::sleep(dur);
+                        },
+                        Err(err) => {
+                            panic!("failed to generate snowflake: {:?}", err);
+                        }
+                    }
+                }
+
+                id_list
+            }));
+        }
+
+        let mut unique_ids = HashSet::new();
+
+        for handle in handles {
+            for flake in handle.join().expect("thread paniced") {
+                assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+            }
+        }
+
+        std::fs::remove_file(&path).expect("failed to remove counts file");
+    }
+}
@@ -0,0 +1,288 @@
+//! stream adapter for generators
+//!
+//! requires the `stream` feature
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::{Stream, FusedStream};
+use snowcloud_core::traits::{NextAvailId, IdGenerator, IdGeneratorAsync};
+
+/// stream of ids from a generator
+///
+/// when the generator returns an error with a next available id the stream
+/// will wait using the provided sleeper before trying again. like
+/// [`async_next_id`](crate::wait::async_next_id) the sleeper allows for any
+/// async runtime to be used. any other error is yielded and the stream will
+/// end after it.
+///
+/// ```rust
+/// use futures::StreamExt;
+/// use snowcloud::cloud::stream::IntoIdStream;
+///
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+///
+/// futures::executor::block_on(async {
+///     let mut stream = cloud.into_stream(|dur| async move { std::thread::sleep(dur) })
+///         .take(10);
+///
+///     while let Some(result) = stream.next().await {
+///         println!("{}", result.expect("failed to create snowflake").id());
+///     }
+/// });
+/// ```
+pub struct IdStream<G, S, Fut> {
+    gen: G,
+    sleeper: S,
+    sleep: Option<Pin<Box<Fut>>>,
+    done: bool,
+}
+
+// the generator and sleeper are never pinned and the sleep future is boxed so
+// the stream can always be moved
+impl<G, S, Fut> Unpin for IdStream<G, S, Fut> {}
+
+impl<G, S, Fut> IdStream<G, S, Fut>
+where
+    G: IdGenerator,
+    G::Error: NextAvailId,
+    G::Output: Into<std::result::Result<G::Id, G::Error>>,
+    S: FnMut(Duration) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    /// returns a new IdStream
+    pub fn new(gen: G, sleeper: S) -> Self {
+        IdStream {
+            gen,
+            sleeper,
+            sleep: None,
+            done: false,
+        }
+    }
+
+    /// returns the generator
+    pub fn get_ref(&self) -> &G {
+        &self.gen
+    }
+
+    /// consumes the stream and returns the generator
+    pub fn into_inner(self) -> G {
+        self.gen
+    }
+}
+
+impl<G, S, Fut> Stream for IdStream<G, S, Fut>
+where
+    G: IdGenerator,
+    G::Error: NextAvailId,
+    G::Output: Into<std::result::Result<G::Id, G::Error>>,
+    S: FnMut(Duration) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Item = std::result::Result<G::Id, G::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            if let Some(sleep) = this.sleep.as_mut() {
+                if sleep.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+
+                this.sleep = None;
+            }
+
+            match this.gen.next_id().into() {
+                Ok(id) => {
+                    return Poll::Ready(Some(Ok(id)));
+                },
+                Err(err) => {
+                    let Some(dur) = err.next_avail_id() else {
+                        this.done = true;
+
+                        return Poll::Ready(Some(Err(err)));
+                    };
+
+                    this.sleep = Some(Box::pin((this.sleeper)(*dur)));
+                }
+            }
+        }
+    }
+}
+
+impl<G, S, Fut> FusedStream for IdStream<G, S, Fut>
+where
+    G: IdGenerator,
+    G::Error: NextAvailId,
+    G::Output: Into<std::result::Result<G::Id, G::Error>>,
+    S: FnMut(Duration) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+/// waits on the generator with the sleeper without going through the stream
+///
+/// only available when the sleeper does not need to be mutable
+impl<G, S, Fut> IdGeneratorAsync for IdStream<G, S, Fut>
+where
+    G: IdGenerator + Sync,
+    G::Error: NextAvailId + Send,
+    G::Id: Send,
+    G::Output: Into<std::result::Result<G::Id, G::Error>>,
+    S: Fn(Duration) -> Fut + Sync,
+    Fut: Future<Output = ()> + Send,
+{
+    type Error = G::Error;
+    type Id = G::Id;
+    type Future<'a> = Pin<Box<dyn Future<Output = std::result::Result<G::Id, G::Error>> + Send + 'a>> where Self: 'a;
+
+    fn next_id(&self) -> Self::Future<'_> {
+        // only borrow what is needed so the stream itself does not have to
+        // be Sync
+        let gen = &self.gen;
+        let sleeper = &self.sleeper;
+
+        Box::pin(async move {
+            loop {
+                let dur = match gen.next_id().into() {
+                    Ok(id) => {
+                        return Ok(id);
+                    },
+                    Err(err) => {
+                        let Some(dur) = err.next_avail_id() else {
+                            return Err(err);
+                        };
+
+                        *dur
+                    }
+                };
+
+                sleeper(dur).await;
+            }
+        })
+    }
+}
+
+/// converts a generator into an [`IdStream`]
+///
+/// implemented for any [`IdGenerator`] that can be used with an IdStream
+pub trait IntoIdStream: IdGenerator + Sized {
+    /// creates an IdStream with the given sleeper
+    fn into_stream<S, Fut>(self, sleeper: S) -> IdStream<Self, S, Fut>
+    where
+        S: FnMut(Duration) -> Fut,
+        Fut: Future<Output = ()>;
+}
+
+impl<G> IntoIdStream for G
+where
+    G: IdGenerator,
+    G::Error: NextAvailId,
+    G::Output: Into<std::result::Result<G::Id, G::Error>>,
+{
+    fn into_stream<S, Fut>(self, sleeper: S) -> IdStream<Self, S, Fut>
+    where
+        S: FnMut(Duration) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        IdStream::new(self, sleeper)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use futures::StreamExt;
+    use snowcloud_flake::i64::SingleIdFlake;
+
+    use super::*;
+    use crate::error;
+    use crate::sync::MutexGenerator;
+
+    const START_TIME: u64 = 1679082337000;
+    const MACHINE_ID: i64 = 1;
+
+    type TestSnowflake = SingleIdFlake<43, 16, 4>;
+    type TestSnowcloud = MutexGenerator<TestSnowflake>;
+
+    async fn sleeper(dur: Duration) {
+        std::thread::sleep(dur);
+    }
+
+    /// generator that always fails without a next available id
+    struct Failing;
+
+    impl IdGenerator for Failing {
+        type Error = error::Error;
+        type Id = TestSnowflake;
+        type Output = error::Result<TestSnowflake>;
+
+        fn next_id(&self) -> Self::Output {
+            Err(error::Error::MutexError)
+        }
+    }
+
+    #[test]
+    fn unique_ids() {
+        let cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let total = TestSnowflake::MAX_SEQUENCE as usize * 3;
+
+        let list: Vec<_> = futures::executor::block_on(
+            cloud.into_stream(sleeper).take(total).collect()
+        );
+        let mut unique_ids = HashSet::new();
+
+        for result in list {
+            let flake = result.expect("failed to generate snowflake");
+
+            assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+        }
+
+        assert_eq!(unique_ids.len(), total);
+    }
+
+    #[test]
+    fn fused_after_error() {
+        let mut stream = IdStream::new(Failing, sleeper);
+
+        futures::executor::block_on(async {
+            assert!(matches!(stream.next().await, Some(Err(error::Error::MutexError))));
+            assert!(stream.is_terminated(), "stream not terminated after error");
+            assert!(stream.next().await.is_none(), "stream yielded after error");
+        });
+    }
+
+    #[test]
+    fn async_next_id() {
+        let stream = TestSnowcloud::new(START_TIME, MACHINE_ID)
+            .unwrap()
+            .into_stream(sleeper);
+        let mut unique_ids = HashSet::new();
+
+        futures::executor::block_on(async {
+            for _ in 0..(TestSnowflake::MAX_SEQUENCE * 2) {
+                let flake = IdGeneratorAsync::next_id(&stream).await
+                    .expect("failed to generate snowflake");
+
+                assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+            }
+        });
+    }
+}
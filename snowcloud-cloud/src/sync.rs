@@ -1,24 +1,30 @@
-use std::sync::{Arc, Mutex};
+use std::fmt;
+use std::sync::{Arc, Mutex, TryLockError};
+use std::thread;
 use std::time::{SystemTime, Duration};
 
-use snowcloud_core::traits::{IdGenerator, FromIdGenerator, IdBuilder};
+use snowcloud_core::traits::{IdGenerator, FromIdGenerator, IdBuilder, UnixMillis, FlakeParts, EpochMillis};
 
 use crate::error;
-use crate::common::Counts;
+use crate::{Generated, EpochPolicy};
+use crate::common::{self, Counts, EpochDisplay, short_type_name};
+use crate::hooks::GeneratorHooks;
 
 /// thread safe snowflake generator
 ///
 /// generates a given snowflake with the provided epoch and id value. epoch is
 /// a specified date that can be in the future of
 /// [`UNIX_EPOCH`](std::time::SystemTime::UNIX_EPOCH) but not in the future of
-/// now. the sequence value will always start at 1 when created.
+/// now unless created with
+/// [`new_with_policy`](MutexGenerator::new_with_policy). the sequence value
+/// will always start at 1 when created.
 ///
 /// this guards the previous time and sequence count behind an
 /// [`Arc`](std::sync::Arc) [`Mutex`](std::sync::Mutex). the critical section
 /// is small and will not block if its unable to get a valid snowflake.
 ///
 /// if you want to wait for the next available id without calling the function
-/// again check out [`blocking_next_id`](crate::wait::blocking_next_id) or
+/// again check out [`try_next_id`](crate::wait::try_next_id) or
 /// other waiting methods depending on how you want to wait for the next 
 /// available id.
 ///
@@ -35,26 +41,33 @@ use crate::common::Counts;
 /// println!("ids: {}", cloud.ids());
 ///
 /// println!("{:?}", cloud.next_id());
+/// println!("{:?}", cloud.last_id());
 /// ```
 pub struct MutexGenerator<F>
 where
-    F: FromIdGenerator
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
 {
     ep: SystemTime,
     ids: F::IdSegType,
-    counts: Arc<Mutex<Counts>>,
+    counts: Arc<Mutex<Counts<<<F as FromIdGenerator>::Builder as IdBuilder>::Output>>>,
+    policy: EpochPolicy,
+    hooks: Option<Arc<dyn GeneratorHooks<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> + Send + Sync>>,
 }
 
 impl<F> Clone for MutexGenerator<F>
 where
     F: FromIdGenerator,
-    F::IdSegType: Clone
+    F::IdSegType: Clone,
+    F::Builder: IdBuilder,
 {
     fn clone(&self) -> Self {
         MutexGenerator {
             ep: self.ep,
             ids: self.ids.clone(),
             counts: Arc::clone(&self.counts),
+            policy: self.policy,
+            hooks: self.hooks.clone(),
         }
     }
 }
@@ -63,16 +76,37 @@ impl<F> MutexGenerator<F>
 where
     F: FromIdGenerator,
     F::Builder: IdBuilder,
+    <<F as FromIdGenerator>::Builder as IdBuilder>::Output: Clone,
 {
     /// returns a new MutexGenerator
     ///
-    /// will return an error if ids is invalid, the timestamp is invalid, it 
+    /// will return an error if the layout of the snowflake uses more bits
+    /// than are available, ids is invalid, the timestamp is invalid, it 
     /// fails to retrieve the current timestamp, or if the epoch is ahead of 
     /// the current timestamp
     pub fn new<I>(epoch: u64, ids: I) -> error::Result<Self>
     where
         I: Into<F::IdSegType>
     {
+        Self::new_with_policy(epoch, ids, EpochPolicy::Reject)
+    }
+
+    /// returns a new MutexGenerator with the given policy for future epochs
+    ///
+    /// same as [`new`](MutexGenerator::new) but an epoch ahead of the
+    /// current timestamp is only rejected with
+    /// [`EpochPolicy::Reject`](crate::EpochPolicy::Reject)
+    pub fn new_with_policy<I>(epoch: u64, ids: I, policy: EpochPolicy) -> error::Result<Self>
+    where
+        I: Into<F::IdSegType>
+    {
+        if !F::valid_layout() {
+            return Err(error::Error::LayoutInvalid {
+                total_bits: F::LAYOUT_BITS,
+                max_bits: F::MAX_LAYOUT_BITS,
+            });
+        }
+
         let ids = ids.into();
 
         if !F::valid_id(&ids) {
@@ -86,7 +120,14 @@ where
         let Some(sys_time) = SystemTime::UNIX_EPOCH.checked_add(Duration::from_millis(epoch)) else {
             return Err(error::Error::TimestampError);
         };
-        let prev_time = sys_time.elapsed()?;
+        let prev_time = match sys_time.elapsed() {
+            Ok(ts) => ts,
+            Err(err) => if policy == EpochPolicy::Reject {
+                return Err(err.into());
+            } else {
+                Duration::ZERO
+            }
+        };
 
         Ok(MutexGenerator {
             ep: sys_time,
@@ -94,15 +135,85 @@ where
             counts: Arc::new(Mutex::new(Counts {
                 sequence: 1,
                 prev_time,
-            }))
+                last: None,
+                exhausted: None,
+            })),
+            policy,
+            hooks: None,
         })
     }
 
+    /// sets the hooks that will be called when generating ids
+    ///
+    /// hooks are shared with any clones made after this is called and are
+    /// only invoked once the lock on the counts has been released. see
+    /// [`GeneratorHooks`](crate::hooks::GeneratorHooks) for the events that
+    /// are available
+    pub fn with_hooks(mut self, hooks: Box<dyn GeneratorHooks<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> + Send + Sync>) -> Self {
+        self.hooks = Some(Arc::from(hooks));
+        self
+    }
+
     /// returns epoch
     pub fn epoch(&self) -> &SystemTime {
         &self.ep
     }
 
+    /// returns the policy used for future epochs
+    pub fn epoch_policy(&self) -> EpochPolicy {
+        self.policy
+    }
+
+    /// returns when the given snowflake was created using the epoch of the
+    /// generator
+    ///
+    /// `None` will be returned if the time cannot be represented
+    pub fn created_at(&self, flake: &<<F as FromIdGenerator>::Builder as IdBuilder>::Output) -> Option<SystemTime>
+    where
+        <<F as FromIdGenerator>::Builder as IdBuilder>::Output: UnixMillis,
+    {
+        let epoch = self.ep.duration_since(SystemTime::UNIX_EPOCH).ok()?;
+
+        flake.created_at(epoch.as_millis() as u64)
+    }
+
+    /// returns how long ago the given snowflake was created using the epoch
+    /// of the generator
+    ///
+    /// `None` will be returned if the snowflake appears to be created in the
+    /// future
+    pub fn age_of(&self, flake: &<<F as FromIdGenerator>::Builder as IdBuilder>::Output) -> Option<Duration>
+    where
+        <<F as FromIdGenerator>::Builder as IdBuilder>::Output: UnixMillis,
+    {
+        SystemTime::now().duration_since(self.created_at(flake)?).ok()
+    }
+
+    /// checks if the given snowflake could have been created by the
+    /// generator
+    ///
+    /// see [`validate`](MutexGenerator::validate) for the checks that are performed
+    pub fn owns(&self, flake: &<<F as FromIdGenerator>::Builder as IdBuilder>::Output) -> bool
+    where
+        <<F as FromIdGenerator>::Builder as IdBuilder>::Output: FlakeParts<IdSegType = F::IdSegType>,
+        F::IdSegType: PartialEq,
+    {
+        self.validate(flake).is_ok()
+    }
+
+    /// checks that the id segments of the given snowflake match the
+    /// generator and that its timestamp is not ahead of the current time
+    ///
+    /// the sequence is not checked so a snowflake that matches could still
+    /// be one that was never issued
+    pub fn validate(&self, flake: &<<F as FromIdGenerator>::Builder as IdBuilder>::Output) -> Result<(), error::ValidationError>
+    where
+        <<F as FromIdGenerator>::Builder as IdBuilder>::Output: FlakeParts<IdSegType = F::IdSegType>,
+        F::IdSegType: PartialEq,
+    {
+        common::validate::<F, _>(&self.ep, &self.ids, flake)
+    }
+
     /// returns ids
     ///
     /// type is determined by the provided snowflake
@@ -110,16 +221,43 @@ where
         &self.ids
     }
 
+    /// returns the last id that was successfully generated
+    ///
+    /// the id is cloned out from behind the mutex. will be None if no id has
+    /// been generated yet or if it fails to lock the mutex. errors returned
+    /// from next_id will not change this value
+    pub fn last_id(&self) -> Option<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> {
+        let Ok(counts) = self.counts.lock() else {
+            return None;
+        };
+
+        counts.last.clone()
+    }
+
     /// retrieves the next available id
     ///
     /// if the current timestamp reaches max, the max sequence value is
     /// reached, or if it fails to get the current timestamp this will
-    /// return an error.
+    /// return an error. if the epoch has not been reached then the
+    /// [`EpochPolicy`](crate::EpochPolicy) determines the result
     pub fn next_id(&self) -> error::Result<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> {
+        self.next_id_detailed().map(|gen| gen.id)
+    }
+
+    /// retrieves the next available id with additional generation details
+    ///
+    /// same as [`next_id`](MutexGenerator::next_id) but also indicates if the
+    /// id started a new millisecond and what sequence it was given
+    pub fn next_id_detailed(&self) -> error::Result<Generated<<<F as FromIdGenerator>::Builder as IdBuilder>::Output>> {
         let mut builder = F::builder(&self.ids);
-        let ts: Duration;
 
-        {
+        // waiting is done before taking the lock so other threads are not
+        // stuck behind a sleeping one
+        if self.policy == EpochPolicy::Wait {
+            common::wait_for_epoch(&self.ep);
+        }
+
+        let (result, anomaly) = {
             // lock down counts for the current thread
             let Ok(mut counts) = self.counts.lock() else {
                 return Err(error::Error::MutexError);
@@ -128,38 +266,44 @@ where
             // since we do not know when the lock will be freed we
             // have to get the time once the lock is freed to have
             // an accurate timestamp
-            ts = self.ep.elapsed()?;
-            let ts_secs = ts.as_secs();
-            let ts_nanos = ts.subsec_nanos();
-            let ts_millis = ts_nanos / 1_000_000;
+            let (ts, until_epoch) = common::elapsed(&self.ep, self.policy)?;
+            let ts_tick = F::ticks(&ts);
 
-            if !builder.with_ts(ts_secs * 1_000 + ts_millis as u64) {
+            if !builder.with_ts(ts_tick) {
                 return Err(error::Error::TimestampMaxReached);
             }
 
-            let prev_secs = counts.prev_time.as_secs();
-            let prev_millis = counts.prev_time.subsec_nanos() / 1_000_000;
+            // hooks are not called while the lock is held so record the
+            // anomaly and report it after
+            let anomaly = if ts < counts.prev_time {
+                Some((counts.prev_time, ts))
+            } else {
+                None
+            };
 
-            // if we are still on the previously recorded millisecond
-            // then we increment the sequence. since the comparison of
-            // durations includes nanoseconds we have to do a little
-            // more work to only compare what we want
-            if prev_secs == ts_secs && prev_millis == ts_millis {
+            // if we are still on the previously recorded tick then we
+            // increment the sequence. since the comparison of durations
+            // includes nanoseconds the flake decides what a tick is
+            let result = if F::ticks(&counts.prev_time) == ts_tick {
                 // before we increment, check to make sure that we
                 // have not reached the maximum sequence value. if
                 // we have then given an estimate to the next
-                // millisecond so that then user can decided on
+                // tick so that then user can decided on
                 // how to wait for the next available value
-                if !builder.with_seq(counts.sequence) {
-                    return Err(error::Error::SequenceMaxReached(
-                        Duration::from_nanos((1_000_000 - (ts_nanos % 1_000_000)) as u64)
-                    ));
-                }
+                if builder.with_seq(counts.sequence) {
+                    // increment to the next sequence number
+                    let tick_sequence = counts.sequence;
+                    counts.sequence += 1;
 
-                // increment to the next sequence number
-                counts.sequence += 1;
+                    Ok(tick_sequence)
+                } else {
+                    // a clamped timestamp will not change until the epoch.
+                    // only the first caller to run out on this tick will
+                    // report it to the hooks
+                    Err((until_epoch.unwrap_or_else(|| F::next_tick(&ts)), counts.exhaust(ts_tick)))
+                }
             } else {
-                // we are not on the previousely recorded millisecond
+                // we are not on the previousely recorded tick
                 // so the sequence value will be set to one
                 builder.with_seq(1);
 
@@ -167,22 +311,172 @@ where
                 // available sequence number
                 counts.prev_time = ts;
                 counts.sequence = 2;
-            }
+
+                Ok(1)
+            };
+
+            // the id has to be built while the lock is held so that the
+            // last id stays in step with the sequence
+            let result = result.map(|tick_sequence| {
+                builder.with_dur(ts);
+
+                let flake = builder.build();
+
+                counts.last = Some(flake.clone());
+
+                (flake, tick_sequence)
+            });
+
+            (result, anomaly)
 
         // counts_lock should be dropped and the mutext should now be
         // unlocked for the next 
+        };
+
+        if let Some(hooks) = &self.hooks {
+            if let Some((prev, now)) = anomaly {
+                hooks.on_clock_anomaly(prev, now);
+            }
+
+            match &result {
+                Ok((flake, _)) => hooks.on_generate(flake),
+                Err((dur, true)) => hooks.on_sequence_exhausted(*dur),
+                Err((_, false)) => {},
+            }
         }
 
-        builder.with_dur(ts);
+        let (flake, tick_sequence) = result.map_err(|(dur, _)| error::Error::SequenceMaxReached(dur))?;
+
+        Ok(Generated {
+            id: flake,
+            new_tick: tick_sequence == 1,
+            tick_sequence,
+        })
+    }
+
+    /// returns a closure that will call [`next_id`](MutexGenerator::next_id)
+    ///
+    /// the closure holds a clone of the generator so it shares the same
+    /// counts and can be cloned or sent to other threads
+    ///
+    /// ```rust
+    /// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+    /// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
+    ///
+    /// const START_TIME: u64 = 1679587200000;
+    ///
+    /// let cloud = MyCloud::new(START_TIME, 1)
+    ///     .expect("failed to create MyCloud");
+    /// let create = cloud.as_fn();
+    /// let mut handles = Vec::new();
+    ///
+    /// for _ in 0..4 {
+    ///     let create = create.clone();
+    ///
+    ///     handles.push(std::thread::spawn(move || {
+    ///         create().expect("failed to create snowflake")
+    ///     }));
+    /// }
+    ///
+    /// for handle in handles {
+    ///     println!("{}", handle.join().unwrap().id());
+    /// }
+    /// ```
+    pub fn as_fn(&self) -> impl Fn() -> error::Result<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> + Clone
+    where
+        F::IdSegType: Clone,
+    {
+        let cloud = self.clone();
+
+        move || cloud.next_id()
+    }
 
-        Ok(builder.build())
+    /// same as [`as_fn`](MutexGenerator::as_fn) but the closure will sleep
+    /// the current thread if the max sequence is reached and try again
+    pub fn as_fn_blocking(&self) -> impl Fn() -> error::Result<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> + Clone
+    where
+        F::IdSegType: Clone,
+    {
+        let cloud = self.clone();
+
+        move || loop {
+            match cloud.next_id() {
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    thread::sleep(dur);
+                },
+                result => {
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+impl<F> fmt::Debug for MutexGenerator<F>
+where
+    F: FromIdGenerator,
+    F::IdSegType: fmt::Display,
+    F::Builder: IdBuilder,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("MutexGenerator");
+        debug.field("epoch", &format_args!("{}", EpochDisplay(&self.ep)))
+            .field("ids", &format_args!("{}", self.ids));
+
+        // this should not block if the lock is currently held by something
+        // else so only show the counts if they are available
+        match self.counts.try_lock() {
+            Ok(counts) => {
+                debug.field("sequence", &counts.sequence)
+                    .field("prev_time", &counts.prev_time);
+            },
+            Err(TryLockError::WouldBlock) => {
+                debug.field("counts", &format_args!("<locked>"));
+            },
+            Err(TryLockError::Poisoned(_)) => {
+                debug.field("counts", &format_args!("<poisoned>"));
+            }
+        }
+
+        debug.finish()
+    }
+}
+
+impl<F> fmt::Display for MutexGenerator<F>
+where
+    F: FromIdGenerator,
+    F::IdSegType: fmt::Display,
+    F::Builder: IdBuilder,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "epoch={}, ids={}, layout={}",
+            EpochDisplay(&self.ep),
+            self.ids,
+            short_type_name::<F>()
+        )
+    }
+}
+
+impl<F> EpochMillis for MutexGenerator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
+{
+    fn epoch_millis(&self) -> u64 {
+        // the epoch is checked to be after UNIX_EPOCH when created
+        self.ep.duration_since(SystemTime::UNIX_EPOCH)
+            .map(|dur| dur.as_millis() as u64)
+            .unwrap_or(0)
     }
 }
 
 impl<F> IdGenerator for MutexGenerator<F>
 where
     F: FromIdGenerator,
-    F::Builder: IdBuilder
+    F::Builder: IdBuilder,
+    <<F as FromIdGenerator>::Builder as IdBuilder>::Output: Clone,
 {
     type Error = error::Error;
     type Id = <<F as FromIdGenerator>::Builder as IdBuilder>::Output;
@@ -203,7 +497,7 @@ mod test {
     use snowcloud_flake::i64::SingleIdFlake;
 
     use super::*;
-    use crate::wait::blocking_next_id;
+    use crate::wait::try_next_id;
 
     const START_TIME: u64 = 1679082337000;
     const MACHINE_ID: i64 = 1;
@@ -211,6 +505,50 @@ mod test {
     type TestSnowflake = SingleIdFlake<43, 8, 12>;
     type TestSnowcloud = MutexGenerator<TestSnowflake>;
 
+    #[test]
+    fn created_at() {
+        let cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+
+        let before = SystemTime::now();
+        let flake = cloud.next_id().unwrap();
+        let after = SystemTime::now();
+
+        let created = cloud.created_at(&flake).unwrap();
+
+        // the timestamp is truncated to the millisecond it was created in
+        assert!(created + Duration::from_millis(1) > before, "{:?} is before {:?}", created, before);
+        assert!(created <= after, "{:?} is after {:?}", created, after);
+    }
+
+    #[test]
+    fn validate() {
+        let cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let flake = cloud.next_id().unwrap();
+
+        assert!(cloud.owns(&flake));
+        assert_eq!(cloud.validate(&flake), Ok(()));
+
+        let other = TestSnowcloud::new(START_TIME, MACHINE_ID + 1).unwrap();
+        let foreign = other.next_id().unwrap();
+
+        assert!(!cloud.owns(&foreign));
+        assert_eq!(cloud.validate(&foreign), Err(error::ValidationError::IdSegMismatch));
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let future = TestSnowflake::from_parts(now - START_TIME as i64 + 60_000, MACHINE_ID, 1).unwrap();
+
+        assert!(!cloud.owns(&future));
+        assert_eq!(cloud.validate(&future), Err(error::ValidationError::FutureTimestamp));
+
+        let clamped = TestSnowcloud::new_with_policy(future_epoch(60_000), MACHINE_ID, EpochPolicy::Clamp).unwrap();
+        let flake = clamped.next_id().unwrap();
+
+        assert_eq!(clamped.validate(&flake), Err(error::ValidationError::PreEpoch));
+    }
+
     #[test]
     fn unique_ids() {
         let cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
@@ -223,20 +561,16 @@ mod test {
             generated.push(cloud.next_id().expect("failed next_id"));
         }
 
-        for i in 0..generated.len() {
-            let flake = &generated[i];
+        for (i, flake) in generated.iter().enumerate() {
             let id: i64 = flake.id();
 
             if let Some(dups) = unique_ids.get_mut(&id) {
                 found_dups = true;
                 total_found += 1;
 
-                dups.push((i, flake.clone()));
+                dups.push((i, *flake));
             } else {
-                let mut dups = Vec::with_capacity(1);
-                dups.push((i, flake.clone()));
-
-                unique_ids.insert(id, dups);
+                unique_ids.insert(id, vec![(i, *flake)]);
             }
         }
 
@@ -260,8 +594,6 @@ mod test {
 
             if let Some(dups) = unique_ids.get(&id) {
                 if dups.len() > 1 {
-                    total_found += 1;
-
                     debug_output.write_fmt(format_args!(
                         "flake: {}\n",
                         id,
@@ -286,24 +618,24 @@ mod test {
             }
         }
 
-        debug_output.write(b"\n").unwrap();
+        debug_output.write_all(b"\n").unwrap();
 
-        for index in 0..generated.len() {
+        for (index, flake) in generated.iter().enumerate() {
             let mut is_dup = false;
-            let id = generated[index].id();
+            let id = flake.id();
 
             if let Some(dups) = unique_ids.get(&id) {
                 is_dup = dups.len() > 1;
             }
 
-            let dur = generated[index].duration().unwrap();
+            let dur = flake.duration().unwrap();
 
             debug_output.write_fmt(format_args!(
                 "{:index_width$} {} {} {:seq_width$} | {}.{} {}\n",
                 index,
-                generated[index].timestamp(),
-                generated[index].primary_id(),
-                generated[index].sequence(),
+                flake.timestamp(),
+                flake.primary_id(),
+                flake.sequence(),
                 dur.as_secs(),
                 dur.subsec_nanos(),
                 if is_dup { 'd' } else { ' ' },
@@ -316,14 +648,14 @@ mod test {
     }
 
     #[test]
-    fn unique_ids_threaded() -> () {
+    fn unique_ids_threaded() {
         let start = std::time::Instant::now();
         let barrier = Arc::new(Barrier::new(3));
         let mut handles = Vec::with_capacity(3);
         let cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
 
         for _ in 0..handles.capacity() {
-            let t = start.clone();
+            let t = start;
             let b = Arc::clone(&barrier);
             let c = cloud.clone();
 
@@ -332,12 +664,8 @@ mod test {
                 b.wait();
 
                 for _ in 0..id_list.capacity() {
-                    let Some(result) = blocking_next_id(&c, 2) else {
-                        panic!("ran out of spin_next_id attempts");
-                    };
-
                     id_list.push((
-                        result.expect("failed spin_next_id"),
+                        try_next_id(&c, 2).expect("failed try_next_id"),
                         t.elapsed()
                     ));
                 }
@@ -347,13 +675,12 @@ mod test {
         }
 
         let mut failed = false;
-        let mut thread: usize = 0;
         let mut ordered_time_groups: Vec<std::time::Duration> = Vec::new();
         let mut time_groups: HashMap<std::time::Duration, Vec<Vec<usize>>> = HashMap::new();
         let mut unique_ids: HashMap<TestSnowflake, Vec<(usize, usize)>> = HashMap::new();
         let mut thread_list: Vec<Vec<(TestSnowflake, std::time::Duration)>> = Vec::with_capacity(handles.len());
 
-        for handle in handles {
+        for (thread, handle) in handles.into_iter().enumerate() {
             let list = handle.join().expect("thread paniced");
 
             thread_list.push(list);
@@ -364,7 +691,7 @@ mod test {
                 if let Some(groups) = time_groups.get_mut(dur) {
                     groups[thread].push(index);
                 } else {
-                    ordered_time_groups.push(dur.clone());
+                    ordered_time_groups.push(*dur);
 
                     let mut group = Vec::with_capacity(thread_list.capacity());
 
@@ -378,21 +705,16 @@ mod test {
                         group.push(v);
                     }
 
-                    time_groups.insert(dur.clone(), group);
+                    time_groups.insert(*dur, group);
                 }
 
                 if let Some(dups) = unique_ids.get_mut(flake) {
                     failed = true;
                     dups.push((thread, index));
                 } else {
-                    let mut dups = Vec::with_capacity(1);
-                    dups.push((thread, index));
-
-                    unique_ids.insert(flake.clone(), dups);
+                    unique_ids.insert(*flake, vec![(thread, index)]);
                 }
             }
-
-            thread += 1;
         }
 
         if !failed {
@@ -426,8 +748,8 @@ mod test {
         let max_duration = (ordered_time_groups.last().unwrap().as_nanos().checked_ilog10().unwrap_or(0) + 1) as usize;
         let mut max_ts_width = 0;
 
-        for thread in 0..thread_list.len() {
-            let decimals = (thread_list[thread].last().unwrap().0.timestamp().checked_ilog10().unwrap_or(0) + 1) as usize;
+        for list in &thread_list {
+            let decimals = (list.last().unwrap().0.timestamp().checked_ilog10().unwrap_or(0) + 1) as usize;
 
             if decimals > max_ts_width {
                 max_ts_width = decimals;
@@ -451,7 +773,6 @@ mod test {
 
             while cntu {
                 cntu = false;
-                let mut thread = 0;
 
                 if !first {
                     timing_groups.write_fmt(format_args!(
@@ -463,7 +784,7 @@ mod test {
                     first = false;
                 }
 
-                for iter in iter_list.iter_mut() {
+                for (thread, iter) in iter_list.iter_mut().enumerate() {
                     if let Some(index) = iter.next() {
                         timing_groups.write_fmt(format_args!(
                             " | {:ts_width$} {} {:seq_width$} {}",
@@ -489,11 +810,9 @@ mod test {
                             seq_width = max_seq_width,
                         )).unwrap();
                     }
-
-                    thread += 1;
                 }
 
-                timing_groups.write(b"\n").unwrap();
+                timing_groups.write_all(b"\n").unwrap();
             }
         }
 
@@ -504,17 +823,17 @@ mod test {
                 width = 4,
             )).unwrap();
 
-            for thread in 0..thread_list.len() {
+            for (thread, list) in thread_list.iter().enumerate() {
                 if thread > 0 {
-                    joined_lists.write(b" | ").unwrap();
+                    joined_lists.write_all(b" | ").unwrap();
                 }
 
                 joined_lists.write_fmt(format_args!(
                     "{:ts_width$} {} {:seq_width$} {}",
-                    thread_list[thread][index].0.timestamp(),
-                    thread_list[thread][index].0.primary_id(),
-                    thread_list[thread][index].0.sequence(),
-                    if unique_ids.get(&thread_list[thread][index].0).unwrap().len() > 1 {
+                    list[index].0.timestamp(),
+                    list[index].0.primary_id(),
+                    list[index].0.sequence(),
+                    if unique_ids.get(&list[index].0).unwrap().len() > 1 {
                         'd'
                     } else {
                         ' '
@@ -524,7 +843,7 @@ mod test {
                 )).unwrap();
             }
 
-            joined_lists.write(b"\n").unwrap();
+            joined_lists.write_all(b"\n").unwrap();
         }
 
         for (flake, dups) in unique_ids {
@@ -551,13 +870,13 @@ mod test {
 
                     let index_decimals = (high.checked_ilog10().unwrap_or(0) + 1) as usize;
 
-                    for prev_index in low..index {
+                    for (prev_index, (before, _)) in thread_list[thread].iter().enumerate().take(index).skip(low) {
                         debug_output.write_fmt(format_args!(
                             "{:width$} {:ts_width$} {} {:seq_width$}\n", 
                             prev_index,
-                            thread_list[thread][prev_index].0.timestamp(),
-                            thread_list[thread][prev_index].0.primary_id(),
-                            thread_list[thread][prev_index].0.sequence(),
+                            before.timestamp(),
+                            before.primary_id(),
+                            before.sequence(),
                             width = index_decimals,
                             ts_width = max_ts_width,
                             seq_width = max_seq_width,
@@ -576,13 +895,13 @@ mod test {
                     )).unwrap();
 
                     if index != next {
-                        for next_index in next..high {
+                        for (next_index, (after, _)) in thread_list[thread].iter().enumerate().take(high).skip(next) {
                             debug_output.write_fmt(format_args!(
                                 "{:width$} {:ts_width$} {} {:seq_width$}\n",
                                 next_index,
-                                thread_list[thread][next_index].0.timestamp(),
-                                thread_list[thread][next_index].0.primary_id(),
-                                thread_list[thread][next_index].0.sequence(),
+                                after.timestamp(),
+                                after.primary_id(),
+                                after.sequence(),
                                 width = index_decimals,
                                 ts_width = max_ts_width,
                                 seq_width = max_seq_width,
@@ -597,4 +916,245 @@ mod test {
 
         panic!("encountered duplidate ids. check MutexGenerator_unique_id_threaded for output");
     }
+
+    #[test]
+    fn last_id() {
+        type SmallSnowflake = SingleIdFlake<43, 16, 4>;
+
+        let cloud = MutexGenerator::<SmallSnowflake>::new(START_TIME, MACHINE_ID).unwrap();
+        let mut last = None;
+
+        assert!(cloud.last_id().is_none(), "last id present before next_id");
+
+        loop {
+            match cloud.next_id() {
+                Ok(flake) => {
+                    assert_eq!(cloud.last_id().as_ref(), Some(&flake), "last id does not match generated id");
+
+                    last = Some(flake);
+                },
+                Err(error::Error::SequenceMaxReached(_)) => {
+                    break;
+                },
+                Err(err) => {
+                    panic!("unexpected error: {:?}", err);
+                }
+            }
+        }
+
+        assert!(last.is_some(), "no ids were generated");
+        assert_eq!(cloud.last_id(), last, "last id changed on error");
+        assert_eq!(cloud.clone().last_id(), last, "cloned generator does not share last id");
+    }
+
+    #[test]
+    fn next_id_detailed() {
+        type SmallSnowflake = SingleIdFlake<43, 16, 4>;
+
+        let cloud = MutexGenerator::<SmallSnowflake>::new(START_TIME, MACHINE_ID).unwrap();
+        let mut generated = Vec::with_capacity(SmallSnowflake::MAX_SEQUENCE as usize * 3);
+        let mut prev_ts = None;
+
+        while generated.len() < generated.capacity() {
+            match cloud.next_id_detailed() {
+                Ok(gen) => {
+                    generated.push(gen);
+                },
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    std::thread::sleep(dur);
+                },
+                Err(err) => {
+                    panic!("unexpected error: {:?}", err);
+                }
+            }
+        }
+
+        for gen in generated {
+            let ts = gen.id.timestamp();
+
+            assert_eq!(gen.new_tick, prev_ts != Some(ts), "new_tick does not match millisecond boundary. {:#?}", gen);
+            assert_eq!(gen.tick_sequence, gen.id.sequence() as u64, "tick_sequence does not match flake sequence. {:#?}", gen);
+
+            prev_ts = Some(ts);
+        }
+    }
+
+    #[test]
+    fn debug_while_locked() {
+        let cloud = TestSnowcloud::new(START_TIME, 3).unwrap();
+
+        let debug = format!("{:?}", cloud);
+
+        assert!(debug.contains("ids: (3)"), "debug missing ids: {}", debug);
+        assert!(debug.contains("sequence: 1"), "debug missing counts: {}", debug);
+
+        let _guard = cloud.counts.lock().unwrap();
+
+        let debug = format!("{:?}", cloud);
+
+        assert!(debug.contains("ids: (3)"), "debug missing ids: {}", debug);
+        assert!(debug.contains("counts: <locked>"), "debug did not report lock: {}", debug);
+
+        let display = cloud.to_string();
+
+        assert!(display.contains("ids=(3), layout=SingleIdFlake<43, 8, 12>"), "unexpected display output: {}", display);
+    }
+
+    #[test]
+    fn layout_validation() {
+        type OverWide = crate::common::OverWide<70, 63>;
+
+        match MutexGenerator::<OverWide>::new(START_TIME, MACHINE_ID) {
+            Err(error::Error::LayoutInvalid { total_bits, max_bits }) => {
+                assert_eq!(total_bits, 70, "invalid total bits");
+                assert_eq!(max_bits, 63, "invalid max bits");
+            },
+            Err(err) => panic!("unexpected error: {:?}", err),
+            Ok(_) => panic!("created generator with an over wide layout"),
+        }
+    }
+
+    fn future_epoch(ahead: u64) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap();
+
+        now.as_millis() as u64 + ahead
+    }
+
+    #[test]
+    fn future_epoch_clamp() {
+        let cloud = TestSnowcloud::new_with_policy(
+            future_epoch(300),
+            MACHINE_ID,
+            EpochPolicy::Clamp
+        ).unwrap();
+
+        for _ in 0..TestSnowflake::MAX_SEQUENCE {
+            let flake = cloud.next_id().expect("failed to generate snowflake");
+
+            assert_eq!(flake.timestamp(), 0, "timestamp was not clamped");
+        }
+
+        // the clamped timestamp will not move until the epoch so the wait
+        // should be for more than the next millisecond
+        match cloud.next_id() {
+            Err(error::Error::SequenceMaxReached(dur)) => {
+                assert!(dur > Duration::from_millis(1), "duration does not wait for epoch: {:?}", dur);
+            },
+            result => panic!("expected SequenceMaxReached: {:?}", result)
+        }
+    }
+
+    #[test]
+    fn future_epoch_wait() {
+        let cloud = TestSnowcloud::new_with_policy(
+            future_epoch(300),
+            MACHINE_ID,
+            EpochPolicy::Wait
+        ).unwrap();
+        let barrier = Arc::new(Barrier::new(3));
+        let mut handles = Vec::with_capacity(3);
+
+        for _ in 0..handles.capacity() {
+            let b = Arc::clone(&barrier);
+            let c = cloud.clone();
+
+            handles.push(thread::spawn(move || {
+                b.wait();
+
+                c.next_id().expect("failed to generate snowflake")
+            }));
+        }
+
+        for handle in handles {
+            let flake = handle.join().expect("thread paniced");
+
+            assert!(flake.timestamp() >= 0);
+        }
+
+        assert!(cloud.epoch().elapsed().is_ok(), "returned before the epoch");
+    }
+
+    #[test]
+    fn as_fn_threaded() {
+        let cloud = MutexGenerator::<SingleIdFlake<43, 16, 4>>::new(START_TIME, MACHINE_ID).unwrap();
+        let create = cloud.as_fn_blocking();
+        let mut handles = Vec::with_capacity(3);
+
+        for _ in 0..handles.capacity() {
+            let c = create.clone();
+
+            handles.push(thread::spawn(move || {
+                let mut list = Vec::with_capacity(16 * 2);
+
+                while list.len() < list.capacity() {
+                    list.push(c().expect("failed to generate snowflake").id());
+                }
+
+                list
+            }));
+        }
+
+        let mut unique_ids = std::collections::HashSet::new();
+
+        for handle in handles {
+            for id in handle.join().expect("thread paniced") {
+                assert!(unique_ids.insert(id), "duplicate id generated: {}", id);
+            }
+        }
+
+        // the closure shares the counts with the generator it came from
+        assert!(cloud.last_id().is_some());
+
+        let boxed: Box<dyn Fn() -> error::Result<TestSnowflake> + Send + Sync> = Box::new(
+            TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap().as_fn()
+        );
+
+        boxed().expect("failed to generate snowflake");
+    }
+
+    #[test]
+    fn seconds_tick_exhausted() {
+        type SecFlake = snowcloud_flake::i64::SingleIdFlakeSec<32, 27, 4>;
+
+        let cloud = MutexGenerator::<SecFlake>::new(START_TIME, MACHINE_ID).unwrap();
+        let mut last = None;
+
+        let dur = loop {
+            match cloud.next_id() {
+                Ok(flake) => {
+                    last = Some(flake);
+                },
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    break dur;
+                },
+                Err(err) => {
+                    panic!("failed to generate snowflake: {:?}", err);
+                }
+            }
+        };
+
+        let last = last.expect("no ids generated");
+        let last_dur = *last.duration().unwrap();
+
+        // the sequence can be exhausted at the end of a second so only
+        // check that the estimate does not go past the next second
+        assert!(
+            last_dur.subsec_nanos() as u128 + dur.as_nanos() <= 1_000_000_000,
+            "estimate goes past the next second. {:?} {:?}",
+            last_dur,
+            dur
+        );
+
+        thread::sleep(dur);
+
+        let next = cloud.next_id_detailed().expect("failed to generate snowflake");
+
+        assert!(next.new_tick, "sequence not reset after waiting. {:#?}", next);
+        assert!(
+            next.id.duration().unwrap().as_secs() > last_dur.as_secs(),
+            "next id is not in the next second"
+        );
+    }
 }
@@ -0,0 +1,307 @@
+//! async generators for use with tokio
+//!
+//! requires the `tokio` feature
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::SystemTime;
+
+use snowcloud_core::traits::{IdGeneratorAsync, FromIdGenerator, IdBuilder, EpochMillis};
+
+use crate::error;
+use crate::hooks::GeneratorHooks;
+use crate::sync::MutexGenerator;
+
+/// async snowflake generator
+///
+/// uses the same logic as [`MutexGenerator`](crate::sync::MutexGenerator)
+/// but when the max sequence is reached it will await
+/// [`tokio::time::sleep`] for the next millisecond instead of returning an
+/// error or blocking the current thread.
+///
+/// the lock on the counts is never held across an await point so dropping a
+/// future returned from [`next_id`](AsyncGenerator::next_id) will not leave
+/// the generator in a bad state. the counts are stored behind an
+/// [`Arc`](std::sync::Arc) so clones are cheap and will share the same
+/// sequence.
+///
+/// ```rust
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::cloud::tokio::AsyncGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// # tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap().block_on(async {
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+///
+/// println!("{:?}", cloud.next_id().await);
+/// println!("{:?}", cloud.next_ids(10).await);
+/// # });
+/// ```
+///
+/// implements [`IdGeneratorAsync`](snowcloud_core::traits::IdGeneratorAsync)
+/// so it can be used in generic code
+///
+/// ```rust
+/// use snowcloud::traits::IdGeneratorAsync;
+///
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::cloud::tokio::AsyncGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// async fn create_ids<G>(gen: &G, n: usize) -> Result<Vec<G::Id>, G::Error>
+/// where
+///     G: IdGeneratorAsync
+/// {
+///     let mut list = Vec::with_capacity(n);
+///
+///     for _ in 0..n {
+///         list.push(gen.next_id().await?);
+///     }
+///
+///     Ok(list)
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap().block_on(async {
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+///
+/// println!("{:?}", create_ids(&cloud, 10).await);
+/// # });
+/// ```
+pub struct AsyncGenerator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
+{
+    inner: MutexGenerator<F>,
+}
+
+impl<F> Clone for AsyncGenerator<F>
+where
+    F: FromIdGenerator,
+    F::IdSegType: Clone,
+    F::Builder: IdBuilder,
+{
+    fn clone(&self) -> Self {
+        AsyncGenerator {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<F> AsyncGenerator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
+    <<F as FromIdGenerator>::Builder as IdBuilder>::Output: Clone,
+{
+    /// returns a new AsyncGenerator
+    ///
+    /// will return an error for the same reasons as
+    /// [`MutexGenerator::new`](crate::sync::MutexGenerator::new)
+    pub fn new<I>(epoch: u64, ids: I) -> error::Result<Self>
+    where
+        I: Into<F::IdSegType>
+    {
+        Ok(AsyncGenerator {
+            inner: MutexGenerator::new(epoch, ids)?,
+        })
+    }
+
+    /// sets the hooks that will be called when generating ids
+    ///
+    /// see [`GeneratorHooks`](crate::hooks::GeneratorHooks) for the events
+    /// that are available
+    pub fn with_hooks(self, hooks: Box<dyn GeneratorHooks<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> + Send + Sync>) -> Self {
+        AsyncGenerator {
+            inner: self.inner.with_hooks(hooks),
+        }
+    }
+
+    /// returns epoch
+    pub fn epoch(&self) -> &SystemTime {
+        self.inner.epoch()
+    }
+
+    /// returns ids
+    ///
+    /// type is determined by the provided snowflake
+    pub fn ids(&self) -> &F::IdSegType {
+        self.inner.ids()
+    }
+
+    /// returns the last id that was successfully generated
+    ///
+    /// same as [`MutexGenerator::last_id`](crate::sync::MutexGenerator::last_id)
+    pub fn last_id(&self) -> Option<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> {
+        self.inner.last_id()
+    }
+
+    /// retrieves the next available id
+    ///
+    /// if the max sequence value is reached then this will sleep until the
+    /// next millisecond and try again. any other error is returned
+    pub async fn next_id(&self) -> error::Result<<<F as FromIdGenerator>::Builder as IdBuilder>::Output> {
+        loop {
+            match self.inner.next_id() {
+                Err(error::Error::SequenceMaxReached(dur)) => {
+                    ::tokio::time::sleep(dur).await;
+                },
+                result => {
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// retrieves the given number of ids
+    ///
+    /// sleeps for the next millisecond as needed. if an error is encountered
+    /// then it is returned and the ids generated so far are discarded
+    pub async fn next_ids(&self, n: usize) -> error::Result<Vec<<<F as FromIdGenerator>::Builder as IdBuilder>::Output>> {
+        let mut list = Vec::with_capacity(n);
+
+        while list.len() < n {
+            list.push(self.next_id().await?);
+        }
+
+        Ok(list)
+    }
+}
+
+impl<F> EpochMillis for AsyncGenerator<F>
+where
+    F: FromIdGenerator,
+    F::Builder: IdBuilder,
+{
+    fn epoch_millis(&self) -> u64 {
+        self.inner.epoch_millis()
+    }
+}
+
+impl<F> IdGeneratorAsync for AsyncGenerator<F>
+where
+    F: FromIdGenerator,
+    F::IdSegType: Send + Sync,
+    F::Builder: IdBuilder,
+    <<F as FromIdGenerator>::Builder as IdBuilder>::Output: Clone + Send,
+{
+    type Error = error::Error;
+    type Id = <<F as FromIdGenerator>::Builder as IdBuilder>::Output;
+    type Future<'a> = Pin<Box<dyn Future<Output = Result<Self::Id, Self::Error>> + Send + 'a>> where Self: 'a;
+
+    fn next_id(&self) -> Self::Future<'_> {
+        Box::pin(AsyncGenerator::next_id(self))
+    }
+}
+
+impl<F> fmt::Debug for AsyncGenerator<F>
+where
+    F: FromIdGenerator,
+    F::IdSegType: fmt::Display,
+    F::Builder: IdBuilder,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncGenerator")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<F> fmt::Display for AsyncGenerator<F>
+where
+    F: FromIdGenerator,
+    F::IdSegType: fmt::Display,
+    F::Builder: IdBuilder,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    use snowcloud_flake::i64::SingleIdFlake;
+
+    use super::*;
+
+    const START_TIME: u64 = 1679082337000;
+    const MACHINE_ID: i64 = 1;
+
+    type TestSnowflake = SingleIdFlake<43, 16, 4>;
+    type TestSnowcloud = AsyncGenerator<TestSnowflake>;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn unique_ids_tasks() {
+        let cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let per_task = TestSnowflake::MAX_SEQUENCE as usize;
+        let mut handles = Vec::with_capacity(3);
+
+        for _ in 0..handles.capacity() {
+            let c = cloud.clone();
+
+            handles.push(tokio::spawn(async move {
+                c.next_ids(per_task).await.expect("failed to generate snowflakes")
+            }));
+        }
+
+        let mut unique_ids = HashSet::new();
+
+        for handle in handles {
+            for flake in handle.await.expect("task paniced") {
+                assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+            }
+        }
+
+        assert_eq!(unique_ids.len(), per_task * 3);
+    }
+
+    #[tokio::test]
+    async fn dropped_future() {
+        let cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let mut unique_ids = HashSet::new();
+
+        for flake in cloud.next_ids(TestSnowflake::MAX_SEQUENCE as usize).await.unwrap() {
+            unique_ids.insert(flake.id());
+        }
+
+        // the sequence is most likely exhausted so the future will be dropped
+        // while sleeping
+        let _ = tokio::time::timeout(Duration::ZERO, cloud.next_id()).await;
+
+        for flake in cloud.next_ids(TestSnowflake::MAX_SEQUENCE as usize).await.unwrap() {
+            assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+        }
+    }
+
+    #[tokio::test]
+    async fn generic_async() {
+        async fn create_ids<G>(gen: &G, n: usize) -> Vec<G::Id>
+        where
+            G: IdGeneratorAsync,
+            G::Error: fmt::Debug,
+        {
+            let mut list = Vec::with_capacity(n);
+
+            for _ in 0..n {
+                list.push(gen.next_id().await.expect("failed to generate snowflake"));
+            }
+
+            list
+        }
+
+        let cloud = TestSnowcloud::new(START_TIME, MACHINE_ID).unwrap();
+        let mut unique_ids = HashSet::new();
+
+        for flake in create_ids(&cloud, TestSnowflake::MAX_SEQUENCE as usize * 2).await {
+            assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+        }
+    }
+}
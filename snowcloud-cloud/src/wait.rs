@@ -1,30 +1,201 @@
 //! methods for waiting on the next available id from a snowcloud
 //!
-//! currently contains only a blocking method but other could be added in
-//! the future
+//! contains blocking methods along with async methods that are not tied to
+//! a specific runtime
 
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, Thread};
 use std::time::{Instant, Duration};
 
 use snowcloud_core::traits::{NextAvailId, IdGenerator, IdGeneratorMut};
 
-/// blocks the current thread for the given duration by sleeping, yielding, or
-/// spinning
-fn block_duration(dur: &Duration) {
+/// longest amount of time a thread will sleep before checking if it should
+/// stop waiting
+const SLEEP_QUANTUM: Duration = Duration::from_millis(1);
+
+/// how a thread will wait for a given duration
+///
+/// the default is [`SleepYieldSpin`](WaitStrategy::SleepYieldSpin) sleeping
+/// when more than 500 microseconds remain and yielding when more than 1
+/// microsecond remains.
+///
+/// sleeps are for the entire remaining time unless the wait can be
+/// cancelled, then they are limited to 1 millisecond so the cancel can be
+/// seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WaitStrategy {
+    /// sleeps while the remaining time is over sleep_over, yields while it
+    /// is over yield_over, and spins for the rest. only the sub second part
+    /// of the remaining time is compared
+    SleepYieldSpin {
+        sleep_over: Duration,
+        yield_over: Duration,
+    },
+
+    /// spins for the entire duration
+    SpinOnly,
+
+    /// sleeps for the entire duration
+    SleepOnly,
+
+    /// yields for the entire duration
+    YieldOnly,
+}
+
+/// strategy used by the functions that do not take one
+const DEFAULT_STRATEGY: WaitStrategy = WaitStrategy::SleepYieldSpin {
+    sleep_over: Duration::from_micros(500),
+    yield_over: Duration::from_micros(1),
+};
+
+impl Default for WaitStrategy {
+    fn default() -> Self {
+        DEFAULT_STRATEGY
+    }
+}
+
+/// blocks the current thread for the given duration using the given strategy
+fn block_duration(dur: &Duration, strategy: &WaitStrategy) {
+    wait_duration(dur, strategy, None::<fn() -> bool>, &mut ThreadWaiter);
+}
+
+/// same as [`block_duration`] but can be stopped early
+///
+/// stop is checked before each sleep, yield, or spin and if it returns true
+/// the wait will end early. sleeps are limited to [`SLEEP_QUANTUM`] so stop
+/// is checked regularly. returns false if the wait was stopped
+fn block_duration_stoppable<P>(dur: &Duration, strategy: &WaitStrategy, stop: P) -> bool
+where
+    P: Fn() -> bool
+{
+    wait_duration(dur, strategy, Some(stop), &mut ThreadWaiter)
+}
+
+/// the different ways a thread can wait
+///
+/// separated from [`wait_duration`] so tests can see what a strategy does
+trait Waiter {
+    fn sleep(&mut self, dur: Duration);
+
+    fn yield_now(&mut self);
+
+    fn spin(&mut self);
+}
+
+/// waits on the current thread
+struct ThreadWaiter;
+
+impl Waiter for ThreadWaiter {
+    fn sleep(&mut self, dur: Duration) {
+        std::thread::sleep(dur);
+    }
+
+    fn yield_now(&mut self) {
+        std::thread::yield_now();
+    }
+
+    fn spin(&mut self) {
+        std::hint::spin_loop();
+    }
+}
+
+fn wait_duration<P, W>(dur: &Duration, strategy: &WaitStrategy, stop: Option<P>, waiter: &mut W) -> bool
+where
+    P: Fn() -> bool,
+    W: Waiter,
+{
     let start = Instant::now();
+    let max_sleep = if stop.is_some() {
+        SLEEP_QUANTUM
+    } else {
+        Duration::MAX
+    };
 
-    loop {
-        let Some(diff) = dur.checked_sub(start.elapsed()) else {
-            break;
-        };
+    while let Some(diff) = dur.checked_sub(start.elapsed()) {
+        if stop.as_ref().map_or(false, |stop| stop()) {
+            return false;
+        }
+
+        match strategy {
+            WaitStrategy::SleepYieldSpin { sleep_over, yield_over } => {
+                let subsec = Duration::from_nanos(diff.subsec_nanos() as u64);
+
+                if subsec > *sleep_over {
+                    waiter.sleep(diff.min(max_sleep));
+                } else if subsec > *yield_over {
+                    waiter.yield_now();
+                } else {
+                    waiter.spin();
+                }
+            },
+            WaitStrategy::SpinOnly => {
+                waiter.spin();
+            },
+            WaitStrategy::SleepOnly => {
+                waiter.sleep(diff.min(max_sleep));
+            },
+            WaitStrategy::YieldOnly => {
+                waiter.yield_now();
+            }
+        }
+    }
+
+    true
+}
+
+/// error returned from the functions that wait for an id
+#[derive(Debug)]
+pub enum WaitError<E> {
+    /// all attempts were used without getting an id
+    Exhausted {
+        attempts: u8,
+    },
 
-        let nanos = diff.subsec_nanos();
+    /// the deadline passed without getting an id
+    DeadlineReached,
 
-        if nanos > 500_000 {
-            std::thread::sleep(diff);
-        } else if nanos > 1_000 {
-            std::thread::yield_now();
-        } else {
-            std::hint::spin_loop();
+    /// the wait was cancelled before an id was retrieved
+    Cancelled,
+
+    /// the generator returned an error that has no next available id
+    Generator(E),
+}
+
+impl<E> From<E> for WaitError<E> {
+    fn from(err: E) -> Self {
+        WaitError::Generator(err)
+    }
+}
+
+impl<E> fmt::Display for WaitError<E>
+where
+    E: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitError::Exhausted { attempts } => write!(f, "attempts exhausted. made {} attempts", attempts),
+            WaitError::DeadlineReached => write!(f, "deadline reached"),
+            WaitError::Cancelled => write!(f, "wait cancelled"),
+            WaitError::Generator(err) => write!(f, "generator error. {}", err),
+        }
+    }
+}
+
+impl<E> std::error::Error for WaitError<E>
+where
+    E: std::error::Error + 'static
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WaitError::Exhausted { .. } | WaitError::DeadlineReached | WaitError::Cancelled => None,
+            WaitError::Generator(err) => Some(err),
         }
     }
 }
@@ -32,63 +203,89 @@ fn block_duration(dur: &Duration) {
 /// blocks the current thread for next available id with a given number of
 /// attempts
 ///
-/// if total attempts reaches 0 then the result will be none otherwise will be
-/// some with whatever happened when generating the id
+/// if total attempts reaches 0 then [`WaitError::Exhausted`] is returned.
+/// any error from the generator without a next available id is returned as
+/// [`WaitError::Generator`]
 ///
 /// ```rust
+/// use snowcloud::cloud::wait::{self, WaitError};
+///
 /// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
 /// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
 ///
 /// const START_TIME: u64 = 1679587200000;
 ///
+/// fn create_ids(cloud: &MyCloud) -> Result<(), WaitError<snowcloud::cloud::error::Error>> {
+///     // create more snowflakes than what is possible in a millisecond
+///     for _ in 0..(MyFlake::MAX_SEQUENCE as usize * 2) {
+///         let flake = wait::try_next_id(cloud, 2)?;
+///
+///         println!("{}", flake.id());
+///     }
+///
+///     Ok(())
+/// }
+///
 /// let cloud = MyCloud::new(START_TIME, 1)
 ///     .expect("failed to create MyCloud");
 ///
-/// // create more snowflakes than what is possible in a millisecond
-/// for _ in 0..(MyFlake::MAX_SEQUENCE as usize * 2) {
-///     let Some(result) = snowcloud::wait::blocking_next_id(&cloud, 2) else {
-///         println!("ran out of attempts to get a new snowflake");
-///         continue;
-///     };
+/// create_ids(&cloud).expect("failed to create snowflakes");
+/// ```
+pub fn try_next_id<C>(cloud: &C, attempts: u8) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    try_next_id_with(cloud, attempts, &DEFAULT_STRATEGY)
+}
+
+/// same as [`try_next_id`] but waits with the given strategy
+///
+/// ```rust
+/// use snowcloud::cloud::wait::{self, WaitStrategy};
 ///
-///     let flake = result.expect("failed to create snowflake");
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
 ///
-///     println!("{}", flake.id());
-/// }
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+///
+/// // sleeping can overshoot on some platforms so spin instead
+/// let flake = wait::try_next_id_with(&cloud, 2, &WaitStrategy::SpinOnly)
+///     .expect("failed to create snowflake");
+///
+/// println!("{}", flake.id());
 /// ```
-pub fn blocking_next_id<C>(cloud: &C, mut attempts: u8) -> Option<std::result::Result<C::Id, C::Error>> 
+pub fn try_next_id_with<C>(cloud: &C, attempts: u8, strategy: &WaitStrategy) -> std::result::Result<C::Id, WaitError<C::Error>>
 where
     C: IdGenerator,
     C::Error: NextAvailId,
     C::Output: Into<std::result::Result<C::Id, C::Error>>,
 {
-    while attempts != 0 {
+    for _ in 0..attempts {
         match cloud.next_id().into() {
             Ok(sf) => {
-                return Some(Ok(sf))
+                return Ok(sf)
             },
             Err(err) => {
                 let Some(dur) = err.next_avail_id() else {
-                    return Some(Err(err));
+                    return Err(WaitError::Generator(err));
                 };
 
-                block_duration(dur);
+                block_duration(dur, strategy);
             }
         }
-
-        attempts -= 1;
     }
 
-    None
+    Err(WaitError::Exhausted { attempts })
 }
 
-/// mutable version of [`blocking_next_id`]
-///
-/// if total attempts reaches 0 then the result will be None otherwise will be
-/// some with whatever happened when generating the id
+/// mutable version of [`try_next_id`]
 ///
 /// ```rust
-/// use snowcloud::Error;
 /// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
 /// type MyCloud = snowcloud::Generator<MyFlake>;
 ///
@@ -99,77 +296,1541 @@ where
 ///
 /// // create more snowflakes than what is possible in a millisecond
 /// for _ in 0..(MyFlake::MAX_SEQUENCE as usize * 2) {
-///     let Some(result) = snowcloud::wait::blocking_next_id_mut(&mut cloud, 2) else {
-///         println!("ran out of attempts to get a new snowflake");
-///         continue;
-///     };
-///
-///     let flake = result.expect("failed to create snowflake");
+///     let flake = snowcloud::wait::try_next_id_mut(&mut cloud, 2)
+///         .expect("failed to create snowflake");
 ///
 ///     println!("{}", flake.id());
 /// }
 /// ```
-pub fn blocking_next_id_mut<C>(cloud: &mut C, mut attempts: u8) -> Option<std::result::Result<C::Id, C::Error>>
+pub fn try_next_id_mut<C>(cloud: &mut C, attempts: u8) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    try_next_id_with_mut(cloud, attempts, &DEFAULT_STRATEGY)
+}
+
+/// mutable version of [`try_next_id_with`]
+pub fn try_next_id_with_mut<C>(cloud: &mut C, attempts: u8, strategy: &WaitStrategy) -> std::result::Result<C::Id, WaitError<C::Error>>
 where
     C: IdGeneratorMut,
     C::Error: NextAvailId,
     C::Output: Into<std::result::Result<C::Id, C::Error>>,
 {
-    while attempts != 0 {
+    for _ in 0..attempts {
         match cloud.next_id().into() {
             Ok(sf) => {
-                return Some(Ok(sf))
+                return Ok(sf)
             },
             Err(err) => {
                 let Some(dur) = err.next_avail_id() else {
-                    return Some(Err(err));
+                    return Err(WaitError::Generator(err));
                 };
 
-                block_duration(dur);
+                block_duration(dur, strategy);
             }
         }
+    }
+
+    Err(WaitError::Exhausted { attempts })
+}
+
+/// blocks the current thread for next available id with a given number of
+/// attempts
+///
+/// if total attempts reaches 0 then the result will be none otherwise will be
+/// some with whatever happened when generating the id
+#[deprecated(note = "use try_next_id which returns a WaitError instead")]
+pub fn blocking_next_id<C>(cloud: &C, attempts: u8) -> Option<std::result::Result<C::Id, C::Error>>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    match try_next_id(cloud, attempts) {
+        Ok(sf) => Some(Ok(sf)),
+        Err(WaitError::Generator(err)) => Some(Err(err)),
+        Err(WaitError::Exhausted { .. } | WaitError::DeadlineReached | WaitError::Cancelled) => None,
+    }
+}
+
+/// mutable version of [`blocking_next_id`]
+///
+/// if total attempts reaches 0 then the result will be None otherwise will be
+/// some with whatever happened when generating the id
+#[deprecated(note = "use try_next_id_mut which returns a WaitError instead")]
+pub fn blocking_next_id_mut<C>(cloud: &mut C, attempts: u8) -> Option<std::result::Result<C::Id, C::Error>>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    match try_next_id_mut(cloud, attempts) {
+        Ok(sf) => Some(Ok(sf)),
+        Err(WaitError::Generator(err)) => Some(Err(err)),
+        Err(WaitError::Exhausted { .. } | WaitError::DeadlineReached | WaitError::Cancelled) => None,
+    }
+}
+
+/// statistics collected while waiting for an id
+///
+/// values are added to so the same stats can be passed to multiple calls to
+/// see totals
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WaitStats {
+    /// number of times the generator was retried after waiting
+    pub retries: u32,
+
+    /// total amount of time spent waiting
+    pub total_waited: Duration,
+
+    /// longest single wait
+    pub longest_wait: Duration,
+}
+
+impl WaitStats {
+    fn record(&mut self, waited: Duration) {
+        self.retries += 1;
+        self.total_waited += waited;
+        self.longest_wait = self.longest_wait.max(waited);
+    }
+}
+
+/// same as [`try_next_id`] but records how long was spent waiting
+///
+/// ```rust
+/// use snowcloud::cloud::wait::{self, WaitStats};
+///
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+/// let mut stats = WaitStats::default();
+///
+/// for _ in 0..(MyFlake::MAX_SEQUENCE as usize * 2) {
+///     wait::try_next_id_stats(&cloud, 2, &mut stats)
+///         .expect("failed to create snowflake");
+/// }
+///
+/// println!("{:?}", stats);
+/// ```
+pub fn try_next_id_stats<C>(cloud: &C, attempts: u8, stats: &mut WaitStats) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    for _ in 0..attempts {
+        match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                let start = Instant::now();
 
-        attempts -= 1;
+                block_duration(dur, &DEFAULT_STRATEGY);
+
+                stats.record(start.elapsed());
+            }
+        }
     }
 
-    None
+    Err(WaitError::Exhausted { attempts })
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+/// mutable version of [`try_next_id_stats`]
+pub fn try_next_id_stats_mut<C>(cloud: &mut C, attempts: u8, stats: &mut WaitStats) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    for _ in 0..attempts {
+        match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
 
-    use crate::{sync, Generator};
+                let start = Instant::now();
 
-    type SIDI64 = snowcloud_flake::i64::SingleIdFlake<43, 16, 4>;
+                block_duration(dur, &DEFAULT_STRATEGY);
 
-    const START_TIME: u64 = 1679082337000;
+                stats.record(start.elapsed());
+            }
+        }
+    }
 
-    #[test]
-    fn check_blocking_next_id_mut() {
-        let mut gen = Generator::<SIDI64>::new(START_TIME, 1)
-            .expect("failed to create generator");
+    Err(WaitError::Exhausted { attempts })
+}
 
-        for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
-            let Some(result) = blocking_next_id_mut(&mut gen, 2) else {
-                panic!("ran out of attempts to get a new snowflake");
-            };
+/// iterator over ids from a generator
+///
+/// created from [`iter`]. errors with a next available id are waited out and
+/// only other errors are yielded. the iterator will end after yielding an
+/// error.
+#[derive(Debug)]
+pub struct Iter<'a, C> {
+    cloud: &'a C,
+    done: bool,
+}
+
+impl<'a, C> Iterator for Iter<'a, C>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    type Item = std::result::Result<C::Id, C::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.cloud.next_id().into() {
+                Ok(sf) => {
+                    return Some(Ok(sf));
+                },
+                Err(err) => {
+                    let Some(dur) = err.next_avail_id() else {
+                        self.done = true;
 
-            result.expect("failed to generate snowflake");
+                        return Some(Err(err));
+                    };
+
+                    block_duration(dur, &DEFAULT_STRATEGY);
+                }
+            }
         }
+
+        None
     }
+}
 
-    #[test]
-    fn check_blocking_next_id() {
-        let gen = sync::MutexGenerator::<SIDI64>::new(START_TIME, 1)
-            .expect("failed to create generator");
+impl<'a, C> FusedIterator for Iter<'a, C>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{}
 
-        for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
-            let Some(result) = blocking_next_id(&gen, 2) else {
-                panic!("ran out of attempts to get a new snowflake");
-            };
+/// mutable version of [`Iter`]
+///
+/// created from [`iter_mut`]
+#[derive(Debug)]
+pub struct IterMut<'a, C> {
+    cloud: &'a mut C,
+    done: bool,
+}
+
+impl<'a, C> Iterator for IterMut<'a, C>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    type Item = std::result::Result<C::Id, C::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.cloud.next_id().into() {
+                Ok(sf) => {
+                    return Some(Ok(sf));
+                },
+                Err(err) => {
+                    let Some(dur) = err.next_avail_id() else {
+                        self.done = true;
 
-            result.expect("failed to generator snowflake");
+                        return Some(Err(err));
+                    };
+
+                    block_duration(dur, &DEFAULT_STRATEGY);
+                }
+            }
         }
+
+        None
+    }
+}
+
+impl<'a, C> FusedIterator for IterMut<'a, C>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{}
+
+/// returns an iterator that waits for each id from the generator
+///
+/// ```rust
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+///
+/// let list = snowcloud::wait::iter(&cloud)
+///     .take(MyFlake::MAX_SEQUENCE as usize * 2)
+///     .collect::<Result<Vec<_>, _>>()
+///     .expect("failed to create snowflakes");
+///
+/// println!("{}", list.len());
+/// ```
+pub fn iter<C>(cloud: &C) -> Iter<'_, C>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    Iter {
+        cloud,
+        done: false,
+    }
+}
+
+/// mutable version of [`iter`]
+pub fn iter_mut<C>(cloud: &mut C) -> IterMut<'_, C>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    IterMut {
+        cloud,
+        done: false,
+    }
+}
+
+/// blocks the current thread for the next available id until the deadline
+///
+/// retries as long as the generator returns an error with a next available
+/// id and the deadline has not passed. waits are shortened to not go past
+/// the deadline. if the deadline is reached then
+/// [`WaitError::DeadlineReached`] is returned
+///
+/// ```rust
+/// use std::time::{Duration, Instant};
+///
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+/// let deadline = Instant::now() + Duration::from_millis(5);
+///
+/// let flake = snowcloud::wait::try_next_id_until(&cloud, deadline)
+///     .expect("failed to create snowflake");
+///
+/// println!("{}", flake.id());
+/// ```
+pub fn try_next_id_until<C>(cloud: &C, deadline: Instant) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    loop {
+        match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                let remaining = deadline.saturating_duration_since(Instant::now());
+
+                if remaining.is_zero() {
+                    return Err(WaitError::DeadlineReached);
+                }
+
+                block_duration(&remaining.min(*dur), &DEFAULT_STRATEGY);
+            }
+        }
+    }
+}
+
+/// mutable version of [`try_next_id_until`]
+pub fn try_next_id_until_mut<C>(cloud: &mut C, deadline: Instant) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    loop {
+        match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                let remaining = deadline.saturating_duration_since(Instant::now());
+
+                if remaining.is_zero() {
+                    return Err(WaitError::DeadlineReached);
+                }
+
+                block_duration(&remaining.min(*dur), &DEFAULT_STRATEGY);
+            }
+        }
+    }
+}
+
+/// parks the current thread for a duration that can be woken early
+///
+/// a thin wrapper around [`thread::park_timeout`] that is tied to the thread
+/// that created it. an [`Unparker`] can be sent to other threads to wake the
+/// parked thread. spurious wakeups are ignored and the thread will park again
+/// for the remaining time. if the unparker is called before the thread parks
+/// then the next park will return immediately.
+#[derive(Debug)]
+pub struct Parker {
+    thread: Thread,
+    notified: Arc<AtomicBool>,
+    // parking only works on the thread that created the parker
+    _not_send: PhantomData<*const ()>,
+}
+
+/// wakes the thread of a [`Parker`]
+#[derive(Debug, Clone)]
+pub struct Unparker {
+    thread: Thread,
+    notified: Arc<AtomicBool>,
+}
+
+impl Parker {
+    /// returns a new Parker for the current thread
+    pub fn new() -> Self {
+        Parker {
+            thread: thread::current(),
+            notified: Arc::new(AtomicBool::new(false)),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// returns an Unparker that will wake this parker
+    pub fn unparker(&self) -> Unparker {
+        Unparker {
+            thread: self.thread.clone(),
+            notified: Arc::clone(&self.notified),
+        }
+    }
+
+    /// parks the current thread until the duration passes or it is unparked
+    ///
+    /// returns true if it was unparked
+    pub fn park_timeout(&self, dur: Duration) -> bool {
+        let start = Instant::now();
+
+        loop {
+            if self.notified.swap(false, Ordering::AcqRel) {
+                return true;
+            }
+
+            let Some(remaining) = dur.checked_sub(start.elapsed()) else {
+                return false;
+            };
+
+            thread::park_timeout(remaining);
+        }
+    }
+}
+
+impl Default for Parker {
+    fn default() -> Self {
+        Parker::new()
+    }
+}
+
+impl Unparker {
+    /// wakes the parked thread
+    pub fn unpark(&self) {
+        self.notified.store(true, Ordering::Release);
+        self.thread.unpark();
+    }
+}
+
+/// parks the current thread for the next available id with a given number
+/// of attempts
+///
+/// same as [`try_next_id`] but parks for the duration instead. if the parker
+/// is unparked the wait is cut short and the generator is tried again,
+/// using up an attempt. useful for when another thread knows that the
+/// millisecond has rolled over or when shutting down.
+///
+/// ```rust
+/// use snowcloud::cloud::wait::{self, Parker};
+///
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+/// let parker = Parker::new();
+/// let unparker = parker.unparker();
+///
+/// // the unparker can be sent to another thread to wake this one
+/// std::thread::spawn(move || unparker.unpark());
+///
+/// let flake = wait::try_next_id_parked(&cloud, 2, &parker)
+///     .expect("failed to create snowflake");
+///
+/// println!("{}", flake.id());
+/// ```
+pub fn try_next_id_parked<C>(cloud: &C, attempts: u8, parker: &Parker) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    for _ in 0..attempts {
+        match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                parker.park_timeout(*dur);
+            }
+        }
+    }
+
+    Err(WaitError::Exhausted { attempts })
+}
+
+/// mutable version of [`try_next_id_parked`]
+pub fn try_next_id_parked_mut<C>(cloud: &mut C, attempts: u8, parker: &Parker) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    for _ in 0..attempts {
+        match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                parker.park_timeout(*dur);
+            }
+        }
+    }
+
+    Err(WaitError::Exhausted { attempts })
+}
+
+/// blocks the current thread for the next available id until attempts run
+/// out or the wait is cancelled
+///
+/// same as [`try_next_id`] but the cancel flag is checked before
+/// retrying and while waiting. once the flag is set this will return
+/// [`WaitError::Cancelled`] without waiting for the full duration.
+///
+/// ```rust
+/// use std::sync::atomic::AtomicBool;
+///
+/// use snowcloud::cloud::wait::{self, WaitError};
+///
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+/// let shutdown = AtomicBool::new(false);
+///
+/// match wait::try_next_id_cancellable(&cloud, 2, &shutdown) {
+///     Ok(flake) => {
+///         println!("{}", flake.id());
+///     },
+///     Err(WaitError::Cancelled) => {
+///         println!("shutting down");
+///     },
+///     Err(err) => {
+///         panic!("failed to create snowflake: {}", err);
+///     }
+/// }
+/// ```
+pub fn try_next_id_cancellable<C>(cloud: &C, attempts: u8, cancel: &AtomicBool) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    for _ in 0..attempts {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(WaitError::Cancelled);
+        }
+
+        match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                if !block_duration_stoppable(dur, &DEFAULT_STRATEGY, || cancel.load(Ordering::Relaxed)) {
+                    return Err(WaitError::Cancelled);
+                }
+            }
+        }
+    }
+
+    Err(WaitError::Exhausted { attempts })
+}
+
+/// mutable version of [`try_next_id_cancellable`]
+pub fn try_next_id_cancellable_mut<C>(cloud: &mut C, attempts: u8, cancel: &AtomicBool) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    for _ in 0..attempts {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(WaitError::Cancelled);
+        }
+
+        match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                if !block_duration_stoppable(dur, &DEFAULT_STRATEGY, || cancel.load(Ordering::Relaxed)) {
+                    return Err(WaitError::Cancelled);
+                }
+            }
+        }
+    }
+
+    Err(WaitError::Exhausted { attempts })
+}
+
+/// waits for the next available id with a given number of attempts
+///
+/// async version of [`try_next_id`]. the sleeper is called with the
+/// duration to wait for and returns a future that resolves once the duration
+/// has passed. this allows for any async runtime to be used. if total
+/// attempts reaches 0 then [`WaitError::Exhausted`] is returned
+///
+/// ```rust
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+///
+/// futures::executor::block_on(async {
+///     // create more snowflakes than what is possible in a millisecond
+///     for _ in 0..(MyFlake::MAX_SEQUENCE as usize * 2) {
+///         // a runtime would provide its own sleep, tokio::time::sleep for
+///         // example
+///         let sleeper = |dur| async move { std::thread::sleep(dur) };
+///
+///         let flake = snowcloud::wait::async_next_id(&cloud, 2, sleeper).await
+///             .expect("failed to create snowflake");
+///
+///         println!("{}", flake.id());
+///     }
+/// });
+/// ```
+pub async fn async_next_id<C, S, Fut>(cloud: &C, attempts: u8, sleeper: S) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+    S: Fn(Duration) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    for _ in 0..attempts {
+        // the error is not held across the await so the future can still be
+        // Send if the id and error are
+        let dur = match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                *dur
+            }
+        };
+
+        sleeper(dur).await;
+    }
+
+    Err(WaitError::Exhausted { attempts })
+}
+
+/// mutable version of [`async_next_id`]
+///
+/// ```rust
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::Generator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let mut cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+///
+/// futures::executor::block_on(async {
+///     let sleeper = |dur| async move { std::thread::sleep(dur) };
+///
+///     let flake = snowcloud::wait::async_next_id_mut(&mut cloud, 2, sleeper).await
+///         .expect("failed to create snowflake");
+///
+///     println!("{}", flake.id());
+/// });
+/// ```
+pub async fn async_next_id_mut<C, S, Fut>(cloud: &mut C, attempts: u8, sleeper: S) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+    S: Fn(Duration) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    for _ in 0..attempts {
+        let dur = match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                *dur
+            }
+        };
+
+        sleeper(dur).await;
+    }
+
+    Err(WaitError::Exhausted { attempts })
+}
+
+/// settings for [`backoff_next_id`] and [`backoff_next_id_mut`]
+///
+/// the delay for a given retry is `initial * multiplier ^ retry` capped at
+/// max_delay. the wait is the larger of the delay and the time to the next
+/// available id with jitter then randomly adding up to a fraction of the
+/// wait so that waiting threads do not all wake at the same time.
+#[derive(Debug, Clone)]
+pub struct BackoffPolicy {
+    /// delay used for the first retry
+    pub initial: Duration,
+
+    /// amount the delay grows by for each retry
+    pub multiplier: f64,
+
+    /// the largest delay before jitter is applied
+    pub max_delay: Duration,
+
+    /// total number of times to try for an id
+    pub max_attempts: u8,
+
+    /// fraction of the wait to randomly add. clamped between 0 and 1
+    pub jitter: f64,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        BackoffPolicy {
+            initial: Duration::from_micros(100),
+            multiplier: 2.0,
+            max_delay: Duration::from_millis(5),
+            max_attempts: 10,
+            jitter: 0.25,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// the delay for a given retry without jitter
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(retry.min(i32::MAX as u32) as i32);
+
+        let secs = (self.initial.as_secs_f64() * factor).min(self.max_delay.as_secs_f64());
+
+        secs_to_duration(secs)
+    }
+
+    /// the delay for a given retry with jitter applied
+    pub fn jittered_delay(&self, retry: u32) -> Duration {
+        self.jittered(self.delay(retry))
+    }
+
+    /// how long to wait for a given retry when the next id is available
+    /// after next_avail
+    ///
+    /// jitter is applied after taking the larger of the two so that threads
+    /// given the same next available duration do not wake together
+    pub fn retry_wait(&self, retry: u32, next_avail: Duration) -> Duration {
+        self.jittered(self.delay(retry).max(next_avail))
+    }
+
+    /// adds a random amount of up to jitter of the given duration
+    fn jittered(&self, dur: Duration) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0);
+
+        if jitter == 0.0 {
+            return dur;
+        }
+
+        secs_to_duration(dur.as_secs_f64() * (1.0 + jitter * random_unit()))
+    }
+}
+
+/// converts seconds to a duration without panicking on large values
+///
+/// `Duration::MAX` as seconds rounds up to a float that no longer fits in a
+/// duration so anything above half of `u64::MAX` seconds is capped to it
+fn secs_to_duration(secs: f64) -> Duration {
+    Duration::from_secs_f64(secs.min(u64::MAX as f64 / 2.0))
+}
+
+/// returns a random value between 0 and 1
+///
+/// each RandomState is created with different keys so hashing nothing is
+/// enough to get a value that is random enough for jitter
+fn random_unit() -> f64 {
+    let value = RandomState::new().build_hasher().finish();
+
+    (value >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// blocks the current thread for the next available id using exponential
+/// backoff
+///
+/// on each error with a next available id this will wait for the larger of
+/// the provided duration and the backoff delay for the current retry with
+/// jitter added, see [`BackoffPolicy::retry_wait`]. if the max attempts is
+/// reached then [`WaitError::Exhausted`] is returned
+///
+/// ```rust
+/// use snowcloud::cloud::wait::{self, BackoffPolicy};
+///
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::sync::MutexGenerator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MyCloud::new(START_TIME, 1)
+///     .expect("failed to create MyCloud");
+/// let policy = BackoffPolicy::default();
+///
+/// let flake = wait::backoff_next_id(&cloud, &policy)
+///     .expect("failed to create snowflake");
+///
+/// println!("{}", flake.id());
+/// ```
+pub fn backoff_next_id<C>(cloud: &C, policy: &BackoffPolicy) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGenerator,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    for retry in 0..(policy.max_attempts as u32) {
+        match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                block_duration(&policy.retry_wait(retry, *dur), &DEFAULT_STRATEGY);
+            }
+        }
+    }
+
+    Err(WaitError::Exhausted { attempts: policy.max_attempts })
+}
+
+/// mutable version of [`backoff_next_id`]
+pub fn backoff_next_id_mut<C>(cloud: &mut C, policy: &BackoffPolicy) -> std::result::Result<C::Id, WaitError<C::Error>>
+where
+    C: IdGeneratorMut,
+    C::Error: NextAvailId,
+    C::Output: Into<std::result::Result<C::Id, C::Error>>,
+{
+    for retry in 0..(policy.max_attempts as u32) {
+        match cloud.next_id().into() {
+            Ok(sf) => {
+                return Ok(sf)
+            },
+            Err(err) => {
+                let Some(dur) = err.next_avail_id() else {
+                    return Err(WaitError::Generator(err));
+                };
+
+                block_duration(&policy.retry_wait(retry, *dur), &DEFAULT_STRATEGY);
+            }
+        }
+    }
+
+    Err(WaitError::Exhausted { attempts: policy.max_attempts })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{sync, Generator};
+
+    type SIDI64 = snowcloud_flake::i64::SingleIdFlake<43, 16, 4>;
+
+    const START_TIME: u64 = 1679082337000;
+
+    /// generator that never has an id available
+    struct Exhausted;
+
+    impl IdGenerator for Exhausted {
+        type Error = crate::error::Error;
+        type Id = SIDI64;
+        type Output = crate::error::Result<SIDI64>;
+
+        fn next_id(&self) -> Self::Output {
+            Err(crate::error::Error::SequenceMaxReached(Duration::from_micros(10)))
+        }
+    }
+
+    #[test]
+    fn check_try_next_id_mut() {
+        let mut gen = Generator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+
+        for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
+            try_next_id_mut(&mut gen, 2).expect("failed to generate snowflake");
+        }
+    }
+
+    #[test]
+    fn check_try_next_id() {
+        let gen = sync::MutexGenerator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+
+        for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
+            try_next_id(&gen, 2).expect("failed to generator snowflake");
+        }
+    }
+
+    #[test]
+    fn try_next_id_exhausted() {
+        match try_next_id(&Exhausted, 3) {
+            Err(WaitError::Exhausted { attempts }) => {
+                assert_eq!(attempts, 3);
+            },
+            result => panic!("expected attempts to be exhausted: {:?}", result)
+        }
+
+        let mut stats = WaitStats::default();
+
+        assert!(matches!(
+            try_next_id_stats(&Exhausted, 3, &mut stats),
+            Err(WaitError::Exhausted { attempts: 3 })
+        ));
+        assert_eq!(stats.retries, 3);
+
+        let policy = BackoffPolicy {
+            initial: Duration::from_micros(1),
+            max_attempts: 4,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            backoff_next_id(&Exhausted, &policy),
+            Err(WaitError::Exhausted { attempts: 4 })
+        ));
+        assert!(matches!(
+            futures::executor::block_on(async_next_id(&Exhausted, 2, |_| async {})),
+            Err(WaitError::Exhausted { attempts: 2 })
+        ));
+        assert!(matches!(
+            try_next_id_cancellable(&Exhausted, 3, &AtomicBool::new(false)),
+            Err(WaitError::Exhausted { attempts: 3 })
+        ));
+
+        #[allow(deprecated)]
+        let result = blocking_next_id(&Exhausted, 3);
+
+        assert!(result.is_none(), "deprecated wrapper did not return None");
+    }
+
+    async fn sleeper(dur: Duration) {
+        std::thread::sleep(dur);
+    }
+
+    #[test]
+    fn check_async_next_id_mut() {
+        let mut gen = Generator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+
+        futures::executor::block_on(async {
+            for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
+                async_next_id_mut(&mut gen, 2, sleeper).await
+                    .expect("failed to generate snowflake");
+            }
+        });
+    }
+
+    #[test]
+    fn check_async_next_id() {
+        let gen = sync::MutexGenerator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+
+        futures::executor::block_on(async {
+            for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
+                async_next_id(&gen, 2, sleeper).await
+                    .expect("failed to generate snowflake");
+            }
+        });
+    }
+
+    #[test]
+    fn async_attempts_exhausted() {
+        let gen = sync::MutexGenerator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+
+        futures::executor::block_on(async {
+            // a sleeper that never waits will exhaust the attempts once the
+            // sequence has been used up for the millisecond
+            let mut exhausted = false;
+
+            for _ in 0..(SIDI64::MAX_SEQUENCE * 2) {
+                if let Err(WaitError::Exhausted { attempts }) = async_next_id(&gen, 1, |_| async {}).await {
+                    assert_eq!(attempts, 1);
+
+                    exhausted = true;
+                    break;
+                }
+            }
+
+            assert!(exhausted, "attempts were never exhausted");
+        });
+    }
+
+    fn future_epoch(ahead: u64) -> u64 {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap();
+
+        now.as_millis() as u64 + ahead
+    }
+
+    #[test]
+    fn cancelled_mid_wait() {
+        // a clamped generator will report the time to the epoch once the
+        // sequence is exhausted giving a long wait to cancel
+        let gen = sync::MutexGenerator::<SIDI64>::new_with_policy(
+            future_epoch(2_000),
+            1,
+            crate::EpochPolicy::Clamp
+        ).expect("failed to create generator");
+
+        for _ in 0..SIDI64::MAX_SEQUENCE {
+            gen.next_id().expect("failed to generate snowflake");
+        }
+
+        match gen.next_id() {
+            Err(crate::error::Error::SequenceMaxReached(dur)) => {
+                assert!(dur >= Duration::from_secs(1), "wait is too short to cancel: {:?}", dur);
+            },
+            result => panic!("expected the sequence to be exhausted: {:?}", result),
+        }
+
+        let cancel = std::sync::Arc::new(AtomicBool::new(false));
+        let setter = {
+            let c = std::sync::Arc::clone(&cancel);
+
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(50));
+
+                let cancelled_at = Instant::now();
+
+                c.store(true, Ordering::Relaxed);
+
+                cancelled_at
+            })
+        };
+
+        let outcome = try_next_id_cancellable(&gen, 2, &cancel);
+        let returned_at = Instant::now();
+
+        let cancelled_at = setter.join().expect("thread paniced");
+        let elapsed = returned_at.saturating_duration_since(cancelled_at);
+
+        assert!(matches!(outcome, Err(WaitError::Cancelled)), "wait was not cancelled: {:?}", outcome);
+        // the flag is checked after every sleep so it should be seen within
+        // a quantum with some room for the scheduler
+        assert!(elapsed <= SLEEP_QUANTUM * 10, "cancel took too long: {:?}", elapsed);
+    }
+
+    #[test]
+    fn check_try_next_id_cancellable_mut() {
+        let mut gen = Generator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+        let cancel = AtomicBool::new(false);
+
+        for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
+            try_next_id_cancellable_mut(&mut gen, 2, &cancel).expect("failed to generate snowflake");
+        }
+
+        cancel.store(true, Ordering::Relaxed);
+
+        assert!(matches!(
+            try_next_id_cancellable_mut(&mut gen, 2, &cancel),
+            Err(WaitError::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn backoff_delays() {
+        let policy = BackoffPolicy {
+            initial: Duration::from_micros(100),
+            multiplier: 2.0,
+            max_delay: Duration::from_millis(1),
+            max_attempts: 8,
+            jitter: 0.0,
+        };
+        let expected = [100, 200, 400, 800, 1_000, 1_000];
+
+        for (retry, micros) in expected.into_iter().enumerate() {
+            assert_eq!(policy.delay(retry as u32), Duration::from_micros(micros));
+            assert_eq!(policy.jittered_delay(retry as u32), Duration::from_micros(micros));
+        }
+
+        let policy = BackoffPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+
+        for retry in 0..8 {
+            let delay = policy.delay(retry);
+            let jittered = policy.jittered_delay(retry);
+
+            assert!(jittered >= delay && jittered <= delay.mul_f64(1.5), "jitter out of range: {:?} {:?}", delay, jittered);
+        }
+    }
+
+    #[test]
+    fn backoff_jitter_after_next_avail() {
+        let policy = BackoffPolicy::default();
+        // longer than the first delays so the jitter has to be applied to it
+        let next_avail = Duration::from_micros(800);
+
+        let first = policy.retry_wait(0, next_avail);
+        let second = policy.retry_wait(0, next_avail);
+
+        assert_ne!(first, second, "waits were not jittered");
+
+        for wait in [first, second] {
+            assert!(wait >= next_avail && wait <= next_avail.mul_f64(1.25), "wait out of range: {:?}", wait);
+        }
+
+        let policy = BackoffPolicy {
+            jitter: 0.0,
+            ..policy
+        };
+
+        assert_eq!(policy.retry_wait(0, next_avail), next_avail);
+        assert_eq!(policy.retry_wait(4, next_avail), Duration::from_micros(1_600));
+    }
+
+    #[test]
+    fn backoff_delays_unbounded() {
+        let policy = BackoffPolicy {
+            initial: Duration::from_micros(100),
+            multiplier: 2.0,
+            max_delay: Duration::MAX,
+            max_attempts: u8::MAX,
+            jitter: 0.0,
+        };
+        let capped = Duration::from_secs(u64::MAX / 2 + 1);
+
+        assert_eq!(policy.delay(10), Duration::from_micros(102_400));
+
+        for retry in [100, 1_000, u32::MAX] {
+            assert_eq!(policy.delay(retry), capped);
+            assert_eq!(policy.jittered_delay(retry), capped);
+        }
+
+        let policy = BackoffPolicy {
+            jitter: 1.0,
+            ..policy
+        };
+
+        for retry in [100, 1_000, u32::MAX] {
+            assert!(policy.jittered_delay(retry) <= capped);
+        }
+    }
+
+    #[test]
+    fn backoff_threaded() {
+        let gen = sync::MutexGenerator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+        let policy = std::sync::Arc::new(BackoffPolicy {
+            max_attempts: 20,
+            ..Default::default()
+        });
+        let start = Instant::now();
+        let mut handles = Vec::with_capacity(4);
+
+        for _ in 0..handles.capacity() {
+            let g = gen.clone();
+            let p = std::sync::Arc::clone(&policy);
+
+            handles.push(std::thread::spawn(move || {
+                let mut list = Vec::with_capacity(SIDI64::MAX_SEQUENCE as usize * 2);
+
+                while list.len() < list.capacity() {
+                    list.push(backoff_next_id(&g, &p).expect("failed to generate snowflake"));
+                }
+
+                list
+            }));
+        }
+
+        let mut unique_ids = std::collections::HashSet::new();
+
+        for handle in handles {
+            for flake in handle.join().expect("thread paniced") {
+                assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+            }
+        }
+
+        // 120 ids at 15 per millisecond with at most a 5ms delay
+        assert!(start.elapsed() < Duration::from_secs(2), "backoff took too long: {:?}", start.elapsed());
+    }
+
+    #[test]
+    fn check_backoff_next_id_mut() {
+        let mut gen = Generator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+        let policy = BackoffPolicy::default();
+
+        for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
+            backoff_next_id_mut(&mut gen, &policy).expect("failed to generate snowflake");
+        }
+    }
+
+    #[test]
+    fn check_try_next_id_until() {
+        let gen = sync::MutexGenerator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+
+        for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
+            let deadline = Instant::now() + Duration::from_millis(50);
+
+            try_next_id_until(&gen, deadline).expect("failed to generate snowflake");
+        }
+    }
+
+    #[test]
+    fn try_next_id_until_passed() {
+        let mut gen = Generator::<SIDI64>::new_with_policy(
+            future_epoch(2_000),
+            1,
+            crate::EpochPolicy::Clamp
+        ).expect("failed to create generator");
+
+        for _ in 0..SIDI64::MAX_SEQUENCE {
+            gen.next_id().expect("failed to generate snowflake");
+        }
+
+        let start = Instant::now();
+
+        let result = try_next_id_until_mut(&mut gen, start);
+
+        assert!(matches!(result, Err(WaitError::DeadlineReached)), "generated id after deadline: {:?}", result);
+        assert!(start.elapsed() < Duration::from_millis(50), "waited past deadline: {:?}", start.elapsed());
+    }
+
+    #[test]
+    fn wait_stats() {
+        let mut gen = Generator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+        let mut stats = WaitStats::default();
+
+        // keep going until the sequence is exhausted to know what the
+        // advertised wait is
+        let advertised = loop {
+            match gen.next_id() {
+                Ok(_) => {},
+                Err(crate::error::Error::SequenceMaxReached(dur)) => {
+                    break dur;
+                },
+                Err(err) => {
+                    panic!("failed to generate snowflake: {:?}", err);
+                }
+            }
+        };
+
+        try_next_id_stats_mut(&mut gen, 2, &mut stats).expect("failed to generate snowflake");
+
+        assert!(stats.retries >= 1, "no retries recorded");
+        assert!(stats.longest_wait <= stats.total_waited);
+        assert!(!stats.total_waited.is_zero(), "no wait recorded");
+        assert!(
+            stats.total_waited <= advertised + Duration::from_millis(5),
+            "waited longer than advertised: {:?} {:?}",
+            stats.total_waited,
+            advertised
+        );
+    }
+
+    #[test]
+    fn check_try_next_id_stats() {
+        let gen = sync::MutexGenerator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+        let mut stats = WaitStats::default();
+
+        for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
+            try_next_id_stats(&gen, 2, &mut stats).expect("failed to generate snowflake");
+        }
+
+        // three milliseconds worth of ids requires at least two rollovers
+        assert!(stats.retries >= 2, "not enough retries recorded: {:?}", stats);
+    }
+
+    #[test]
+    fn wait_strategies() {
+        let dur = Duration::from_millis(3);
+        let strategies = [
+            WaitStrategy::default(),
+            WaitStrategy::SleepYieldSpin {
+                sleep_over: Duration::from_millis(1),
+                yield_over: Duration::from_micros(100),
+            },
+            WaitStrategy::SpinOnly,
+            WaitStrategy::SleepOnly,
+            WaitStrategy::YieldOnly,
+        ];
+
+        for strategy in &strategies {
+            let start = Instant::now();
+
+            block_duration(&dur, strategy);
+
+            assert!(start.elapsed() >= dur, "{:?} returned early", strategy);
+
+            let start = Instant::now();
+
+            assert!(block_duration_stoppable(&dur, strategy, || false));
+            assert!(start.elapsed() >= dur, "{:?} returned early", strategy);
+        }
+    }
+
+    /// counts how many times each kind of wait is used while still waiting
+    /// on the current thread
+    #[derive(Debug, Default)]
+    struct CountingWaiter {
+        sleeps: usize,
+        longest_sleep: Duration,
+        yields: usize,
+        spins: usize,
+    }
+
+    impl Waiter for CountingWaiter {
+        fn sleep(&mut self, dur: Duration) {
+            self.sleeps += 1;
+            self.longest_sleep = self.longest_sleep.max(dur);
+            ThreadWaiter.sleep(dur);
+        }
+
+        fn yield_now(&mut self) {
+            self.yields += 1;
+            ThreadWaiter.yield_now();
+        }
+
+        fn spin(&mut self) {
+            self.spins += 1;
+            ThreadWaiter.spin();
+        }
+    }
+
+    #[test]
+    fn spin_only_never_sleeps() {
+        let mut waiter = CountingWaiter::default();
+
+        assert!(wait_duration(&Duration::from_millis(3), &WaitStrategy::SpinOnly, None::<fn() -> bool>, &mut waiter));
+        assert_eq!((waiter.sleeps, waiter.yields), (0, 0), "{:?}", waiter);
+        assert!(waiter.spins > 0, "{:?}", waiter);
+    }
+
+    #[test]
+    fn default_strategy_sleeps_once() {
+        let dur = Duration::from_millis(50);
+
+        // a single sleep for the whole duration when the wait cannot be
+        // stopped
+        let mut waiter = CountingWaiter::default();
+
+        wait_duration(&dur, &DEFAULT_STRATEGY, None::<fn() -> bool>, &mut waiter);
+
+        assert_eq!(waiter.sleeps, 1, "{:?}", waiter);
+        assert!(waiter.longest_sleep > SLEEP_QUANTUM, "{:?}", waiter);
+
+        // a stoppable wait can only sleep for a quantum at a time
+        let mut waiter = CountingWaiter::default();
+
+        wait_duration(&dur, &DEFAULT_STRATEGY, Some(|| false), &mut waiter);
+
+        assert!(waiter.sleeps > 1, "{:?}", waiter);
+        assert!(waiter.longest_sleep <= SLEEP_QUANTUM, "{:?}", waiter);
+    }
+
+    #[test]
+    fn check_try_next_id_with() {
+        let gen = sync::MutexGenerator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+
+        for strategy in [WaitStrategy::SpinOnly, WaitStrategy::SleepOnly, WaitStrategy::YieldOnly] {
+            for _ in 0..(SIDI64::MAX_SEQUENCE * 2) {
+                try_next_id_with(&gen, 2, &strategy).expect("failed to generate snowflake");
+            }
+        }
+
+        let mut gen = Generator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+
+        for _ in 0..(SIDI64::MAX_SEQUENCE * 2) {
+            try_next_id_with_mut(&mut gen, 2, &WaitStrategy::SpinOnly).expect("failed to generate snowflake");
+        }
+    }
+
+    #[test]
+    fn parker_timeout() {
+        let parker = Parker::new();
+        let dur = Duration::from_millis(5);
+        let start = Instant::now();
+
+        assert!(!parker.park_timeout(dur), "parker was unparked");
+        assert!(start.elapsed() >= dur, "parker returned early");
+    }
+
+    #[test]
+    fn parker_unparked() {
+        let gen = sync::MutexGenerator::<SIDI64>::new_with_policy(
+            future_epoch(2_000),
+            1,
+            crate::EpochPolicy::Clamp
+        ).expect("failed to create generator");
+
+        for _ in 0..SIDI64::MAX_SEQUENCE {
+            gen.next_id().expect("failed to generate snowflake");
+        }
+
+        let parker = Parker::new();
+        let unparker = parker.unparker();
+        let waker = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            unparker.unpark();
+        });
+
+        let start = Instant::now();
+        let result = try_next_id_parked(&gen, 1, &parker);
+        let elapsed = start.elapsed();
+
+        waker.join().expect("thread paniced");
+
+        assert!(matches!(result, Err(WaitError::Exhausted { attempts: 1 })), "unexpected result: {:?}", result);
+        assert!(elapsed < Duration::from_millis(500), "unpark took too long: {:?}", elapsed);
+    }
+
+    #[test]
+    fn check_try_next_id_parked_mut() {
+        let mut gen = Generator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+        let parker = Parker::new();
+
+        for _ in 0..(SIDI64::MAX_SEQUENCE * 3) {
+            try_next_id_parked_mut(&mut gen, 2, &parker).expect("failed to generate snowflake");
+        }
+    }
+
+    #[test]
+    fn check_iter() {
+        let gen = sync::MutexGenerator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+        let total = SIDI64::MAX_SEQUENCE as usize * 4;
+
+        let list = iter(&gen)
+            .take(total)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to generate snowflakes");
+        let mut unique_ids = std::collections::HashSet::new();
+
+        for flake in list {
+            assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+        }
+
+        assert_eq!(unique_ids.len(), total);
+    }
+
+    #[test]
+    fn check_iter_mut() {
+        let mut gen = Generator::<SIDI64>::new(START_TIME, 1)
+            .expect("failed to create generator");
+        let total = SIDI64::MAX_SEQUENCE as usize * 4;
+        let mut unique_ids = std::collections::HashSet::new();
+
+        for result in iter_mut(&mut gen).take(total) {
+            let flake = result.expect("failed to generate snowflake");
+
+            assert!(unique_ids.insert(flake.id()), "duplicate id generated: {:?}", flake);
+        }
+
+        assert_eq!(unique_ids.len(), total);
+    }
+
+    #[test]
+    fn iter_error() {
+        // 40 bits of milliseconds from UNIX_EPOCH ran out in 2004
+        let mut gen = Generator::<snowcloud_flake::i64::SingleIdFlake<40, 11, 12>>::new(0, 1)
+            .expect("failed to create generator");
+        let mut iter = iter_mut(&mut gen);
+
+        assert!(matches!(iter.next(), Some(Err(crate::error::Error::TimestampMaxReached))));
+        assert!(iter.next().is_none(), "iterator continued after error");
     }
 }
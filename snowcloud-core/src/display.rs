@@ -0,0 +1,55 @@
+//! helpers for displaying timestamps
+
+use std::fmt;
+use std::time::Duration;
+
+/// displays a duration since UNIX_EPOCH as an RFC 3339 timestamp in UTC with
+/// millisecond precision
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use snowcloud_core::display::UtcDisplay;
+///
+/// let created = UtcDisplay(Duration::from_millis(1679587200000));
+///
+/// assert_eq!(created.to_string(), "2023-03-23T16:00:00.000Z");
+///
+/// let leap_day = UtcDisplay(Duration::from_millis(1709251199999));
+///
+/// assert_eq!(leap_day.to_string(), "2024-02-29T23:59:59.999Z");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtcDisplay(pub Duration);
+
+impl fmt::Display for UtcDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let days = secs / 86_400;
+        let day_secs = secs % 86_400;
+
+        // converts days since UNIX_EPOCH to a civil date. based on
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let doe = z % 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            year,
+            month,
+            day,
+            day_secs / 3_600,
+            (day_secs % 3_600) / 60,
+            day_secs % 60,
+            self.0.subsec_millis(),
+        )
+    }
+}
@@ -3,7 +3,8 @@
 //! good use case could be for implementing different waiter functions or ways
 //! of getting ids from the base struct
 
-use std::time::Duration;
+use std::future::{self, Future};
+use std::time::{Duration, SystemTime};
 
 /// basics of an id generator
 ///
@@ -19,7 +20,8 @@ pub trait IdGenerator {
 
     /// to help with allowing for different situations, Output can
     /// what ever is needed. a [`Result`](std::result::Result) or if used in
-    /// an async context then an impl of [`Future`](core::future::Future) 
+    /// an async context then an impl of [`Future`](core::future::Future).
+    /// see [`IdGeneratorAsync`] for a defined async contract
     type Output;
 
     /// call to get the next available id
@@ -47,6 +49,44 @@ pub trait IdGeneratorMut {
     fn next_id(&mut self) -> Self::Output;
 }
 
+/// async version of [`IdGenerator`](crate::traits::IdGenerator)
+///
+/// the future returned from next_id must be Send so that it can be used with
+/// multi threaded runtimes. any IdGenerator that returns a Result with Send
+/// values implements this trait and will resolve immediately with the result
+/// of the generator.
+pub trait IdGeneratorAsync {
+    /// the potential error that could be returned from next_id
+    type Error;
+
+    /// the actual Id type that is returned from next_id
+    type Id;
+
+    /// the future returned from next_id
+    type Future<'a>: Future<Output = Result<Self::Id, Self::Error>> + Send + 'a
+    where
+        Self: 'a;
+
+    /// call to get the next available id
+    fn next_id(&self) -> Self::Future<'_>;
+}
+
+impl<G> IdGeneratorAsync for G
+where
+    G: IdGenerator,
+    G::Output: Into<Result<G::Id, G::Error>>,
+    G::Id: Send,
+    G::Error: Send,
+{
+    type Error = G::Error;
+    type Id = G::Id;
+    type Future<'a> = future::Ready<Result<G::Id, G::Error>> where Self: 'a;
+
+    fn next_id(&self) -> Self::Future<'_> {
+        future::ready(IdGenerator::next_id(self).into())
+    }
+}
+
 /// for retrieving the duration of the next available id
 ///
 /// [`Error`](crate::Error) implements this trait as an example
@@ -67,9 +107,11 @@ pub trait Id {
 pub trait IdBuilder {
     type Output;
 
+    /// sets the timestamp of the id. the value is in the ticks returned from
+    /// [`FromIdGenerator::ticks`] and will return false if it does not fit
     fn with_ts(&mut self, ts: u64) -> bool;
     fn with_seq(&mut self, seq: u64) -> bool;
-    fn with_dur(&mut self, dur: Duration) -> () {}
+    fn with_dur(&mut self, _dur: Duration) {}
 
     fn build(self) -> Self::Output;
 }
@@ -85,24 +127,129 @@ pub trait FromIdGenerator: Sized {
     type IdSegType;
     type Builder;
 
+    /// total number of bits used by all segments of the id
+    const LAYOUT_BITS: u32;
+
+    /// max number of bits that are available to the id. an example would be
+    /// 63 for an `i64` since the sign bit cannot be used
+    const MAX_LAYOUT_BITS: u32;
+
     /// validates a given IdSegType.
     fn valid_id(v: &Self::IdSegType) -> bool;
 
     /// validates a given epoch value
     fn valid_epoch(e: &u64) -> bool;
 
+    /// validates that the bits used by the id fit within the available bits
+    fn valid_layout() -> bool {
+        Self::LAYOUT_BITS <= Self::MAX_LAYOUT_BITS
+    }
+
     fn builder(ids: &Self::IdSegType) -> Self::Builder;
+
+    /// converts the time since the epoch into the ticks that will be given to
+    /// [`IdBuilder::with_ts`]. defaults to milliseconds
+    ///
+    /// generators will also use this to determine if two ids were created in
+    /// the same tick and the sequence needs to be incremented
+    fn ticks(dur: &Duration) -> u64 {
+        dur.as_secs() * 1_000 + dur.subsec_millis() as u64
+    }
+
+    /// returns the time remaining until the next tick. defaults to the next
+    /// millisecond
+    fn next_tick(dur: &Duration) -> Duration {
+        Duration::from_nanos((1_000_000 - (dur.subsec_nanos() % 1_000_000)) as u64)
+    }
 }
 
-// when generic_const_exprs is stable this will be used to check that the
-// provided bit values equal to 63
-/*
-enum Assert<const CHECK: bool> {}
+/// access to the parts of a flake that are set by a generator
+///
+/// allows for a generator to check if a flake could have been created by it
+pub trait FlakeParts: FromIdGenerator {
+    /// returns the id segments of the flake in the same form that is given
+    /// to a generator
+    fn id_segs(&self) -> Self::IdSegType;
+
+    /// returns the timestamp of the flake in the ticks returned from
+    /// [`FromIdGenerator::ticks`]
+    fn timestamp_ticks(&self) -> u64;
+}
 
-trait IsTrue {}
-trait IsFalse {}
+/// bits used by each part of a flake
+///
+/// provided by [`FlakeLayout`] for code that needs to know the layout of a
+/// flake without being generic over its const parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    /// total bits of the base type. an `i64` will be 64 even though the sign
+    /// bit cannot be used
+    pub base_bits: u8,
+
+    /// bits used by the timestamp
+    pub ts_bits: u8,
+
+    /// bits used by each id segment starting with the primary id
+    pub seg_bits: &'static [u8],
+
+    /// bits used by the sequence
+    pub seq_bits: u8,
+}
 
-impl IsTrue for Assert<true> {}
-impl IsFalse for Assert<false> {}
-*/
+/// describes the bit layout of a flake
+pub trait FlakeLayout {
+    /// the layout of the flake
+    const LAYOUT: Layout;
+}
 
+/// returns the layout of a flake as the bits of each part separated by `-`
+///
+/// a flake with a 43 bit timestamp, 8 bit primary id, and 12 bit sequence
+/// will return `43-8-12`
+pub fn describe<F: FlakeLayout>() -> String {
+    let mut rtn = F::LAYOUT.ts_bits.to_string();
+
+    for bits in F::LAYOUT.seg_bits {
+        rtn.push('-');
+        rtn.push_str(&bits.to_string());
+    }
+
+    rtn.push('-');
+    rtn.push_str(&F::LAYOUT.seq_bits.to_string());
+    rtn
+}
+
+/// generators that create snowflakes relative to an epoch
+pub trait EpochMillis {
+    /// returns the epoch of the generator in milliseconds since
+    /// [`UNIX_EPOCH`](std::time::SystemTime::UNIX_EPOCH)
+    fn epoch_millis(&self) -> u64;
+}
+
+/// snowflakes that can provide when they were created
+///
+/// the timestamp of a snowflake is relative to the epoch of the generator
+/// that created it so the epoch must be provided to get the actual time.
+pub trait UnixMillis {
+    /// the error returned if the creation time cannot be calculated
+    type Error;
+
+    /// returns the unix timestamp in milliseconds of when the snowflake was
+    /// created using the given epoch in milliseconds
+    fn unix_millis(&self, epoch: u64) -> Result<u64, Self::Error>;
+
+    /// returns when the snowflake was created using the given epoch in
+    /// milliseconds. `None` is returned if the time cannot be represented
+    fn created_at(&self, epoch: u64) -> Option<SystemTime> {
+        let millis = self.unix_millis(epoch).ok()?;
+
+        SystemTime::UNIX_EPOCH.checked_add(Duration::from_millis(millis))
+    }
+
+    /// returns how long ago the snowflake was created using the given epoch
+    /// in milliseconds. `None` is returned if the snowflake appears to be
+    /// created in the future
+    fn age(&self, epoch: u64) -> Option<Duration> {
+        SystemTime::now().duration_since(self.created_at(epoch)?).ok()
+    }
+}
@@ -0,0 +1,93 @@
+#ifndef SNOWCLOUD_H
+#define SNOWCLOUD_H
+
+/* generated with cbindgen from snowcloud-ffi/src/lib.rs. do not edit by hand */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// status codes returned by the C bindings
+//
+// each generator error has its own code along with codes for invalid
+// arguments from C.
+typedef enum SnowcloudStatus {
+  // the call succeeded
+  SNOWCLOUD_STATUS_OK = 0,
+  // a required pointer was null
+  SNOWCLOUD_STATUS_NULL_POINTER = 1,
+  // the primary id is outside of the range allowed by the snowflake
+  SNOWCLOUD_STATUS_ID_SEG_INVALID = 2,
+  // the epoch is invalid
+  SNOWCLOUD_STATUS_EPOCH_INVALID = 3,
+  // the layout of the snowflake is invalid
+  SNOWCLOUD_STATUS_LAYOUT_INVALID = 4,
+  // the sequence is invalid
+  SNOWCLOUD_STATUS_SEQUENCE_INVALID = 5,
+  // the max timestamp has been reached and no more ids can be created
+  SNOWCLOUD_STATUS_TIMESTAMP_MAX_REACHED = 6,
+  // the sequence for the current millisecond has run out. try again after
+  // the microseconds written to the retry out pointer
+  SNOWCLOUD_STATUS_RETRY_AFTER = 7,
+  // the system time could not be read
+  SNOWCLOUD_STATUS_TIMESTAMP_ERROR = 8,
+  // the generator mutex could not be locked
+  SNOWCLOUD_STATUS_MUTEX_ERROR = 9,
+  // the shared state file of the generator could not be used
+  SNOWCLOUD_STATUS_FILE_UNAVAILABLE = 10,
+  // the call panicked
+  SNOWCLOUD_STATUS_PANIC = 11,
+} SnowcloudStatus;
+
+// opaque handle to a generator
+typedef struct SnowcloudGenerator SnowcloudGenerator;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// creates a new generator with the epoch in milliseconds and primary id
+//
+// the handle is written to `out` and must be freed with
+// [`snowcloud_free`].
+//
+// # Safety
+//
+// `out` must be null or valid for writing a pointer
+enum SnowcloudStatus snowcloud_new(uint64_t epoch_ms,
+                                   int64_t primary_id,
+                                   struct SnowcloudGenerator **out);
+
+// creates the next id from the generator and writes it to `out_id`
+//
+// [`RetryAfter`](SnowcloudStatus::RetryAfter) is returned when the
+// sequence has run out for the current millisecond and the microseconds to
+// wait are written to `out_retry_micros` if it is not null. nothing is
+// written to `out_id` unless the call succeeds.
+//
+// # Safety
+//
+// `handle` must be null or a handle from [`snowcloud_new`] that has not
+// been freed. `out_id` and `out_retry_micros` must be null or valid for
+// writing
+enum SnowcloudStatus snowcloud_next_id(const struct SnowcloudGenerator *handle,
+                                       int64_t *out_id,
+                                       uint64_t *out_retry_micros);
+
+// frees a generator created with [`snowcloud_new`]
+//
+// does nothing if `handle` is null
+//
+// # Safety
+//
+// `handle` must be null or a handle from [`snowcloud_new`] that has not
+// already been freed. the handle cannot be used after this call
+void snowcloud_free(struct SnowcloudGenerator *handle);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SNOWCLOUD_H */
@@ -0,0 +1,213 @@
+//! BSON values for snowflakes
+//!
+//! i64 snowflakes convert into [`Bson::Int64`] and can be converted back from
+//! `Int64`, `Int32`, or a `String` of the decimal id so collections that
+//! stored ids in any of those forms can still be read.
+//!
+//! the serde impls work with [`bson::to_document`] and
+//! [`bson::from_document`] so derived models will store the id as `Int64`.
+//! the bson serializer reports itself as human readable so with the
+//! `serde-human-string` feature the id is stored as a `String` instead. use
+//! [`SerializerOptions`](bson::SerializerOptions) with `human_readable(false)`
+//! to keep storing integers.
+//!
+//! ```rust
+//! use std::time::{Duration, SystemTime};
+//!
+//! use bson::{doc, Bson};
+//! use snowcloud::flake::bson_ext::id_range_filter;
+//!
+//! type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! const START_TIME: u64 = 1679587200000;
+//!
+//! let flake = MyFlake::from_parts(1, 1, 1).unwrap();
+//!
+//! assert_eq!(Bson::from(flake), Bson::Int64(flake.id()));
+//!
+//! let parsed: MyFlake = Bson::String(flake.id().to_string()).try_into().unwrap();
+//! assert_eq!(parsed, flake);
+//!
+//! // every id created during the first day after the epoch
+//! let start = SystemTime::UNIX_EPOCH + Duration::from_millis(START_TIME);
+//! let end = start + Duration::from_secs(60 * 60 * 24);
+//!
+//! let range = MyFlake::id_range_for(START_TIME, start, end).unwrap();
+//! let filter = id_range_filter("_id", range.clone());
+//!
+//! assert_eq!(filter, doc! {
+//!     "_id": { "$gte": *range.start(), "$lt": *range.end() + 1 }
+//! });
+//! ```
+
+use std::ops::RangeInclusive;
+
+use bson::{doc, Bson, Document};
+
+use crate::error;
+use crate::i64::SegmentBits;
+
+/// creates a filter that matches the ids of the given range in a field
+///
+/// the range is usually from `id_range_for` of the snowflake. the end of the
+/// range is made exclusive with `$lt` so the filter reads the same as the
+/// time range it was created from.
+pub fn id_range_filter(field: &str, range: RangeInclusive<i64>) -> Document {
+    let (start, end) = range.into_inner();
+
+    let bounds = match end.checked_add(1) {
+        Some(after) => doc! { "$gte": start, "$lt": after },
+        None => doc! { "$gte": start, "$lte": end },
+    };
+
+    let mut filter = Document::new();
+    filter.insert(field, bounds);
+    filter
+}
+
+macro_rules! bson_impls {
+    ([$($gen:tt)*] $flake:ty $(where $($bound:tt)*)?) => {
+        impl<$($gen)*> From<$flake> for Bson $(where $($bound)*)? {
+            fn from(flake: $flake) -> Self {
+                Bson::Int64(flake.id())
+            }
+        }
+
+        impl<$($gen)*> TryFrom<Bson> for $flake $(where $($bound)*)? {
+            type Error = error::Error;
+
+            fn try_from(value: Bson) -> Result<Self, Self::Error> {
+                match value {
+                    Bson::Int64(int) => <$flake>::try_from(&int),
+                    Bson::Int32(int) => <$flake>::try_from(&i64::from(int)),
+                    Bson::String(string) => string.parse(),
+                    _ => Err(error::Error::InvalidId),
+                }
+            }
+        }
+    };
+}
+
+bson_impls!([const TS: u8, const PID: u8, const SEQ: u8] crate::i64::SingleIdFlake<TS, PID, SEQ>);
+bson_impls!([const TS: u8, const PID: u8, const SEQ: u8] crate::i64::SingleIdFlakeSec<TS, PID, SEQ>);
+bson_impls!([const TS: u8, const PID: u8, const SID: u8, const SEQ: u8] crate::i64::DualIdFlake<TS, PID, SID, SEQ>);
+bson_impls!([const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8] crate::i64::TripleIdFlake<TS, PID, SID, TID, SEQ>);
+bson_impls!([const TS: u8, const SEQ: u8, const N: usize, B] crate::i64::NIdFlake<TS, SEQ, N, B> where B: SegmentBits<N>);
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use bson::{doc, Bson};
+
+    use super::*;
+
+    type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+    type I64DID = crate::i64::DualIdFlake<43, 4, 4, 12>;
+
+    const START_TIME: u64 = 1679587200000;
+
+    /// the inherent `try_from` of the snowflakes hides the trait method
+    fn convert<F>(value: Bson) -> error::Result<F>
+    where
+        F: TryFrom<Bson, Error = error::Error>
+    {
+        value.try_into()
+    }
+
+    #[test]
+    fn round_trip() {
+        let flake = I64SID::from_parts(1, 1, 1).unwrap();
+
+        assert_eq!(Bson::from(flake), Bson::Int64(1052673));
+        assert_eq!(convert::<I64SID>(Bson::from(flake)).unwrap(), flake);
+        assert_eq!(convert::<I64SID>(Bson::Int32(1052673)).unwrap(), flake);
+        assert_eq!(convert::<I64SID>(Bson::String("1052673".into())).unwrap(), flake);
+
+        let flake = I64DID::MAX;
+
+        assert_eq!(convert::<I64DID>(Bson::from(flake)).unwrap(), flake);
+        assert_eq!(convert::<I64DID>(Bson::String(flake.id().to_string())).unwrap(), flake);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(convert::<I64SID>(Bson::Int64(-1)), Err(error::Error::InvalidId)));
+        assert!(matches!(convert::<I64SID>(Bson::Int32(-1)), Err(error::Error::InvalidId)));
+        assert!(matches!(
+            convert::<I64SID>(Bson::String("not a number".into())),
+            Err(error::Error::ParseInt(_))
+        ));
+        assert!(matches!(convert::<I64SID>(Bson::String("-1".into())), Err(error::Error::InvalidId)));
+        assert!(matches!(convert::<I64SID>(Bson::Double(1.0)), Err(error::Error::InvalidId)));
+        assert!(matches!(convert::<I64SID>(Bson::Null), Err(error::Error::InvalidId)));
+    }
+
+    #[test]
+    fn range_filter() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_millis(START_TIME + 1000);
+        let end = start + Duration::from_millis(10);
+        let range = I64SID::id_range_for(START_TIME, start, end).unwrap();
+
+        assert_eq!(id_range_filter("_id", range), doc! {
+            "_id": {
+                "$gte": I64SID::first_for_timestamp(1000).unwrap().id(),
+                "$lt": I64SID::first_for_timestamp(1010).unwrap().id(),
+            }
+        });
+
+        assert_eq!(id_range_filter("parent", 10..=i64::MAX), doc! {
+            "parent": { "$gte": 10i64, "$lte": i64::MAX }
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_ext {
+        use serde::{Serialize, Deserialize};
+        use bson::{doc, Bson};
+
+        use super::*;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Record {
+            _id: I64SID,
+            parent: Option<I64DID>,
+            name: String,
+        }
+
+        #[test]
+        fn document() {
+            let record = Record {
+                _id: I64SID::from_parts(1, 1, 1).unwrap(),
+                parent: Some(I64DID::from_parts(1, 1, 1, 1).unwrap()),
+                name: "first".into(),
+            };
+
+            let document = bson::to_document(&record).unwrap();
+
+            #[cfg(not(feature = "serde-human-string"))]
+            assert_eq!(document, doc! {
+                "_id": record._id.id(),
+                "parent": record.parent.unwrap().id(),
+                "name": "first",
+            });
+
+            #[cfg(feature = "serde-human-string")]
+            assert_eq!(document.get("_id"), Some(&Bson::String(record._id.to_string())));
+
+            assert_eq!(bson::from_document::<Record>(document).unwrap(), record);
+
+            // older documents may have stored a smaller integer
+            let document = doc! {
+                "_id": Bson::Int32(1052673),
+                "parent": Bson::Null,
+                "name": "second",
+            };
+
+            let found = bson::from_document::<Record>(document).unwrap();
+
+            assert_eq!(found._id, record._id);
+            assert_eq!(found.parent, None);
+        }
+    }
+}
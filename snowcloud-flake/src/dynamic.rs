@@ -0,0 +1,420 @@
+//! snowflakes with a layout that is only known at runtime
+//!
+//! the const generic snowflakes need to know their layout when compiling.
+//! this is for tooling that needs to decode and encode ids from different
+//! sources where the layout comes from something like a config file. these
+//! cannot be used with generators.
+//!
+//! ```rust
+//! use std::sync::Arc;
+//!
+//! use snowcloud::flake::dynamic::{Layout, DynamicFlake};
+//!
+//! // same layout as snowcloud::i64::SingleIdFlake<43, 8, 12>
+//! let layout = Arc::new(Layout::new(43, vec![8], 12).unwrap());
+//! let flake = DynamicFlake::new(layout, 1052673).unwrap();
+//!
+//! assert_eq!(flake.timestamp(), 1);
+//! assert_eq!(flake.segments(), vec![1]);
+//! assert_eq!(flake.sequence(), 1);
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use snowcloud_core::traits;
+
+use crate::error;
+
+/// bit layout of a snowflake
+///
+/// the timestamp is stored in the highest bits followed by each segment in
+/// the order given and then the sequence. the total bits cannot be more than
+/// 63 since the sign bit is not used and every part must have at least 1 bit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Layout {
+    ts_bits: u8,
+    seg_bits: Vec<u8>,
+    seq_bits: u8,
+}
+
+impl Layout {
+    /// max number of bits that are available to the layout
+    pub const MAX_BITS: u32 = 63;
+
+    /// creates a new layout
+    ///
+    /// returns [`LayoutInvalid`](crate::error::Error::LayoutInvalid) if any
+    /// part has 0 bits or the total bits are more than
+    /// [`MAX_BITS`](Layout::MAX_BITS)
+    pub fn new(ts_bits: u8, seg_bits: Vec<u8>, seq_bits: u8) -> error::Result<Self> {
+        if ts_bits == 0 || seq_bits == 0 || seg_bits.contains(&0) {
+            return Err(error::Error::LayoutInvalid);
+        }
+
+        let layout = Layout { ts_bits, seg_bits, seq_bits };
+
+        if layout.total_bits() > Self::MAX_BITS {
+            return Err(error::Error::LayoutInvalid);
+        }
+
+        Ok(layout)
+    }
+
+    /// creates a layout from the layout of a flake
+    ///
+    /// returns [`LayoutInvalid`](crate::error::Error::LayoutInvalid) for the
+    /// same reasons as [`new`](Layout::new)
+    pub fn from_flake<F>() -> error::Result<Self>
+    where
+        F: traits::FlakeLayout
+    {
+        Self::new(F::LAYOUT.ts_bits, F::LAYOUT.seg_bits.to_vec(), F::LAYOUT.seq_bits)
+    }
+
+    /// returns the bits used by the timestamp
+    pub fn ts_bits(&self) -> u8 {
+        self.ts_bits
+    }
+
+    /// returns the bits used by each segment
+    pub fn seg_bits(&self) -> &[u8] {
+        &self.seg_bits
+    }
+
+    /// returns the bits used by the sequence
+    pub fn seq_bits(&self) -> u8 {
+        self.seq_bits
+    }
+
+    /// total number of bits used by all parts of the layout
+    pub fn total_bits(&self) -> u32 {
+        self.ts_bits as u32 + self.seq_bits as u32 + self.seg_bits.iter()
+            .map(|bits| *bits as u32)
+            .sum::<u32>()
+    }
+
+    /// bits to shift the timestamp
+    fn ts_shift(&self) -> u32 {
+        self.total_bits() - self.ts_bits as u32
+    }
+
+    /// bits to shift each segment in the same order as the segments
+    fn seg_shifts(&self) -> impl Iterator<Item = (u32, u8)> + '_ {
+        let mut shift = self.ts_shift();
+
+        self.seg_bits.iter().map(move |bits| {
+            shift -= *bits as u32;
+
+            (shift, *bits)
+        })
+    }
+}
+
+/// prints the bits of each part separated by `-` starting with the
+/// timestamp and ending with the sequence
+///
+/// `Layout::new(43, vec![8], 12)` is printed as `43-8-12`
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ts_bits)?;
+
+        for bits in &self.seg_bits {
+            write!(f, "-{}", bits)?;
+        }
+
+        write!(f, "-{}", self.seq_bits)
+    }
+}
+
+/// parses a layout in the same format that it is displayed with
+///
+/// returns [`ParseInt`](crate::error::Error::ParseInt) if any of the bits
+/// are not a valid number and
+/// [`LayoutInvalid`](crate::error::Error::LayoutInvalid) if there are less
+/// than 2 parts or the layout is invalid
+impl FromStr for Layout {
+    type Err = error::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut bits = string.split('-')
+            .map(|part| part.parse::<u8>().map_err(error::Error::ParseInt))
+            .collect::<error::Result<Vec<u8>>>()?;
+
+        if bits.len() < 2 {
+            return Err(error::Error::LayoutInvalid);
+        }
+
+        let seq_bits = bits.pop().unwrap();
+        let ts_bits = bits.remove(0);
+
+        Layout::new(ts_bits, bits, seq_bits)
+    }
+}
+
+#[inline]
+fn max_value(bits: u8) -> i64 {
+    (1 << bits as i64) - 1
+}
+
+/// the individual values of an id
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Parts {
+    pub timestamp: i64,
+    pub segments: Vec<i64>,
+    pub sequence: i64,
+}
+
+/// splits an id into its parts using the given layout
+///
+/// any bits outside of the layout are ignored
+pub fn decode(id: i64, layout: &Layout) -> Parts {
+    let timestamp = (id >> layout.ts_shift()) & max_value(layout.ts_bits);
+    let segments = layout.seg_shifts()
+        .map(|(shift, bits)| (id >> shift) & max_value(bits))
+        .collect();
+    let sequence = id & max_value(layout.seq_bits);
+
+    Parts { timestamp, segments, sequence }
+}
+
+/// creates an id from the parts using the given layout
+///
+/// checks will be performed on each part to ensure that they fit in the
+/// layout. the number of segments must equal the number of segments in the
+/// layout
+pub fn encode(parts: Parts, layout: &Layout) -> error::Result<i64> {
+    if parts.timestamp < 0 || parts.timestamp > max_value(layout.ts_bits) {
+        return Err(error::Error::EpochInvalid);
+    }
+
+    if parts.segments.len() > layout.seg_bits.len() {
+        return Err(error::Error::TooManySegments);
+    }
+
+    if parts.segments.len() < layout.seg_bits.len() {
+        return Err(error::Error::IdSegInvalid);
+    }
+
+    if parts.sequence < 0 || parts.sequence > max_value(layout.seq_bits) {
+        return Err(error::Error::SequenceInvalid);
+    }
+
+    let mut id = parts.timestamp << layout.ts_shift();
+
+    for (seg, (shift, bits)) in parts.segments.iter().zip(layout.seg_shifts()) {
+        if *seg < 0 || *seg > max_value(bits) {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        id |= seg << shift;
+    }
+
+    Ok(id | parts.sequence)
+}
+
+/// snowflake with a layout that is provided at runtime
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DynamicFlake {
+    layout: Arc<Layout>,
+    value: i64,
+}
+
+impl DynamicFlake {
+    /// creates a snowflake from the given id
+    ///
+    /// returns [`InvalidId`](crate::error::Error::InvalidId) if the id is
+    /// negative or uses bits outside of the layout
+    pub fn new(layout: Arc<Layout>, value: i64) -> error::Result<Self> {
+        if value < 0 || value >> layout.total_bits() != 0 {
+            return Err(error::Error::InvalidId);
+        }
+
+        Ok(DynamicFlake { layout, value })
+    }
+
+    /// creates a snowflake from the provided parts
+    pub fn from_parts(layout: Arc<Layout>, parts: Parts) -> error::Result<Self> {
+        let value = encode(parts, &layout)?;
+
+        Ok(DynamicFlake { layout, value })
+    }
+
+    /// returns the layout of the snowflake
+    pub fn layout(&self) -> &Arc<Layout> {
+        &self.layout
+    }
+
+    /// returns the id
+    pub fn id(&self) -> i64 {
+        self.value
+    }
+
+    /// returns timestamp
+    pub fn timestamp(&self) -> i64 {
+        (self.value >> self.layout.ts_shift()) & max_value(self.layout.ts_bits)
+    }
+
+    /// returns the segment at the given index if it exists
+    pub fn segment(&self, index: usize) -> Option<i64> {
+        self.layout.seg_shifts()
+            .nth(index)
+            .map(|(shift, bits)| (self.value >> shift) & max_value(bits))
+    }
+
+    /// returns all segments
+    pub fn segments(&self) -> Vec<i64> {
+        self.layout.seg_shifts()
+            .map(|(shift, bits)| (self.value >> shift) & max_value(bits))
+            .collect()
+    }
+
+    /// returns sequence
+    pub fn sequence(&self) -> i64 {
+        self.value & max_value(self.layout.seq_bits)
+    }
+
+    /// splits the snowflake into its individual parts
+    pub fn parts(&self) -> Parts {
+        decode(self.value, &self.layout)
+    }
+}
+
+impl From<DynamicFlake> for i64 {
+    #[inline(always)]
+    fn from(flake: DynamicFlake) -> i64 {
+        flake.value
+    }
+}
+
+impl From<&DynamicFlake> for i64 {
+    #[inline(always)]
+    fn from(flake: &DynamicFlake) -> i64 {
+        flake.value
+    }
+}
+
+impl fmt::Display for DynamicFlake {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+impl fmt::Debug for DynamicFlake {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicFlake")
+            .field("id", &self.value)
+            .field("layout", &self.layout)
+            .field("tsm", &self.timestamp())
+            .field("segments", &self.segments())
+            .field("seq", &self.sequence())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::i64::SingleIdFlake;
+
+    type StaticFlake = SingleIdFlake<43, 8, 12>;
+
+    fn static_layout() -> Arc<Layout> {
+        Arc::new(Layout::new(43, vec![8], 12).unwrap())
+    }
+
+    #[test]
+    fn invalid_layouts() {
+        assert!(Layout::new(43, vec![8], 12).is_ok());
+        assert!(Layout::new(43, vec![9], 12).is_err());
+        assert!(Layout::new(43, vec![4, 0, 4], 12).is_err());
+        assert!(Layout::new(0, vec![8], 12).is_err());
+        assert!(Layout::new(43, vec![8], 0).is_err());
+    }
+
+    #[test]
+    fn layout_strings() {
+        let layout: Layout = "43-8-12".parse().unwrap();
+
+        assert_eq!(layout, Layout::new(43, vec![8], 12).unwrap());
+        assert_eq!(layout.to_string(), "43-8-12");
+
+        let layout: Layout = "43-4-4-12".parse().unwrap();
+
+        assert_eq!(layout, Layout::from_flake::<crate::i64::DualIdFlake<43, 4, 4, 12>>().unwrap());
+        assert_eq!(layout.to_string(), "43-4-4-12");
+        assert!("51-12".parse::<Layout>().unwrap().seg_bits().is_empty());
+
+        assert!(matches!("43".parse::<Layout>(), Err(error::Error::LayoutInvalid)));
+        assert!(matches!("43-9-12".parse::<Layout>(), Err(error::Error::LayoutInvalid)));
+        assert!(matches!("43-x-12".parse::<Layout>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("43--12".parse::<Layout>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("".parse::<Layout>(), Err(error::Error::ParseInt(_))));
+    }
+
+    #[test]
+    fn matches_static_flake() {
+        let layout = static_layout();
+        let values = [
+            (0, 0, 0),
+            (1, 1, 1),
+            (1679082337000, 200, 3000),
+            (StaticFlake::MAX_TIMESTAMP, StaticFlake::MAX_PRIMARY_ID, StaticFlake::MAX_SEQUENCE),
+        ];
+
+        for (tsm, pid, seq) in values {
+            let static_flake = StaticFlake::from_parts(tsm, pid, seq).unwrap();
+            let parts = Parts {
+                timestamp: tsm,
+                segments: vec![pid],
+                sequence: seq,
+            };
+
+            let id = encode(parts.clone(), &layout).unwrap();
+
+            assert_eq!(id, static_flake.id(), "encoded ids differ for {:?}", parts);
+            assert_eq!(decode(static_flake.id(), &layout), parts, "decoded parts differ for {}", id);
+
+            let flake = DynamicFlake::new(layout.clone(), static_flake.id()).unwrap();
+
+            assert_eq!(flake.timestamp(), static_flake.timestamp());
+            assert_eq!(flake.segment(0), Some(static_flake.primary_id()));
+            assert_eq!(flake.segment(1), None);
+            assert_eq!(flake.sequence(), static_flake.sequence());
+            assert_eq!(flake.to_string(), static_flake.id().to_string());
+        }
+    }
+
+    #[test]
+    fn multiple_segments() {
+        let layout = Layout::new(43, vec![4, 4], 12).unwrap();
+        let parts = Parts {
+            timestamp: 1,
+            segments: vec![2, 3],
+            sequence: 4,
+        };
+
+        let id = encode(parts.clone(), &layout).unwrap();
+
+        assert_eq!(id, crate::i64::DualIdFlake::<43, 4, 4, 12>::from_parts(1, 2, 3, 4).unwrap().id());
+        assert_eq!(decode(id, &layout), parts);
+    }
+
+    #[test]
+    fn invalid_parts() {
+        let layout = static_layout();
+
+        let parts = |timestamp, segments, sequence| Parts { timestamp, segments, sequence };
+
+        assert!(matches!(encode(parts(1 << 43, vec![1], 1), &layout), Err(error::Error::EpochInvalid)));
+        assert!(matches!(encode(parts(1, vec![256], 1), &layout), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(encode(parts(1, vec![], 1), &layout), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(encode(parts(1, vec![1, 1], 1), &layout), Err(error::Error::TooManySegments)));
+        assert!(matches!(encode(parts(1, vec![1], 4096), &layout), Err(error::Error::SequenceInvalid)));
+
+        assert!(DynamicFlake::new(layout.clone(), -1).is_err());
+        assert!(DynamicFlake::new(Arc::new(Layout::new(10, vec![4], 4).unwrap()), 1 << 18).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! base58 encoding using the bitcoin alphabet
+//!
+//! the characters `0`, `O`, `I`, and `l` are not part of the alphabet since
+//! they are easy to confuse with each other. an id of 0 is encoded as `1`,
+//! the first character of the alphabet, and leading `1`s are ignored when
+//! decoding.
+
+use snowcloud_core::traits;
+
+use crate::error;
+use crate::encoding::{Alphabet, Encodable};
+
+const ALPHABET: Alphabet = Alphabet {
+    digits: b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+    ambiguous: b"0OIl",
+    aliases: &[],
+    ignore_case: false,
+};
+
+/// encodes the id of the snowflake as base58
+pub fn encode<F>(flake: &F) -> String
+where
+    F: traits::Id,
+    F::BaseType: Encodable,
+{
+    ALPHABET.encode(flake.id().into_u128())
+}
+
+/// decodes a base58 string into a snowflake
+///
+/// [`AmbiguousCharacter`](crate::error::Error::AmbiguousCharacter) is
+/// returned for any of `0OIl` and
+/// [`InvalidCharacter`](crate::error::Error::InvalidCharacter) for anything
+/// else outside of the alphabet. [`InvalidId`](crate::error::Error::InvalidId)
+/// is returned if the value does not fit in the base type or is not a valid
+/// snowflake
+pub fn decode<F>(string: &str) -> error::Result<F>
+where
+    F: traits::Id + TryFrom<F::BaseType>,
+    F::BaseType: Encodable,
+{
+    crate::encoding::flake_from_u128(ALPHABET.decode(string)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Signed = crate::i64::SingleIdFlake<43, 8, 12>;
+    type Unsigned = crate::u64::SingleIdFlake<44, 8, 12>;
+
+    #[test]
+    fn boundaries() {
+        for flake in [Signed::NIL, Signed::try_from(&1).unwrap(), Signed::MAX] {
+            assert_eq!(decode::<Signed>(&encode(&flake)).unwrap(), flake);
+        }
+
+        for flake in [Unsigned::NIL, Unsigned::try_from(&1).unwrap(), Unsigned::MAX] {
+            assert_eq!(decode::<Unsigned>(&encode(&flake)).unwrap(), flake);
+        }
+
+        assert_eq!(encode(&Signed::NIL), "1");
+        assert_eq!(decode::<Signed>("1112").unwrap(), Signed::try_from(&1).unwrap());
+    }
+
+    #[test]
+    fn known_values() {
+        let expected = [
+            (0u64, "1"),
+            (1, "2"),
+            (57, "z"),
+            (58, "21"),
+            (1679082337000, "m7BksSX"),
+            (i64::MAX as u64, "NQm6nKp8qFC"),
+            (u64::MAX, "jpXCZedGfVQ"),
+        ];
+
+        for (id, string) in expected {
+            let flake = Unsigned::try_from(&id).unwrap();
+
+            assert_eq!(encode(&flake), string, "encoding changed for {}", id);
+            assert_eq!(decode::<Unsigned>(string).unwrap(), flake);
+        }
+    }
+
+    #[test]
+    fn rejected_characters() {
+        for ch in ['0', 'O', 'I', 'l'] {
+            let string = format!("2{}2", ch);
+
+            assert!(matches!(decode::<Signed>(&string), Err(error::Error::AmbiguousCharacter(c)) if c == ch));
+        }
+
+        assert!(matches!(decode::<Signed>("2+2"), Err(error::Error::InvalidCharacter('+'))));
+        assert!(matches!(decode::<Signed>("2é"), Err(error::Error::InvalidCharacter('é'))));
+        assert!(matches!(decode::<Signed>(""), Err(error::Error::InvalidId)));
+    }
+
+    #[test]
+    fn out_of_range() {
+        let too_large = encode(&Unsigned::MAX);
+
+        // u64::MAX is larger than any i64
+        assert!(matches!(decode::<Signed>(&too_large), Err(error::Error::InvalidId)));
+    }
+}
@@ -0,0 +1,108 @@
+//! base62 encoding using `0-9A-Za-z`
+//!
+//! digits are ordered the same as ascii so the output matches other base62
+//! libraries that use the standard alphabet. an id of 0 is encoded as `0` and
+//! leading `0`s are ignored when decoding.
+
+use snowcloud_core::traits;
+
+use crate::error;
+use crate::encoding::{Alphabet, Encodable};
+
+const ALPHABET: Alphabet = Alphabet {
+    digits: b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+    ambiguous: b"",
+    aliases: &[],
+    ignore_case: false,
+};
+
+/// encodes the id of the snowflake as base62
+pub fn encode<F>(flake: &F) -> String
+where
+    F: traits::Id,
+    F::BaseType: Encodable,
+{
+    ALPHABET.encode(flake.id().into_u128())
+}
+
+/// decodes a base62 string into a snowflake
+///
+/// [`InvalidCharacter`](crate::error::Error::InvalidCharacter) is returned
+/// for anything outside of the alphabet and
+/// [`InvalidId`](crate::error::Error::InvalidId) if the value does not fit in
+/// the base type or is not a valid snowflake
+pub fn decode<F>(string: &str) -> error::Result<F>
+where
+    F: traits::Id + TryFrom<F::BaseType>,
+    F::BaseType: Encodable,
+{
+    crate::encoding::flake_from_u128(ALPHABET.decode(string)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Signed = crate::i64::SingleIdFlake<43, 8, 12>;
+    type Unsigned = crate::u64::SingleIdFlake<44, 8, 12>;
+
+    #[test]
+    fn boundaries() {
+        for flake in [Signed::NIL, Signed::try_from(&1).unwrap(), Signed::MAX] {
+            assert_eq!(decode::<Signed>(&encode(&flake)).unwrap(), flake);
+        }
+
+        for flake in [Unsigned::NIL, Unsigned::try_from(&1).unwrap(), Unsigned::MAX] {
+            assert_eq!(decode::<Unsigned>(&encode(&flake)).unwrap(), flake);
+        }
+
+        assert_eq!(encode(&Signed::NIL), "0");
+        assert_eq!(decode::<Signed>("0001").unwrap(), Signed::try_from(&1).unwrap());
+    }
+
+    #[test]
+    fn known_values() {
+        // matches the output of the base62 crate
+        let expected = [
+            (0u64, "0"),
+            (1, "1"),
+            (61, "z"),
+            (62, "10"),
+            (3843, "zz"),
+            (1679082337000, "TYnCMzQ"),
+            (i64::MAX as u64, "AzL8n0Y58m7"),
+            (u64::MAX, "LygHa16AHYF"),
+        ];
+
+        for (id, string) in expected {
+            let flake = Unsigned::try_from(&id).unwrap();
+
+            assert_eq!(encode(&flake), string, "encoding changed for {}", id);
+            assert_eq!(decode::<Unsigned>(string).unwrap(), flake);
+        }
+    }
+
+    #[test]
+    fn rejected_characters() {
+        for ch in ['-', '_', '+', '/', ' ', 'é'] {
+            let string = format!("1{}1", ch);
+
+            assert!(matches!(decode::<Signed>(&string), Err(error::Error::InvalidCharacter(c)) if c == ch));
+        }
+
+        assert!(matches!(decode::<Signed>(""), Err(error::Error::InvalidId)));
+    }
+
+    #[test]
+    fn out_of_range() {
+        // one past i64::MAX
+        assert!(matches!(decode::<Signed>("AzL8n0Y58m8"), Err(error::Error::InvalidId)));
+        assert!(decode::<Unsigned>("AzL8n0Y58m8").is_ok());
+
+        // one past u64::MAX
+        assert!(matches!(decode::<Unsigned>("LygHa16AHYG"), Err(error::Error::InvalidId)));
+
+        // larger than a u128
+        assert!(matches!(decode::<Unsigned>(&"z".repeat(23)), Err(error::Error::InvalidId)));
+    }
+}
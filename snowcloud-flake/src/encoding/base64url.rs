@@ -0,0 +1,164 @@
+//! unpadded base64url encoding of the big endian bytes of an id
+//!
+//! the output is the same as the url safe alphabet from RFC 4648 with the
+//! `=` padding removed. 64 bit ids are always 11 characters, 32 bit ids are
+//! 6, and 128 bit ids are 22.
+//!
+//! decoding only accepts the exact length for the base type of the flake
+//! and rejects padding along with the `+` and `/` characters from the
+//! standard alphabet.
+
+use snowcloud_core::traits;
+
+use crate::error;
+use crate::encoding::Encodable;
+
+const DIGITS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// characters needed for the given number of bytes
+const fn encoded_len(bytes: usize) -> usize {
+    (bytes * 4 + 2) / 3
+}
+
+/// encodes the big endian bytes of the snowflake id as base64url
+pub fn encode<F>(flake: &F) -> String
+where
+    F: traits::Id,
+    F::BaseType: Encodable,
+{
+    let size = (F::BaseType::BITS / 8) as usize;
+    let bytes = flake.id().into_u128().to_be_bytes();
+    let mut rtn = String::with_capacity(encoded_len(size));
+
+    for chunk in bytes[(16 - size)..].chunks(3) {
+        let mut buffer = [0u8; 3];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+
+        let block = (buffer[0] as u32) << 16 | (buffer[1] as u32) << 8 | buffer[2] as u32;
+
+        for index in 0..(chunk.len() + 1) {
+            let digit = (block >> (18 - index * 6)) & 0x3f;
+
+            rtn.push(char::from(DIGITS[digit as usize]));
+        }
+    }
+
+    rtn
+}
+
+/// decodes an unpadded base64url string into a snowflake
+///
+/// [`InvalidCharacter`](crate::error::Error::InvalidCharacter) is returned
+/// for anything outside of the url safe alphabet, including `=`.
+/// [`InvalidLength`](crate::error::Error::InvalidLength) is returned if the
+/// string is not the length of the base type and
+/// [`InvalidId`](crate::error::Error::InvalidId) if the unused bits of the
+/// last character are set or the value is not a valid snowflake
+pub fn decode<F>(string: &str) -> error::Result<F>
+where
+    F: traits::Id + TryFrom<F::BaseType>,
+    F::BaseType: Encodable,
+{
+    let size = (F::BaseType::BITS / 8) as usize;
+    let mut value: u128 = 0;
+    let mut buffer: u32 = 0;
+    let mut buffered: u32 = 0;
+
+    for ch in string.chars() {
+        let digit = u8::try_from(ch).ok()
+            .and_then(|byte| DIGITS.iter().position(|d| *d == byte))
+            .ok_or(error::Error::InvalidCharacter(ch))?;
+
+        buffer = buffer << 6 | digit as u32;
+        buffered += 6;
+
+        if buffered >= 8 {
+            buffered -= 8;
+            // strings longer than the base type will push bytes out of the
+            // value but are rejected by the length check
+            value = value << 8 | ((buffer >> buffered) & 0xff) as u128;
+        }
+    }
+
+    if string.len() != encoded_len(size) {
+        return Err(error::Error::InvalidLength(string.len()));
+    }
+
+    if buffer & ((1 << buffered) - 1) != 0 {
+        return Err(error::Error::InvalidId);
+    }
+
+    crate::encoding::flake_from_u128(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Signed = crate::i64::SingleIdFlake<43, 8, 12>;
+    type Unsigned = crate::u64::SingleIdFlake<44, 8, 12>;
+    type Small = crate::u32::SingleIdFlake<22, 4, 6>;
+    type Large = crate::u128::SingleIdFlake<64, 32, 32>;
+
+    #[test]
+    fn boundaries() {
+        for flake in [Signed::NIL, Signed::try_from(&1).unwrap(), Signed::MAX] {
+            assert_eq!(decode::<Signed>(&encode(&flake)).unwrap(), flake);
+        }
+
+        for flake in [Unsigned::NIL, Unsigned::try_from(&1).unwrap(), Unsigned::MAX] {
+            assert_eq!(decode::<Unsigned>(&encode(&flake)).unwrap(), flake);
+        }
+
+        for bits in crate::random_bits(1000) {
+            let flake = Unsigned::try_from(&(bits as u64)).unwrap();
+
+            assert_eq!(decode::<Unsigned>(&encode(&flake)).unwrap(), flake);
+        }
+
+        assert_eq!(encode(&Small::MAX), "_____w");
+        assert_eq!(encode(&Large::MAX), "_____________________w");
+        assert_eq!(decode::<Small>("_____w").unwrap(), Small::MAX);
+        assert_eq!(decode::<Large>("_____________________w").unwrap(), Large::MAX);
+    }
+
+    #[test]
+    fn known_values() {
+        // printf '\x00\x00\x00\x00\x00\x10\x10\x01' | base64 | tr '+/' '-_' | tr -d '='
+        let expected = [
+            (0u64, "AAAAAAAAAAA"),
+            (1, "AAAAAAAAAAE"),
+            (1052673, "AAAAAAAQEAE"),
+            (1679082337000, "AAABhvEbMug"),
+            (i64::MAX as u64, "f_________8"),
+            (u64::MAX, "__________8"),
+            (0xfbff000000000000, "-_8AAAAAAAA"),
+        ];
+
+        for (id, string) in expected {
+            let flake = Unsigned::try_from(&id).unwrap();
+
+            assert_eq!(encode(&flake), string, "encoding changed for {}", id);
+            assert_eq!(decode::<Unsigned>(string).unwrap(), flake);
+        }
+    }
+
+    #[test]
+    fn rejected() {
+        assert!(matches!(decode::<Unsigned>("AAAAAAAAAAE="), Err(error::Error::InvalidCharacter('='))));
+        assert!(matches!(decode::<Unsigned>("+_8AAAAAAAA"), Err(error::Error::InvalidCharacter('+'))));
+        assert!(matches!(decode::<Unsigned>("-/8AAAAAAAA"), Err(error::Error::InvalidCharacter('/'))));
+        assert!(matches!(decode::<Unsigned>("AAAAAAAAAé"), Err(error::Error::InvalidCharacter('é'))));
+
+        assert!(matches!(decode::<Unsigned>(""), Err(error::Error::InvalidLength(0))));
+        assert!(matches!(decode::<Unsigned>("AAAAAAAAAE"), Err(error::Error::InvalidLength(10))));
+        assert!(matches!(decode::<Unsigned>("AAAAAAAAAAAE"), Err(error::Error::InvalidLength(12))));
+        assert!(matches!(decode::<Small>("AAAAAAAAAAE"), Err(error::Error::InvalidLength(11))));
+
+        // last character has unused bits set
+        assert!(matches!(decode::<Unsigned>("AAAAAAAAAAF"), Err(error::Error::InvalidId)));
+
+        // larger than i64::MAX
+        assert!(matches!(decode::<Signed>("gAAAAAAAAAA"), Err(error::Error::InvalidId)));
+    }
+}
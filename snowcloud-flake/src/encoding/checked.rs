@@ -0,0 +1,284 @@
+//! string encodings with a trailing check character
+//!
+//! the check character is calculated with the damm algorithm over the digits
+//! of the encoding. it catches every mistyped character and every swap of
+//! two neighboring characters, so a typo is reported as
+//! [`ChecksumMismatch`](crate::error::Error::ChecksumMismatch) instead of
+//! being decoded into an id that does not exist.
+//!
+//! ```rust
+//! use snowcloud::flake::encoding::checked::{self, Variant};
+//!
+//! type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! let flake = MyFlake::from_parts(1000, 1, 1).unwrap();
+//! let string = checked::encode(&flake, Variant::Crockford);
+//!
+//! assert_eq!(string.len(), 14);
+//! assert_eq!(checked::decode::<MyFlake>(&string, Variant::Crockford).unwrap(), flake);
+//! ```
+
+use snowcloud_core::traits;
+
+use crate::error;
+use crate::encoding::{crockford, Alphabet, Encodable};
+
+const DECIMAL: Alphabet = Alphabet {
+    digits: b"0123456789",
+    ambiguous: b"",
+    aliases: &[],
+    ignore_case: false,
+};
+
+/// totally anti-symmetric quasigroup of order 10 from damm's thesis
+const DECIMAL_TABLE: [[u8; 10]; 10] = [
+    [0, 3, 1, 7, 5, 9, 8, 6, 4, 2],
+    [7, 0, 9, 2, 1, 5, 4, 8, 6, 3],
+    [4, 2, 0, 6, 8, 7, 1, 3, 5, 9],
+    [1, 7, 5, 0, 9, 8, 3, 4, 2, 6],
+    [6, 1, 2, 3, 0, 4, 5, 9, 7, 8],
+    [3, 6, 7, 4, 2, 0, 9, 5, 8, 1],
+    [5, 8, 6, 9, 7, 2, 0, 1, 3, 4],
+    [8, 9, 4, 5, 3, 6, 2, 0, 1, 7],
+    [9, 4, 3, 8, 6, 1, 7, 2, 0, 5],
+    [2, 5, 8, 1, 4, 3, 6, 7, 9, 0],
+];
+
+/// the encodings that can have a check character
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// base 10 digits followed by a check digit
+    Decimal,
+
+    /// fixed width [`crockford`](crate::encoding::crockford) base32 followed
+    /// by a check character from the same alphabet
+    Crockford,
+}
+
+impl Variant {
+    fn alphabet(&self) -> &'static Alphabet {
+        match self {
+            Variant::Decimal => &DECIMAL,
+            Variant::Crockford => &crockford::ALPHABET,
+        }
+    }
+
+    /// combines the current interim digit with the next digit
+    fn operation(&self, interim: u8, digit: u8) -> u8 {
+        match self {
+            Variant::Decimal => DECIMAL_TABLE[interim as usize][digit as usize],
+            // x * y = 2x + y over GF(32) is totally anti-symmetric since
+            // 2 is not 0 or 1
+            Variant::Crockford => gf32_double(interim) ^ digit,
+        }
+    }
+
+    /// calculates the digit that brings the interim back to 0
+    fn check_digit<I>(&self, digits: I) -> u8
+    where
+        I: IntoIterator<Item = u8>
+    {
+        let interim = digits.into_iter()
+            .fold(0, |interim, digit| self.operation(interim, digit));
+
+        match self {
+            // the table has 0 on the diagonal so the interim is the check
+            Variant::Decimal => interim,
+            // addition is xor so 2x + 2x = 0
+            Variant::Crockford => gf32_double(interim),
+        }
+    }
+}
+
+/// multiplies by 2 in GF(32) using x^5 + x^2 + 1
+#[inline]
+fn gf32_double(value: u8) -> u8 {
+    let doubled = value << 1;
+
+    if doubled & 0x20 != 0 {
+        doubled ^ 0x25
+    } else {
+        doubled
+    }
+}
+
+/// encodes the id of the snowflake followed by a check character
+pub fn encode<F>(flake: &F, variant: Variant) -> String
+where
+    F: traits::Id,
+    F::BaseType: Encodable,
+{
+    let alphabet = variant.alphabet();
+    let value = flake.id().into_u128();
+
+    let mut rtn = match variant {
+        Variant::Decimal => alphabet.encode(value),
+        Variant::Crockford => alphabet.encode_fixed(value, crockford::width::<F::BaseType>()),
+    };
+
+    let check = variant.check_digit(rtn.bytes().map(|byte| {
+        alphabet.digits.iter().position(|d| *d == byte).unwrap() as u8
+    }));
+
+    rtn.push(char::from(alphabet.digits[check as usize]));
+    rtn
+}
+
+/// decodes a string created by [`encode`] with the same variant
+///
+/// [`ChecksumMismatch`](crate::error::Error::ChecksumMismatch) is returned if
+/// the check character does not match the rest of the string. the errors for
+/// invalid characters and values are the same as the underlying encoding.
+/// the crockford variant accepts lowercase and the aliases of the crockford
+/// alphabet
+pub fn decode<F>(string: &str, variant: Variant) -> error::Result<F>
+where
+    F: traits::Id + TryFrom<F::BaseType>,
+    F::BaseType: Encodable,
+{
+    let alphabet = variant.alphabet();
+    let mut digits = Vec::with_capacity(string.len());
+
+    for ch in string.chars() {
+        let digit = alphabet.digit(ch).ok_or(error::Error::InvalidCharacter(ch))?;
+
+        digits.push(digit as u8);
+    }
+
+    let Some(check) = digits.pop() else {
+        return Err(error::Error::InvalidId);
+    };
+
+    if variant.check_digit(digits) != check {
+        return Err(error::Error::ChecksumMismatch);
+    }
+
+    // every character is in the alphabet so the check character is a
+    // single byte
+    crate::encoding::flake_from_u128(alphabet.decode(&string[..(string.len() - 1)])?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Signed = crate::i64::SingleIdFlake<43, 8, 12>;
+    type Unsigned = crate::u64::SingleIdFlake<44, 8, 12>;
+
+    const VARIANTS: [Variant; 2] = [Variant::Decimal, Variant::Crockford];
+
+    #[test]
+    fn known_values() {
+        // the check digit of 572 from the damm algorithm examples is 4
+        let flake = Unsigned::try_from(&572).unwrap();
+
+        assert_eq!(encode(&flake, Variant::Decimal), "5724");
+
+        let expected = [
+            (0u64, "00", "00000000000000"),
+            (1, "13", "00000000000012"),
+            (1679082337000, "16790823370005", "00001GVRHPCQ84"),
+            (u64::MAX, "184467440737095516158", "FZZZZZZZZZZZZX"),
+        ];
+
+        for (id, decimal, crockford) in expected {
+            let flake = Unsigned::try_from(&id).unwrap();
+
+            assert_eq!(encode(&flake, Variant::Decimal), decimal, "decimal changed for {}", id);
+            assert_eq!(encode(&flake, Variant::Crockford), crockford, "crockford changed for {}", id);
+            assert_eq!(decode::<Unsigned>(decimal, Variant::Decimal).unwrap(), flake);
+            assert_eq!(decode::<Unsigned>(crockford, Variant::Crockford).unwrap(), flake);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for variant in VARIANTS {
+            for flake in [Signed::NIL, Signed::try_from(&1).unwrap(), Signed::MAX] {
+                assert_eq!(decode::<Signed>(&encode(&flake, variant), variant).unwrap(), flake);
+            }
+
+            for bits in crate::random_bits(1000) {
+                let flake = Unsigned::try_from(&(bits as u64)).unwrap();
+
+                assert_eq!(decode::<Unsigned>(&encode(&flake, variant), variant).unwrap(), flake);
+            }
+        }
+
+        let flake = Unsigned::try_from(&1679082337000).unwrap();
+
+        assert_eq!(decode::<Unsigned>("00001gvrhpcq84", Variant::Crockford).unwrap(), flake);
+        assert_eq!(decode::<Unsigned>("OOOOIGVRHPCQ84", Variant::Crockford).unwrap(), flake);
+    }
+
+    #[test]
+    fn substitutions() {
+        for variant in VARIANTS {
+            let digits = variant.alphabet().digits;
+
+            for bits in crate::random_bits(200) {
+                let flake = Unsigned::try_from(&(bits as u64)).unwrap();
+                let encoded = encode(&flake, variant).into_bytes();
+
+                for index in 0..encoded.len() {
+                    for digit in digits.iter().filter(|d| **d != encoded[index]) {
+                        let mut changed = encoded.clone();
+                        changed[index] = *digit;
+
+                        let changed = String::from_utf8(changed).unwrap();
+
+                        assert!(
+                            matches!(decode::<Unsigned>(&changed, variant), Err(error::Error::ChecksumMismatch)),
+                            "{:?} accepted {:?}",
+                            variant,
+                            changed
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transpositions() {
+        for variant in VARIANTS {
+            for bits in crate::random_bits(1000) {
+                let flake = Unsigned::try_from(&(bits as u64)).unwrap();
+                let encoded = encode(&flake, variant).into_bytes();
+
+                for index in 1..encoded.len() {
+                    if encoded[index - 1] == encoded[index] {
+                        continue;
+                    }
+
+                    let mut changed = encoded.clone();
+                    changed.swap(index - 1, index);
+
+                    let changed = String::from_utf8(changed).unwrap();
+
+                    assert!(
+                        matches!(decode::<Unsigned>(&changed, variant), Err(error::Error::ChecksumMismatch)),
+                        "{:?} accepted {:?}",
+                        variant,
+                        changed
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(decode::<Unsigned>("", Variant::Decimal), Err(error::Error::InvalidId)));
+        assert!(matches!(decode::<Unsigned>("12a", Variant::Decimal), Err(error::Error::InvalidCharacter('a'))));
+        assert!(matches!(decode::<Unsigned>("00000000000U12", Variant::Crockford), Err(error::Error::InvalidCharacter('U'))));
+
+        // a check digit without a value
+        assert!(matches!(decode::<Unsigned>("0", Variant::Decimal), Err(error::Error::InvalidId)));
+
+        // valid check digit for a value larger than an i64
+        let too_large = encode(&Unsigned::MAX, Variant::Decimal);
+
+        assert!(matches!(decode::<Signed>(&too_large, Variant::Decimal), Err(error::Error::InvalidId)));
+    }
+}
@@ -0,0 +1,156 @@
+//! crockford base32 encoding with a fixed width
+//!
+//! the id is padded with `0`s to the number of characters needed for the
+//! full base type of the flake, 7 for 32 bits, 13 for 64 bits, and 26 for
+//! 128 bits. since every string has the same length and the alphabet is in
+//! ascii order, sorting the strings sorts the ids which also sorts them by
+//! time.
+//!
+//! decoding follows the crockford spec. lowercase is accepted, `I` and `L`
+//! are read as `1`, and `O` is read as `0`. `U` is not part of the alphabet
+//! and is rejected. strings shorter than the fixed width are accepted with
+//! the missing leading `0`s assumed.
+
+use snowcloud_core::traits;
+
+use crate::error;
+use crate::encoding::{Alphabet, Encodable};
+
+pub(crate) const ALPHABET: Alphabet = Alphabet {
+    digits: b"0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+    ambiguous: b"",
+    aliases: &[(b'I', b'1'), (b'L', b'1'), (b'O', b'0')],
+    ignore_case: true,
+};
+
+/// characters needed to encode every value of the given base type
+pub(crate) const fn width<T: Encodable>() -> usize {
+    ((T::BITS + 4) / 5) as usize
+}
+
+/// encodes the id of the snowflake padded to the width of the base type
+pub fn encode_fixed<F>(flake: &F) -> String
+where
+    F: traits::Id,
+    F::BaseType: Encodable,
+{
+    ALPHABET.encode_fixed(flake.id().into_u128(), width::<F::BaseType>())
+}
+
+/// decodes a crockford base32 string into a snowflake
+///
+/// [`InvalidCharacter`](crate::error::Error::InvalidCharacter) is returned
+/// for anything outside of the alphabet or its aliases and
+/// [`InvalidId`](crate::error::Error::InvalidId) if the value does not fit in
+/// the base type or is not a valid snowflake
+pub fn decode<F>(string: &str) -> error::Result<F>
+where
+    F: traits::Id + TryFrom<F::BaseType>,
+    F::BaseType: Encodable,
+{
+    crate::encoding::flake_from_u128(ALPHABET.decode(string)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Signed = crate::i64::SingleIdFlake<43, 8, 12>;
+    type Unsigned = crate::u64::SingleIdFlake<44, 8, 12>;
+    type Small = crate::u32::SingleIdFlake<22, 4, 6>;
+    type Large = crate::u128::SingleIdFlake<64, 32, 32>;
+
+    #[test]
+    fn boundaries() {
+        for flake in [Signed::NIL, Signed::try_from(&1).unwrap(), Signed::MAX] {
+            assert_eq!(decode::<Signed>(&encode_fixed(&flake)).unwrap(), flake);
+        }
+
+        for flake in [Unsigned::NIL, Unsigned::try_from(&1).unwrap(), Unsigned::MAX] {
+            assert_eq!(decode::<Unsigned>(&encode_fixed(&flake)).unwrap(), flake);
+        }
+
+        assert_eq!(encode_fixed(&Small::MAX), "3ZZZZZZ");
+        assert_eq!(encode_fixed(&Large::MAX), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        assert_eq!(decode::<Small>("3ZZZZZZ").unwrap(), Small::MAX);
+        assert_eq!(decode::<Large>("7ZZZZZZZZZZZZZZZZZZZZZZZZZ").unwrap(), Large::MAX);
+    }
+
+    #[test]
+    fn known_values() {
+        let expected = [
+            (0u64, "0000000000000"),
+            (1, "0000000000001"),
+            (31, "000000000000Z"),
+            (32, "0000000000010"),
+            (1679082337000, "00001GVRHPCQ8"),
+            (i64::MAX as u64, "7ZZZZZZZZZZZZ"),
+            (u64::MAX, "FZZZZZZZZZZZZ"),
+        ];
+
+        for (id, string) in expected {
+            let flake = Unsigned::try_from(&id).unwrap();
+
+            assert_eq!(encode_fixed(&flake), string, "encoding changed for {}", id);
+            assert_eq!(decode::<Unsigned>(string).unwrap(), flake);
+        }
+    }
+
+    #[test]
+    fn sorted() {
+        let mut flakes: Vec<Unsigned> = crate::random_bits(1000)
+            .map(|bits| Unsigned::try_from(&(bits as u64)).unwrap())
+            .collect();
+        let mut strings: Vec<String> = flakes.iter()
+            .map(encode_fixed)
+            .collect();
+
+        flakes.sort();
+        strings.sort();
+
+        for (flake, string) in flakes.iter().zip(strings.iter()) {
+            assert_eq!(encode_fixed(flake), *string);
+        }
+
+        let mut flakes: Vec<Signed> = crate::random_bits(1000)
+            .map(|bits| Signed::try_from(&(bits as i64 & i64::MAX)).unwrap())
+            .collect();
+        let mut strings: Vec<String> = flakes.iter()
+            .map(encode_fixed)
+            .collect();
+
+        flakes.sort();
+        strings.sort();
+
+        for (flake, string) in flakes.iter().zip(strings.iter()) {
+            assert_eq!(encode_fixed(flake), *string);
+        }
+    }
+
+    #[test]
+    fn aliases() {
+        let expected = Unsigned::try_from(&1679082337000).unwrap();
+
+        for string in ["00001GVRHPCQ8", "00001gvrhpcq8", "OOOOIGVRHPCQ8", "oooolgvrhpcq8", "1GVRHPCQ8"] {
+            assert_eq!(decode::<Unsigned>(string).unwrap(), expected, "failed to decode {}", string);
+        }
+
+        assert_eq!(decode::<Unsigned>("I").unwrap(), decode::<Unsigned>("1").unwrap());
+        assert_eq!(decode::<Unsigned>("L").unwrap(), decode::<Unsigned>("1").unwrap());
+        assert_eq!(decode::<Unsigned>("O").unwrap(), decode::<Unsigned>("0").unwrap());
+    }
+
+    #[test]
+    fn rejected_characters() {
+        for ch in ['U', 'u', '-', '*', 'é'] {
+            let string = format!("1{}1", ch);
+
+            assert!(matches!(decode::<Signed>(&string), Err(error::Error::InvalidCharacter(c)) if c == ch));
+        }
+
+        assert!(matches!(decode::<Signed>(""), Err(error::Error::InvalidId)));
+
+        // one past i64::MAX
+        assert!(matches!(decode::<Signed>("8000000000000"), Err(error::Error::InvalidId)));
+    }
+}
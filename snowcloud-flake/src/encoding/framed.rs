@@ -0,0 +1,323 @@
+//! binary encoding of an id along with its layout and epoch
+//!
+//! the const generic snowflakes only know how to read ids with the same
+//! layout they were compiled with. a frame carries the layout and epoch in a
+//! small header so the receiver can check that it has the right type or
+//! read the parts of an id without knowing the layout ahead of time.
+//!
+//! all values are big endian:
+//!
+//! | bytes | contents |
+//! |-------|----------|
+//! | 1     | magic byte, `0xf1` |
+//! | 1     | bits of the base type, 32 or 64 |
+//! | 1     | bits of the timestamp |
+//! | 1     | number of segments |
+//! | 1 each | bits of each segment |
+//! | 1     | bits of the sequence |
+//! | 8     | epoch in milliseconds |
+//! | 4 or 8 | the id |
+//!
+//! only layouts that fit in a [`dynamic::Layout`](crate::dynamic::Layout)
+//! can be framed, which leaves out the u64 and 128 bit snowflakes since they
+//! use more than 63 bits.
+//!
+//! ```rust
+//! use snowcloud::flake::encoding::framed;
+//!
+//! type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! const EPOCH: u64 = 1679082337000;
+//!
+//! let flake = MyFlake::from_parts(1, 1, 1).unwrap();
+//! let bytes = framed::encode(&flake, EPOCH).unwrap();
+//!
+//! // without knowing the layout
+//! let frame = framed::decode(&bytes).unwrap();
+//!
+//! assert_eq!(frame.epoch, EPOCH);
+//! assert_eq!(frame.layout.ts_bits(), 43);
+//! assert_eq!(frame.parts.segments, vec![1]);
+//!
+//! // checking that the layout matches
+//! assert_eq!(frame.to_flake::<MyFlake>().unwrap(), flake);
+//! ```
+
+use std::sync::Arc;
+
+use snowcloud_core::traits;
+
+use crate::error;
+use crate::dynamic::{self, Layout, Parts, DynamicFlake};
+use crate::encoding::Encodable;
+
+/// first byte of every frame
+pub const MAGIC: u8 = 0xf1;
+
+/// the contents of a frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedFrame {
+    /// bits of the base type that the id was stored in
+    pub base_bits: u8,
+
+    /// epoch in milliseconds of the generator that created the id
+    pub epoch: u64,
+
+    /// the layout of the id
+    pub layout: Layout,
+
+    /// the id
+    pub id: i64,
+
+    /// the parts of the id split using the layout
+    pub parts: Parts,
+}
+
+impl DecodedFrame {
+    /// checks that the frame has the same layout as the given flake and
+    /// creates the flake from the id
+    ///
+    /// returns [`LayoutMismatch`](crate::error::Error::LayoutMismatch) if the
+    /// base type or any part of the layout are different
+    pub fn to_flake<F>(&self) -> error::Result<F>
+    where
+        F: traits::Id + traits::FlakeLayout + TryFrom<F::BaseType>,
+        F::BaseType: Encodable,
+    {
+        let expected = F::LAYOUT;
+
+        if expected.base_bits != self.base_bits
+            || expected.ts_bits != self.layout.ts_bits()
+            || expected.seg_bits != self.layout.seg_bits()
+            || expected.seq_bits != self.layout.seq_bits()
+        {
+            return Err(error::Error::LayoutMismatch);
+        }
+
+        crate::encoding::flake_from_u128(self.id as u128)
+    }
+
+    /// converts the frame into a [`DynamicFlake`]
+    pub fn into_dynamic(self) -> DynamicFlake {
+        // the id was checked against the layout when decoding
+        DynamicFlake::new(Arc::new(self.layout), self.id).unwrap()
+    }
+}
+
+/// writes the layout, epoch, and id of the flake into a frame
+///
+/// returns [`LayoutInvalid`](crate::error::Error::LayoutInvalid) if the
+/// layout of the flake does not fit in a
+/// [`dynamic::Layout`](crate::dynamic::Layout) or the base type is not 32 or
+/// 64 bits
+pub fn encode<F>(flake: &F, epoch: u64) -> error::Result<Vec<u8>>
+where
+    F: traits::Id + traits::FlakeLayout,
+    F::BaseType: Encodable,
+{
+    let layout = F::LAYOUT;
+    let id_bytes = id_bytes(layout.base_bits)?;
+
+    Layout::from_flake::<F>()?;
+
+    let mut rtn = Vec::with_capacity(6 + layout.seg_bits.len() + 8 + id_bytes);
+    rtn.push(MAGIC);
+    rtn.push(layout.base_bits);
+    rtn.push(layout.ts_bits);
+    rtn.push(layout.seg_bits.len() as u8);
+    rtn.extend_from_slice(layout.seg_bits);
+    rtn.push(layout.seq_bits);
+    rtn.extend_from_slice(&epoch.to_be_bytes());
+    rtn.extend_from_slice(&flake.id().into_u128().to_be_bytes()[(16 - id_bytes)..]);
+
+    Ok(rtn)
+}
+
+/// reads a frame created by [`encode`]
+///
+/// returns [`InvalidFrame`](crate::error::Error::InvalidFrame) if the magic
+/// byte or base type are unknown,
+/// [`InvalidLength`](crate::error::Error::InvalidLength) if the frame is too
+/// short or long, [`LayoutInvalid`](crate::error::Error::LayoutInvalid) if
+/// the layout is not valid for the base type, and
+/// [`InvalidId`](crate::error::Error::InvalidId) if the id uses bits outside
+/// of the layout
+pub fn decode(bytes: &[u8]) -> error::Result<DecodedFrame> {
+    let [magic, base_bits, ts_bits, seg_count, rest @ ..] = bytes else {
+        return Err(error::Error::InvalidLength(bytes.len()));
+    };
+
+    if *magic != MAGIC {
+        return Err(error::Error::InvalidFrame);
+    }
+
+    let id_bytes = id_bytes(*base_bits).map_err(|_| error::Error::InvalidFrame)?;
+    let seg_count = *seg_count as usize;
+
+    if rest.len() != seg_count + 1 + 8 + id_bytes {
+        return Err(error::Error::InvalidLength(bytes.len()));
+    }
+
+    let (seg_bits, rest) = rest.split_at(seg_count);
+    let (seq_bits, rest) = rest.split_at(1);
+    let (epoch, id) = rest.split_at(8);
+
+    let layout = Layout::new(*ts_bits, seg_bits.to_vec(), seq_bits[0])?;
+
+    if layout.total_bits() > *base_bits as u32 {
+        return Err(error::Error::LayoutInvalid);
+    }
+
+    let epoch = u64::from_be_bytes(epoch.try_into().unwrap());
+    let id = id.iter().fold(0u64, |value, byte| value << 8 | *byte as u64);
+
+    if id >> layout.total_bits() != 0 {
+        return Err(error::Error::InvalidId);
+    }
+
+    let id = id as i64;
+    let parts = dynamic::decode(id, &layout);
+
+    Ok(DecodedFrame {
+        base_bits: *base_bits,
+        epoch,
+        layout,
+        id,
+        parts,
+    })
+}
+
+/// bytes used by the id for the given base type
+fn id_bytes(base_bits: u8) -> error::Result<usize> {
+    match base_bits {
+        32 => Ok(4),
+        64 => Ok(8),
+        _ => Err(error::Error::LayoutInvalid),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EPOCH: u64 = 1679082337000;
+
+    type Single = crate::i64::SingleIdFlake<43, 8, 12>;
+    type Dual = crate::i64::DualIdFlake<43, 4, 4, 12>;
+    type Small = crate::u32::SingleIdFlake<22, 4, 6>;
+
+    #[test]
+    fn known_frames() {
+        let flake = Single::from_parts(1, 1, 1).unwrap();
+        let expected = [
+            0xf1, 64, 43, 1, 8, 12,
+            0x00, 0x00, 0x01, 0x86, 0xf1, 0x1b, 0x32, 0xe8,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x10, 0x01,
+        ];
+
+        assert_eq!(encode(&flake, EPOCH).unwrap(), expected);
+
+        let frame = decode(&expected).unwrap();
+
+        assert_eq!(frame.base_bits, 64);
+        assert_eq!(frame.epoch, EPOCH);
+        assert_eq!(frame.layout, Layout::new(43, vec![8], 12).unwrap());
+        assert_eq!(frame.id, 1052673);
+        assert_eq!(frame.parts, Parts { timestamp: 1, segments: vec![1], sequence: 1 });
+        assert_eq!(frame.to_flake::<Single>().unwrap(), flake);
+
+        let flake = Dual::from_parts(1, 2, 3, 4).unwrap();
+        let expected = [
+            0xf1, 64, 43, 2, 4, 4, 12,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0x30, 0x04,
+        ];
+
+        assert_eq!(encode(&flake, 0).unwrap(), expected);
+        assert_eq!(decode(&expected).unwrap().to_flake::<Dual>().unwrap(), flake);
+
+        let flake = Small::from_parts(1, 1, 1).unwrap();
+        let expected = [
+            0xf1, 32, 22, 1, 4, 6,
+            0x00, 0x00, 0x01, 0x86, 0xf1, 0x1b, 0x32, 0xe8,
+            0x00, 0x00, 0x04, 0x41,
+        ];
+
+        assert_eq!(encode(&flake, EPOCH).unwrap(), expected);
+        assert_eq!(decode(&expected).unwrap().to_flake::<Small>().unwrap(), flake);
+    }
+
+    #[test]
+    fn dynamic() {
+        let flake = Dual::from_parts(1679082337, 15, 7, 4095).unwrap();
+        let dynamic = decode(&encode(&flake, EPOCH).unwrap())
+            .unwrap()
+            .into_dynamic();
+
+        assert_eq!(dynamic.id(), flake.id());
+        assert_eq!(dynamic.timestamp(), flake.timestamp());
+        assert_eq!(dynamic.segments(), vec![15, 7]);
+        assert_eq!(dynamic.sequence(), 4095);
+    }
+
+    #[test]
+    fn layout_mismatch() {
+        let frame = decode(&encode(&Single::from_parts(1, 1, 1).unwrap(), EPOCH).unwrap()).unwrap();
+
+        assert!(matches!(frame.to_flake::<Dual>(), Err(error::Error::LayoutMismatch)));
+        assert!(matches!(frame.to_flake::<crate::i64::SingleIdFlake<42, 9, 12>>(), Err(error::Error::LayoutMismatch)));
+        assert!(matches!(frame.to_flake::<crate::i64::SingleIdFlake<43, 7, 13>>(), Err(error::Error::LayoutMismatch)));
+        assert!(matches!(frame.to_flake::<Small>(), Err(error::Error::LayoutMismatch)));
+
+        let frame = decode(&encode(&Dual::from_parts(1, 1, 1, 1).unwrap(), EPOCH).unwrap()).unwrap();
+
+        assert!(matches!(frame.to_flake::<Single>(), Err(error::Error::LayoutMismatch)));
+    }
+
+    #[test]
+    fn invalid() {
+        let valid = encode(&Single::from_parts(1, 1, 1).unwrap(), EPOCH).unwrap();
+
+        let mut bytes = valid.clone();
+        bytes[0] = 0xf0;
+        assert!(matches!(decode(&bytes), Err(error::Error::InvalidFrame)));
+
+        let mut bytes = valid.clone();
+        bytes[1] = 128;
+        assert!(matches!(decode(&bytes), Err(error::Error::InvalidFrame)));
+
+        assert!(matches!(decode(&valid[..3]), Err(error::Error::InvalidLength(3))));
+        assert!(matches!(decode(&valid[..21]), Err(error::Error::InvalidLength(21))));
+
+        let mut bytes = valid.clone();
+        bytes.push(0);
+        assert!(matches!(decode(&bytes), Err(error::Error::InvalidLength(23))));
+
+        // more segments than the frame holds
+        let mut bytes = valid.clone();
+        bytes[3] = 2;
+        assert!(matches!(decode(&bytes), Err(error::Error::InvalidLength(22))));
+
+        // 64 bit layout
+        let mut bytes = valid.clone();
+        bytes[2] = 44;
+        assert!(matches!(decode(&bytes), Err(error::Error::LayoutInvalid)));
+
+        // segment without bits
+        let mut bytes = valid.clone();
+        bytes[4] = 0;
+        assert!(matches!(decode(&bytes), Err(error::Error::LayoutInvalid)));
+
+        // id uses the sign bit
+        let mut bytes = valid.clone();
+        bytes[14] = 0x80;
+        assert!(matches!(decode(&bytes), Err(error::Error::InvalidId)));
+
+        // layouts that cannot be framed
+        let full = crate::u64::SingleIdFlake::<44, 8, 12>::from_parts(1, 1, 1).unwrap();
+        let large = crate::u128::SingleIdFlake::<108, 8, 12>::from_parts(1, 1, 1).unwrap();
+
+        assert!(matches!(encode(&full, EPOCH), Err(error::Error::LayoutInvalid)));
+        assert!(matches!(encode(&large, EPOCH), Err(error::Error::LayoutInvalid)));
+    }
+}
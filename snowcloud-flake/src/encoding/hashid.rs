@@ -0,0 +1,428 @@
+//! hashids compatible obfuscated strings
+//!
+//! a [`Codec`] turns an id into a short string that does not show the order
+//! of the ids and can only be decoded with the same salt. the string is the
+//! hashid of the id followed by a check value derived from the salt and the
+//! id. other hashids libraries using the default alphabet and the same salt
+//! will decode it to those two numbers.
+//!
+//! hashids alone will decode about 1 in 44 strings from a different salt
+//! into some other number. with the check value a string from a different
+//! salt is only accepted about once in every 44,000 and is otherwise
+//! rejected with [`Error::InvalidHash`]. strings from a different min length
+//! are always rejected.
+//!
+//! like [`opaque`](crate::opaque) this hides the ids from casual observation
+//! and is not encryption.
+//!
+//! ```rust
+//! use snowcloud::flake::encoding::hashid::Codec;
+//!
+//! type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! let codec = Codec::new("my salt", 8);
+//! let flake = MyFlake::from_parts(1000, 1, 1).unwrap();
+//!
+//! let string = codec.encode(&flake);
+//!
+//! assert!(string.len() >= 8);
+//! assert_eq!(codec.decode::<MyFlake>(&string).unwrap(), flake);
+//! ```
+
+use std::fmt;
+
+use snowcloud_core::traits;
+
+use crate::encoding::Encodable;
+use crate::opaque::mix;
+
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
+const SEPARATORS: &[u8] = b"cfhistuCFHISTU";
+
+/// max ratio of alphabet characters to separators
+const SEPARATOR_RATIO: f64 = 3.5;
+
+/// ratio of alphabet characters to guards
+const GUARD_RATIO: usize = 12;
+
+/// range of the check value encoded after the id
+const CHECK_RANGE: u64 = 1000;
+
+/// possible errors when decoding a hashid
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// the string was not created by this codec. this includes strings
+    /// created with a different salt or min length
+    InvalidHash,
+
+    /// the string decoded to a value that is not a valid snowflake
+    InvalidId,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidHash => write!(f, "invalid hash"),
+            Error::InvalidId => write!(f, "invalid id"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// shuffles the alphabet using the given salt. the same salt will always
+/// produce the same order
+fn shuffle(alphabet: &mut [u8], salt: &[u8]) {
+    if salt.is_empty() {
+        return;
+    }
+
+    let mut v = 0;
+    let mut p = 0;
+
+    for i in (1..alphabet.len()).rev() {
+        v %= salt.len();
+
+        let integer = salt[v] as usize;
+        p += integer;
+
+        alphabet.swap(i, (integer + v + p) % i);
+
+        v += 1;
+    }
+}
+
+/// encodes and decodes ids as hashids with a given salt
+#[derive(Clone)]
+pub struct Codec {
+    salt: Vec<u8>,
+    min_length: usize,
+    alphabet: Vec<u8>,
+    separators: Vec<u8>,
+    guards: Vec<u8>,
+}
+
+impl Codec {
+    /// creates a codec with the given salt and minimum length of the encoded
+    /// strings
+    pub fn new(salt: &str, min_length: usize) -> Self {
+        let salt = salt.as_bytes().to_vec();
+        let mut separators = SEPARATORS.to_vec();
+        let mut alphabet: Vec<u8> = ALPHABET.iter()
+            .copied()
+            .filter(|ch| !separators.contains(ch))
+            .collect();
+
+        shuffle(&mut separators, &salt);
+
+        if alphabet.len() as f64 / separators.len() as f64 > SEPARATOR_RATIO {
+            let wanted = ((alphabet.len() as f64 / SEPARATOR_RATIO).ceil() as usize).max(2);
+
+            if wanted > separators.len() {
+                let diff = wanted - separators.len();
+
+                separators.extend(alphabet.drain(..diff));
+            } else {
+                separators.truncate(wanted);
+            }
+        }
+
+        shuffle(&mut alphabet, &salt);
+
+        let guard_count = (alphabet.len() + GUARD_RATIO - 1) / GUARD_RATIO;
+        let guards = alphabet.drain(..guard_count).collect();
+
+        Codec {
+            salt,
+            min_length,
+            alphabet,
+            separators,
+            guards,
+        }
+    }
+
+    /// shuffles the alphabet for the next number of a hash
+    fn next_alphabet(&self, alphabet: &mut [u8], lottery: u8) {
+        let mut buffer = Vec::with_capacity(1 + self.salt.len() + alphabet.len());
+        buffer.push(lottery);
+        buffer.extend_from_slice(&self.salt);
+        buffer.extend_from_slice(alphabet);
+        buffer.truncate(alphabet.len());
+
+        shuffle(alphabet, &buffer);
+    }
+
+    /// salted check value that is encoded after the id
+    fn check(&self, value: u128) -> u128 {
+        // fnv-1a of the salt
+        let key = self.salt.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+        });
+
+        (mix(key ^ value as u64 ^ mix((value >> 64) as u64)) % CHECK_RANGE) as u128
+    }
+
+    /// encodes a list of numbers the same as hashids
+    fn encode_numbers(&self, numbers: &[u128]) -> String {
+        let mut alphabet = self.alphabet.clone();
+        let len = alphabet.len() as u128;
+        let numbers_hash = numbers.iter()
+            .enumerate()
+            .map(|(index, value)| (value % (index as u128 + 100)) as usize)
+            .sum::<usize>();
+        let lottery = alphabet[numbers_hash % alphabet.len()];
+
+        let mut rtn = vec![lottery];
+
+        for (index, value) in numbers.iter().enumerate() {
+            self.next_alphabet(&mut alphabet, lottery);
+
+            let start = rtn.len();
+            let mut remaining = *value;
+
+            loop {
+                rtn.insert(start, alphabet[(remaining % len) as usize]);
+                remaining /= len;
+
+                if remaining == 0 {
+                    break;
+                }
+            }
+
+            if index + 1 < numbers.len() {
+                let remaining = value % (rtn[start] as u128 + index as u128);
+
+                rtn.push(self.separators[(remaining % self.separators.len() as u128) as usize]);
+            }
+        }
+
+        if rtn.len() < self.min_length {
+            let index = (numbers_hash + rtn[0] as usize) % self.guards.len();
+            rtn.insert(0, self.guards[index]);
+
+            if rtn.len() < self.min_length {
+                let index = (numbers_hash + rtn[2] as usize) % self.guards.len();
+                rtn.push(self.guards[index]);
+            }
+        }
+
+        let half = alphabet.len() / 2;
+
+        while rtn.len() < self.min_length {
+            let salt = alphabet.clone();
+            shuffle(&mut alphabet, &salt);
+
+            let mut padded = alphabet[half..].to_vec();
+            padded.extend_from_slice(&rtn);
+            padded.extend_from_slice(&alphabet[..half]);
+
+            let excess = padded.len().saturating_sub(self.min_length);
+            let start = excess / 2;
+
+            rtn = padded;
+
+            if excess > 0 {
+                rtn = rtn[start..(start + self.min_length)].to_vec();
+            }
+        }
+
+        rtn.into_iter().map(char::from).collect()
+    }
+
+    /// decodes a list of numbers the same as hashids
+    ///
+    /// the numbers are encoded again and compared to the string so only
+    /// strings created by this codec will decode
+    fn decode_numbers(&self, string: &str) -> Option<Vec<u128>> {
+        let bytes = string.as_bytes();
+        let parts: Vec<&[u8]> = bytes.split(|ch| self.guards.contains(ch)).collect();
+        let part = if parts.len() == 2 || parts.len() == 3 {
+            parts[1]
+        } else {
+            parts[0]
+        };
+
+        let (lottery, rest) = part.split_first()?;
+        let mut alphabet = self.alphabet.clone();
+        let mut rtn = Vec::new();
+
+        for digits in rest.split(|ch| self.separators.contains(ch)) {
+            if digits.is_empty() {
+                return None;
+            }
+
+            self.next_alphabet(&mut alphabet, *lottery);
+
+            let mut value: u128 = 0;
+
+            for ch in digits {
+                let digit = alphabet.iter().position(|d| d == ch)?;
+
+                value = value.checked_mul(alphabet.len() as u128)?
+                    .checked_add(digit as u128)?;
+            }
+
+            rtn.push(value);
+        }
+
+        if self.encode_numbers(&rtn) != string {
+            return None;
+        }
+
+        Some(rtn)
+    }
+
+    /// encodes the id of the snowflake
+    pub fn encode<F>(&self, flake: &F) -> String
+    where
+        F: traits::Id,
+        F::BaseType: Encodable,
+    {
+        let value = flake.id().into_u128();
+
+        self.encode_numbers(&[value, self.check(value)])
+    }
+
+    /// decodes a string created by [`encode`](Codec::encode)
+    ///
+    /// returns [`Error::InvalidHash`] if the string was not created by this
+    /// codec and [`Error::InvalidId`] if the value does not fit in the base
+    /// type or is not a valid snowflake
+    pub fn decode<F>(&self, string: &str) -> Result<F, Error>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: Encodable,
+    {
+        let numbers = self.decode_numbers(string).ok_or(Error::InvalidHash)?;
+
+        let [value, check] = numbers[..] else {
+            return Err(Error::InvalidHash);
+        };
+
+        if check != self.check(value) {
+            return Err(Error::InvalidHash);
+        }
+
+        crate::encoding::flake_from_u128(value).map_err(|_| Error::InvalidId)
+    }
+}
+
+impl fmt::Debug for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Codec")
+            .field("min_length", &self.min_length)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Signed = crate::i64::SingleIdFlake<43, 8, 12>;
+    type Unsigned = crate::u64::SingleIdFlake<44, 8, 12>;
+
+    #[test]
+    fn hashids_compatible() {
+        // examples from the hashids documentation
+        assert_eq!(Codec::new("this is my salt", 0).encode_numbers(&[12345]), "NkK9");
+        assert_eq!(Codec::new("this is my salt", 8).encode_numbers(&[1]), "gB0NV05e");
+        assert_eq!(Codec::new("My Project", 0).encode_numbers(&[1, 2, 3]), "Z4UrtW");
+        assert_eq!(Codec::new("", 0).encode_numbers(&[1]), "jR");
+
+        assert_eq!(Codec::new("My Project", 0).decode_numbers("Z4UrtW"), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn round_trip() {
+        for salt in ["", "snowcloud", "a much longer salt than the others"] {
+            for min_length in [0, 1, 8, 12, 20, 40] {
+                let codec = Codec::new(salt, min_length);
+
+                for flake in [Unsigned::NIL, Unsigned::try_from(&1).unwrap(), Unsigned::MAX] {
+                    let string = codec.encode(&flake);
+
+                    assert!(string.len() >= min_length, "{:?} shorter than {}", string, min_length);
+                    assert_eq!(codec.decode::<Unsigned>(&string).unwrap(), flake);
+                }
+
+                for bits in crate::random_bits(100) {
+                    let flake = Unsigned::try_from(&(bits as u64)).unwrap();
+
+                    assert_eq!(codec.decode::<Unsigned>(&codec.encode(&flake)).unwrap(), flake);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn stable_test_vector() {
+        let expected = [
+            ("", 0, [
+                (0, "jQclz"),
+                (1, "4xfGG"),
+                (1052673, "44KB2HJm"),
+                (1679082337000, "lRM0L3r99c6m"),
+                (i64::MAX as u64, "G80Rkj1rvG518CGy"),
+                (u64::MAX, "9XBLpN58pJgKXf1w"),
+            ]),
+            ("snowcloud", 0, [
+                (0, "OohyP"),
+                (1, "k9t55"),
+                (1052673, "KXrDAsZ"),
+                (1679082337000, "LN4ABYjnnFYQ"),
+                (i64::MAX as u64, "yqG0rmPkJ3nPqhXO"),
+                (u64::MAX, "Z6xAX53nXzka6TkY"),
+            ]),
+            ("snowcloud", 16, [
+                (0, "rBjKvVOohyPVmp64"),
+                (1, "2PvmLek9t55V4A7R"),
+                (1052673, "dK3BVKXrDAsZlGag"),
+                (1679082337000, "B8LN4ABYjnnFYQlX"),
+                (i64::MAX as u64, "yqG0rmPkJ3nPqhXO"),
+                (u64::MAX, "Z6xAX53nXzka6TkY"),
+            ]),
+        ];
+
+        for (salt, min_length, vectors) in expected {
+            let codec = Codec::new(salt, min_length);
+
+            for (id, string) in vectors {
+                let flake = Unsigned::try_from(&id).unwrap();
+
+                assert_eq!(codec.encode(&flake), string, "encoding changed for {} with {:?}", id, salt);
+                assert_eq!(codec.decode::<Unsigned>(string).unwrap(), flake);
+            }
+        }
+    }
+
+    #[test]
+    fn wrong_salt() {
+        let codec = Codec::new("snowcloud", 0);
+        let other = Codec::new("not snowcloud", 0);
+        let padded = Codec::new("snowcloud", 16);
+
+        for bits in crate::random_bits(1000) {
+            let flake = Unsigned::try_from(&(bits as u64)).unwrap();
+            let string = codec.encode(&flake);
+
+            assert_eq!(other.decode::<Unsigned>(&string), Err(Error::InvalidHash), "decoded {:?}", string);
+
+            if string.len() < 16 {
+                assert_eq!(padded.decode::<Unsigned>(&string), Err(Error::InvalidHash), "decoded {:?}", string);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let codec = Codec::new("snowcloud", 0);
+
+        for string in ["", "O", "Oohy", "OohyP-", "OohyPOohyP", "!!", "E0"] {
+            assert_eq!(codec.decode::<Unsigned>(string), Err(Error::InvalidHash), "decoded {:?}", string);
+        }
+
+        // u64::MAX does not fit in an i64
+        assert_eq!(codec.decode::<Signed>("Z6xAX53nXzka6TkY"), Err(Error::InvalidId));
+    }
+}
@@ -0,0 +1,307 @@
+//! string encodings for snowflakes
+//!
+//! each encoding has its own module that provides `encode` and `decode`
+//! functions for any snowflake. the snowflakes also provide methods for each
+//! encoding so the functions only need to be used directly in generic code.
+//!
+//! ```rust
+//! use snowcloud::flake::encoding::base58;
+//!
+//! type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! let flake = MyFlake::from_parts(1000, 1, 1).unwrap();
+//! let string = base58::encode(&flake);
+//!
+//! assert_eq!(string, flake.to_base58());
+//! assert_eq!(base58::decode::<MyFlake>(&string).unwrap(), flake);
+//! ```
+
+use snowcloud_core::traits;
+
+use crate::error;
+
+pub mod base58;
+pub mod base62;
+pub mod base64url;
+pub mod checked;
+pub mod crockford;
+pub mod framed;
+pub mod hashid;
+
+/// integers that can be used by the encodings
+///
+/// the encodings work with the value of an id as a u128 so any snowflake
+/// base type can be encoded
+pub trait Encodable: Sized {
+    /// total bits of the integer
+    const BITS: u32;
+
+    /// returns the value as a u128
+    fn into_u128(self) -> u128;
+
+    /// creates the integer from a u128 if it is within range
+    fn from_u128(value: u128) -> Option<Self>;
+
+    /// parses the integer from a string in the given radix
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, std::num::ParseIntError>;
+}
+
+macro_rules! encodable {
+    ($t:ty) => {
+        impl Encodable for $t {
+            const BITS: u32 = <$t>::BITS;
+
+            #[inline]
+            fn into_u128(self) -> u128 {
+                self as u128
+            }
+
+            #[inline]
+            fn from_u128(value: u128) -> Option<Self> {
+                <$t>::try_from(value).ok()
+            }
+
+            #[inline]
+            fn from_str_radix(src: &str, radix: u32) -> Result<Self, std::num::ParseIntError> {
+                <$t>::from_str_radix(src, radix)
+            }
+        }
+    };
+}
+
+encodable!(u32);
+encodable!(i64);
+encodable!(u64);
+encodable!(i128);
+
+impl Encodable for u128 {
+    const BITS: u32 = u128::BITS;
+
+    #[inline]
+    fn into_u128(self) -> u128 {
+        self
+    }
+
+    #[inline]
+    fn from_u128(value: u128) -> Option<Self> {
+        Some(value)
+    }
+
+    #[inline]
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, std::num::ParseIntError> {
+        u128::from_str_radix(src, radix)
+    }
+}
+
+/// digits used by [`to_string_radix`]
+const RADIX_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// prints the value in the given radix using lowercase letters and no prefix
+///
+/// panics if the radix is not in the range 2 to 36
+pub(crate) fn to_string_radix(value: u128, radix: u32) -> String {
+    assert!((2..=36).contains(&radix), "radix must be in the range 2 to 36");
+
+    let alphabet = Alphabet {
+        digits: &RADIX_DIGITS[..(radix as usize)],
+        ambiguous: b"",
+        aliases: &[],
+        ignore_case: false,
+    };
+
+    alphabet.encode(value)
+}
+
+/// parses a value that may start with a `0x`, `0o`, or `0b` prefix
+///
+/// the prefix and hex digits can be upper or lowercase. strings without a
+/// prefix are parsed as decimal and a sign is only allowed without a prefix
+pub(crate) fn parse_prefixed<T>(string: &str) -> error::Result<T>
+where
+    T: Encodable,
+{
+    let (digits, radix) = match string.get(..2) {
+        Some("0x" | "0X") => (&string[2..], 16),
+        Some("0o" | "0O") => (&string[2..], 8),
+        Some("0b" | "0B") => (&string[2..], 2),
+        _ => (string, 10),
+    };
+
+    if radix != 10 {
+        if let Some(sign @ ('+' | '-')) = digits.chars().next() {
+            return Err(error::Error::InvalidCharacter(sign));
+        }
+    }
+
+    T::from_str_radix(digits, radix).map_err(error::Error::ParseInt)
+}
+
+/// creates a snowflake from a decoded value
+///
+/// values that do not fit in the base type of the snowflake or are not a
+/// valid snowflake are [`InvalidId`](crate::error::Error::InvalidId)
+pub(crate) fn flake_from_u128<F>(value: u128) -> error::Result<F>
+where
+    F: traits::Id + TryFrom<F::BaseType>,
+    F::BaseType: Encodable,
+{
+    let id = F::BaseType::from_u128(value).ok_or(error::Error::InvalidId)?;
+
+    F::try_from(id).map_err(|_| error::Error::InvalidId)
+}
+
+/// characters used by a positional encoding
+pub(crate) struct Alphabet {
+    /// the character for each digit starting with 0
+    pub digits: &'static [u8],
+
+    /// characters that are left out of the digits since they are easily
+    /// confused with others
+    pub ambiguous: &'static [u8],
+
+    /// characters that are decoded as another character in the digits
+    pub aliases: &'static [(u8, u8)],
+
+    /// if lowercase characters are decoded the same as uppercase
+    pub ignore_case: bool,
+}
+
+impl Alphabet {
+    /// encodes the value with the most significant digit first
+    ///
+    /// 0 is encoded as the first digit of the alphabet
+    pub fn encode(&self, mut value: u128) -> String {
+        let base = self.digits.len() as u128;
+        let mut rtn = Vec::new();
+
+        loop {
+            rtn.push(char::from(self.digits[(value % base) as usize]));
+            value /= base;
+
+            if value == 0 {
+                break;
+            }
+        }
+
+        rtn.iter().rev().collect()
+    }
+
+    /// encodes the value padded with the first digit to the given width
+    ///
+    /// the width should be large enough for any value that will be given
+    pub fn encode_fixed(&self, value: u128, width: usize) -> String {
+        let encoded = self.encode(value);
+        let mut rtn = String::with_capacity(width.max(encoded.len()));
+
+        for _ in encoded.len()..width {
+            rtn.push(char::from(self.digits[0]));
+        }
+
+        rtn.push_str(&encoded);
+        rtn
+    }
+
+    /// finds the value of a character
+    pub fn digit(&self, ch: char) -> Option<u128> {
+        let mut byte = u8::try_from(ch).ok()?;
+
+        if self.ignore_case {
+            byte = byte.to_ascii_uppercase();
+        }
+
+        if let Some((_, to)) = self.aliases.iter().find(|(from, _)| *from == byte) {
+            byte = *to;
+        }
+
+        self.digits.iter()
+            .position(|d| *d == byte)
+            .map(|digit| digit as u128)
+    }
+
+    /// decodes a value with the most significant digit first
+    ///
+    /// leading zero digits do not change the value.
+    /// [`AmbiguousCharacter`](crate::error::Error::AmbiguousCharacter) and
+    /// [`InvalidCharacter`](crate::error::Error::InvalidCharacter) are
+    /// returned for characters outside of the alphabet and
+    /// [`InvalidId`](crate::error::Error::InvalidId) if the string is empty
+    /// or the value is larger than a u128
+    pub fn decode(&self, string: &str) -> error::Result<u128> {
+        if string.is_empty() {
+            return Err(error::Error::InvalidId);
+        }
+
+        let base = self.digits.len() as u128;
+        let mut value: u128 = 0;
+
+        for ch in string.chars() {
+            let Some(digit) = self.digit(ch) else {
+                return Err(if u8::try_from(ch).map_or(false, |byte| self.ambiguous.contains(&byte)) {
+                    error::Error::AmbiguousCharacter(ch)
+                } else {
+                    error::Error::InvalidCharacter(ch)
+                });
+            };
+
+            value = value.checked_mul(base)
+                .and_then(|value| value.checked_add(digit))
+                .ok_or(error::Error::InvalidId)?;
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn prefixed() {
+        let expected: [(&str, i64); 10] = [
+            ("1052673", 1052673),
+            ("0x101001", 1052673),
+            ("0X101001", 1052673),
+            ("0xabcDEF", 0xabcdef),
+            ("0XABCdef", 0xabcdef),
+            ("0o4010001", 1052673),
+            ("0b100000001000000000001", 1052673),
+            ("0B11", 3),
+            ("-12", -12),
+            ("0", 0),
+        ];
+
+        for (string, value) in expected {
+            assert_eq!(parse_prefixed::<i64>(string).unwrap(), value, "failed to parse {:?}", string);
+        }
+
+        for string in ["0x", "0b102", "0o8", "0xg", "12a", "", "0d10", "x10", "0x7fffffffffffffff0"] {
+            assert!(matches!(parse_prefixed::<i64>(string), Err(error::Error::ParseInt(_))), "parsed {:?}", string);
+        }
+
+        assert!(matches!(parse_prefixed::<i64>("0x-1"), Err(error::Error::InvalidCharacter('-'))));
+        assert!(matches!(parse_prefixed::<i64>("0b+1"), Err(error::Error::InvalidCharacter('+'))));
+        assert!(matches!(parse_prefixed::<u64>("-1"), Err(error::Error::ParseInt(_))));
+
+        assert_eq!(parse_prefixed::<u64>("0xffffffffffffffff").unwrap(), u64::MAX);
+        assert!(matches!(parse_prefixed::<i64>("0xffffffffffffffff"), Err(error::Error::ParseInt(_))));
+    }
+
+    #[test]
+    fn radix_strings() {
+        assert_eq!(to_string_radix(1052673, 16), "101001");
+        assert_eq!(to_string_radix(0xabcdef, 16), "abcdef");
+        assert_eq!(to_string_radix(1052673, 2), "100000001000000000001");
+        assert_eq!(to_string_radix(1052673, 8), "4010001");
+        assert_eq!(to_string_radix(1052673, 10), "1052673");
+        assert_eq!(to_string_radix(35, 36), "z");
+        assert_eq!(to_string_radix(0, 2), "0");
+        assert_eq!(to_string_radix(u128::MAX, 16), "f".repeat(32));
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_radix() {
+        to_string_radix(1, 37);
+    }
+}
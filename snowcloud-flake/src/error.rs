@@ -50,7 +50,42 @@ pub enum Error {
     InvalidId,
 
     /// provided too many segments for creating a Snowflake
-    TooManySegments
+    TooManySegments,
+
+    /// a provided layout uses more bits than are available or has a segment
+    /// without any bits
+    LayoutInvalid,
+
+    /// a provided string is not a valid integer
+    ParseInt(std::num::ParseIntError),
+
+    /// a provided string contains a character that is not part of the
+    /// encoding
+    InvalidCharacter(char),
+
+    /// a provided string contains a character that the encoding leaves out
+    /// since it is easily confused with another
+    AmbiguousCharacter(char),
+
+    /// a provided string is not the length required by the encoding
+    InvalidLength(usize),
+
+    /// the check character of a provided string does not match the rest of
+    /// the string
+    ChecksumMismatch,
+
+    /// a provided frame does not start with the expected header
+    InvalidFrame,
+
+    /// the layout of a provided frame does not match the expected snowflake
+    LayoutMismatch,
+
+    /// the epoch stored with an id does not match the expected epoch
+    EpochMismatch,
+
+    /// a provided bucket size is 0 or the bucket does not contain any
+    /// timestamps of a Snowflake
+    BucketInvalid,
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -72,13 +107,46 @@ impl std::fmt::Display for Error {
             ),
             Error::TooManySegments => write!(
                 f, "too many segments"
-            )
+            ),
+            Error::LayoutInvalid => write!(
+                f, "layout invalid"
+            ),
+            Error::ParseInt(err) => write!(
+                f, "parse int. {}", err
+            ),
+            Error::InvalidCharacter(ch) => write!(
+                f, "invalid character {:?}", ch
+            ),
+            Error::AmbiguousCharacter(ch) => write!(
+                f, "ambiguous character {:?}", ch
+            ),
+            Error::InvalidLength(len) => write!(
+                f, "invalid length {}", len
+            ),
+            Error::ChecksumMismatch => write!(
+                f, "checksum mismatch"
+            ),
+            Error::InvalidFrame => write!(
+                f, "invalid frame"
+            ),
+            Error::LayoutMismatch => write!(
+                f, "layout mismatch"
+            ),
+            Error::EpochMismatch => write!(
+                f, "epoch mismatch"
+            ),
+            Error::BucketInvalid => write!(
+                f, "bucket invalid"
+            ),
         }
     }
 }
 
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+        match self {
+            Error::ParseInt(err) => Some(err),
+            _ => None
+        }
     }
 }
@@ -1,7 +1,18 @@
 //! provides i64 based snowflakes
 
 mod single;
+mod single_sec;
 mod dual;
+mod triple;
+mod parts;
+mod n_id;
 
 pub use single::SingleIdFlake;
+pub use single_sec::SingleIdFlakeSec;
 pub use dual::DualIdFlake;
+pub use triple::TripleIdFlake;
+pub use parts::{SingleIdParts, DualIdParts, TripleIdParts};
+pub use n_id::{NIdFlake, SegmentBits};
+
+// storing ids as an `Option<NonZeroI64>` should cost nothing over an i64
+const _: () = assert!(std::mem::size_of::<Option<std::num::NonZeroI64>>() == 8);
@@ -0,0 +1,1647 @@
+use std::hash::Hasher;
+use std::num::NonZeroI64;
+use std::ops::RangeInclusive;
+use std::marker::PhantomData;
+use std::time::{Duration, SystemTime};
+
+use snowcloud_core::traits;
+
+#[cfg(feature = "serde")]
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{de, ser};
+
+#[cfg(feature = "postgres")]
+use postgres_types::{to_sql_checked, accepts, IsNull, FromSql, ToSql, Type as PgType};
+#[cfg(feature = "postgres")]
+use bytes::{BytesMut, BufMut};
+
+use crate::error;
+use crate::opaque::Permuter;
+use crate::Segments;
+
+/// provides the bits for each id segment of a [`NIdFlake`]
+///
+/// arrays cannot be used as const generic parameters on stable so the bits
+/// are provided by a type instead
+///
+/// ```rust
+/// use snowcloud::i64::{NIdFlake, SegmentBits};
+///
+/// struct MySegments;
+///
+/// impl SegmentBits<2> for MySegments {
+///     const BITS: [u8; 2] = [4, 4];
+/// }
+///
+/// type MyFlake = NIdFlake<43, 12, 2, MySegments>;
+/// ```
+pub trait SegmentBits<const N: usize> {
+    /// bits for each segment starting with the highest bits of the id
+    const BITS: [u8; N];
+}
+
+const fn sum_bits<const N: usize>(bits: &[u8; N]) -> u32 {
+    let mut total = 0;
+    let mut i = 0;
+
+    while i < N {
+        total += bits[i] as u32;
+        i += 1;
+    }
+
+    total
+}
+
+const fn max_values<const N: usize>(bits: &[u8; N]) -> [i64; N] {
+    let mut rtn = [0; N];
+    let mut i = 0;
+
+    while i < N {
+        rtn[i] = (1 << bits[i] as i64) - 1;
+        i += 1;
+    }
+
+    rtn
+}
+
+const fn shifts<const N: usize>(bits: &[u8; N], seq: u8) -> [i64; N] {
+    let mut rtn = [0; N];
+    let mut shift = seq as i64;
+    let mut i = N;
+
+    while i > 0 {
+        i -= 1;
+        rtn[i] = shift;
+        shift += bits[i] as i64;
+    }
+
+    rtn
+}
+
+const fn masks<const N: usize>(max: &[i64; N], shifts: &[i64; N]) -> [i64; N] {
+    let mut rtn = [0; N];
+    let mut i = 0;
+
+    while i < N {
+        rtn[i] = max[i] << shifts[i];
+        i += 1;
+    }
+
+    rtn
+}
+
+/// i64 Snowflake with N id segments
+///
+/// the timestamp is stored in the highest bits followed by each id segment and
+/// then the sequence. the bits of each segment are provided by `B` which
+/// allows a single type to cover any number of segments. with 43 bit
+/// timestamp, 2 segments of 4 bits, and 12 bit sequence the format is the same
+/// as [`DualIdFlake`](crate::i64::DualIdFlake):
+///
+/// ```text
+///  01111111111111111111111111111111111111111111 - 1111 - 1111 - 111111111111
+///  |                                          |   |  |   |  |   |          |
+/// 64                                         21  20 17  16 13  12          1
+///                                     timestamp      |      |              |
+///                                            segment 0      |              |
+///                                                   segment 1              |
+///                                                                   sequence
+/// ```
+///
+/// the total amount of bits should equal 63 since the sign bit cannot be used
+/// otherwise you will get negative id values.
+///
+/// Note: the total is checked at compile time once the snowflake is used and
+/// will fail to compile if it does not equal 63. the `relaxed-layout`
+/// feature will only require the total to be less than or equal to 63.
+///
+/// ```compile_fail
+/// use snowcloud_flake::i64::{NIdFlake, SegmentBits};
+///
+/// struct MySegments;
+///
+/// // 67 bits will never fit in an i64
+/// impl SegmentBits<3> for MySegments {
+///     const BITS: [u8; 3] = [8, 4, 4];
+/// }
+///
+/// type MyFlake = NIdFlake<43, 8, 3, MySegments>;
+///
+/// let flake = MyFlake::from_parts(1, [1, 1, 1], 1);
+/// ```
+///
+#[cfg_attr(not(feature = "relaxed-layout"), doc = "```compile_fail")]
+#[cfg_attr(feature = "relaxed-layout", doc = "```")]
+/// use snowcloud_flake::i64::{NIdFlake, SegmentBits};
+///
+/// struct MySegments;
+///
+/// // 62 bits only compiles with the relaxed-layout feature
+/// impl SegmentBits<3> for MySegments {
+///     const BITS: [u8; 3] = [4, 4, 3];
+/// }
+///
+/// type MyFlake = NIdFlake<43, 8, 3, MySegments>;
+///
+/// let flake = MyFlake::from_parts(1, [1, 1, 1], 1);
+/// ```
+///
+/// # De/Serialize
+///
+/// with the `serde` feature you can de/serialize a snowflake to and from an
+/// [`i64`](core::primitive::i64) by default
+///
+/// # Example Usage
+///
+/// ```rust
+/// use snowcloud::i64::{NIdFlake, SegmentBits};
+///
+/// struct MySegments;
+///
+/// impl SegmentBits<3> for MySegments {
+///     const BITS: [u8; 3] = [4, 4, 4];
+/// }
+///
+/// type MyFlake = NIdFlake<43, 8, 3, MySegments>;
+/// type MyCloud = snowcloud::Generator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let mut cloud = MyCloud::new(START_TIME, [1, 2, 3])
+///     .expect("failed to create MyCloud");
+/// let flake: MyFlake = cloud.next_id()
+///     .expect("failed to create snowflake");
+///
+/// println!("{:?}", flake);
+/// ```
+pub struct NIdFlake<const TS: u8, const SEQ: u8, const N: usize, B> {
+    pub(crate) dur: Option<Duration>,
+    pub(crate) tsm: i64,
+    pub(crate) segs: [i64; N],
+    pub(crate) seq: i64,
+    bits: PhantomData<fn() -> B>,
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    /// max value that a timestamp can be. `(1 << TS as i64) - 1`
+    pub const MAX_TIMESTAMP: i64 = (1 << TS as i64) - 1;
+    /// max value for each segment
+    pub const MAX_SEGMENTS: [i64; N] = max_values(&B::BITS);
+    /// max value a sequence can be. `(1 << SEQ as i64) - 1`
+    pub const MAX_SEQUENCE: i64 = (1 << SEQ as i64) - 1;
+
+    /// total bits to shift the timestamp. sum of all segments and `SEQ`
+    pub const TIMESTAMP_SHIFT: i64 = sum_bits(&B::BITS) as i64 + SEQ as i64;
+    /// total bits to shift each segment
+    pub const SEGMENT_SHIFTS: [i64; N] = shifts(&B::BITS, SEQ);
+
+    /// bit mask for timestamp. `Self::MAX_TIMESTAMP << Self::TIMESTAMP_SHIFT`
+    pub const TIMESTAMP_MASK: i64 = Self::MAX_TIMESTAMP << Self::TIMESTAMP_SHIFT;
+    /// bit mask for each segment
+    pub const SEGMENT_MASKS: [i64; N] = masks(&Self::MAX_SEGMENTS, &Self::SEGMENT_SHIFTS);
+    /// bit mask for sequence. `Self::MAX_SEQUENCE`
+    pub const SEQUENCE_MASK: i64 = Self::MAX_SEQUENCE;
+
+    const MAX_EPOCH: u64 = (1 << TS as u64) - 1;
+
+    /// snowflake with every part set to 0
+    pub const NIL: Self = Self { dur: None, tsm: 0, segs: [0; N], seq: 0, bits: PhantomData };
+    /// smallest possible snowflake. same as `Self::NIL`
+    pub const MIN: Self = Self::NIL;
+    /// largest possible snowflake with every part set to its max value
+    pub const MAX: Self = Self {
+        dur: None,
+        tsm: Self::MAX_TIMESTAMP,
+        segs: Self::MAX_SEGMENTS,
+        seq: Self::MAX_SEQUENCE,
+        bits: PhantomData,
+    };
+
+    /// fails to compile if the layout does not fit the available bits
+    const VALID_LAYOUT: () = assert!(
+        crate::layout_fits(
+            <Self as traits::FromIdGenerator>::LAYOUT_BITS,
+            <Self as traits::FromIdGenerator>::MAX_LAYOUT_BITS
+        ),
+        "NIdFlake bit widths must sum to 63"
+    );
+
+    /// returns the time since the epoch that the snowflake was generated at
+    ///
+    /// only snowflakes from a generator will have this. anything created
+    /// from parts or an integer will be `None` and only the millisecond
+    /// precision of the timestamp is available
+    pub fn duration(&self) -> Option<&Duration> {
+        self.dur.as_ref()
+    }
+
+    /// returns timestamp
+    pub fn timestamp(&self) -> i64 {
+        self.tsm
+    }
+
+    /// returns the unix timestamp in milliseconds of when the snowflake was
+    /// created using the given epoch
+    ///
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the result does not fit in a u64
+    pub fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        epoch.checked_add(self.tsm as u64)
+            .ok_or(error::Error::EpochInvalid)
+    }
+
+    /// returns id segments reference
+    pub fn segments(&self) -> &[i64; N] {
+        &self.segs
+    }
+
+    /// returns sequence
+    pub fn sequence(&self) -> i64 {
+        self.seq
+    }
+
+    /// extracts the timestamp from an id without any validation
+    ///
+    /// the sign bit is outside of every mask so a negative id will never
+    /// produce a negative value
+    #[inline]
+    pub const fn timestamp_of(id: i64) -> i64 {
+        (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT
+    }
+
+    /// extracts the segment at the given index from an id without any
+    /// validation. `None` if the index is not less than `N`
+    #[inline]
+    pub const fn segment_of(id: i64, index: usize) -> Option<i64> {
+        if index < N {
+            Some((id & Self::SEGMENT_MASKS[index]) >> Self::SEGMENT_SHIFTS[index])
+        } else {
+            None
+        }
+    }
+
+    /// extracts the sequence from an id without any validation
+    #[inline]
+    pub const fn sequence_of(id: i64) -> i64 {
+        id & Self::SEQUENCE_MASK
+    }
+
+    /// generates a Snowflake from the provided parts
+    ///
+    /// checks will be performed on each part to ensure that they are
+    /// valid for the given Snowflake.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// any segment is invalid
+    pub fn from_parts(tsm: i64, segs: [i64; N], seq: i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm < 0 || tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        for (seg, max) in segs.iter().zip(Self::MAX_SEGMENTS) {
+            if *seg < 0 || *seg > max {
+                return Err(error::Error::IdSegInvalid);
+            }
+        }
+
+        if seq < 0 || seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        Ok(Self { dur: None, tsm, segs, seq, bits: PhantomData })
+    }
+
+    /// generates a Snowflake from the timestamp, id segments, and sequence
+    ///
+    /// the ids can be anything that converts into the same segments given to
+    /// a generator like a single value, a tuple, or
+    /// [`Segments`](crate::Segments). the same checks as
+    /// [`from_parts`](Self::from_parts) will be performed
+    pub fn from_segments<I>(tsm: i64, ids: I, seq: i64) -> error::Result<Self>
+    where
+        I: Into<Segments<i64, N>>
+    {
+        Self::from_parts(tsm, ids.into().into_inner(), seq)
+    }
+
+    /// generates the smallest Snowflake for the given timestamp
+    ///
+    /// every other part is set to 0. useful as the lower bound when
+    /// searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn first_for_timestamp(tsm: i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm < 0 || tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::NIL })
+    }
+
+    /// generates the largest Snowflake for the given timestamp
+    ///
+    /// every other part is set to its max value. useful as the upper bound
+    /// when searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn last_for_timestamp(tsm: i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm < 0 || tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::MAX })
+    }
+
+    /// returns the range of ids that were created between the given times
+    /// using the epoch in milliseconds
+    ///
+    /// `start` is inclusive and `end` is exclusive. the returned range is
+    /// inclusive so it can be used directly with something like
+    /// `WHERE id BETWEEN $1 AND $2`. a `start` before the epoch or an `end`
+    /// after the max timestamp will be clamped to what the snowflake can
+    /// represent. [`EpochInvalid`](crate::error::Error::EpochInvalid) will be
+    /// returned if none of the time range can be represented
+    pub fn id_range_for(epoch_millis: u64, start: SystemTime, end: SystemTime) -> error::Result<RangeInclusive<i64>> {
+        let (first, last) = crate::tick_range::<Self>(epoch_millis, start, end, Self::MAX_TIMESTAMP as u64)?;
+
+        Ok(Self::first_for_timestamp(first as i64)?.id()..=Self::last_for_timestamp(last as i64)?.id())
+    }
+
+    /// returns the first id of every boundary between the given times using
+    /// the epoch in milliseconds
+    ///
+    /// boundaries are multiples of `step` since the unix epoch so a step of
+    /// one day will give the first id of each day in UTC. `start` is
+    /// inclusive and `end` is exclusive. boundaries that are before the epoch
+    /// or after the max timestamp are skipped and a `step` of 0 will not
+    /// produce any ids. the ids are calculated as the iterator is consumed
+    pub fn id_partition_boundaries(epoch_millis: u64, start: SystemTime, end: SystemTime, step: Duration) -> impl Iterator<Item = i64> {
+        crate::boundary_ticks::<Self>(epoch_millis, start, end, step, Self::MAX_TIMESTAMP as u64)
+            .filter_map(|tick| Self::first_for_timestamp(tick as i64).ok())
+            .map(|flake| flake.id())
+    }
+
+    /// splits the current Snowflake into its individual parts
+    pub fn into_parts(self) -> (i64, [i64; N], i64) {
+        (self.tsm, self.segs, self.seq)
+    }
+
+    /// generates the unique id
+    pub fn id(&self) -> i64 {
+        let mut id = (self.tsm << Self::TIMESTAMP_SHIFT) | self.seq;
+
+        for (seg, shift) in self.segs.iter().zip(Self::SEGMENT_SHIFTS) {
+            id |= seg << shift;
+        }
+
+        id
+    }
+
+    /// renders the bits of the id split at each part with the name and
+    /// value of each part underneath
+    ///
+    /// useful for checking that a layout places the parts where they are
+    /// expected to be
+    pub fn fmt_layout(&self) -> String {
+        let labels: Vec<String> = (0..N).map(|index| format!("seg{}", index)).collect();
+        let mut parts = vec![("ts", TS)];
+
+        parts.extend(labels.iter().map(String::as_str).zip(B::BITS));
+        parts.push(("seq", SEQ));
+
+        crate::layout_diagram(self.id() as u128, 64, &parts)
+    }
+
+    /// encodes the id as base58
+    ///
+    /// see [`encoding::base58`](crate::encoding::base58) for more information
+    pub fn to_base58(&self) -> String {
+        crate::encoding::base58::encode(self)
+    }
+
+    /// decodes a base58 string created by [`to_base58`](Self::to_base58)
+    pub fn from_base58(string: &str) -> error::Result<Self> {
+        crate::encoding::base58::decode(string)
+    }
+
+    /// encodes the id as base62
+    ///
+    /// see [`encoding::base62`](crate::encoding::base62) for more information
+    pub fn to_base62(&self) -> String {
+        crate::encoding::base62::encode(self)
+    }
+
+    /// decodes a base62 string created by [`to_base62`](Self::to_base62)
+    pub fn from_base62(string: &str) -> error::Result<Self> {
+        crate::encoding::base62::decode(string)
+    }
+
+    /// encodes the id as fixed width crockford base32
+    ///
+    /// see [`encoding::crockford`](crate::encoding::crockford) for more
+    /// information
+    pub fn to_crockford(&self) -> String {
+        crate::encoding::crockford::encode_fixed(self)
+    }
+
+    /// decodes a crockford base32 string created by
+    /// [`to_crockford`](Self::to_crockford)
+    pub fn from_crockford(string: &str) -> error::Result<Self> {
+        crate::encoding::crockford::decode(string)
+    }
+
+    /// encodes the big endian bytes of the id as unpadded base64url
+    ///
+    /// see [`encoding::base64url`](crate::encoding::base64url) for more
+    /// information
+    pub fn to_base64url(&self) -> String {
+        crate::encoding::base64url::encode(self)
+    }
+
+    /// decodes a base64url string created by
+    /// [`to_base64url`](Self::to_base64url)
+    pub fn from_base64url(string: &str) -> error::Result<Self> {
+        crate::encoding::base64url::decode(string)
+    }
+
+    /// prints the id in the given radix using lowercase letters and no
+    /// prefix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn to_string_radix(&self, radix: u32) -> String {
+        crate::encoding::to_string_radix(self.id() as u128, radix)
+    }
+
+    /// parses a snowflake from an id in the given radix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn from_str_radix(string: &str, radix: u32) -> error::Result<Self> {
+        let id = i64::from_str_radix(string, radix).map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+
+    /// parses a snowflake from an id that may start with a `0x`, `0o`, or
+    /// `0b` prefix
+    ///
+    /// strings without a prefix are parsed as decimal
+    pub fn parse_prefixed(string: &str) -> error::Result<Self> {
+        Self::try_from(&crate::encoding::parse_prefixed::<i64>(string)?)
+    }
+
+    /// returns the id if it is not zero
+    ///
+    /// only [`NIL`](Self::NIL) will return `None`
+    pub fn id_nonzero(&self) -> Option<NonZeroI64> {
+        NonZeroI64::new(self.id())
+    }
+
+    /// returns the id scrambled with the given permuter
+    ///
+    /// see [`opaque`](crate::opaque) for more information
+    pub fn to_opaque(&self, permuter: &Permuter) -> i64 {
+        permuter.encode(self.id())
+    }
+
+    /// creates a snowflake from an id scrambled with
+    /// [`to_opaque`](Self::to_opaque) using the same permuter
+    pub fn from_opaque(id: i64, permuter: &Permuter) -> error::Result<Self> {
+        Self::try_from(&permuter.decode(id))
+    }
+
+    /// checks if every part of the snowflake is 0
+    pub fn is_nil(&self) -> bool {
+        self == &Self::NIL
+    }
+
+    /// returns the snowflake that comes after this one
+    ///
+    /// same as adding 1 to the id so the sequence will carry over into the
+    /// id segments and timestamp. `None` will be returned if this is
+    /// [`MAX`](Self::MAX)
+    pub fn successor(&self) -> Option<Self> {
+        if *self == Self::MAX {
+            return None;
+        }
+
+        Self::try_from(&(self.id() + 1)).ok()
+    }
+
+    /// returns the snowflake that comes before this one
+    ///
+    /// same as subtracting 1 from the id. `None` will be returned if this is
+    /// [`MIN`](Self::MIN)
+    pub fn predecessor(&self) -> Option<Self> {
+        if *self == Self::MIN {
+            return None;
+        }
+
+        Self::try_from(&(self.id() - 1)).ok()
+    }
+
+    /// returns the snowflake with the id segment at the given index replaced
+    ///
+    /// the duration is kept as is.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the index is out of bounds or the value is invalid
+    pub fn with_segment(mut self, index: usize, value: i64) -> error::Result<Self> {
+        let Some(max) = Self::MAX_SEGMENTS.get(index) else {
+            return Err(error::Error::IdSegInvalid);
+        };
+
+        if value < 0 || value > *max {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        self.segs[index] = value;
+
+        Ok(self)
+    }
+
+    /// returns the snowflake with the sequence replaced
+    ///
+    /// the duration is kept as is.
+    /// [`SequenceInvalid`](crate::error::Error::SequenceInvalid) will be returned if
+    /// the sequence is invalid
+    pub fn with_sequence(self, seq: i64) -> error::Result<Self> {
+        if seq < 0 || seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        Ok(Self { seq, ..self })
+    }
+
+    /// returns a copy of the snowflake with the sequence set to 0
+    ///
+    /// snowflakes created in the same tick will be equal which hides the
+    /// order they were created in. the id segments will also be set to 0 if
+    /// `clear_ids` is true. the result is only meant for exporting and is
+    /// not checked against the generator rules for id segments
+    pub fn anonymize(&self, clear_ids: bool) -> Self {
+        if clear_ids {
+            Self { tsm: self.tsm, ..Self::NIL }
+        } else {
+            Self { tsm: self.tsm, segs: self.segs, ..Self::NIL }
+        }
+    }
+
+    /// returns the milliseconds since the epoch rounded down to the start of
+    /// the given bucket
+    ///
+    /// a bucket shorter than a millisecond will return the milliseconds
+    /// unchanged
+    pub fn bucket_millis(&self, bucket: Duration) -> i64 {
+        let millis = self.tsm;
+        let bucket = i64::try_from(bucket.as_millis()).unwrap_or(i64::MAX);
+
+        if bucket == 0 {
+            millis
+        } else {
+            millis - millis % bucket
+        }
+    }
+
+    /// returns the bucket that the snowflake belongs to when grouping ids
+    /// into buckets of the given milliseconds since the epoch
+    ///
+    /// same as `(id >> TIMESTAMP_SHIFT) / bucket_millis` for millisecond
+    /// timestamps. [`BucketInvalid`](crate::error::Error::BucketInvalid) will
+    /// be returned if `bucket_millis` is 0
+    pub fn bucket(&self, bucket_millis: u64) -> error::Result<u64> {
+        if bucket_millis == 0 {
+            return Err(error::Error::BucketInvalid);
+        }
+
+        Ok(self.tsm as u64 / bucket_millis)
+    }
+
+    /// returns the range of ids that belong to the given bucket
+    ///
+    /// the reverse of [`bucket`](Self::bucket). the range is inclusive and
+    /// the end is clamped to the max timestamp.
+    /// [`BucketInvalid`](crate::error::Error::BucketInvalid) will be returned
+    /// if `bucket_millis` is 0 or the bucket does not contain any timestamps
+    /// and [`EpochInvalid`](crate::error::Error::EpochInvalid) will be
+    /// returned if the bucket starts after the max timestamp.
+    pub fn bucket_id_range(bucket: u64, bucket_millis: u64) -> error::Result<RangeInclusive<i64>> {
+        let (first, last) = crate::bucket_ticks::<Self>(bucket, bucket_millis, Self::MAX_TIMESTAMP as u64)?;
+
+        Ok(Self::first_for_timestamp(first as i64)?.id()..=Self::last_for_timestamp(last as i64)?.id())
+    }
+
+    /// returns the shard that the id belongs to out of the given number of
+    /// shards
+    ///
+    /// the full id is hashed before picking the shard so ids spread evenly
+    /// even when most of them have a small sequence. the result is stable
+    /// across versions, see [`shard`](crate::shard) for the algorithm. panics
+    /// if `shards` is 0
+    pub fn shard(&self, shards: u32) -> u32 {
+        crate::shard::shard_u64(self.id() as u64, shards)
+    }
+
+    /// returns the shard of the first id segment out of the given number of shards
+    ///
+    /// the first id segment is used as is without hashing so every id with the same
+    /// first id segment will be in the same shard. panics if `shards` is 0
+    pub fn shard_by_primary(&self, shards: u32) -> u32 {
+        crate::shard::shard_segment(self.segs.first().map_or(0, |seg| *seg as u128), shards)
+    }
+
+    /// returns the snowflake with the given milliseconds added to the
+    /// timestamp
+    ///
+    /// every other part is left untouched and the duration is cleared like
+    /// [`from_parts`](Self::from_parts).
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the new timestamp is less than 0 or greater than the max timestamp
+    pub fn add_millis(&self, delta: i64) -> error::Result<Self> {
+        let tsm = (self.tsm as i128).checked_add(delta as i128)
+            .and_then(|tsm| i64::try_from(tsm).ok())
+            .filter(|tsm| *tsm >= 0 && *tsm <= Self::MAX_TIMESTAMP)
+            .ok_or(error::Error::EpochInvalid)?;
+
+        Ok(Self { dur: None, tsm, ..*self })
+    }
+
+    /// returns the snowflake with the given milliseconds subtracted from the
+    /// timestamp
+    ///
+    /// same as [`add_millis`](Self::add_millis) with a negated delta
+    pub fn sub_millis(&self, delta: i64) -> error::Result<Self> {
+        let Some(delta) = delta.checked_neg() else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        self.add_millis(delta)
+    }
+
+    /// returns the snowflake with the timestamp set to the given time using
+    /// the epoch in milliseconds
+    ///
+    /// every other part is left untouched.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the time is before the epoch or after the max timestamp
+    pub fn with_timestamp_offset_from(&self, epoch_millis: u64, time: SystemTime) -> error::Result<Self> {
+        let Ok(since_unix) = time.duration_since(SystemTime::UNIX_EPOCH) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        let target = since_unix.as_millis() as i128 - epoch_millis as i128;
+
+        let Ok(delta) = i64::try_from(target - (self.tsm as i128)) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        self.add_millis(delta)
+    }
+
+    /// attempts to generated a snowflake from the given i64
+    ///
+    /// integer must be greater than or equal to `0` and less than or equal to
+    /// [`i64::MAX`](i64::MAX)
+    pub fn try_from(id: &i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if *id < 0 {
+            return Err(error::Error::InvalidId);
+        }
+
+        let mut segs = [0; N];
+
+        for (i, seg) in segs.iter_mut().enumerate() {
+            *seg = (id & Self::SEGMENT_MASKS[i]) >> Self::SEGMENT_SHIFTS[i];
+        }
+
+        Ok(Self {
+            dur: None,
+            tsm: (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT,
+            segs,
+            seq: id & Self::SEQUENCE_MASK,
+            bits: PhantomData,
+        })
+    }
+
+    /// returns the id as big endian bytes
+    ///
+    /// the bytes will sort in the same order as the ids which makes them
+    /// useful as keys in ordered byte stores
+    pub fn to_be_bytes(&self) -> [u8; 8] {
+        self.id().to_be_bytes()
+    }
+
+    /// returns the id as little endian bytes
+    pub fn to_le_bytes(&self) -> [u8; 8] {
+        self.id().to_le_bytes()
+    }
+
+    /// creates a snowflake from the big endian bytes of an id
+    ///
+    /// negative ids are rejected with
+    /// [`InvalidId`](crate::error::Error::InvalidId) the same as
+    /// [`try_from`](Self::try_from)
+    pub fn from_be_bytes(bytes: [u8; 8]) -> error::Result<Self> {
+        Self::try_from(&i64::from_be_bytes(bytes))
+    }
+
+    /// writes the big endian bytes of the id to the given writer
+    pub fn write_be(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&self.to_be_bytes())
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> traits::Id for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    type BaseType = i64;
+
+    fn id(&self) -> Self::BaseType {
+        NIdFlake::id(self)
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> traits::UnixMillis for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    type Error = error::Error;
+
+    fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        NIdFlake::unix_millis(self, epoch)
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> From<NIdFlake<TS, SEQ, N, B>> for i64
+where
+    B: SegmentBits<N>
+{
+    #[inline(always)]
+    fn from(flake: NIdFlake<TS, SEQ, N, B>) -> i64 {
+        flake.id()
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> From<&NIdFlake<TS, SEQ, N, B>> for i64
+where
+    B: SegmentBits<N>
+{
+    #[inline(always)]
+    fn from(flake: &NIdFlake<TS, SEQ, N, B>) -> i64 {
+        flake.id()
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> TryFrom<i64> for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: i64) -> Result<Self, Self::Error> {
+        NIdFlake::try_from(&id)
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> TryFrom<&i64> for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: &i64) -> Result<Self, Self::Error> {
+        NIdFlake::try_from(id)
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> TryFrom<NIdFlake<TS, SEQ, N, B>> for NonZeroI64
+where
+    B: SegmentBits<N>
+{
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(flake: NIdFlake<TS, SEQ, N, B>) -> Result<Self, Self::Error> {
+        flake.id_nonzero().ok_or(error::Error::InvalidId)
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> TryFrom<NonZeroI64> for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: NonZeroI64) -> Result<Self, Self::Error> {
+        Self::try_from(&id.get())
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> Clone for NIdFlake<TS, SEQ, N, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> Copy for NIdFlake<TS, SEQ, N, B> {}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::cmp::PartialEq for NIdFlake<TS, SEQ, N, B> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.tsm == rhs.tsm && self.segs == rhs.segs && self.seq == rhs.seq
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::cmp::PartialEq<i64> for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn eq(&self, rhs: &i64) -> bool {
+        self.id() == *rhs
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::cmp::PartialOrd for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::cmp::Ord for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    /// compares the ids of both snowflakes which orders them by timestamp
+    /// first followed by the id segments and then the sequence
+    fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
+        self.id().cmp(&rhs.id())
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::cmp::Eq for NIdFlake<TS, SEQ, N, B> {}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::hash::Hash for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    /// hashes the id of the snowflake so the hash will be the same as the
+    /// hash of the i64 it is stored as
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::fmt::Debug for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = self.id();
+
+        f.debug_struct("NIdFlake")
+            .field("id", &id)
+            .field("dur", &self.dur)
+            .field("tsm", &self.tsm)
+            .field("segs", &self.segs)
+            .field("seq", &self.seq)
+            .finish()
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::fmt::Display for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::fmt::LowerHex for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::fmt::UpperHex for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::UpperHex::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::fmt::Binary for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Binary::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::fmt::Octal for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Octal::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> std::str::FromStr for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.parse::<i64>().map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<const TS: u8, const SEQ: u8, const N: usize, B> NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    /// places the id into a version 8 uuid
+    ///
+    /// see [`uuid_ext`](crate::uuid_ext) for the layout of the uuid
+    pub fn to_uuid(&self) -> uuid::Uuid {
+        crate::uuid_ext::from_id(self.id() as u64)
+    }
+
+    /// creates a snowflake from a uuid created by [`to_uuid`](Self::to_uuid)
+    ///
+    /// the uuid must be version 8 with only the bits used by the id set
+    pub fn from_uuid(uuid: uuid::Uuid) -> error::Result<Self> {
+        let id = crate::uuid_ext::to_id(&uuid).ok_or(error::Error::InvalidId)?;
+
+        Self::try_from(&(id as i64))
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<const TS: u8, const SEQ: u8, const N: usize, B> From<NIdFlake<TS, SEQ, N, B>> for uuid::Uuid
+where
+    B: SegmentBits<N>
+{
+    fn from(flake: NIdFlake<TS, SEQ, N, B>) -> Self {
+        flake.to_uuid()
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> traits::FromIdGenerator for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    type IdSegType = Segments<i64, N>;
+    type Builder = Builder<TS, SEQ, N, B>;
+
+    const LAYOUT_BITS: u32 = TS as u32 + sum_bits(&B::BITS) + SEQ as u32;
+    const MAX_LAYOUT_BITS: u32 = 63;
+
+    fn valid_id(v: &Self::IdSegType) -> bool {
+        v.inner().iter()
+            .zip(Self::MAX_SEGMENTS)
+            .all(|(seg, max)| *seg > 0 && *seg <= max)
+    }
+
+    fn valid_epoch(e: &u64) -> bool {
+        *e <= Self::MAX_EPOCH
+    }
+
+    fn builder(ids: &Self::IdSegType) -> Self::Builder {
+        let () = Self::VALID_LAYOUT;
+
+        Builder {
+            dur: Duration::new(0,0),
+            ts: 0,
+            seq: 0,
+            segs: *ids.inner(),
+            bits: PhantomData,
+        }
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> traits::FlakeParts for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn id_segs(&self) -> Self::IdSegType {
+        Segments::from(self.segs)
+    }
+
+    fn timestamp_ticks(&self) -> u64 {
+        self.tsm as u64
+    }
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> traits::FlakeLayout for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    const LAYOUT: traits::Layout = traits::Layout {
+        base_bits: 64,
+        ts_bits: TS,
+        seg_bits: &B::BITS,
+        seq_bits: SEQ,
+    };
+}
+
+pub struct Builder<const TS: u8, const SEQ: u8, const N: usize, B> {
+    dur: Duration,
+    ts: u64,
+    seq: u64,
+    segs: [i64; N],
+    bits: PhantomData<fn() -> B>,
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> Builder<TS, SEQ, N, B> {
+    const MAX_EPOCH: u64 = (1 << TS as u64) - 1;
+    const MAX_U64_SEQUENCE: u64 = (1 << SEQ as u64) - 1;
+}
+
+impl<const TS: u8, const SEQ: u8, const N: usize, B> traits::IdBuilder for Builder<TS, SEQ, N, B> {
+    type Output = NIdFlake<TS, SEQ, N, B>;
+
+    fn with_ts(&mut self, ts: u64) -> bool {
+        if ts > Self::MAX_EPOCH {
+            false
+        } else {
+            self.ts = ts;
+            true
+        }
+    }
+
+    fn with_seq(&mut self, seq: u64) -> bool {
+        if seq > Self::MAX_U64_SEQUENCE {
+            false
+        } else {
+            self.seq = seq;
+            true
+        }
+    }
+
+    fn with_dur(&mut self, dur: Duration) {
+        self.dur = dur;
+    }
+
+    fn build(self) -> Self::Output {
+        NIdFlake {
+            dur: Some(self.dur),
+            tsm: self.ts as i64,
+            segs: self.segs,
+            seq: self.seq as i64,
+            bits: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const TS: u8, const SEQ: u8, const N: usize, B> ser::Serialize for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer
+    {
+        let id = self.id();
+
+        #[cfg(feature = "serde-human-string")]
+        if serializer.is_human_readable() {
+            return serializer.collect_str(&id);
+        }
+
+        serializer.serialize_i64(id)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct NumVisitor<const TS: u8, const SEQ: u8, const N: usize, B> {
+    bits: PhantomData<fn() -> B>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const TS: u8, const SEQ: u8, const N: usize, B> de::Visitor<'de> for NumVisitor<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    type Value = NIdFlake<TS, SEQ, N, B>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "integer from 0 to i64::MAX")
+    }
+
+    fn visit_i64<E>(self, i: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(flake) = NIdFlake::try_from(&i) else {
+            return Err(E::invalid_value(de::Unexpected::Signed(i), &self));
+        };
+
+        Ok(flake)
+    }
+
+    fn visit_f64<E>(self, f: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        self.visit_i64(crate::serde_ext::float_to_int(f)?)
+    }
+
+    fn visit_u64<E>(self, u: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        // values above i64::MAX would wrap to negative ids
+        if u > i64::MAX as u64 {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(u), &self));
+        }
+
+        let Ok(flake) = NIdFlake::try_from(&(u as i64)) else {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(u), &self));
+        };
+
+        Ok(flake)
+    }
+
+    #[cfg(feature = "serde-human-string")]
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(flake) = s.parse() else {
+            return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+        };
+
+        Ok(flake)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const TS: u8, const SEQ: u8, const N: usize, B> de::Deserialize<'de> for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        #[cfg(feature = "serde-human-string")]
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(NumVisitor { bits: PhantomData });
+        }
+
+        deserializer.deserialize_i64(NumVisitor { bits: PhantomData })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a, const TS: u8, const SEQ: u8, const N: usize, B> FromSql<'a> for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn from_sql(
+        _: &PgType,
+        raw: &'a [u8]
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let Some(int) = crate::pg::read_i64(raw) else {
+            return Err("invalid buffer size".into());
+        };
+
+        Self::try_from(&int).map_err(Into::into)
+    }
+
+    accepts!(INT8);
+}
+
+#[cfg(feature = "postgres")]
+impl<const TS: u8, const SEQ: u8, const N: usize, B> ToSql for NIdFlake<TS, SEQ, N, B>
+where
+    B: SegmentBits<N>
+{
+    fn to_sql(
+        &self,
+        _: &PgType,
+        buf: &mut BytesMut
+    ) -> Result<IsNull, Box<dyn std::error::Error + Send + Sync>> {
+        let id = self.id();
+
+        buf.put_i64(id);
+
+        Ok(IsNull::No)
+    }
+
+    accepts!(INT8);
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::i64::{SingleIdFlake, DualIdFlake, TripleIdFlake};
+
+    struct OneSeg;
+
+    impl SegmentBits<1> for OneSeg {
+        const BITS: [u8; 1] = [8];
+    }
+
+    struct TwoSeg;
+
+    impl SegmentBits<2> for TwoSeg {
+        const BITS: [u8; 2] = [4, 4];
+    }
+
+    struct ThreeSeg;
+
+    impl SegmentBits<3> for ThreeSeg {
+        const BITS: [u8; 3] = [4, 4, 4];
+    }
+
+    type NSingle = NIdFlake<43, 12, 1, OneSeg>;
+    type NDual = NIdFlake<43, 12, 2, TwoSeg>;
+    type NTriple = NIdFlake<43, 8, 3, ThreeSeg>;
+
+    type Single = SingleIdFlake<43, 8, 12>;
+    type Dual = DualIdFlake<43, 4, 4, 12>;
+    type Triple = TripleIdFlake<43, 4, 4, 4, 8>;
+
+    #[test]
+    fn single_consts() {
+        assert_eq!(NSingle::MAX_TIMESTAMP, Single::MAX_TIMESTAMP);
+        assert_eq!(NSingle::MAX_SEGMENTS, [Single::MAX_PRIMARY_ID]);
+        assert_eq!(NSingle::MAX_SEQUENCE, Single::MAX_SEQUENCE);
+
+        assert_eq!(NSingle::TIMESTAMP_SHIFT, Single::TIMESTAMP_SHIFT);
+        assert_eq!(NSingle::SEGMENT_SHIFTS, [Single::PRIMARY_ID_SHIFT]);
+
+        assert_eq!(NSingle::TIMESTAMP_MASK, Single::TIMESTAMP_MASK);
+        assert_eq!(NSingle::SEGMENT_MASKS, [Single::PRIMARY_ID_MASK]);
+        assert_eq!(NSingle::SEQUENCE_MASK, Single::SEQUENCE_MASK);
+
+        assert_eq!(
+            <NSingle as traits::FromIdGenerator>::LAYOUT_BITS,
+            <Single as traits::FromIdGenerator>::LAYOUT_BITS
+        );
+    }
+
+    #[test]
+    fn dual_consts() {
+        assert_eq!(NDual::MAX_TIMESTAMP, Dual::MAX_TIMESTAMP);
+        assert_eq!(NDual::MAX_SEGMENTS, [Dual::MAX_PRIMARY_ID, Dual::MAX_SECONDARY_ID]);
+        assert_eq!(NDual::MAX_SEQUENCE, Dual::MAX_SEQUENCE);
+
+        assert_eq!(NDual::TIMESTAMP_SHIFT, Dual::TIMESTAMP_SHIFT);
+        assert_eq!(NDual::SEGMENT_SHIFTS, [Dual::PRIMARY_ID_SHIFT, Dual::SECONDARY_ID_SHIFT]);
+
+        assert_eq!(NDual::TIMESTAMP_MASK, Dual::TIMESTAMP_MASK);
+        assert_eq!(NDual::SEGMENT_MASKS, [Dual::PRIMARY_ID_MASK, Dual::SECONDARY_ID_MASK]);
+        assert_eq!(NDual::SEQUENCE_MASK, Dual::SEQUENCE_MASK);
+
+        assert_eq!(
+            <NDual as traits::FromIdGenerator>::LAYOUT_BITS,
+            <Dual as traits::FromIdGenerator>::LAYOUT_BITS
+        );
+    }
+
+    #[test]
+    fn triple_consts() {
+        assert_eq!(NTriple::MAX_TIMESTAMP, Triple::MAX_TIMESTAMP);
+        assert_eq!(
+            NTriple::MAX_SEGMENTS,
+            [Triple::MAX_PRIMARY_ID, Triple::MAX_SECONDARY_ID, Triple::MAX_TERTIARY_ID]
+        );
+        assert_eq!(NTriple::MAX_SEQUENCE, Triple::MAX_SEQUENCE);
+
+        assert_eq!(NTriple::TIMESTAMP_SHIFT, Triple::TIMESTAMP_SHIFT);
+        assert_eq!(
+            NTriple::SEGMENT_SHIFTS,
+            [Triple::PRIMARY_ID_SHIFT, Triple::SECONDARY_ID_SHIFT, Triple::TERTIARY_ID_SHIFT]
+        );
+
+        assert_eq!(NTriple::TIMESTAMP_MASK, Triple::TIMESTAMP_MASK);
+        assert_eq!(
+            NTriple::SEGMENT_MASKS,
+            [Triple::PRIMARY_ID_MASK, Triple::SECONDARY_ID_MASK, Triple::TERTIARY_ID_MASK]
+        );
+        assert_eq!(NTriple::SEQUENCE_MASK, Triple::SEQUENCE_MASK);
+
+        assert_eq!(
+            <NTriple as traits::FromIdGenerator>::LAYOUT_BITS,
+            <Triple as traits::FromIdGenerator>::LAYOUT_BITS
+        );
+    }
+
+    #[test]
+    fn same_ids() {
+        let single = Single::from_parts(1679082337000, 200, 3000).unwrap();
+        let n_single = NSingle::from_parts(1679082337000, [200], 3000).unwrap();
+
+        assert_eq!(n_single.id(), single.id());
+        assert_eq!(NSingle::try_from(&single.id()).unwrap(), n_single);
+
+        let dual = Dual::from_parts(1679082337000, 5, 10, 3000).unwrap();
+        let n_dual = NDual::from_parts(1679082337000, [5, 10], 3000).unwrap();
+
+        assert_eq!(n_dual.id(), dual.id());
+        assert_eq!(NDual::try_from(&dual.id()).unwrap(), n_dual);
+
+        let triple = Triple::from_parts(1679082337000, 5, 10, 15, 200).unwrap();
+        let n_triple = NTriple::from_parts(1679082337000, [5, 10, 15], 200).unwrap();
+
+        assert_eq!(n_triple.id(), triple.id());
+        assert_eq!(NTriple::try_from(&triple.id()).unwrap(), n_triple);
+    }
+
+    #[test]
+    fn from_segments() {
+        let expected = NDual::from_parts(1000, [5, 6], 10).unwrap();
+
+        assert_eq!(NDual::from_segments(1000, (5, 6), 10).unwrap(), expected);
+        assert_eq!(NDual::from_segments(1000, Segments::<i64, 2>::from_parts(5, 6), 10).unwrap(), expected);
+    }
+
+    #[test]
+    fn with_modifiers() {
+        let flake = NDual::from_parts(1000, [1, 1], 1).unwrap()
+            .with_segment(1, 3).unwrap()
+            .with_sequence(4).unwrap();
+
+        assert_eq!(flake, NDual::from_parts(1000, [1, 3], 4).unwrap());
+
+        assert!(matches!(flake.with_segment(2, 1), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(flake.with_segment(0, NDual::MAX_SEGMENTS[0] + 1), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(flake.with_sequence(-1), Err(error::Error::SequenceInvalid)));
+    }
+
+    #[test]
+    fn invalid_parts() {
+        assert!(matches!(NDual::from_parts(-1, [1, 1], 1), Err(error::Error::EpochInvalid)));
+        assert!(matches!(NDual::from_parts(1, [1, 16], 1), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(NDual::from_parts(1, [-1, 1], 1), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(NDual::from_parts(1, [1, 1], 4096), Err(error::Error::SequenceInvalid)));
+        assert!(NDual::try_from(&-1).is_err());
+    }
+
+    #[test]
+    fn layout_descriptor() {
+        assert_eq!(
+            <NTriple as traits::FlakeLayout>::LAYOUT,
+            <Triple as traits::FlakeLayout>::LAYOUT
+        );
+        assert_eq!(traits::describe::<NTriple>(), "43-4-4-4-8");
+    }
+
+    #[test]
+    fn extract_helpers() {
+        for bits in crate::random_bits(1000) {
+            let id = bits as i64;
+            let positive = id & i64::MAX;
+
+            assert_eq!(NTriple::timestamp_of(id), NTriple::timestamp_of(positive));
+            assert_eq!(NTriple::sequence_of(id), NTriple::sequence_of(positive));
+            assert_eq!(NTriple::segment_of(id, 3), None);
+
+            let Ok(flake) = NTriple::try_from(&positive) else {
+                continue;
+            };
+
+            assert_eq!(NTriple::timestamp_of(id), flake.timestamp());
+            assert_eq!(NTriple::sequence_of(id), flake.sequence());
+
+            for (index, seg) in flake.segments().iter().enumerate() {
+                assert_eq!(NTriple::segment_of(id, index), Some(*seg));
+            }
+        }
+    }
+
+    #[test]
+    fn be_bytes() {
+        let flakes = [
+            NDual::NIL,
+            NDual::try_from(&1).unwrap(),
+            NDual::try_from(&256).unwrap(),
+            NDual::MAX,
+        ];
+
+        for flake in flakes {
+            let mut written = Vec::new();
+            flake.write_be(&mut written).unwrap();
+
+            assert_eq!(NDual::from_be_bytes(flake.to_be_bytes()).unwrap(), flake);
+            assert_eq!(flake.to_le_bytes(), flake.id().to_le_bytes());
+            assert_eq!(written, flake.to_be_bytes());
+        }
+
+        assert!(flakes.windows(2).all(|pair| pair[0].to_be_bytes() < pair[1].to_be_bytes()));
+        assert!(matches!(NDual::from_be_bytes((-1i64).to_be_bytes()), Err(error::Error::InvalidId)));
+    }
+
+    #[test]
+    fn nonzero() {
+        assert_eq!(NDual::NIL.id_nonzero(), None);
+        assert!(matches!(NonZeroI64::try_from(NDual::NIL), Err(error::Error::InvalidId)));
+
+        let flake = NDual::MAX;
+        let id = flake.id_nonzero().unwrap();
+
+        assert_eq!(id.get(), flake.id());
+        assert_eq!(NonZeroI64::try_from(flake).unwrap(), id);
+
+        let back: NDual = id.try_into().unwrap();
+
+        assert_eq!(back, flake);
+    }
+
+    #[test]
+    fn anonymize() {
+        let first = NDual::MAX.with_sequence(1).unwrap();
+
+        assert_ne!(first, NDual::MAX);
+        assert_eq!(first.anonymize(false), NDual::MAX.anonymize(false));
+        assert_eq!(first.anonymize(false).sequence(), 0);
+        assert_eq!(
+            NDual::first_for_timestamp(5000).unwrap().anonymize(true),
+            NDual::last_for_timestamp(5000).unwrap().anonymize(true)
+        );
+    }
+
+    #[test]
+    fn bucket_millis() {
+        let bucket = Duration::from_secs(10);
+        let first = NDual::first_for_timestamp(25000).unwrap();
+        let last = NDual::last_for_timestamp(29000).unwrap();
+        let next = NDual::first_for_timestamp(35000).unwrap();
+
+        assert_eq!(first.bucket_millis(bucket), 20_000);
+        assert_eq!(first.bucket_millis(bucket), last.bucket_millis(bucket));
+        assert_ne!(first.bucket_millis(bucket), next.bucket_millis(bucket));
+        assert_eq!(first.bucket_millis(Duration::ZERO), 25_000);
+    }
+
+    #[test]
+    fn bucket() {
+        let first = NDual::first_for_timestamp(25000).unwrap();
+        let last = NDual::last_for_timestamp(25000).unwrap();
+        let next = NDual::first_for_timestamp(35000).unwrap();
+
+        assert_eq!(first.bucket(10_000).unwrap(), 2);
+        assert_eq!(first.bucket(10_000).unwrap(), last.bucket(10_000).unwrap());
+        assert_eq!(next.bucket(10_000).unwrap(), 3);
+        assert_eq!(first.bucket(1).unwrap(), first.id() as u64 >> NDual::TIMESTAMP_SHIFT);
+        assert!(matches!(first.bucket(0), Err(error::Error::BucketInvalid)));
+
+        let range = NDual::bucket_id_range(2, 10_000).unwrap();
+
+        assert_eq!(range, NDual::first_for_timestamp(20000).unwrap().id()..=NDual::last_for_timestamp(29999).unwrap().id());
+        assert!(range.contains(&first.id()));
+        assert!(range.contains(&last.id()));
+        assert!(!range.contains(&next.id()));
+
+        let max_bucket = NDual::MAX.bucket(10_000).unwrap();
+
+        assert_eq!(*NDual::bucket_id_range(max_bucket, 10_000).unwrap().end(), NDual::MAX.id());
+        assert!(matches!(NDual::bucket_id_range(max_bucket + 1, 10_000), Err(error::Error::EpochInvalid)));
+        assert!(matches!(NDual::bucket_id_range(2, 0), Err(error::Error::BucketInvalid)));
+    }
+
+    #[test]
+    fn shard() {
+        let first = NDual::first_for_timestamp(5000).unwrap();
+        let next = first.with_sequence(1).unwrap();
+
+        assert_eq!(first.shard(16), crate::shard::shard_u64(first.id() as u64, 16));
+        assert!(next.shard(7) < 7);
+        assert_eq!(first.shard_by_primary(4), next.shard_by_primary(4));
+        assert_eq!(NDual::MAX.shard_by_primary(3), (NDual::MAX.segments()[0] % 3) as u32);
+    }
+
+    #[test]
+    fn opaque() {
+        let permuter = Permuter::new(42);
+        let flake = NDual::MAX.with_sequence(1).unwrap();
+        let opaque = flake.to_opaque(&permuter);
+
+        assert_ne!(opaque, flake.id());
+        assert_eq!(NDual::from_opaque(opaque, &permuter).unwrap(), flake);
+    }
+
+    #[test]
+    fn display_and_from_str() {
+        let flake = NDual::MAX.with_sequence(1).unwrap();
+        let string = flake.to_string();
+
+        assert_eq!(string, flake.id().to_string());
+        assert_eq!(string.parse::<NDual>().unwrap(), flake);
+        assert_eq!(format!("{:>50}", flake), format!("{:>50}", flake.id()));
+
+        assert!(matches!(format!(" {}", string).parse::<NDual>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!(format!("{} ", string).parse::<NDual>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("".parse::<NDual>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("-1".parse::<NDual>(), Err(error::Error::InvalidId)));
+    }
+
+    #[test]
+    fn radix_formatting() {
+        let flake = NDual::from_parts(1, [1, 1], 1).unwrap();
+
+        assert_eq!(format!("{:x}", flake), "111001");
+        assert_eq!(format!("{:#X}", flake), "0x111001");
+        assert_eq!(format!("{:o}", flake), "4210001");
+        assert_eq!(format!("{:#o}", flake), "0o4210001");
+        assert_eq!(format!("{:b}", flake), "100010001000000000001");
+        assert_eq!(format!("{:#066b}", flake), "0b0000000000000000000000000000000000000000000100010001000000000001");
+        assert_eq!(format!("{:>#12x}", flake), "    0x111001");
+    }
+
+    #[test]
+    fn fmt_layout() {
+        let flake = NDual::from_parts(5, [2, 3], 4).unwrap();
+        let expected = [
+            "0 | 0000000000000000000000000000000000000000101 | 0010 | 0011 | 000000000100",
+            "  | ts                                          | seg0 | seg1 | seq",
+            "  | 5                                           | 2    | 3    | 4",
+        ];
+
+        assert_eq!(flake.fmt_layout(), expected.join("\n"));
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_ext {
+        use super::*;
+
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        #[derive(Serialize, Deserialize)]
+        struct IdFlake {
+            id: NDual,
+        }
+
+        #[cfg(not(feature = "serde-human-string"))]
+        #[test]
+        fn to_int() {
+            let obj = IdFlake {
+                id: NDual::from_parts(1, [1, 1], 1).unwrap(),
+            };
+
+            match serde_json::to_string(&obj) {
+                Ok(json_string) => {
+                    assert_eq!(
+                        json_string,
+                        String::from("{\"id\":1118209}"),
+                        "invalid json string"
+                    );
+                },
+                Err(err) => {
+                    panic!("failed to create json string. {:#?}", err);
+                }
+            }
+        }
+
+        #[test]
+        fn from_int() {
+            let json_str = "{\"id\":1118209}";
+
+            match serde_json::from_str::<IdFlake>(json_str) {
+                Ok(obj) => {
+                    assert_eq!(
+                        obj.id,
+                        NDual::from_parts(1, [1, 1], 1).unwrap(),
+                        "invalid parsed id"
+                    );
+                },
+                Err(err) => {
+                    panic!("failed to parse json string. {:#?}", err);
+                }
+            }
+        }
+    }
+}
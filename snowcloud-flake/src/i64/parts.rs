@@ -0,0 +1,101 @@
+//! named parts of the i64 snowflakes
+//!
+//! an alternative to the tuples returned from `into_parts` so that the
+//! values cannot be mixed up
+//!
+//! ```rust
+//! use snowcloud::flake::i64::{SingleIdFlake, SingleIdParts};
+//!
+//! type MyFlake = SingleIdFlake<43, 8, 12>;
+//!
+//! let parts = SingleIdParts {
+//!     timestamp: 1,
+//!     primary_id: 2,
+//!     sequence: 3,
+//! };
+//!
+//! let flake = MyFlake::from_parts_named(parts).unwrap();
+//!
+//! assert_eq!(flake.into_parts_named(), parts);
+//! ```
+
+/// named parts of a [`SingleIdFlake`](crate::i64::SingleIdFlake)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct SingleIdParts {
+    pub timestamp: i64,
+    pub primary_id: i64,
+    pub sequence: i64,
+}
+
+/// named parts of a [`DualIdFlake`](crate::i64::DualIdFlake)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct DualIdParts {
+    pub timestamp: i64,
+    pub primary_id: i64,
+    pub secondary_id: i64,
+    pub sequence: i64,
+}
+
+/// named parts of a [`TripleIdFlake`](crate::i64::TripleIdFlake)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct TripleIdParts {
+    pub timestamp: i64,
+    pub primary_id: i64,
+    pub secondary_id: i64,
+    pub tertiary_id: i64,
+    pub sequence: i64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::i64::{SingleIdFlake, DualIdFlake, TripleIdFlake};
+
+    #[test]
+    fn round_trip() {
+        let single = SingleIdFlake::<43, 8, 12>::from_parts(1, 2, 3).unwrap();
+        let parts = SingleIdParts::from(single);
+
+        assert_eq!(parts, SingleIdParts { timestamp: 1, primary_id: 2, sequence: 3 });
+        assert_eq!(SingleIdFlake::<43, 8, 12>::from_parts_named(parts).unwrap(), single);
+
+        let dual = DualIdFlake::<43, 4, 4, 12>::from_parts(1, 2, 3, 4).unwrap();
+        let parts = dual.into_parts_named();
+
+        assert_eq!(parts, DualIdParts { timestamp: 1, primary_id: 2, secondary_id: 3, sequence: 4 });
+        assert_eq!(DualIdFlake::<43, 4, 4, 12>::from_parts_named(parts).unwrap(), dual);
+
+        let triple = TripleIdFlake::<43, 4, 4, 4, 8>::from_parts(1, 2, 3, 4, 5).unwrap();
+        let parts = TripleIdParts::from(triple);
+
+        assert_eq!(parts.tertiary_id, 4);
+        assert_eq!(TripleIdFlake::<43, 4, 4, 4, 8>::from_parts_named(parts).unwrap(), triple);
+    }
+
+    #[test]
+    fn invalid_parts() {
+        let parts = SingleIdParts { timestamp: 1, primary_id: 256, sequence: 3 };
+
+        assert!(matches!(
+            SingleIdFlake::<43, 8, 12>::from_parts_named(parts),
+            Err(crate::error::Error::IdSegInvalid)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_named_fields() {
+        let parts = DualIdParts { timestamp: 1, primary_id: 2, secondary_id: 3, sequence: 4 };
+        let json = serde_json::to_string(&parts).unwrap();
+
+        assert_eq!(json, r#"{"timestamp":1,"primary_id":2,"secondary_id":3,"sequence":4}"#);
+        assert_eq!(serde_json::from_str::<DualIdParts>(&json).unwrap(), parts);
+    }
+}
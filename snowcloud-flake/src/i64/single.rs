@@ -1,5 +1,7 @@
 use std::hash::Hasher;
-use std::time::Duration;
+use std::num::NonZeroI64;
+use std::ops::RangeInclusive;
+use std::time::{Duration, SystemTime};
 
 use snowcloud_core::traits;
 
@@ -14,7 +16,9 @@ use postgres_types::{to_sql_checked, accepts, IsNull, FromSql, ToSql, Type as Pg
 use bytes::{BytesMut, BufMut};
 
 use crate::error;
+use crate::opaque::Permuter;
 use crate::Segments;
+use crate::i64::SingleIdParts;
 
 /// i64 Snowflake with 1 id segment
 ///
@@ -34,8 +38,24 @@ use crate::Segments;
 /// the total amount of bits should equal 63 since the sign bit cannot be used
 /// otherwise you will get negative id values.
 ///
-/// Note: there is currently no way to ensure that the values provided are
-/// valid. `generic_const_exprs` would help with this but is unstable currently
+/// Note: the total is checked at compile time once the snowflake is used and
+/// will fail to compile if it does not equal 63. the `relaxed-layout`
+/// feature will only require the total to be less than or equal to 63.
+///
+/// ```compile_fail
+/// // 70 bits will never fit in an i64
+/// type MyFlake = snowcloud_flake::i64::SingleIdFlake<50, 8, 12>;
+///
+/// let flake = MyFlake::from_parts(1, 1, 1);
+/// ```
+///
+#[cfg_attr(not(feature = "relaxed-layout"), doc = "```compile_fail")]
+#[cfg_attr(feature = "relaxed-layout", doc = "```")]
+/// // 62 bits only compiles with the relaxed-layout feature
+/// type MyFlake = snowcloud_flake::i64::SingleIdFlake<42, 8, 12>;
+///
+/// let flake = MyFlake::from_parts(1, 1, 1);
+/// ```
 ///
 /// # Timestamp
 ///
@@ -103,7 +123,7 @@ use crate::Segments;
 ///     .expect("invalid i64 was provided");
 /// println!("{:?}", and_back);
 /// ```
-#[derive(Eq, Clone)]
+#[derive(Eq, Clone, Copy)]
 pub struct SingleIdFlake<const TS: u8, const PID: u8, const SEQ: u8> {
     pub(crate) dur: Option<Duration>,
     pub(crate) tsm: i64,
@@ -133,23 +153,102 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> SingleIdFlake<TS, PID, SEQ> {
 
     const MAX_EPOCH: u64 = (1 << TS as u64) - 1;
 
+    /// snowflake with every part set to 0
+    pub const NIL: Self = Self { dur: None, tsm: 0, pid: 0, seq: 0 };
+    /// smallest possible snowflake. same as `Self::NIL`
+    pub const MIN: Self = Self::NIL;
+    /// largest possible snowflake with every part set to its max value
+    pub const MAX: Self = Self {
+        dur: None,
+        tsm: Self::MAX_TIMESTAMP,
+        pid: Self::MAX_PRIMARY_ID,
+        seq: Self::MAX_SEQUENCE,
+    };
+
+    /// fails to compile if the layout does not fit the available bits
+    const VALID_LAYOUT: () = assert!(
+        crate::layout_fits(
+            <Self as traits::FromIdGenerator>::LAYOUT_BITS,
+            <Self as traits::FromIdGenerator>::MAX_LAYOUT_BITS
+        ),
+        "SingleIdFlake bit widths must sum to 63"
+    );
+
+    /// returns the time since the epoch that the snowflake was generated at
+    ///
+    /// only snowflakes from a generator will have this. anything created
+    /// from parts or an integer will be `None` and only the millisecond
+    /// precision of the timestamp is available
     pub fn duration(&self) -> Option<&Duration> {
         self.dur.as_ref()
     }
 
     /// returns timestamp
-    pub fn timestamp(&self) -> &i64 {
-        &self.tsm
+    pub fn timestamp(&self) -> i64 {
+        self.tsm
+    }
+
+    /// returns the unix timestamp in milliseconds of when the snowflake was
+    /// created using the given epoch
+    ///
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the result does not fit in a u64
+    pub fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        epoch.checked_add(self.tsm as u64)
+            .ok_or(error::Error::EpochInvalid)
     }
 
-    /// returns primary id reference
-    pub fn primary_id(&self) -> &i64 {
-        &self.pid
+    /// returns when the snowflake was created using the given epoch
+    ///
+    /// `None` will be returned if the time cannot be represented
+    pub fn created_at(&self, epoch_millis: u64) -> Option<SystemTime> {
+        let millis = self.unix_millis(epoch_millis).ok()?;
+
+        SystemTime::UNIX_EPOCH.checked_add(Duration::from_millis(millis))
     }
 
-    /// returns sequence reference
-    pub fn sequence(&self) -> &i64 {
-        &self.seq
+    /// returns how long ago the snowflake was created using the given epoch
+    ///
+    /// `None` will be returned if the snowflake appears to be created in the
+    /// future
+    pub fn age(&self, epoch_millis: u64) -> Option<Duration> {
+        SystemTime::now().duration_since(self.created_at(epoch_millis)?).ok()
+    }
+
+    /// checks if both snowflakes were created in the same millisecond
+    pub fn same_tick(&self, other: &Self) -> bool {
+        self.tsm == other.tsm
+    }
+
+    /// returns primary id
+    pub fn primary_id(&self) -> i64 {
+        self.pid
+    }
+
+    /// returns sequence
+    pub fn sequence(&self) -> i64 {
+        self.seq
+    }
+
+    /// extracts the timestamp from an id without any validation
+    ///
+    /// the sign bit is outside of every mask so a negative id will never
+    /// produce a negative value
+    #[inline]
+    pub const fn timestamp_of(id: i64) -> i64 {
+        (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT
+    }
+
+    /// extracts the primary id from an id without any validation
+    #[inline]
+    pub const fn primary_id_of(id: i64) -> i64 {
+        (id & Self::PRIMARY_ID_MASK) >> Self::PRIMARY_ID_SHIFT
+    }
+
+    /// extracts the sequence from an id without any validation
+    #[inline]
+    pub const fn sequence_of(id: i64) -> i64 {
+        id & Self::SEQUENCE_MASK
     }
 
     /// generates a Snowflake from the provided parts
@@ -159,6 +258,8 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> SingleIdFlake<TS, PID, SEQ> {
     /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
     /// the primary id is invalid
     pub fn from_parts(tsm: i64, pid: i64, seq: i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
         if tsm < 0 || tsm > Self::MAX_TIMESTAMP {
             return Err(error::Error::EpochInvalid);
         }
@@ -174,21 +275,426 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> SingleIdFlake<TS, PID, SEQ> {
         Ok(Self { dur: None, tsm, pid, seq })
     }
 
+    /// iterates every snowflake that can be created with the given timestamp
+    /// and ids starting with a sequence of 0
+    ///
+    /// the timestamp and ids are checked the same as
+    /// [`from_parts`](Self::from_parts) before creating the iterator
+    pub fn iter_sequence(tsm: i64, pid: i64) -> error::Result<impl ExactSizeIterator<Item = Self> + DoubleEndedIterator> {
+        let first = Self::from_parts(tsm, pid, 0)?;
+        let max = usize::try_from(Self::MAX_SEQUENCE).unwrap_or(usize::MAX - 1);
+
+        Ok((0..max + 1).map(move |seq| Self { seq: seq as i64, ..first }))
+    }
+
+    /// generates a Snowflake from the timestamp, id segments, and sequence
+    ///
+    /// the ids can be anything that converts into the same segments given to
+    /// a generator like a single value, a tuple, or
+    /// [`Segments`](crate::Segments). the same checks as
+    /// [`from_parts`](Self::from_parts) will be performed
+    pub fn from_segments<I>(tsm: i64, ids: I, seq: i64) -> error::Result<Self>
+    where
+        I: Into<Segments<i64, 1>>
+    {
+        let ids = ids.into();
+
+        Self::from_parts(tsm, ids[0], seq)
+    }
+
+    /// generates a Snowflake from the provided named parts
+    ///
+    /// the same checks as [`from_parts`](Self::from_parts) will be performed
+    pub fn from_parts_named(parts: SingleIdParts) -> error::Result<Self> {
+        Self::from_parts(parts.timestamp, parts.primary_id, parts.sequence)
+    }
+
+    /// generates the smallest Snowflake for the given timestamp
+    ///
+    /// every other part is set to 0. useful as the lower bound when
+    /// searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn first_for_timestamp(tsm: i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm < 0 || tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::NIL })
+    }
+
+    /// generates the largest Snowflake for the given timestamp
+    ///
+    /// every other part is set to its max value. useful as the upper bound
+    /// when searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn last_for_timestamp(tsm: i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm < 0 || tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::MAX })
+    }
+
+    /// returns the range of ids that were created between the given times
+    /// using the epoch in milliseconds
+    ///
+    /// `start` is inclusive and `end` is exclusive. the returned range is
+    /// inclusive so it can be used directly with something like
+    /// `WHERE id BETWEEN $1 AND $2`. a `start` before the epoch or an `end`
+    /// after the max timestamp will be clamped to what the snowflake can
+    /// represent. [`EpochInvalid`](crate::error::Error::EpochInvalid) will be
+    /// returned if none of the time range can be represented
+    pub fn id_range_for(epoch_millis: u64, start: SystemTime, end: SystemTime) -> error::Result<RangeInclusive<i64>> {
+        let (first, last) = crate::tick_range::<Self>(epoch_millis, start, end, Self::MAX_TIMESTAMP as u64)?;
+
+        Ok(Self::first_for_timestamp(first as i64)?.id()..=Self::last_for_timestamp(last as i64)?.id())
+    }
+
+    /// returns the first id of every boundary between the given times using
+    /// the epoch in milliseconds
+    ///
+    /// boundaries are multiples of `step` since the unix epoch so a step of
+    /// one day will give the first id of each day in UTC. `start` is
+    /// inclusive and `end` is exclusive. boundaries that are before the epoch
+    /// or after the max timestamp are skipped and a `step` of 0 will not
+    /// produce any ids. the ids are calculated as the iterator is consumed
+    pub fn id_partition_boundaries(epoch_millis: u64, start: SystemTime, end: SystemTime, step: Duration) -> impl Iterator<Item = i64> {
+        crate::boundary_ticks::<Self>(epoch_millis, start, end, step, Self::MAX_TIMESTAMP as u64)
+            .filter_map(|tick| Self::first_for_timestamp(tick as i64).ok())
+            .map(|flake| flake.id())
+    }
+
     /// splits the current Snowflake into its individual parts
     pub fn into_parts(self) -> (i64, i64, i64) {
         (self.tsm, self.pid, self.seq)
     }
 
+    /// splits the current Snowflake into its individual named parts
+    pub fn into_parts_named(self) -> SingleIdParts {
+        SingleIdParts {
+            timestamp: self.tsm,
+            primary_id: self.pid,
+            sequence: self.seq,
+        }
+    }
+
     /// generates the unique id
     pub fn id(&self) -> i64 {
         (self.tsm << Self::TIMESTAMP_SHIFT) | (self.pid << Self::PRIMARY_ID_SHIFT) | self.seq
     }
 
+    /// renders the bits of the id split at each part with the name and
+    /// value of each part underneath
+    ///
+    /// useful for checking that a layout places the parts where they are
+    /// expected to be
+    pub fn fmt_layout(&self) -> String {
+        crate::layout_diagram(self.id() as u128, 64, &[("ts", TS), ("pid", PID), ("seq", SEQ)])
+    }
+
+    /// encodes the id as base58
+    ///
+    /// see [`encoding::base58`](crate::encoding::base58) for more information
+    pub fn to_base58(&self) -> String {
+        crate::encoding::base58::encode(self)
+    }
+
+    /// decodes a base58 string created by [`to_base58`](Self::to_base58)
+    pub fn from_base58(string: &str) -> error::Result<Self> {
+        crate::encoding::base58::decode(string)
+    }
+
+    /// encodes the id as base62
+    ///
+    /// see [`encoding::base62`](crate::encoding::base62) for more information
+    pub fn to_base62(&self) -> String {
+        crate::encoding::base62::encode(self)
+    }
+
+    /// decodes a base62 string created by [`to_base62`](Self::to_base62)
+    pub fn from_base62(string: &str) -> error::Result<Self> {
+        crate::encoding::base62::decode(string)
+    }
+
+    /// encodes the id as fixed width crockford base32
+    ///
+    /// see [`encoding::crockford`](crate::encoding::crockford) for more
+    /// information
+    pub fn to_crockford(&self) -> String {
+        crate::encoding::crockford::encode_fixed(self)
+    }
+
+    /// decodes a crockford base32 string created by
+    /// [`to_crockford`](Self::to_crockford)
+    pub fn from_crockford(string: &str) -> error::Result<Self> {
+        crate::encoding::crockford::decode(string)
+    }
+
+    /// encodes the big endian bytes of the id as unpadded base64url
+    ///
+    /// see [`encoding::base64url`](crate::encoding::base64url) for more
+    /// information
+    pub fn to_base64url(&self) -> String {
+        crate::encoding::base64url::encode(self)
+    }
+
+    /// decodes a base64url string created by
+    /// [`to_base64url`](Self::to_base64url)
+    pub fn from_base64url(string: &str) -> error::Result<Self> {
+        crate::encoding::base64url::decode(string)
+    }
+
+    /// prints the id in the given radix using lowercase letters and no
+    /// prefix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn to_string_radix(&self, radix: u32) -> String {
+        crate::encoding::to_string_radix(self.id() as u128, radix)
+    }
+
+    /// parses a snowflake from an id in the given radix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn from_str_radix(string: &str, radix: u32) -> error::Result<Self> {
+        let id = i64::from_str_radix(string, radix).map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+
+    /// parses a snowflake from an id that may start with a `0x`, `0o`, or
+    /// `0b` prefix
+    ///
+    /// strings without a prefix are parsed as decimal
+    pub fn parse_prefixed(string: &str) -> error::Result<Self> {
+        Self::try_from(&crate::encoding::parse_prefixed::<i64>(string)?)
+    }
+
+    /// returns the id if it is not zero
+    ///
+    /// only [`NIL`](Self::NIL) will return `None`
+    pub fn id_nonzero(&self) -> Option<NonZeroI64> {
+        NonZeroI64::new(self.id())
+    }
+
+    /// returns the id scrambled with the given permuter
+    ///
+    /// see [`opaque`](crate::opaque) for more information
+    pub fn to_opaque(&self, permuter: &Permuter) -> i64 {
+        permuter.encode(self.id())
+    }
+
+    /// creates a snowflake from an id scrambled with
+    /// [`to_opaque`](Self::to_opaque) using the same permuter
+    pub fn from_opaque(id: i64, permuter: &Permuter) -> error::Result<Self> {
+        Self::try_from(&permuter.decode(id))
+    }
+
+    /// converts to the u64 snowflake with the same id and sequence widths
+    ///
+    /// the timestamp width can differ so that the u64 snowflake is able to
+    /// use the extra bit. [`EpochInvalid`](crate::error::Error::EpochInvalid)
+    /// will be returned if the timestamp does not fit
+    pub fn to_unsigned<const UTS: u8>(&self) -> error::Result<crate::u64::SingleIdFlake<UTS, PID, SEQ>> {
+        let mut flake = crate::u64::SingleIdFlake::from_parts(self.tsm as u64, self.pid as u64, self.seq as u64)?;
+        flake.dur = self.dur;
+
+        Ok(flake)
+    }
+
+    /// checks if every part of the snowflake is 0
+    pub fn is_nil(&self) -> bool {
+        self == &Self::NIL
+    }
+
+    /// returns the snowflake that comes after this one
+    ///
+    /// same as adding 1 to the id so the sequence will carry over into the
+    /// id segments and timestamp. `None` will be returned if this is
+    /// [`MAX`](Self::MAX)
+    pub fn successor(&self) -> Option<Self> {
+        if *self == Self::MAX {
+            return None;
+        }
+
+        Self::try_from(&(self.id() + 1)).ok()
+    }
+
+    /// returns the snowflake that comes before this one
+    ///
+    /// same as subtracting 1 from the id. `None` will be returned if this is
+    /// [`MIN`](Self::MIN)
+    pub fn predecessor(&self) -> Option<Self> {
+        if *self == Self::MIN {
+            return None;
+        }
+
+        Self::try_from(&(self.id() - 1)).ok()
+    }
+
+    /// returns the snowflake with the primary id replaced
+    ///
+    /// the duration is kept as is.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the primary id is invalid
+    pub fn with_primary_id(self, pid: i64) -> error::Result<Self> {
+        if pid < 0 || pid > Self::MAX_PRIMARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        Ok(Self { pid, ..self })
+    }
+
+    /// returns the snowflake with the sequence replaced
+    ///
+    /// the duration is kept as is.
+    /// [`SequenceInvalid`](crate::error::Error::SequenceInvalid) will be returned if
+    /// the sequence is invalid
+    pub fn with_sequence(self, seq: i64) -> error::Result<Self> {
+        if seq < 0 || seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        Ok(Self { seq, ..self })
+    }
+
+    /// returns a copy of the snowflake with the sequence set to 0
+    ///
+    /// snowflakes created in the same tick will be equal which hides the
+    /// order they were created in. the ids will also be set to 0 if
+    /// `clear_ids` is true. the result is only meant for exporting and is
+    /// not checked against the generator rules for ids
+    pub fn anonymize(&self, clear_ids: bool) -> Self {
+        if clear_ids {
+            Self { tsm: self.tsm, ..Self::NIL }
+        } else {
+            Self { tsm: self.tsm, pid: self.pid, ..Self::NIL }
+        }
+    }
+
+    /// returns the milliseconds since the epoch rounded down to the start of
+    /// the given bucket
+    ///
+    /// a bucket shorter than a millisecond will return the milliseconds
+    /// unchanged
+    pub fn bucket_millis(&self, bucket: Duration) -> i64 {
+        let millis = self.tsm;
+        let bucket = i64::try_from(bucket.as_millis()).unwrap_or(i64::MAX);
+
+        if bucket == 0 {
+            millis
+        } else {
+            millis - millis % bucket
+        }
+    }
+
+    /// returns the bucket that the snowflake belongs to when grouping ids
+    /// into buckets of the given milliseconds since the epoch
+    ///
+    /// same as `(id >> TIMESTAMP_SHIFT) / bucket_millis` for millisecond
+    /// timestamps. [`BucketInvalid`](crate::error::Error::BucketInvalid) will
+    /// be returned if `bucket_millis` is 0
+    pub fn bucket(&self, bucket_millis: u64) -> error::Result<u64> {
+        if bucket_millis == 0 {
+            return Err(error::Error::BucketInvalid);
+        }
+
+        Ok(self.tsm as u64 / bucket_millis)
+    }
+
+    /// returns the range of ids that belong to the given bucket
+    ///
+    /// the reverse of [`bucket`](Self::bucket). the range is inclusive and
+    /// the end is clamped to the max timestamp.
+    /// [`BucketInvalid`](crate::error::Error::BucketInvalid) will be returned
+    /// if `bucket_millis` is 0 or the bucket does not contain any timestamps
+    /// and [`EpochInvalid`](crate::error::Error::EpochInvalid) will be
+    /// returned if the bucket starts after the max timestamp.
+    pub fn bucket_id_range(bucket: u64, bucket_millis: u64) -> error::Result<RangeInclusive<i64>> {
+        let (first, last) = crate::bucket_ticks::<Self>(bucket, bucket_millis, Self::MAX_TIMESTAMP as u64)?;
+
+        Ok(Self::first_for_timestamp(first as i64)?.id()..=Self::last_for_timestamp(last as i64)?.id())
+    }
+
+    /// returns the shard that the id belongs to out of the given number of
+    /// shards
+    ///
+    /// the full id is hashed before picking the shard so ids spread evenly
+    /// even when most of them have a small sequence. the result is stable
+    /// across versions, see [`shard`](crate::shard) for the algorithm. panics
+    /// if `shards` is 0
+    pub fn shard(&self, shards: u32) -> u32 {
+        crate::shard::shard_u64(self.id() as u64, shards)
+    }
+
+    /// returns the shard of the primary id out of the given number of shards
+    ///
+    /// the primary id is used as is without hashing so every id with the same
+    /// primary id will be in the same shard. panics if `shards` is 0
+    pub fn shard_by_primary(&self, shards: u32) -> u32 {
+        crate::shard::shard_segment(self.pid as u128, shards)
+    }
+
+    /// returns the snowflake with the given milliseconds added to the
+    /// timestamp
+    ///
+    /// every other part is left untouched and the duration is cleared like
+    /// [`from_parts`](Self::from_parts).
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the new timestamp is less than 0 or greater than the max timestamp
+    pub fn add_millis(&self, delta: i64) -> error::Result<Self> {
+        let tsm = (self.tsm as i128).checked_add(delta as i128)
+            .and_then(|tsm| i64::try_from(tsm).ok())
+            .filter(|tsm| *tsm >= 0 && *tsm <= Self::MAX_TIMESTAMP)
+            .ok_or(error::Error::EpochInvalid)?;
+
+        Ok(Self { dur: None, tsm, ..*self })
+    }
+
+    /// returns the snowflake with the given milliseconds subtracted from the
+    /// timestamp
+    ///
+    /// same as [`add_millis`](Self::add_millis) with a negated delta
+    pub fn sub_millis(&self, delta: i64) -> error::Result<Self> {
+        let Some(delta) = delta.checked_neg() else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        self.add_millis(delta)
+    }
+
+    /// returns the snowflake with the timestamp set to the given time using
+    /// the epoch in milliseconds
+    ///
+    /// every other part is left untouched.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the time is before the epoch or after the max timestamp
+    pub fn with_timestamp_offset_from(&self, epoch_millis: u64, time: SystemTime) -> error::Result<Self> {
+        let Ok(since_unix) = time.duration_since(SystemTime::UNIX_EPOCH) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        let target = since_unix.as_millis() as i128 - epoch_millis as i128;
+
+        let Ok(delta) = i64::try_from(target - (self.tsm as i128)) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        self.add_millis(delta)
+    }
+
     /// attempts to generated a snowflake from the given i64
     ///
     /// integer must be greater than or equal to `0` and less than or equal to
     /// [`i64::MAX`](i64::MAX)
     pub fn try_from(id: &i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
         if *id < 0 {
             return Err(error::Error::InvalidId);
         }
@@ -201,6 +707,33 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> SingleIdFlake<TS, PID, SEQ> {
         })
     }
 
+    /// returns the id as big endian bytes
+    ///
+    /// the bytes will sort in the same order as the ids which makes them
+    /// useful as keys in ordered byte stores
+    pub fn to_be_bytes(&self) -> [u8; 8] {
+        self.id().to_be_bytes()
+    }
+
+    /// returns the id as little endian bytes
+    pub fn to_le_bytes(&self) -> [u8; 8] {
+        self.id().to_le_bytes()
+    }
+
+    /// creates a snowflake from the big endian bytes of an id
+    ///
+    /// negative ids are rejected with
+    /// [`InvalidId`](crate::error::Error::InvalidId) the same as
+    /// [`try_from`](Self::try_from)
+    pub fn from_be_bytes(bytes: [u8; 8]) -> error::Result<Self> {
+        Self::try_from(&i64::from_be_bytes(bytes))
+    }
+
+    /// writes the big endian bytes of the id to the given writer
+    pub fn write_be(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&self.to_be_bytes())
+    }
+
 }
 
 impl<const TS: u8, const PID: u8, const SEQ: u8> traits::Id for SingleIdFlake<TS, PID, SEQ> {
@@ -211,6 +744,14 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> traits::Id for SingleIdFlake<TS
     }
 }
 
+impl<const TS: u8, const PID: u8, const SEQ: u8> traits::UnixMillis for SingleIdFlake<TS, PID, SEQ> {
+    type Error = error::Error;
+
+    fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        SingleIdFlake::unix_millis(self, epoch)
+    }
+}
+
 impl<const TS: u8, const PID: u8, const SEQ: u8> From<SingleIdFlake<TS, PID, SEQ>> for i64 {
     #[inline(always)]
     fn from(flake: SingleIdFlake<TS, PID, SEQ>) -> i64 {
@@ -225,6 +766,13 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> From<&SingleIdFlake<TS, PID, SE
     }
 }
 
+impl<const TS: u8, const PID: u8, const SEQ: u8> From<SingleIdFlake<TS, PID, SEQ>> for SingleIdParts {
+    #[inline(always)]
+    fn from(flake: SingleIdFlake<TS, PID, SEQ>) -> SingleIdParts {
+        flake.into_parts_named()
+    }
+}
+
 impl<const TS: u8, const PID: u8, const SEQ: u8> TryFrom<i64> for SingleIdFlake<TS, PID, SEQ> {
     type Error = error::Error;
 
@@ -243,17 +791,64 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> TryFrom<&i64> for SingleIdFlake
     }
 }
 
+impl<const TS: u8, const PID: u8, const SEQ: u8> TryFrom<SingleIdFlake<TS, PID, SEQ>> for NonZeroI64 {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(flake: SingleIdFlake<TS, PID, SEQ>) -> Result<Self, Self::Error> {
+        flake.id_nonzero().ok_or(error::Error::InvalidId)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SEQ: u8> TryFrom<NonZeroI64> for SingleIdFlake<TS, PID, SEQ> {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: NonZeroI64) -> Result<Self, Self::Error> {
+        Self::try_from(&id.get())
+    }
+}
+
+impl<const TS: u8, const UTS: u8, const PID: u8, const SEQ: u8> TryFrom<crate::u64::SingleIdFlake<UTS, PID, SEQ>> for SingleIdFlake<TS, PID, SEQ> {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(flake: crate::u64::SingleIdFlake<UTS, PID, SEQ>) -> Result<Self, Self::Error> {
+        flake.to_signed()
+    }
+}
+
 impl<const TS: u8, const PID: u8, const SEQ: u8> std::cmp::PartialEq for SingleIdFlake<TS, PID, SEQ> {
     fn eq(&self, rhs: &Self) -> bool {
         self.tsm == rhs.tsm && self.pid == rhs.pid && self.seq == rhs.seq
     }
 }
 
+impl<const TS: u8, const PID: u8, const SEQ: u8> std::cmp::PartialEq<i64> for SingleIdFlake<TS, PID, SEQ> {
+    fn eq(&self, rhs: &i64) -> bool {
+        self.id() == *rhs
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SEQ: u8> std::cmp::PartialOrd for SingleIdFlake<TS, PID, SEQ> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SEQ: u8> std::cmp::Ord for SingleIdFlake<TS, PID, SEQ> {
+    /// compares the ids of both snowflakes which orders them by timestamp
+    /// first followed by the id segments and then the sequence
+    fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
+        self.id().cmp(&rhs.id())
+    }
+}
+
 impl<const TS: u8, const PID: u8, const SEQ: u8> std::hash::Hash for SingleIdFlake<TS, PID, SEQ> {
+    /// hashes the id of the snowflake so the hash will be the same as the
+    /// hash of the i64 it is stored as
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.tsm.hash(state);
-        self.pid.hash(state);
-        self.seq.hash(state);
+        self.id().hash(state);
     }
 }
 
@@ -271,10 +866,79 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> std::fmt::Debug for SingleIdFla
     }
 }
 
+impl<const TS: u8, const PID: u8, const SEQ: u8> std::fmt::Display for SingleIdFlake<TS, PID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SEQ: u8> std::fmt::LowerHex for SingleIdFlake<TS, PID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SEQ: u8> std::fmt::UpperHex for SingleIdFlake<TS, PID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::UpperHex::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SEQ: u8> std::fmt::Binary for SingleIdFlake<TS, PID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Binary::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SEQ: u8> std::fmt::Octal for SingleIdFlake<TS, PID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Octal::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SEQ: u8> std::str::FromStr for SingleIdFlake<TS, PID, SEQ> {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.parse::<i64>().map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<const TS: u8, const PID: u8, const SEQ: u8> SingleIdFlake<TS, PID, SEQ> {
+    /// places the id into a version 8 uuid
+    ///
+    /// see [`uuid_ext`](crate::uuid_ext) for the layout of the uuid
+    pub fn to_uuid(&self) -> uuid::Uuid {
+        crate::uuid_ext::from_id(self.id() as u64)
+    }
+
+    /// creates a snowflake from a uuid created by [`to_uuid`](Self::to_uuid)
+    ///
+    /// the uuid must be version 8 with only the bits used by the id set
+    pub fn from_uuid(uuid: uuid::Uuid) -> error::Result<Self> {
+        let id = crate::uuid_ext::to_id(&uuid).ok_or(error::Error::InvalidId)?;
+
+        Self::try_from(&(id as i64))
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<const TS: u8, const PID: u8, const SEQ: u8> From<SingleIdFlake<TS, PID, SEQ>> for uuid::Uuid {
+    fn from(flake: SingleIdFlake<TS, PID, SEQ>) -> Self {
+        flake.to_uuid()
+    }
+}
+
 impl<const TS: u8, const PID: u8, const SEQ: u8> traits::FromIdGenerator for SingleIdFlake<TS, PID, SEQ> {
     type IdSegType = Segments<i64, 1>;
     type Builder = Builder<TS, PID, SEQ>;
 
+    const LAYOUT_BITS: u32 = TS as u32 + PID as u32 + SEQ as u32;
+    const MAX_LAYOUT_BITS: u32 = 63;
+
     fn valid_id(v: &Self::IdSegType) -> bool {
         *v.primary() > 0 && *v.primary() <= Self::MAX_PRIMARY_ID
     }
@@ -284,6 +948,8 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> traits::FromIdGenerator for Sin
     }
 
     fn builder(ids: &Self::IdSegType) -> Self::Builder {
+        let () = Self::VALID_LAYOUT;
+
         Builder {
             dur: Duration::new(0,0),
             ts: 0,
@@ -293,6 +959,25 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> traits::FromIdGenerator for Sin
     }
 }
 
+impl<const TS: u8, const PID: u8, const SEQ: u8> traits::FlakeParts for SingleIdFlake<TS, PID, SEQ> {
+    fn id_segs(&self) -> Self::IdSegType {
+        Segments::from([self.pid])
+    }
+
+    fn timestamp_ticks(&self) -> u64 {
+        self.tsm as u64
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SEQ: u8> traits::FlakeLayout for SingleIdFlake<TS, PID, SEQ> {
+    const LAYOUT: traits::Layout = traits::Layout {
+        base_bits: 64,
+        ts_bits: TS,
+        seg_bits: &[PID],
+        seq_bits: SEQ,
+    };
+}
+
 pub struct Builder<const TS: u8, const PID: u8, const SEQ: u8> {
     dur: Duration,
     ts: u64,
@@ -326,7 +1011,7 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> traits::IdBuilder for Builder<T
         }
     }
 
-    fn with_dur(&mut self, dur: Duration) -> () {
+    fn with_dur(&mut self, dur: Duration) {
         self.dur = dur;
     }
 
@@ -348,6 +1033,11 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> ser::Serialize for SingleIdFlak
     {
         let id = self.id();
 
+        #[cfg(feature = "serde-human-string")]
+        if serializer.is_human_readable() {
+            return serializer.collect_str(&id);
+        }
+
         serializer.serialize_i64(id)
     }
 }
@@ -374,16 +1064,40 @@ impl<'de, const TS: u8, const PID: u8, const SEQ: u8> de::Visitor<'de> for NumVi
         Ok(flake)
     }
 
+    fn visit_f64<E>(self, f: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        self.visit_i64(crate::serde_ext::float_to_int(f)?)
+    }
+
     fn visit_u64<E>(self, u: u64) -> Result<Self::Value, E>
     where
         E: de::Error
     {
+        // values above i64::MAX would wrap to negative ids
+        if u > i64::MAX as u64 {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(u), &self));
+        }
+
         let Ok(flake) = SingleIdFlake::try_from(&(u as i64)) else {
             return Err(E::invalid_value(de::Unexpected::Unsigned(u), &self));
         };
 
         Ok(flake)
     }
+
+    #[cfg(feature = "serde-human-string")]
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(flake) = s.parse() else {
+            return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+        };
+
+        Ok(flake)
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -392,10 +1106,26 @@ impl<'de, const TS: u8, const PID: u8, const SEQ: u8> de::Deserialize<'de> for S
     where
         D: de::Deserializer<'de>,
     {
+        #[cfg(feature = "serde-human-string")]
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(NumVisitor {});
+        }
+
         deserializer.deserialize_i64(NumVisitor {})
     }
 }
 
+#[cfg(feature = "schemars")]
+impl<const TS: u8, const PID: u8, const SEQ: u8> schemars::JsonSchema for SingleIdFlake<TS, PID, SEQ> {
+    fn schema_name() -> String {
+        format!("SingleIdFlake_{}_{}_{}", TS, PID, SEQ)
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        crate::schemars_ext::id_schema(Self::MAX.id() as u64, "int64")
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a, const TS: u8, const PID: u8, const SEQ: u8> FromSql<'a> for SingleIdFlake<TS, PID, SEQ> {
     fn from_sql(
@@ -432,6 +1162,7 @@ impl<const TS: u8, const PID: u8, const SEQ: u8> ToSql for SingleIdFlake<TS, PID
 }
 
 #[cfg(test)]
+#[allow(clippy::unusual_byte_groupings)]
 mod test {
     use super::*;
 
@@ -472,6 +1203,288 @@ mod test {
         assert_eq!(to_flake, flake);
     }
 
+    #[test]
+    fn created_at() {
+        let flake = TestSnowflake::from_parts(1000, 1, 1).unwrap();
+
+        assert_eq!(
+            flake.created_at(1679082337000),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_millis(1679082338000))
+        );
+        assert_eq!(flake.created_at(u64::MAX), None);
+    }
+
+    #[test]
+    fn age() {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let past = TestSnowflake::from_parts(1000, 1, 1).unwrap();
+        let future = TestSnowflake::from_parts(60_000, 1, 1).unwrap();
+
+        assert!(past.age(now - 2000).unwrap() >= Duration::from_millis(1000));
+        assert_eq!(future.age(now), None);
+    }
+
+    #[test]
+    fn same_tick() {
+        let flake = TestSnowflake::from_parts(1, 1, 1).unwrap();
+
+        assert!(flake.same_tick(&TestSnowflake::from_parts(1, 2, 2).unwrap()));
+        assert!(!flake.same_tick(&TestSnowflake::from_parts(2, 1, 1).unwrap()));
+    }
+
+    #[test]
+    fn ordered_by_id() {
+        use std::collections::BTreeSet;
+
+        let values = [(2, 0, 0), (1, 255, 4095), (1, 2, 0), (1, 1, 1), (0, 0, 0)];
+        let set: BTreeSet<TestSnowflake> = values.iter()
+            .map(|(tsm, pid, seq)| TestSnowflake::from_parts(*tsm, *pid, *seq).unwrap())
+            .collect();
+
+        let ids: Vec<i64> = set.iter().map(|flake| flake.id()).collect();
+        let mut expected: Vec<i64> = set.iter().map(|flake| flake.id()).collect();
+        expected.sort();
+
+        assert_eq!(set.len(), values.len());
+        assert_eq!(ids, expected);
+        assert_eq!(set.first().unwrap().id(), 0);
+        assert_eq!(set.last().unwrap().id(), 2 << TestSnowflake::TIMESTAMP_SHIFT);
+        assert!(set.contains(&TestSnowflake::from_parts(1, 2, 0).unwrap()));
+    }
+
+    #[test]
+    fn hashed_as_id() {
+        use std::collections::HashSet;
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::Hash;
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let flake = TestSnowflake::from_parts(1679082337000, 200, 3000).unwrap();
+        let id = flake.id();
+
+        assert_eq!(hash_of(&flake), hash_of(&id));
+        assert_eq!(flake, id);
+        assert_ne!(flake, id + 1);
+
+        let set: HashSet<TestSnowflake> = HashSet::from([flake, TestSnowflake::from_parts(1, 1, 1).unwrap()]);
+
+        assert!(set.contains(&TestSnowflake::try_from(&id).unwrap()));
+        assert!(set.iter().any(|found| *found == id));
+    }
+
+    #[test]
+    fn nil_min_max() {
+        assert_eq!(TestSnowflake::NIL.id(), 0);
+        assert_eq!(TestSnowflake::MIN, TestSnowflake::NIL);
+        assert_eq!(TestSnowflake::MAX.id(), i64::MAX);
+
+        assert!(TestSnowflake::NIL.is_nil());
+        assert!(!TestSnowflake::MAX.is_nil());
+        assert!(!TestSnowflake::from_parts(0, 0, 1).unwrap().is_nil());
+
+        assert_eq!(TestSnowflake::try_from(&0).unwrap(), TestSnowflake::NIL);
+        assert_eq!(TestSnowflake::try_from(&i64::MAX).unwrap(), TestSnowflake::MAX);
+        assert_eq!(
+            TestSnowflake::from_parts(
+                TestSnowflake::MAX_TIMESTAMP,
+                TestSnowflake::MAX_PRIMARY_ID,
+                TestSnowflake::MAX_SEQUENCE
+            ).unwrap(),
+            TestSnowflake::MAX
+        );
+    }
+
+    #[test]
+    fn timestamp_bounds() {
+        let first = TestSnowflake::first_for_timestamp(2).unwrap();
+        let last = TestSnowflake::last_for_timestamp(2).unwrap();
+
+        assert_eq!(first.into_parts(), (2, 0, 0));
+        assert_eq!(last.into_parts(), (2, TestSnowflake::MAX_PRIMARY_ID, TestSnowflake::MAX_SEQUENCE));
+        assert_eq!(last.id() + 1, TestSnowflake::first_for_timestamp(3).unwrap().id());
+
+        assert!(matches!(TestSnowflake::first_for_timestamp(-1), Err(error::Error::EpochInvalid)));
+        assert!(matches!(
+            TestSnowflake::last_for_timestamp(TestSnowflake::MAX_TIMESTAMP + 1),
+            Err(error::Error::EpochInvalid)
+        ));
+    }
+
+    #[test]
+    fn id_range_for() {
+        const EPOCH: u64 = 1679082337000;
+
+        let at = |millis: u64, nanos: u32| {
+            SystemTime::UNIX_EPOCH + Duration::from_millis(EPOCH + millis) + Duration::from_nanos(nanos as u64)
+        };
+        let bounds = |first: i64, last: i64| {
+            TestSnowflake::first_for_timestamp(first).unwrap().id()..=TestSnowflake::last_for_timestamp(last).unwrap().id()
+        };
+
+        // end is exclusive so ids from 2000 are not included
+        assert_eq!(TestSnowflake::id_range_for(EPOCH, at(1000, 0), at(2000, 0)).unwrap(), bounds(1000, 1999));
+
+        // partial milliseconds include the millisecond they are in
+        assert_eq!(TestSnowflake::id_range_for(EPOCH, at(1000, 500), at(2000, 500)).unwrap(), bounds(1000, 2000));
+        assert_eq!(TestSnowflake::id_range_for(EPOCH, at(1000, 0), at(1000, 1)).unwrap(), bounds(1000, 1000));
+
+        // clamped to what can be represented
+        let before_epoch = SystemTime::UNIX_EPOCH + Duration::from_millis(EPOCH - 1000);
+        let after_max = at(TestSnowflake::MAX_TIMESTAMP as u64 + 1000, 0);
+
+        assert_eq!(TestSnowflake::id_range_for(EPOCH, before_epoch, at(10, 0)).unwrap(), bounds(0, 9));
+        assert_eq!(
+            TestSnowflake::id_range_for(EPOCH, at(10, 0), after_max).unwrap(),
+            bounds(10, TestSnowflake::MAX_TIMESTAMP)
+        );
+
+        // nothing can be represented
+        assert!(TestSnowflake::id_range_for(EPOCH, before_epoch, at(0, 0)).is_err());
+        assert!(TestSnowflake::id_range_for(EPOCH, after_max, after_max + Duration::from_secs(1)).is_err());
+        assert!(TestSnowflake::id_range_for(EPOCH, at(2000, 0), at(1000, 0)).is_err());
+        assert!(TestSnowflake::id_range_for(EPOCH, at(1000, 0), at(1000, 0)).is_err());
+    }
+
+    #[test]
+    fn id_partition_boundaries() {
+        const EPOCH: u64 = 1679082337000;
+        const DAY: u64 = 24 * 60 * 60 * 1000;
+        // 2023-04-01T00:00:00Z
+        const APRIL: u64 = 1680307200000;
+
+        let at = |millis: u64| SystemTime::UNIX_EPOCH + Duration::from_millis(millis);
+        let day = Duration::from_millis(DAY);
+
+        let ids: Vec<i64> = TestSnowflake::id_partition_boundaries(EPOCH, at(APRIL), at(APRIL + 30 * DAY), day)
+            .collect();
+
+        assert_eq!(ids.len(), 30);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]), "boundaries are not increasing");
+
+        for (index, id) in ids.iter().enumerate() {
+            let expected = TestSnowflake::first_for_timestamp((APRIL + index as u64 * DAY - EPOCH) as i64).unwrap();
+
+            assert_eq!(*id, expected.id(), "invalid boundary for day {}", index);
+        }
+
+        // the step does not evenly divide the range
+        let uneven: Vec<i64> = TestSnowflake::id_partition_boundaries(EPOCH, at(APRIL + DAY / 2), at(APRIL + 2 * DAY + DAY / 4), day)
+            .collect();
+
+        assert_eq!(uneven, ids[1..3]);
+
+        // days before the epoch are skipped
+        let before: Vec<i64> = TestSnowflake::id_partition_boundaries(EPOCH, at(EPOCH - 10 * DAY), at(EPOCH + 2 * DAY), day)
+            .collect();
+
+        assert_eq!(before.len(), 2);
+        assert!(before[0] > 0);
+
+        assert_eq!(TestSnowflake::id_partition_boundaries(EPOCH, at(APRIL), at(APRIL + DAY), Duration::ZERO).count(), 0);
+    }
+
+    #[test]
+    fn successor_and_predecessor() {
+        let parts = |flake: Option<TestSnowflake>| flake.map(|flake| flake.into_parts());
+        let flake = |tsm, pid, seq| TestSnowflake::from_parts(tsm, pid, seq).unwrap();
+
+        let max_pid = TestSnowflake::MAX_PRIMARY_ID;
+        let max_seq = TestSnowflake::MAX_SEQUENCE;
+
+        assert_eq!(parts(flake(1, 1, 1).successor()), Some((1, 1, 2)));
+        assert_eq!(parts(flake(1, 1, 1).predecessor()), Some((1, 1, 0)));
+
+        // sequence carries into the primary id
+        assert_eq!(parts(flake(1, 1, max_seq).successor()), Some((1, 2, 0)));
+        assert_eq!(parts(flake(1, 2, 0).predecessor()), Some((1, 1, max_seq)));
+
+        // primary id carries into the timestamp
+        assert_eq!(parts(flake(1, max_pid, max_seq).successor()), Some((2, 0, 0)));
+        assert_eq!(parts(flake(2, 0, 0).predecessor()), Some((1, max_pid, max_seq)));
+
+        assert_eq!(parts(TestSnowflake::MAX.successor()), None);
+        assert_eq!(parts(TestSnowflake::MIN.predecessor()), None);
+        assert_eq!(TestSnowflake::MAX.predecessor().unwrap().id(), i64::MAX - 1);
+        assert_eq!(TestSnowflake::MIN.successor().unwrap().id(), 1);
+    }
+
+    #[test]
+    fn timestamp_arithmetic() {
+        let flake = TestSnowflake::from_parts(1000, 5, 10).unwrap();
+
+        assert_eq!(flake.add_millis(500).unwrap().into_parts(), (1500, 5, 10));
+        assert_eq!(flake.add_millis(-500).unwrap().into_parts(), (500, 5, 10));
+        assert_eq!(flake.sub_millis(1000).unwrap().into_parts(), (0, 5, 10));
+        assert_eq!(flake.add_millis(0).unwrap(), flake);
+
+        assert!(matches!(flake.sub_millis(1001), Err(error::Error::EpochInvalid)));
+        assert!(matches!(flake.sub_millis(i64::MIN), Err(error::Error::EpochInvalid)));
+        assert!(matches!(flake.add_millis(i64::MAX), Err(error::Error::EpochInvalid)));
+        assert!(matches!(
+            flake.add_millis(TestSnowflake::MAX_TIMESTAMP - 999),
+            Err(error::Error::EpochInvalid)
+        ));
+        assert_eq!(
+            flake.add_millis(TestSnowflake::MAX_TIMESTAMP - 1000).unwrap().timestamp(),
+            TestSnowflake::MAX_TIMESTAMP
+        );
+    }
+
+    #[test]
+    fn timestamp_offset_from() {
+        const EPOCH: u64 = 1679082337000;
+
+        let flake = TestSnowflake::from_parts(1000, 5, 10).unwrap();
+        let at = |millis: u64| SystemTime::UNIX_EPOCH + Duration::from_millis(millis);
+
+        let moved = flake.with_timestamp_offset_from(EPOCH, at(EPOCH + 30_000)).unwrap();
+
+        assert_eq!(moved.into_parts(), (30_000, 5, 10));
+        assert_eq!(moved.duration(), None);
+        assert_eq!(flake.with_timestamp_offset_from(EPOCH, at(EPOCH)).unwrap().into_parts(), (0, 5, 10));
+        assert!(flake.with_timestamp_offset_from(EPOCH, at(EPOCH - 1)).is_err());
+    }
+
+    #[test]
+    fn with_modifiers() {
+        let dur = Duration::from_millis(1000);
+        let flake = TestSnowflake { dur: Some(dur), ..TestSnowflake::from_parts(1000, 1, 1).unwrap() };
+
+        let modified = flake.with_primary_id(2).unwrap().with_sequence(3).unwrap();
+
+        assert_eq!(modified.into_parts(), (1000, 2, 3));
+        assert_eq!(modified.duration(), Some(&dur));
+
+        assert!(matches!(flake.with_primary_id(-1), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(flake.with_primary_id(TestSnowflake::MAX_PRIMARY_ID + 1), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(flake.with_sequence(-1), Err(error::Error::SequenceInvalid)));
+        assert!(matches!(flake.with_sequence(TestSnowflake::MAX_SEQUENCE + 1), Err(error::Error::SequenceInvalid)));
+
+        let last = TestSnowflake::first_for_timestamp(1000).unwrap()
+            .with_primary_id(TestSnowflake::MAX_PRIMARY_ID).unwrap()
+            .with_sequence(TestSnowflake::MAX_SEQUENCE).unwrap();
+
+        assert_eq!(last, TestSnowflake::last_for_timestamp(1000).unwrap());
+    }
+
+    #[test]
+    fn from_segments() {
+        let expected = TestSnowflake::from_parts(1000, 5, 10).unwrap();
+
+        assert_eq!(TestSnowflake::from_segments(1000, 5, 10).unwrap(), expected);
+        assert_eq!(TestSnowflake::from_segments(1000, Segments::<i64, 1>::from_parts(5), 10).unwrap(), expected);
+        assert_eq!(TestSnowflake::from_segments(1000, [5], 10).unwrap(), expected);
+        assert!(matches!(TestSnowflake::from_segments(1000, 256, 10), Err(error::Error::IdSegInvalid)));
+    }
+
     #[test]
     fn properly_shifted_integers() {
         let flake = TestSnowflake::from_parts(1, 1, 1).unwrap();
@@ -488,6 +1501,279 @@ mod test {
         );
     }
 
+    #[test]
+    fn layout_descriptor() {
+        use traits::FlakeLayout;
+
+        // same layouts used in the benches
+        let layout = SingleIdFlake::<43, 7, 13>::LAYOUT;
+
+        assert_eq!(layout.base_bits, 64);
+        assert_eq!(layout.ts_bits, 43);
+        assert_eq!(layout.seg_bits, &[7]);
+        assert_eq!(layout.seq_bits, 13);
+
+        assert_eq!(traits::describe::<SingleIdFlake<43, 7, 13>>(), "43-7-13");
+        assert_eq!(traits::describe::<TestSnowflake>(), "43-8-12");
+    }
+
+    #[test]
+    fn extract_helpers() {
+        for bits in crate::random_bits(1000) {
+            let id = bits as i64;
+            let positive = id & i64::MAX;
+
+            assert_eq!(TestSnowflake::timestamp_of(id), TestSnowflake::timestamp_of(positive));
+            assert_eq!(TestSnowflake::primary_id_of(id), TestSnowflake::primary_id_of(positive));
+            assert_eq!(TestSnowflake::sequence_of(id), TestSnowflake::sequence_of(positive));
+
+            let Ok(flake) = TestSnowflake::try_from(&positive) else {
+                continue;
+            };
+
+            assert_eq!(TestSnowflake::timestamp_of(id), flake.timestamp());
+            assert_eq!(TestSnowflake::primary_id_of(id), flake.primary_id());
+            assert_eq!(TestSnowflake::sequence_of(id), flake.sequence());
+        }
+    }
+
+    #[test]
+    fn be_bytes() {
+        let flakes = [
+            TestSnowflake::NIL,
+            TestSnowflake::try_from(&1).unwrap(),
+            TestSnowflake::try_from(&256).unwrap(),
+            TestSnowflake::MAX,
+        ];
+
+        for flake in flakes {
+            let mut written = Vec::new();
+            flake.write_be(&mut written).unwrap();
+
+            assert_eq!(TestSnowflake::from_be_bytes(flake.to_be_bytes()).unwrap(), flake);
+            assert_eq!(flake.to_le_bytes(), flake.id().to_le_bytes());
+            assert_eq!(written, flake.to_be_bytes());
+        }
+
+        assert!(flakes.windows(2).all(|pair| pair[0].to_be_bytes() < pair[1].to_be_bytes()));
+        assert!(matches!(TestSnowflake::from_be_bytes((-1i64).to_be_bytes()), Err(error::Error::InvalidId)));
+    }
+
+    #[test]
+    fn nonzero() {
+        assert_eq!(TestSnowflake::NIL.id_nonzero(), None);
+        assert!(matches!(NonZeroI64::try_from(TestSnowflake::NIL), Err(error::Error::InvalidId)));
+
+        let flake = TestSnowflake::MAX;
+        let id = flake.id_nonzero().unwrap();
+
+        assert_eq!(id.get(), flake.id());
+        assert_eq!(NonZeroI64::try_from(flake).unwrap(), id);
+
+        let back: TestSnowflake = id.try_into().unwrap();
+
+        assert_eq!(back, flake);
+    }
+
+    #[test]
+    fn unsigned_conversion() {
+        type Unsigned = crate::u64::SingleIdFlake<44, 8, 12>;
+
+        let flake = TestSnowflake::from_parts(TestSnowflake::MAX_TIMESTAMP, 200, 3000).unwrap();
+        let unsigned: Unsigned = flake.to_unsigned().unwrap();
+
+        assert_eq!(unsigned.id(), flake.id() as u64);
+        assert_eq!(unsigned.timestamp(), flake.timestamp() as u64);
+
+        let back: TestSnowflake = unsigned.try_into().unwrap();
+
+        assert_eq!(back, flake);
+
+        // the highest bit of the timestamp is the 64th bit of the id
+        let high = Unsigned::from_parts(Unsigned::MAX_TIMESTAMP, 1, 1).unwrap();
+
+        assert!(high.id() > i64::MAX as u64);
+        assert!(matches!(high.to_signed::<43>(), Err(error::Error::EpochInvalid)));
+    }
+
+    #[test]
+    fn anonymize() {
+        let first = TestSnowflake::MAX.with_sequence(1).unwrap();
+
+        assert_ne!(first, TestSnowflake::MAX);
+        assert_eq!(first.anonymize(false), TestSnowflake::MAX.anonymize(false));
+        assert_eq!(first.anonymize(false).sequence(), 0);
+        assert_eq!(
+            TestSnowflake::first_for_timestamp(5000).unwrap().anonymize(true),
+            TestSnowflake::last_for_timestamp(5000).unwrap().anonymize(true)
+        );
+    }
+
+    #[test]
+    fn bucket_millis() {
+        let bucket = Duration::from_secs(10);
+        let first = TestSnowflake::first_for_timestamp(25000).unwrap();
+        let last = TestSnowflake::last_for_timestamp(29000).unwrap();
+        let next = TestSnowflake::first_for_timestamp(35000).unwrap();
+
+        assert_eq!(first.bucket_millis(bucket), 20_000);
+        assert_eq!(first.bucket_millis(bucket), last.bucket_millis(bucket));
+        assert_ne!(first.bucket_millis(bucket), next.bucket_millis(bucket));
+        assert_eq!(first.bucket_millis(Duration::ZERO), 25_000);
+    }
+
+    #[test]
+    fn bucket() {
+        let first = TestSnowflake::first_for_timestamp(25000).unwrap();
+        let last = TestSnowflake::last_for_timestamp(25000).unwrap();
+        let next = TestSnowflake::first_for_timestamp(35000).unwrap();
+
+        assert_eq!(first.bucket(10_000).unwrap(), 2);
+        assert_eq!(first.bucket(10_000).unwrap(), last.bucket(10_000).unwrap());
+        assert_eq!(next.bucket(10_000).unwrap(), 3);
+        assert_eq!(first.bucket(1).unwrap(), first.id() as u64 >> TestSnowflake::TIMESTAMP_SHIFT);
+        assert!(matches!(first.bucket(0), Err(error::Error::BucketInvalid)));
+
+        let range = TestSnowflake::bucket_id_range(2, 10_000).unwrap();
+
+        assert_eq!(range, TestSnowflake::first_for_timestamp(20000).unwrap().id()..=TestSnowflake::last_for_timestamp(29999).unwrap().id());
+        assert!(range.contains(&first.id()));
+        assert!(range.contains(&last.id()));
+        assert!(!range.contains(&next.id()));
+
+        let max_bucket = TestSnowflake::MAX.bucket(10_000).unwrap();
+
+        assert_eq!(*TestSnowflake::bucket_id_range(max_bucket, 10_000).unwrap().end(), TestSnowflake::MAX.id());
+        assert!(matches!(TestSnowflake::bucket_id_range(max_bucket + 1, 10_000), Err(error::Error::EpochInvalid)));
+        assert!(matches!(TestSnowflake::bucket_id_range(2, 0), Err(error::Error::BucketInvalid)));
+    }
+
+    #[test]
+    fn shard() {
+        let first = TestSnowflake::first_for_timestamp(5000).unwrap();
+        let next = first.with_sequence(1).unwrap();
+
+        assert_eq!(first.shard(16), crate::shard::shard_u64(first.id() as u64, 16));
+        assert!(next.shard(7) < 7);
+        assert_eq!(first.shard_by_primary(4), next.shard_by_primary(4));
+        assert_eq!(TestSnowflake::MAX.shard_by_primary(3), (TestSnowflake::MAX.primary_id() % 3) as u32);
+    }
+
+    #[test]
+    fn opaque() {
+        let permuter = Permuter::new(42);
+        let flake = TestSnowflake::MAX.with_sequence(1).unwrap();
+        let opaque = flake.to_opaque(&permuter);
+
+        assert_ne!(opaque, flake.id());
+        assert_eq!(TestSnowflake::from_opaque(opaque, &permuter).unwrap(), flake);
+    }
+
+    #[test]
+    fn iter_sequence() {
+        let mut iter = TestSnowflake::iter_sequence(10, 1).unwrap();
+
+        assert_eq!(iter.len() as i64, TestSnowflake::MAX_SEQUENCE + 1);
+        assert_eq!(iter.next().unwrap(), TestSnowflake::from_parts(10, 1, 0).unwrap());
+        assert_eq!(iter.next_back().unwrap(), TestSnowflake::from_parts(10, 1, TestSnowflake::MAX_SEQUENCE).unwrap());
+        assert_eq!(iter.len() as i64, TestSnowflake::MAX_SEQUENCE - 1);
+
+        let reversed: Vec<i64> = TestSnowflake::iter_sequence(10, 1).unwrap()
+            .rev()
+            .map(|flake| flake.sequence())
+            .collect();
+
+        assert_eq!(reversed, (0..=TestSnowflake::MAX_SEQUENCE).rev().collect::<Vec<i64>>());
+        assert!(matches!(TestSnowflake::iter_sequence(10, TestSnowflake::MAX_PRIMARY_ID + 1), Err(error::Error::IdSegInvalid)));
+    }
+
+    #[test]
+    fn display_and_from_str() {
+        let flake = TestSnowflake::MAX.with_sequence(1).unwrap();
+        let string = flake.to_string();
+
+        assert_eq!(string, flake.id().to_string());
+        assert_eq!(string.parse::<TestSnowflake>().unwrap(), flake);
+        assert_eq!(format!("{:>50}", flake), format!("{:>50}", flake.id()));
+
+        assert!(matches!(format!(" {}", string).parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!(format!("{} ", string).parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("".parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("-1".parse::<TestSnowflake>(), Err(error::Error::InvalidId)));
+    }
+
+    #[test]
+    fn radix_formatting() {
+        let flake = TestSnowflake::from_parts(1, 1, 1).unwrap();
+
+        assert_eq!(format!("{:x}", flake), "101001");
+        assert_eq!(format!("{:#X}", flake), "0x101001");
+        assert_eq!(format!("{:o}", flake), "4010001");
+        assert_eq!(format!("{:#o}", flake), "0o4010001");
+        assert_eq!(format!("{:b}", flake), "100000001000000000001");
+        assert_eq!(format!("{:#066b}", flake), "0b0000000000000000000000000000000000000000000100000001000000000001");
+        assert_eq!(format!("{:>#12x}", flake), "    0x101001");
+    }
+
+    #[test]
+    fn fmt_layout() {
+        let flake = TestSnowflake::from_parts(5, 2, 4).unwrap();
+        let expected = [
+            "0 | 0000000000000000000000000000000000000000101 | 00000010 | 000000000100",
+            "  | ts                                          | pid      | seq",
+            "  | 5                                           | 2        | 4",
+        ];
+
+        assert_eq!(flake.fmt_layout(), expected.join("\n"));
+    }
+
+    #[test]
+    fn radix_parsing() {
+        let flake = TestSnowflake::from_parts(1, 1, 1).unwrap();
+
+        assert_eq!(flake.to_string_radix(16), "101001");
+        assert_eq!(flake.to_string_radix(2), "100000001000000000001");
+
+        for string in ["1052673", "0x101001", "0X101001", "0o4010001", "0b100000001000000000001"] {
+            assert_eq!(TestSnowflake::parse_prefixed(string).unwrap(), flake, "failed to parse {:?}", string);
+        }
+
+        assert_eq!(TestSnowflake::from_str_radix("101001", 16).unwrap(), flake);
+        assert!(matches!(TestSnowflake::from_str_radix("10g001", 16), Err(error::Error::ParseInt(_))));
+        assert!(matches!(TestSnowflake::parse_prefixed("0b102"), Err(error::Error::ParseInt(_))));
+        assert!(matches!(TestSnowflake::parse_prefixed("-1"), Err(error::Error::InvalidId)));
+        assert!(matches!(TestSnowflake::parse_prefixed("0x-1"), Err(error::Error::InvalidCharacter('-'))));
+
+        for radix in [2, 8, 16, 36] {
+            let string = TestSnowflake::MAX.to_string_radix(radix);
+
+            assert_eq!(TestSnowflake::from_str_radix(&string, radix).unwrap(), TestSnowflake::MAX);
+        }
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid() {
+        let flake = TestSnowflake::from_parts(1, 1, 1).unwrap();
+        let uuid = flake.to_uuid();
+
+        assert_eq!(uuid.to_string(), "00000000-0010-8100-8400-000000000000");
+        assert_eq!(uuid::Uuid::from(flake), uuid);
+        assert_eq!(TestSnowflake::from_uuid(uuid).unwrap(), flake);
+
+        for flake in [TestSnowflake::NIL, TestSnowflake::MAX] {
+            assert_eq!(TestSnowflake::from_uuid(flake.to_uuid()).unwrap(), flake);
+        }
+
+        let v4 = uuid::Uuid::parse_str("00000000-0010-4100-8400-000000000000").unwrap();
+
+        assert!(matches!(TestSnowflake::from_uuid(v4), Err(error::Error::InvalidId)));
+        // the sign bit is set
+        let negative = uuid::Uuid::parse_str("80000000-0000-8000-8000-000000000000").unwrap();
+
+        assert!(matches!(TestSnowflake::from_uuid(negative), Err(error::Error::InvalidId)));
+    }
+
     #[cfg(feature = "serde")]
     mod serde_ext {
         use super::*;
@@ -495,11 +1781,12 @@ mod test {
         use serde::{Serialize, Deserialize};
         use serde_json;
 
-        #[derive(Serialize, Deserialize)]
+        #[derive(Debug, Serialize, Deserialize)]
         struct IdFlake {
             id: TestSnowflake,
         }
 
+        #[cfg(not(feature = "serde-human-string"))]
         #[test]
         fn to_int() {
             let obj = IdFlake {
@@ -520,6 +1807,20 @@ mod test {
             }
         }
 
+        #[cfg(not(feature = "serde-human-string"))]
+        #[test]
+        fn nil_and_max() {
+            for flake in [TestSnowflake::NIL, TestSnowflake::MAX] {
+                let json_string = serde_json::to_string(&IdFlake { id: flake }).unwrap();
+
+                assert_eq!(json_string, format!("{{\"id\":{}}}", flake.id()));
+
+                let obj: IdFlake = serde_json::from_str(&json_string).unwrap();
+
+                assert_eq!(obj.id, flake);
+            }
+        }
+
         #[test]
         fn from_int() {
             let json_str = "{\"id\":1052673}";
@@ -537,6 +1838,68 @@ mod test {
                 }
             }
         }
-    }
 
+        #[test]
+        fn floats() {
+            let parsed: IdFlake = serde_json::from_str("{\"id\":1052673.0}")
+                .expect("failed to parse exact float");
+
+            assert_eq!(parsed.id, TestSnowflake::from_parts(1, 1, 1).unwrap());
+
+            let err = serde_json::from_str::<IdFlake>("{\"id\":1052673.5}").unwrap_err();
+
+            assert!(err.to_string().contains("is not an integer"), "{}", err);
+
+            let err = serde_json::from_str::<IdFlake>("{\"id\":9.3e18}").unwrap_err();
+
+            assert!(err.to_string().contains("larger than 2^53"), "{}", err);
+            assert!(serde_json::from_str::<IdFlake>("{\"id\":-1.0}").is_err());
+        }
+
+        #[test]
+        fn unsigned_range() {
+            let parsed: IdFlake = serde_json::from_str(&format!("{{\"id\":{}}}", i64::MAX))
+                .expect("failed to parse i64::MAX");
+
+            assert_eq!(parsed.id, TestSnowflake::MAX);
+
+            for value in [i64::MAX as u64 + 1, u64::MAX] {
+                let err = serde_json::from_str::<IdFlake>(&format!("{{\"id\":{}}}", value))
+                    .unwrap_err();
+
+                assert!(err.to_string().contains(&format!("invalid value: integer `{}`", value)), "{}", err);
+            }
+        }
+
+        #[test]
+        fn binary() {
+            let obj = IdFlake {
+                id: TestSnowflake::from_parts(1, 1, 1).unwrap(),
+            };
+
+            let bytes = bincode::serialize(&obj).unwrap();
+
+            assert_eq!(bytes, 1052673i64.to_le_bytes());
+            assert_eq!(bincode::deserialize::<IdFlake>(&bytes).unwrap().id, obj.id);
+        }
+
+        #[cfg(feature = "serde-human-string")]
+        #[test]
+        fn human_string() {
+            let obj = IdFlake {
+                id: TestSnowflake::from_parts(1, 1, 1).unwrap(),
+            };
+
+            let json_string = serde_json::to_string(&obj).unwrap();
+
+            assert_eq!(json_string, "{\"id\":\"1052673\"}");
+            assert_eq!(serde_json::from_str::<IdFlake>(&json_string).unwrap().id, obj.id);
+
+            // integers are still accepted
+            assert_eq!(serde_json::from_str::<IdFlake>("{\"id\":1052673}").unwrap().id, obj.id);
+
+            assert!(serde_json::from_str::<IdFlake>("{\"id\":\"\"}").is_err());
+            assert!(serde_json::from_str::<IdFlake>("{\"id\":\"-1\"}").is_err());
+        }
+    }
 }
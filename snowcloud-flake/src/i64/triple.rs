@@ -0,0 +1,1606 @@
+use std::hash::Hasher;
+use std::num::NonZeroI64;
+use std::ops::RangeInclusive;
+use std::time::{Duration, SystemTime};
+
+use snowcloud_core::traits;
+
+#[cfg(feature = "serde")]
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{de, ser};
+
+#[cfg(feature = "postgres")]
+use postgres_types::{to_sql_checked, accepts, IsNull, FromSql, ToSql, Type as PgType};
+#[cfg(feature = "postgres")]
+use bytes::{BytesMut, BufMut};
+
+use crate::error;
+use crate::opaque::Permuter;
+use crate::Segments;
+use crate::i64::TripleIdParts;
+
+/// i64 Snowflake with 3 id segments
+///
+/// the format is as follows with a 43 bit timestamp, 4 bit primary id, 4 bit
+/// secondary id, 4 bit tertiary id, and 8 bit sequence:
+///
+/// ```text
+///  01111111111111111111111111111111111111111111 - 1111 - 1111 - 1111 - 11111111
+///  |                                          |   |  |   |  |   |  |   |      |
+/// 64                                         21  20 17  16 13  12  9   8      1
+///                                     timestamp      |      |      |          |
+///                                           primary id      |      |          |
+///                                                secondary id      |          |
+///                                                        tertiary id          |
+///                                                                      sequence
+/// ```
+///
+/// bit values for each segment can be specified by `TS`, `PID`, `SID`,
+/// `TID`, and `SEQ`. the total amount of bits should equal 63 since the sign
+/// bit cannot be used otherwise you will get negative id values.
+///
+/// Note: the total is checked at compile time once the snowflake is used and
+/// will fail to compile if it does not equal 63. the `relaxed-layout`
+/// feature will only require the total to be less than or equal to 63.
+///
+/// # Timestamp
+///
+/// timestamp is in milliseconds with a bit value specified by the `TS` const.
+/// the snowflake holds the duration value of when the snowflake was created
+/// and the timestamp will be pulled from that.
+///
+/// Note: when creating a snowflake outside of a generator the duration will
+/// only be as accurate as the provided ts.
+///
+/// # Primary Id
+///
+/// specified by the `PID` const. used to help differentiate ids outside of 
+/// the timestamp and sequence values. an example representation could be 
+/// different server ids if being used across multiple machines in a web 
+/// server.
+///
+/// # Secondary Id
+///
+/// specified by the `SID` const. similar to the primary but for more 
+/// distinction. example could different instances on a single server or a
+/// thread id
+///
+/// # Tertiary Id
+///
+/// specified by the `TID` const. one more level of distinction after the
+/// secondary id. example could be a worker on a given instance
+///
+/// # Sequence
+///
+/// specified by the `SEQ` const. indicates the count of when the snowflake 
+/// was generated in the same millisecond.
+///
+/// # De/Serialize
+///
+/// with the `serde` feature you can de/serialize a snowflake to and from an
+/// [`i64`](core::primitive::i64) by default
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+///
+/// type MyFlake = snowcloud::i64::TripleIdFlake<43, 4, 4, 4, 8>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     id: MyFlake
+/// }
+///
+/// let my_struct = MyStruct {
+///     id: MyFlake::from_parts(1, 1, 1, 1, 1).unwrap(),
+/// };
+///
+/// let json_string = serde_json::to_string(&my_struct).unwrap();
+///
+/// println!("{}", json_string);
+/// ```
+///
+/// if you want more options check out [`serde_ext`](crate::serde_ext)
+///
+/// # Example Usage
+///
+/// ```rust
+/// type MyFlake = snowcloud::i64::TripleIdFlake<43, 4, 4, 4, 8>;
+/// type MyCloud = snowcloud::Generator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let mut cloud = MyCloud::new(START_TIME, (1, 1, 1))
+///     .expect("failed to create MyCloud");
+/// let flake: MyFlake = cloud.next_id()
+///     .expect("failed to create snowflake");
+///
+/// let id: i64 = flake.into();
+/// println!("{}", id);
+///
+/// let and_back: MyFlake = id.try_into()
+///     .expect("invalid i64 was provided");
+/// println!("{:?}", and_back);
+/// ```
+#[derive(Eq, Clone, Copy)]
+pub struct TripleIdFlake<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> {
+    pub(crate) dur: Option<Duration>,
+    pub(crate) tsm: i64,
+    pub(crate) pid: i64,
+    pub(crate) sid: i64,
+    pub(crate) tid: i64,
+    pub(crate) seq: i64,
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    /// max value that a timestamp can be.
+    /// `(1 << TS as i64) - 1`
+    pub const MAX_TIMESTAMP: i64 = (1 << TS as i64) - 1;
+    /// max value that a primary id can be.
+    /// `(1 << PID as i64) - 1`
+    pub const MAX_PRIMARY_ID: i64 = (1 << PID as i64) - 1;
+    /// max value that a secondary id can be.
+    /// `(1 << SID as i64) - 1`
+    pub const MAX_SECONDARY_ID: i64 = (1 << SID as i64) - 1;
+    /// max value that a tertiary id can be.
+    /// `(1 << TID as i64) - 1`
+    pub const MAX_TERTIARY_ID: i64 = (1 << TID as i64) - 1;
+    /// max value a sequence can be.
+    /// `(1 << SEQ as i64) - 1`
+    pub const MAX_SEQUENCE: i64 = (1 << SEQ as i64) - 1;
+
+    /// total bits to shift the timestamp.
+    /// `PID as i64 + SID as i64 + TID as i64 + SEQ as i64`
+    pub const TIMESTAMP_SHIFT: i64 = PID as i64 + SID as i64 + TID as i64 + SEQ as i64;
+    /// total bits to shift the primary id
+    /// `SID as i64 + TID as i64 + SEQ as i64`
+    pub const PRIMARY_ID_SHIFT: i64 = SID as i64 + TID as i64 + SEQ as i64;
+    /// total bits to shift the secondary id
+    /// `TID as i64 + SEQ as i64`
+    pub const SECONDARY_ID_SHIFT: i64 = TID as i64 + SEQ as i64;
+    /// total bits to shift the tertiary id
+    /// `SEQ as i64`
+    pub const TERTIARY_ID_SHIFT: i64 = SEQ as i64;
+
+    /// bit mask for timestamp
+    /// `Self::MAX_TIMESTAMP << Self::TIMESTAMP_SHIFT`
+    pub const TIMESTAMP_MASK: i64 = Self::MAX_TIMESTAMP << Self::TIMESTAMP_SHIFT;
+    /// bit mask for primary id
+    /// `Self::MAX_PRIMARY_ID << Self::PRIMARY_ID_SHIFT`
+    pub const PRIMARY_ID_MASK: i64 = Self::MAX_PRIMARY_ID << Self::PRIMARY_ID_SHIFT;
+    /// bit mask for secondary id
+    /// `Self::MAX_SECONDARY_ID << Self::SECONDARY_ID_SHIFT`
+    pub const SECONDARY_ID_MASK: i64 = Self::MAX_SECONDARY_ID << Self::SECONDARY_ID_SHIFT;
+    /// bit mask for tertiary id
+    /// `Self::MAX_TERTIARY_ID << Self::TERTIARY_ID_SHIFT`
+    pub const TERTIARY_ID_MASK: i64 = Self::MAX_TERTIARY_ID << Self::TERTIARY_ID_SHIFT;
+    /// bit mask for sequence
+    /// `Self::MAX_SEQUENCE`
+    pub const SEQUENCE_MASK: i64 = Self::MAX_SEQUENCE;
+
+    const MAX_EPOCH: u64 = (1 << TS as u64) - 1;
+
+    /// snowflake with every part set to 0
+    pub const NIL: Self = Self { dur: None, tsm: 0, pid: 0, sid: 0, tid: 0, seq: 0 };
+    /// smallest possible snowflake. same as `Self::NIL`
+    pub const MIN: Self = Self::NIL;
+    /// largest possible snowflake with every part set to its max value
+    pub const MAX: Self = Self {
+        dur: None,
+        tsm: Self::MAX_TIMESTAMP,
+        pid: Self::MAX_PRIMARY_ID,
+        sid: Self::MAX_SECONDARY_ID,
+        tid: Self::MAX_TERTIARY_ID,
+        seq: Self::MAX_SEQUENCE,
+    };
+
+    /// fails to compile if the layout does not fit the available bits
+    const VALID_LAYOUT: () = assert!(
+        crate::layout_fits(
+            <Self as traits::FromIdGenerator>::LAYOUT_BITS,
+            <Self as traits::FromIdGenerator>::MAX_LAYOUT_BITS
+        ),
+        "TripleIdFlake bit widths must sum to 63"
+    );
+
+    /// returns the time since the epoch that the snowflake was generated at
+    ///
+    /// only snowflakes from a generator will have this. anything created
+    /// from parts or an integer will be `None` and only the millisecond
+    /// precision of the timestamp is available
+    pub fn duration(&self) -> Option<&Duration> {
+        self.dur.as_ref()
+    }
+
+    /// returns timestamp
+    pub fn timestamp(&self) -> i64 {
+        self.tsm
+    }
+
+    /// returns the unix timestamp in milliseconds of when the snowflake was
+    /// created using the given epoch
+    ///
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the result does not fit in a u64
+    pub fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        epoch.checked_add(self.tsm as u64)
+            .ok_or(error::Error::EpochInvalid)
+    }
+
+    /// returns when the snowflake was created using the given epoch
+    ///
+    /// `None` will be returned if the time cannot be represented
+    pub fn created_at(&self, epoch_millis: u64) -> Option<SystemTime> {
+        let millis = self.unix_millis(epoch_millis).ok()?;
+
+        SystemTime::UNIX_EPOCH.checked_add(Duration::from_millis(millis))
+    }
+
+    /// returns how long ago the snowflake was created using the given epoch
+    ///
+    /// `None` will be returned if the snowflake appears to be created in the
+    /// future
+    pub fn age(&self, epoch_millis: u64) -> Option<Duration> {
+        SystemTime::now().duration_since(self.created_at(epoch_millis)?).ok()
+    }
+
+    /// checks if both snowflakes were created in the same millisecond
+    pub fn same_tick(&self, other: &Self) -> bool {
+        self.tsm == other.tsm
+    }
+
+    /// returns primary id
+    pub fn primary_id(&self) -> i64 {
+        self.pid
+    }
+
+    /// returns secondary id
+    pub fn secondary_id(&self) -> i64 {
+        self.sid
+    }
+
+    /// returns tertiary id
+    pub fn tertiary_id(&self) -> i64 {
+        self.tid
+    }
+
+    /// returns sequence
+    pub fn sequence(&self) -> i64 {
+        self.seq
+    }
+
+    /// extracts the timestamp from an id without any validation
+    ///
+    /// the sign bit is outside of every mask so a negative id will never
+    /// produce a negative value
+    #[inline]
+    pub const fn timestamp_of(id: i64) -> i64 {
+        (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT
+    }
+
+    /// extracts the primary id from an id without any validation
+    #[inline]
+    pub const fn primary_id_of(id: i64) -> i64 {
+        (id & Self::PRIMARY_ID_MASK) >> Self::PRIMARY_ID_SHIFT
+    }
+
+    /// extracts the secondary id from an id without any validation
+    #[inline]
+    pub const fn secondary_id_of(id: i64) -> i64 {
+        (id & Self::SECONDARY_ID_MASK) >> Self::SECONDARY_ID_SHIFT
+    }
+
+    /// extracts the tertiary id from an id without any validation
+    #[inline]
+    pub const fn tertiary_id_of(id: i64) -> i64 {
+        (id & Self::TERTIARY_ID_MASK) >> Self::TERTIARY_ID_SHIFT
+    }
+
+    /// extracts the sequence from an id without any validation
+    #[inline]
+    pub const fn sequence_of(id: i64) -> i64 {
+        id & Self::SEQUENCE_MASK
+    }
+
+    /// generates a Snowflake from the provided parts
+    ///
+    /// checks will be performed on each part to ensure that they are
+    /// valid for the given Snowflake. 
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the primary/secondary/tertiary id is invalid
+    pub fn from_parts(tsm: i64, pid: i64, sid: i64, tid: i64, seq: i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm < 0 || tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        if pid < 0 || pid > Self::MAX_PRIMARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        if sid < 0 || sid > Self::MAX_SECONDARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        if tid < 0 || tid > Self::MAX_TERTIARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        if seq < 0 || seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        Ok(Self { dur: None, tsm, pid, sid, tid, seq })
+    }
+
+    /// iterates every snowflake that can be created with the given timestamp
+    /// and ids starting with a sequence of 0
+    ///
+    /// the timestamp and ids are checked the same as
+    /// [`from_parts`](Self::from_parts) before creating the iterator
+    pub fn iter_sequence(tsm: i64, pid: i64, sid: i64, tid: i64) -> error::Result<impl ExactSizeIterator<Item = Self> + DoubleEndedIterator> {
+        let first = Self::from_parts(tsm, pid, sid, tid, 0)?;
+        let max = usize::try_from(Self::MAX_SEQUENCE).unwrap_or(usize::MAX - 1);
+
+        Ok((0..max + 1).map(move |seq| Self { seq: seq as i64, ..first }))
+    }
+
+    /// generates a Snowflake from the timestamp, id segments, and sequence
+    ///
+    /// the ids can be anything that converts into the same segments given to
+    /// a generator like a single value, a tuple, or
+    /// [`Segments`](crate::Segments). the same checks as
+    /// [`from_parts`](Self::from_parts) will be performed
+    pub fn from_segments<I>(tsm: i64, ids: I, seq: i64) -> error::Result<Self>
+    where
+        I: Into<Segments<i64, 3>>
+    {
+        let ids = ids.into();
+
+        Self::from_parts(tsm, ids[0], ids[1], ids[2], seq)
+    }
+
+    /// generates a Snowflake from the provided named parts
+    ///
+    /// the same checks as [`from_parts`](Self::from_parts) will be performed
+    pub fn from_parts_named(parts: TripleIdParts) -> error::Result<Self> {
+        Self::from_parts(parts.timestamp, parts.primary_id, parts.secondary_id, parts.tertiary_id, parts.sequence)
+    }
+
+    /// generates the smallest Snowflake for the given timestamp
+    ///
+    /// every other part is set to 0. useful as the lower bound when
+    /// searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn first_for_timestamp(tsm: i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm < 0 || tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::NIL })
+    }
+
+    /// generates the largest Snowflake for the given timestamp
+    ///
+    /// every other part is set to its max value. useful as the upper bound
+    /// when searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn last_for_timestamp(tsm: i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm < 0 || tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::MAX })
+    }
+
+    /// returns the range of ids that were created between the given times
+    /// using the epoch in milliseconds
+    ///
+    /// `start` is inclusive and `end` is exclusive. the returned range is
+    /// inclusive so it can be used directly with something like
+    /// `WHERE id BETWEEN $1 AND $2`. a `start` before the epoch or an `end`
+    /// after the max timestamp will be clamped to what the snowflake can
+    /// represent. [`EpochInvalid`](crate::error::Error::EpochInvalid) will be
+    /// returned if none of the time range can be represented
+    pub fn id_range_for(epoch_millis: u64, start: SystemTime, end: SystemTime) -> error::Result<RangeInclusive<i64>> {
+        let (first, last) = crate::tick_range::<Self>(epoch_millis, start, end, Self::MAX_TIMESTAMP as u64)?;
+
+        Ok(Self::first_for_timestamp(first as i64)?.id()..=Self::last_for_timestamp(last as i64)?.id())
+    }
+
+    /// returns the first id of every boundary between the given times using
+    /// the epoch in milliseconds
+    ///
+    /// boundaries are multiples of `step` since the unix epoch so a step of
+    /// one day will give the first id of each day in UTC. `start` is
+    /// inclusive and `end` is exclusive. boundaries that are before the epoch
+    /// or after the max timestamp are skipped and a `step` of 0 will not
+    /// produce any ids. the ids are calculated as the iterator is consumed
+    pub fn id_partition_boundaries(epoch_millis: u64, start: SystemTime, end: SystemTime, step: Duration) -> impl Iterator<Item = i64> {
+        crate::boundary_ticks::<Self>(epoch_millis, start, end, step, Self::MAX_TIMESTAMP as u64)
+            .filter_map(|tick| Self::first_for_timestamp(tick as i64).ok())
+            .map(|flake| flake.id())
+    }
+
+    /// splits the current Snowflake into its individual parts
+    pub fn into_parts(self) -> (i64, i64, i64, i64, i64) {
+        (self.tsm, self.pid, self.sid, self.tid, self.seq)
+    }
+
+    /// splits the current Snowflake into its individual named parts
+    pub fn into_parts_named(self) -> TripleIdParts {
+        TripleIdParts {
+            timestamp: self.tsm,
+            primary_id: self.pid,
+            secondary_id: self.sid,
+            tertiary_id: self.tid,
+            sequence: self.seq,
+        }
+    }
+
+    /// generates the unique id
+    pub fn id(&self) -> i64 {
+        (self.tsm << Self::TIMESTAMP_SHIFT) | 
+        (self.pid << Self::PRIMARY_ID_SHIFT) | 
+        (self.sid << Self::SECONDARY_ID_SHIFT) |
+        (self.tid << Self::TERTIARY_ID_SHIFT) |
+        self.seq
+    }
+
+    /// renders the bits of the id split at each part with the name and
+    /// value of each part underneath
+    ///
+    /// useful for checking that a layout places the parts where they are
+    /// expected to be
+    pub fn fmt_layout(&self) -> String {
+        crate::layout_diagram(self.id() as u128, 64, &[("ts", TS), ("pid", PID), ("sid", SID), ("tid", TID), ("seq", SEQ)])
+    }
+
+    /// encodes the id as base58
+    ///
+    /// see [`encoding::base58`](crate::encoding::base58) for more information
+    pub fn to_base58(&self) -> String {
+        crate::encoding::base58::encode(self)
+    }
+
+    /// decodes a base58 string created by [`to_base58`](Self::to_base58)
+    pub fn from_base58(string: &str) -> error::Result<Self> {
+        crate::encoding::base58::decode(string)
+    }
+
+    /// encodes the id as base62
+    ///
+    /// see [`encoding::base62`](crate::encoding::base62) for more information
+    pub fn to_base62(&self) -> String {
+        crate::encoding::base62::encode(self)
+    }
+
+    /// decodes a base62 string created by [`to_base62`](Self::to_base62)
+    pub fn from_base62(string: &str) -> error::Result<Self> {
+        crate::encoding::base62::decode(string)
+    }
+
+    /// encodes the id as fixed width crockford base32
+    ///
+    /// see [`encoding::crockford`](crate::encoding::crockford) for more
+    /// information
+    pub fn to_crockford(&self) -> String {
+        crate::encoding::crockford::encode_fixed(self)
+    }
+
+    /// decodes a crockford base32 string created by
+    /// [`to_crockford`](Self::to_crockford)
+    pub fn from_crockford(string: &str) -> error::Result<Self> {
+        crate::encoding::crockford::decode(string)
+    }
+
+    /// encodes the big endian bytes of the id as unpadded base64url
+    ///
+    /// see [`encoding::base64url`](crate::encoding::base64url) for more
+    /// information
+    pub fn to_base64url(&self) -> String {
+        crate::encoding::base64url::encode(self)
+    }
+
+    /// decodes a base64url string created by
+    /// [`to_base64url`](Self::to_base64url)
+    pub fn from_base64url(string: &str) -> error::Result<Self> {
+        crate::encoding::base64url::decode(string)
+    }
+
+    /// prints the id in the given radix using lowercase letters and no
+    /// prefix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn to_string_radix(&self, radix: u32) -> String {
+        crate::encoding::to_string_radix(self.id() as u128, radix)
+    }
+
+    /// parses a snowflake from an id in the given radix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn from_str_radix(string: &str, radix: u32) -> error::Result<Self> {
+        let id = i64::from_str_radix(string, radix).map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+
+    /// parses a snowflake from an id that may start with a `0x`, `0o`, or
+    /// `0b` prefix
+    ///
+    /// strings without a prefix are parsed as decimal
+    pub fn parse_prefixed(string: &str) -> error::Result<Self> {
+        Self::try_from(&crate::encoding::parse_prefixed::<i64>(string)?)
+    }
+
+    /// returns the id if it is not zero
+    ///
+    /// only [`NIL`](Self::NIL) will return `None`
+    pub fn id_nonzero(&self) -> Option<NonZeroI64> {
+        NonZeroI64::new(self.id())
+    }
+
+    /// returns the id scrambled with the given permuter
+    ///
+    /// see [`opaque`](crate::opaque) for more information
+    pub fn to_opaque(&self, permuter: &Permuter) -> i64 {
+        permuter.encode(self.id())
+    }
+
+    /// creates a snowflake from an id scrambled with
+    /// [`to_opaque`](Self::to_opaque) using the same permuter
+    pub fn from_opaque(id: i64, permuter: &Permuter) -> error::Result<Self> {
+        Self::try_from(&permuter.decode(id))
+    }
+
+    /// converts to the u64 snowflake with the same id and sequence widths
+    ///
+    /// the timestamp width can differ so that the u64 snowflake is able to
+    /// use the extra bit. [`EpochInvalid`](crate::error::Error::EpochInvalid)
+    /// will be returned if the timestamp does not fit
+    pub fn to_unsigned<const UTS: u8>(&self) -> error::Result<crate::u64::TripleIdFlake<UTS, PID, SID, TID, SEQ>> {
+        let mut flake = crate::u64::TripleIdFlake::from_parts(self.tsm as u64, self.pid as u64, self.sid as u64, self.tid as u64, self.seq as u64)?;
+        flake.dur = self.dur;
+
+        Ok(flake)
+    }
+
+    /// checks if every part of the snowflake is 0
+    pub fn is_nil(&self) -> bool {
+        self == &Self::NIL
+    }
+
+    /// returns the snowflake that comes after this one
+    ///
+    /// same as adding 1 to the id so the sequence will carry over into the
+    /// id segments and timestamp. `None` will be returned if this is
+    /// [`MAX`](Self::MAX)
+    pub fn successor(&self) -> Option<Self> {
+        if *self == Self::MAX {
+            return None;
+        }
+
+        Self::try_from(&(self.id() + 1)).ok()
+    }
+
+    /// returns the snowflake that comes before this one
+    ///
+    /// same as subtracting 1 from the id. `None` will be returned if this is
+    /// [`MIN`](Self::MIN)
+    pub fn predecessor(&self) -> Option<Self> {
+        if *self == Self::MIN {
+            return None;
+        }
+
+        Self::try_from(&(self.id() - 1)).ok()
+    }
+
+    /// returns the snowflake with the primary id replaced
+    ///
+    /// the duration is kept as is.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the primary id is invalid
+    pub fn with_primary_id(self, pid: i64) -> error::Result<Self> {
+        if pid < 0 || pid > Self::MAX_PRIMARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        Ok(Self { pid, ..self })
+    }
+
+    /// returns the snowflake with the secondary id replaced
+    ///
+    /// the duration is kept as is.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the secondary id is invalid
+    pub fn with_secondary_id(self, sid: i64) -> error::Result<Self> {
+        if sid < 0 || sid > Self::MAX_SECONDARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        Ok(Self { sid, ..self })
+    }
+
+    /// returns the snowflake with the tertiary id replaced
+    ///
+    /// the duration is kept as is.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the tertiary id is invalid
+    pub fn with_tertiary_id(self, tid: i64) -> error::Result<Self> {
+        if tid < 0 || tid > Self::MAX_TERTIARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        Ok(Self { tid, ..self })
+    }
+
+    /// returns the snowflake with the sequence replaced
+    ///
+    /// the duration is kept as is.
+    /// [`SequenceInvalid`](crate::error::Error::SequenceInvalid) will be returned if
+    /// the sequence is invalid
+    pub fn with_sequence(self, seq: i64) -> error::Result<Self> {
+        if seq < 0 || seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        Ok(Self { seq, ..self })
+    }
+
+    /// returns a copy of the snowflake with the sequence set to 0
+    ///
+    /// snowflakes created in the same tick will be equal which hides the
+    /// order they were created in. the ids will also be set to 0 if
+    /// `clear_ids` is true. the result is only meant for exporting and is
+    /// not checked against the generator rules for ids
+    pub fn anonymize(&self, clear_ids: bool) -> Self {
+        if clear_ids {
+            Self { tsm: self.tsm, ..Self::NIL }
+        } else {
+            Self { tsm: self.tsm, pid: self.pid, sid: self.sid, tid: self.tid, ..Self::NIL }
+        }
+    }
+
+    /// returns the milliseconds since the epoch rounded down to the start of
+    /// the given bucket
+    ///
+    /// a bucket shorter than a millisecond will return the milliseconds
+    /// unchanged
+    pub fn bucket_millis(&self, bucket: Duration) -> i64 {
+        let millis = self.tsm;
+        let bucket = i64::try_from(bucket.as_millis()).unwrap_or(i64::MAX);
+
+        if bucket == 0 {
+            millis
+        } else {
+            millis - millis % bucket
+        }
+    }
+
+    /// returns the bucket that the snowflake belongs to when grouping ids
+    /// into buckets of the given milliseconds since the epoch
+    ///
+    /// same as `(id >> TIMESTAMP_SHIFT) / bucket_millis` for millisecond
+    /// timestamps. [`BucketInvalid`](crate::error::Error::BucketInvalid) will
+    /// be returned if `bucket_millis` is 0
+    pub fn bucket(&self, bucket_millis: u64) -> error::Result<u64> {
+        if bucket_millis == 0 {
+            return Err(error::Error::BucketInvalid);
+        }
+
+        Ok(self.tsm as u64 / bucket_millis)
+    }
+
+    /// returns the range of ids that belong to the given bucket
+    ///
+    /// the reverse of [`bucket`](Self::bucket). the range is inclusive and
+    /// the end is clamped to the max timestamp.
+    /// [`BucketInvalid`](crate::error::Error::BucketInvalid) will be returned
+    /// if `bucket_millis` is 0 or the bucket does not contain any timestamps
+    /// and [`EpochInvalid`](crate::error::Error::EpochInvalid) will be
+    /// returned if the bucket starts after the max timestamp.
+    pub fn bucket_id_range(bucket: u64, bucket_millis: u64) -> error::Result<RangeInclusive<i64>> {
+        let (first, last) = crate::bucket_ticks::<Self>(bucket, bucket_millis, Self::MAX_TIMESTAMP as u64)?;
+
+        Ok(Self::first_for_timestamp(first as i64)?.id()..=Self::last_for_timestamp(last as i64)?.id())
+    }
+
+    /// returns the shard that the id belongs to out of the given number of
+    /// shards
+    ///
+    /// the full id is hashed before picking the shard so ids spread evenly
+    /// even when most of them have a small sequence. the result is stable
+    /// across versions, see [`shard`](crate::shard) for the algorithm. panics
+    /// if `shards` is 0
+    pub fn shard(&self, shards: u32) -> u32 {
+        crate::shard::shard_u64(self.id() as u64, shards)
+    }
+
+    /// returns the shard of the primary id out of the given number of shards
+    ///
+    /// the primary id is used as is without hashing so every id with the same
+    /// primary id will be in the same shard. panics if `shards` is 0
+    pub fn shard_by_primary(&self, shards: u32) -> u32 {
+        crate::shard::shard_segment(self.pid as u128, shards)
+    }
+
+    /// returns the snowflake with the given milliseconds added to the
+    /// timestamp
+    ///
+    /// every other part is left untouched and the duration is cleared like
+    /// [`from_parts`](Self::from_parts).
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the new timestamp is less than 0 or greater than the max timestamp
+    pub fn add_millis(&self, delta: i64) -> error::Result<Self> {
+        let tsm = (self.tsm as i128).checked_add(delta as i128)
+            .and_then(|tsm| i64::try_from(tsm).ok())
+            .filter(|tsm| *tsm >= 0 && *tsm <= Self::MAX_TIMESTAMP)
+            .ok_or(error::Error::EpochInvalid)?;
+
+        Ok(Self { dur: None, tsm, ..*self })
+    }
+
+    /// returns the snowflake with the given milliseconds subtracted from the
+    /// timestamp
+    ///
+    /// same as [`add_millis`](Self::add_millis) with a negated delta
+    pub fn sub_millis(&self, delta: i64) -> error::Result<Self> {
+        let Some(delta) = delta.checked_neg() else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        self.add_millis(delta)
+    }
+
+    /// returns the snowflake with the timestamp set to the given time using
+    /// the epoch in milliseconds
+    ///
+    /// every other part is left untouched.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the time is before the epoch or after the max timestamp
+    pub fn with_timestamp_offset_from(&self, epoch_millis: u64, time: SystemTime) -> error::Result<Self> {
+        let Ok(since_unix) = time.duration_since(SystemTime::UNIX_EPOCH) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        let target = since_unix.as_millis() as i128 - epoch_millis as i128;
+
+        let Ok(delta) = i64::try_from(target - (self.tsm as i128)) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        self.add_millis(delta)
+    }
+
+    /// attempts to generated a snowflake from the given i64
+    ///
+    /// integer must be greater than or equal to `0` and less than or euqal to
+    /// [`i64::MAX`](i64::MAX)
+    pub fn try_from(id: &i64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if *id < 0 {
+            return Err(error::Error::InvalidId);
+        }
+
+        Ok(Self {
+            dur: None,
+            tsm: (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT,
+            pid: (id & Self::PRIMARY_ID_MASK) >> Self::PRIMARY_ID_SHIFT,
+            sid: (id & Self::SECONDARY_ID_MASK) >> Self::SECONDARY_ID_SHIFT,
+            tid: (id & Self::TERTIARY_ID_MASK) >> Self::TERTIARY_ID_SHIFT,
+            seq: id & Self::SEQUENCE_MASK,
+        })
+    }
+
+    /// returns the id as big endian bytes
+    ///
+    /// the bytes will sort in the same order as the ids which makes them
+    /// useful as keys in ordered byte stores
+    pub fn to_be_bytes(&self) -> [u8; 8] {
+        self.id().to_be_bytes()
+    }
+
+    /// returns the id as little endian bytes
+    pub fn to_le_bytes(&self) -> [u8; 8] {
+        self.id().to_le_bytes()
+    }
+
+    /// creates a snowflake from the big endian bytes of an id
+    ///
+    /// negative ids are rejected with
+    /// [`InvalidId`](crate::error::Error::InvalidId) the same as
+    /// [`try_from`](Self::try_from)
+    pub fn from_be_bytes(bytes: [u8; 8]) -> error::Result<Self> {
+        Self::try_from(&i64::from_be_bytes(bytes))
+    }
+
+    /// writes the big endian bytes of the id to the given writer
+    pub fn write_be(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&self.to_be_bytes())
+    }
+
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> traits::Id for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    type BaseType = i64;
+
+    fn id(&self) -> Self::BaseType {
+        TripleIdFlake::id(self)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> traits::UnixMillis for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    type Error = error::Error;
+
+    fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        TripleIdFlake::unix_millis(self, epoch)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> From<TripleIdFlake<TS, PID, SID, TID, SEQ>> for i64 {
+    #[inline(always)]
+    fn from(flake: TripleIdFlake<TS, PID, SID, TID, SEQ>) -> i64 {
+        flake.id()
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> From<&TripleIdFlake<TS, PID, SID, TID, SEQ>> for i64 {
+    #[inline(always)]
+    fn from(flake: &TripleIdFlake<TS, PID, SID, TID, SEQ>) -> i64 {
+        flake.id()
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> From<TripleIdFlake<TS, PID, SID, TID, SEQ>> for TripleIdParts {
+    #[inline(always)]
+    fn from(flake: TripleIdFlake<TS, PID, SID, TID, SEQ>) -> TripleIdParts {
+        flake.into_parts_named()
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> TryFrom<i64> for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: i64) -> Result<Self, Self::Error> {
+        TripleIdFlake::try_from(&id)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> TryFrom<&i64> for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: &i64) -> Result<Self, Self::Error> {
+        TripleIdFlake::try_from(id)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> TryFrom<TripleIdFlake<TS, PID, SID, TID, SEQ>> for NonZeroI64 {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(flake: TripleIdFlake<TS, PID, SID, TID, SEQ>) -> Result<Self, Self::Error> {
+        flake.id_nonzero().ok_or(error::Error::InvalidId)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> TryFrom<NonZeroI64> for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: NonZeroI64) -> Result<Self, Self::Error> {
+        Self::try_from(&id.get())
+    }
+}
+
+impl<const TS: u8, const UTS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> TryFrom<crate::u64::TripleIdFlake<UTS, PID, SID, TID, SEQ>> for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(flake: crate::u64::TripleIdFlake<UTS, PID, SID, TID, SEQ>) -> Result<Self, Self::Error> {
+        flake.to_signed()
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::cmp::PartialEq for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.tsm == rhs.tsm && self.pid == rhs.pid && self.sid == rhs.sid && self.tid == rhs.tid && self.seq == rhs.seq
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::cmp::PartialEq<i64> for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn eq(&self, rhs: &i64) -> bool {
+        self.id() == *rhs
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::cmp::PartialOrd for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::cmp::Ord for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    /// compares the ids of both snowflakes which orders them by timestamp
+    /// first followed by the id segments and then the sequence
+    fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
+        self.id().cmp(&rhs.id())
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::hash::Hash for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    /// hashes the id of the snowflake so the hash will be the same as the
+    /// hash of the i64 it is stored as
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::fmt::Debug for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = self.id();
+
+        f.debug_struct("TripleIdFlake")
+            .field("id", &id)
+            .field("dur", &self.dur)
+            .field("tsm", &self.tsm)
+            .field("pid", &self.pid)
+            .field("sid", &self.sid)
+            .field("tid", &self.tid)
+            .field("seq", &self.seq)
+            .finish()
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::fmt::Display for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::fmt::LowerHex for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::fmt::UpperHex for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::UpperHex::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::fmt::Binary for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Binary::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::fmt::Octal for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Octal::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> std::str::FromStr for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.parse::<i64>().map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    /// places the id into a version 8 uuid
+    ///
+    /// see [`uuid_ext`](crate::uuid_ext) for the layout of the uuid
+    pub fn to_uuid(&self) -> uuid::Uuid {
+        crate::uuid_ext::from_id(self.id() as u64)
+    }
+
+    /// creates a snowflake from a uuid created by [`to_uuid`](Self::to_uuid)
+    ///
+    /// the uuid must be version 8 with only the bits used by the id set
+    pub fn from_uuid(uuid: uuid::Uuid) -> error::Result<Self> {
+        let id = crate::uuid_ext::to_id(&uuid).ok_or(error::Error::InvalidId)?;
+
+        Self::try_from(&(id as i64))
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> From<TripleIdFlake<TS, PID, SID, TID, SEQ>> for uuid::Uuid {
+    fn from(flake: TripleIdFlake<TS, PID, SID, TID, SEQ>) -> Self {
+        flake.to_uuid()
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> traits::FromIdGenerator for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    type IdSegType = Segments<i64, 3>;
+    type Builder = Builder<TS, PID, SID, TID, SEQ>;
+
+    const LAYOUT_BITS: u32 = TS as u32 + PID as u32 + SID as u32 + TID as u32 + SEQ as u32;
+    const MAX_LAYOUT_BITS: u32 = 63;
+
+    fn valid_id(v: &Self::IdSegType) -> bool {
+        *v.primary() > 0 && *v.primary() <= Self::MAX_PRIMARY_ID && 
+        *v.secondary() > 0 && *v.secondary() <= Self::MAX_SECONDARY_ID &&
+        *v.tertiary() > 0 && *v.tertiary() <= Self::MAX_TERTIARY_ID
+    }
+
+    fn valid_epoch(e: &u64) -> bool {
+        *e <= Self::MAX_EPOCH
+    }
+
+    fn builder(ids: &Self::IdSegType) -> Self::Builder {
+        let () = Self::VALID_LAYOUT;
+
+        Builder {
+            dur: Duration::new(0,0),
+            ts: 0,
+            seq: 0,
+            pid: *ids.primary(),
+            sid: *ids.secondary(),
+            tid: *ids.tertiary(),
+        }
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> traits::FlakeParts for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn id_segs(&self) -> Self::IdSegType {
+        Segments::from([self.pid, self.sid, self.tid])
+    }
+
+    fn timestamp_ticks(&self) -> u64 {
+        self.tsm as u64
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> traits::FlakeLayout for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    const LAYOUT: traits::Layout = traits::Layout {
+        base_bits: 64,
+        ts_bits: TS,
+        seg_bits: &[PID, SID, TID],
+        seq_bits: SEQ,
+    };
+}
+
+pub struct Builder<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> {
+    dur: Duration,
+    ts: u64,
+    seq: u64,
+    pid: i64,
+    sid: i64,
+    tid: i64,
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> Builder<TS, PID, SID, TID, SEQ> {
+    const MAX_EPOCH: u64 = (1 << TS as u64) - 1;
+    const MAX_U64_SEQUENCE: u64 = (1 << SEQ as u64) - 1;
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> traits::IdBuilder for Builder<TS, PID, SID, TID, SEQ> {
+    type Output = TripleIdFlake<TS, PID, SID, TID, SEQ>;
+
+    fn with_ts(&mut self, ts: u64) -> bool {
+        if ts > Self::MAX_EPOCH {
+            false
+        } else {
+            self.ts = ts;
+            true
+        }
+    }
+
+    fn with_seq(&mut self, seq: u64) -> bool {
+        if seq > Self::MAX_U64_SEQUENCE {
+            false
+        } else {
+            self.seq = seq;
+            true
+        }
+    }
+
+    fn with_dur(&mut self, dur: Duration) {
+        self.dur = dur;
+    }
+
+    fn build(self) -> Self::Output {
+        TripleIdFlake {
+            dur: Some(self.dur),
+            tsm: self.ts as i64,
+            pid: self.pid,
+            sid: self.sid,
+            tid: self.tid,
+            seq: self.seq as i64
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> ser::Serialize for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer
+    {
+        let id = self.id();
+
+        #[cfg(feature = "serde-human-string")]
+        if serializer.is_human_readable() {
+            return serializer.collect_str(&id);
+        }
+
+        serializer.serialize_i64(id)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct NumVisitor<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> {}
+
+#[cfg(feature = "serde")]
+impl<'de, const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> de::Visitor<'de> for NumVisitor<TS, PID, SID, TID, SEQ> {
+    type Value = TripleIdFlake<TS, PID, SID, TID, SEQ>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "integer from 0 to i64::MAX")
+    }
+
+    fn visit_i64<E>(self, i: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(flake) = TryFrom::try_from(i) else {
+            return Err(E::invalid_value(de::Unexpected::Signed(i), &self));
+        };
+
+        Ok(flake)
+    }
+
+    fn visit_f64<E>(self, f: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        self.visit_i64(crate::serde_ext::float_to_int(f)?)
+    }
+
+    fn visit_u64<E>(self, u: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        // values above i64::MAX would wrap to negative ids
+        if u > i64::MAX as u64 {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(u), &self));
+        }
+
+        let Ok(flake) = TryFrom::try_from(u as i64) else {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(u), &self));
+        };
+
+        Ok(flake)
+    }
+
+    #[cfg(feature = "serde-human-string")]
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(flake) = s.parse() else {
+            return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+        };
+
+        Ok(flake)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> de::Deserialize<'de> for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        #[cfg(feature = "serde-human-string")]
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(NumVisitor {});
+        }
+
+        deserializer.deserialize_i64(NumVisitor {})
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a, const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> FromSql<'a> for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn from_sql(
+        _: &PgType,
+        raw: &'a [u8]
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let Some(int) = crate::pg::read_i64(raw) else {
+            return Err("invalid buffer size".into());
+        };
+
+        Self::try_from(&int).map_err(Into::into)
+    }
+
+    accepts!(INT8);
+}
+
+#[cfg(feature = "postgres")]
+impl<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> ToSql for TripleIdFlake<TS, PID, SID, TID, SEQ> {
+    fn to_sql(
+        &self,
+        _: &PgType,
+        buf: &mut BytesMut
+    ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        let id = self.id();
+
+        buf.put_i64(id);
+
+        Ok(IsNull::No)
+    }
+
+    accepts!(INT8);
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+#[allow(clippy::unusual_byte_groupings)]
+mod test {
+    use super::*;
+
+    type TestSnowflake = TripleIdFlake<43, 4, 4, 4, 8>;
+
+    #[test]
+    fn properly_calculated_consts() {
+        let max_timestamp: i64 = 0b1111111111111111111111111111111111111111111;
+        let max_primary_id: i64 = 0b1111;
+        let max_secondary_id: i64 = 0b1111;
+        let max_tertiary_id: i64 = 0b1111;
+        let max_sequence: i64 = 0b11111111;
+
+        let timestamp_shift: i64 = 20;
+        let primary_id_shift: i64 = 16;
+        let secondary_id_shift: i64 = 12;
+        let tertiary_id_shift: i64 = 8;
+
+        let timestamp_mask: i64 =    0b0_1111111111111111111111111111111111111111111_0000_0000_0000_00000000;
+        let primary_id_mask: i64 =   0b0_0000000000000000000000000000000000000000000_1111_0000_0000_00000000;
+        let secondary_id_mask: i64 = 0b0_0000000000000000000000000000000000000000000_0000_1111_0000_00000000;
+        let tertiary_id_mask: i64 =  0b0_0000000000000000000000000000000000000000000_0000_0000_1111_00000000;
+        let sequence_mask: i64 =     0b0_0000000000000000000000000000000000000000000_0000_0000_0000_11111111;
+
+        assert_eq!(TestSnowflake::MAX_TIMESTAMP, max_timestamp, "invalid max timestamp");
+        assert_eq!(TestSnowflake::MAX_PRIMARY_ID, max_primary_id, "invalid max primary id");
+        assert_eq!(TestSnowflake::MAX_SECONDARY_ID, max_secondary_id, "invalid max secondary id");
+        assert_eq!(TestSnowflake::MAX_TERTIARY_ID, max_tertiary_id, "invalid max tertiary id");
+        assert_eq!(TestSnowflake::MAX_SEQUENCE, max_sequence, "invalid max sequence");
+
+        assert_eq!(TestSnowflake::TIMESTAMP_SHIFT, timestamp_shift, "invalid timestamp shift");
+        assert_eq!(TestSnowflake::PRIMARY_ID_SHIFT, primary_id_shift, "invalid primary id shift");
+        assert_eq!(TestSnowflake::SECONDARY_ID_SHIFT, secondary_id_shift, "invalid secondary id shift");
+        assert_eq!(TestSnowflake::TERTIARY_ID_SHIFT, tertiary_id_shift, "invalid tertiary id shift");
+
+        assert_eq!(TestSnowflake::TIMESTAMP_MASK, timestamp_mask, "invalid timestamp mask");
+        assert_eq!(TestSnowflake::PRIMARY_ID_MASK, primary_id_mask, "invalid primary id mask");
+        assert_eq!(TestSnowflake::SECONDARY_ID_MASK, secondary_id_mask, "invalid secondary id mask");
+        assert_eq!(TestSnowflake::TERTIARY_ID_MASK, tertiary_id_mask, "invalid tertiary id mask");
+        assert_eq!(TestSnowflake::SEQUENCE_MASK, sequence_mask, "invalid sequence mask");
+    }
+
+    #[test]
+    fn with_modifiers() {
+        let flake = TestSnowflake::from_parts(1000, 1, 1, 1, 1).unwrap();
+
+        let modified = flake.with_primary_id(2).unwrap()
+            .with_secondary_id(3).unwrap()
+            .with_tertiary_id(4).unwrap()
+            .with_sequence(5).unwrap();
+
+        assert_eq!(modified.into_parts(), (1000, 2, 3, 4, 5));
+
+        assert!(matches!(flake.with_secondary_id(-1), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(flake.with_tertiary_id(TestSnowflake::MAX_TERTIARY_ID + 1), Err(error::Error::IdSegInvalid)));
+    }
+
+    #[test]
+    fn to_int_and_back() {
+        let flake = TestSnowflake::from_parts(1, 1, 1, 1, 1).unwrap();
+
+        let to_int: i64 = (&flake).into();
+        let to_flake: TestSnowflake = (&to_int).try_into().unwrap();
+
+        assert_eq!(to_flake, flake);
+    }
+
+    #[test]
+    fn properly_shifted_integers() {
+        let flake = TestSnowflake::from_parts(1, 1, 1, 1, 1).unwrap();
+
+        let expected: i64 = 0b0_0000000000000000000000000000000000000000001_0001_0001_0001_00000001;
+
+        assert_eq!(
+            flake.id(),
+            expected,
+            "impropperly formatted snowflake.\n{:064b}\n{:064b}\n{:#?}",
+            expected,
+            flake.id(),
+            flake
+        );
+    }
+
+    #[test]
+    fn extract_helpers() {
+        for bits in crate::random_bits(1000) {
+            let id = bits as i64;
+            let positive = id & i64::MAX;
+
+            assert_eq!(TestSnowflake::timestamp_of(id), TestSnowflake::timestamp_of(positive));
+            assert_eq!(TestSnowflake::primary_id_of(id), TestSnowflake::primary_id_of(positive));
+            assert_eq!(TestSnowflake::secondary_id_of(id), TestSnowflake::secondary_id_of(positive));
+            assert_eq!(TestSnowflake::tertiary_id_of(id), TestSnowflake::tertiary_id_of(positive));
+            assert_eq!(TestSnowflake::sequence_of(id), TestSnowflake::sequence_of(positive));
+
+            let Ok(flake) = TestSnowflake::try_from(&positive) else {
+                continue;
+            };
+
+            assert_eq!(TestSnowflake::timestamp_of(id), flake.timestamp());
+            assert_eq!(TestSnowflake::primary_id_of(id), flake.primary_id());
+            assert_eq!(TestSnowflake::secondary_id_of(id), flake.secondary_id());
+            assert_eq!(TestSnowflake::tertiary_id_of(id), flake.tertiary_id());
+            assert_eq!(TestSnowflake::sequence_of(id), flake.sequence());
+        }
+    }
+
+    #[test]
+    fn be_bytes() {
+        let flakes = [
+            TestSnowflake::NIL,
+            TestSnowflake::try_from(&1).unwrap(),
+            TestSnowflake::try_from(&256).unwrap(),
+            TestSnowflake::MAX,
+        ];
+
+        for flake in flakes {
+            let mut written = Vec::new();
+            flake.write_be(&mut written).unwrap();
+
+            assert_eq!(TestSnowflake::from_be_bytes(flake.to_be_bytes()).unwrap(), flake);
+            assert_eq!(flake.to_le_bytes(), flake.id().to_le_bytes());
+            assert_eq!(written, flake.to_be_bytes());
+        }
+
+        assert!(flakes.windows(2).all(|pair| pair[0].to_be_bytes() < pair[1].to_be_bytes()));
+        assert!(matches!(TestSnowflake::from_be_bytes((-1i64).to_be_bytes()), Err(error::Error::InvalidId)));
+    }
+
+    #[test]
+    fn nonzero() {
+        assert_eq!(TestSnowflake::NIL.id_nonzero(), None);
+        assert!(matches!(NonZeroI64::try_from(TestSnowflake::NIL), Err(error::Error::InvalidId)));
+
+        let flake = TestSnowflake::MAX;
+        let id = flake.id_nonzero().unwrap();
+
+        assert_eq!(id.get(), flake.id());
+        assert_eq!(NonZeroI64::try_from(flake).unwrap(), id);
+
+        let back: TestSnowflake = id.try_into().unwrap();
+
+        assert_eq!(back, flake);
+    }
+
+    #[test]
+    fn unsigned_conversion() {
+        type Unsigned = crate::u64::TripleIdFlake<44, 4, 4, 4, 8>;
+
+        let flake = TestSnowflake::from_parts(TestSnowflake::MAX_TIMESTAMP, 10, 12, 14, 200).unwrap();
+        let unsigned: Unsigned = flake.to_unsigned().unwrap();
+
+        assert_eq!(unsigned.id(), flake.id() as u64);
+        assert_eq!(unsigned.timestamp(), flake.timestamp() as u64);
+
+        let back: TestSnowflake = unsigned.try_into().unwrap();
+
+        assert_eq!(back, flake);
+
+        // the highest bit of the timestamp is the 64th bit of the id
+        let high = Unsigned::from_parts(Unsigned::MAX_TIMESTAMP, 1, 1, 1, 1).unwrap();
+
+        assert!(high.id() > i64::MAX as u64);
+        assert!(matches!(high.to_signed::<43>(), Err(error::Error::EpochInvalid)));
+    }
+
+    #[test]
+    fn anonymize() {
+        let first = TestSnowflake::MAX.with_sequence(1).unwrap();
+
+        assert_ne!(first, TestSnowflake::MAX);
+        assert_eq!(first.anonymize(false), TestSnowflake::MAX.anonymize(false));
+        assert_eq!(first.anonymize(false).sequence(), 0);
+        assert_eq!(
+            TestSnowflake::first_for_timestamp(5000).unwrap().anonymize(true),
+            TestSnowflake::last_for_timestamp(5000).unwrap().anonymize(true)
+        );
+    }
+
+    #[test]
+    fn bucket_millis() {
+        let bucket = Duration::from_secs(10);
+        let first = TestSnowflake::first_for_timestamp(25000).unwrap();
+        let last = TestSnowflake::last_for_timestamp(29000).unwrap();
+        let next = TestSnowflake::first_for_timestamp(35000).unwrap();
+
+        assert_eq!(first.bucket_millis(bucket), 20_000);
+        assert_eq!(first.bucket_millis(bucket), last.bucket_millis(bucket));
+        assert_ne!(first.bucket_millis(bucket), next.bucket_millis(bucket));
+        assert_eq!(first.bucket_millis(Duration::ZERO), 25_000);
+    }
+
+    #[test]
+    fn bucket() {
+        let first = TestSnowflake::first_for_timestamp(25000).unwrap();
+        let last = TestSnowflake::last_for_timestamp(25000).unwrap();
+        let next = TestSnowflake::first_for_timestamp(35000).unwrap();
+
+        assert_eq!(first.bucket(10_000).unwrap(), 2);
+        assert_eq!(first.bucket(10_000).unwrap(), last.bucket(10_000).unwrap());
+        assert_eq!(next.bucket(10_000).unwrap(), 3);
+        assert_eq!(first.bucket(1).unwrap(), first.id() as u64 >> TestSnowflake::TIMESTAMP_SHIFT);
+        assert!(matches!(first.bucket(0), Err(error::Error::BucketInvalid)));
+
+        let range = TestSnowflake::bucket_id_range(2, 10_000).unwrap();
+
+        assert_eq!(range, TestSnowflake::first_for_timestamp(20000).unwrap().id()..=TestSnowflake::last_for_timestamp(29999).unwrap().id());
+        assert!(range.contains(&first.id()));
+        assert!(range.contains(&last.id()));
+        assert!(!range.contains(&next.id()));
+
+        let max_bucket = TestSnowflake::MAX.bucket(10_000).unwrap();
+
+        assert_eq!(*TestSnowflake::bucket_id_range(max_bucket, 10_000).unwrap().end(), TestSnowflake::MAX.id());
+        assert!(matches!(TestSnowflake::bucket_id_range(max_bucket + 1, 10_000), Err(error::Error::EpochInvalid)));
+        assert!(matches!(TestSnowflake::bucket_id_range(2, 0), Err(error::Error::BucketInvalid)));
+    }
+
+    #[test]
+    fn shard() {
+        let first = TestSnowflake::first_for_timestamp(5000).unwrap();
+        let next = first.with_sequence(1).unwrap();
+
+        assert_eq!(first.shard(16), crate::shard::shard_u64(first.id() as u64, 16));
+        assert!(next.shard(7) < 7);
+        assert_eq!(first.shard_by_primary(4), next.shard_by_primary(4));
+        assert_eq!(TestSnowflake::MAX.shard_by_primary(3), (TestSnowflake::MAX.primary_id() % 3) as u32);
+    }
+
+    #[test]
+    fn opaque() {
+        let permuter = Permuter::new(42);
+        let flake = TestSnowflake::MAX.with_sequence(1).unwrap();
+        let opaque = flake.to_opaque(&permuter);
+
+        assert_ne!(opaque, flake.id());
+        assert_eq!(TestSnowflake::from_opaque(opaque, &permuter).unwrap(), flake);
+    }
+
+    #[test]
+    fn iter_sequence() {
+        let mut iter = TestSnowflake::iter_sequence(10, 1, 1, 1).unwrap();
+
+        assert_eq!(iter.len() as i64, TestSnowflake::MAX_SEQUENCE + 1);
+        assert_eq!(iter.next().unwrap(), TestSnowflake::from_parts(10, 1, 1, 1, 0).unwrap());
+        assert_eq!(iter.next_back().unwrap(), TestSnowflake::from_parts(10, 1, 1, 1, TestSnowflake::MAX_SEQUENCE).unwrap());
+        assert_eq!(iter.len() as i64, TestSnowflake::MAX_SEQUENCE - 1);
+
+        let reversed: Vec<i64> = TestSnowflake::iter_sequence(10, 1, 1, 1).unwrap()
+            .rev()
+            .map(|flake| flake.sequence())
+            .collect();
+
+        assert_eq!(reversed, (0..=TestSnowflake::MAX_SEQUENCE).rev().collect::<Vec<i64>>());
+        assert!(matches!(TestSnowflake::iter_sequence(10, TestSnowflake::MAX_PRIMARY_ID + 1, 1, 1), Err(error::Error::IdSegInvalid)));
+    }
+
+    #[test]
+    fn display_and_from_str() {
+        let flake = TestSnowflake::MAX.with_sequence(1).unwrap();
+        let string = flake.to_string();
+
+        assert_eq!(string, flake.id().to_string());
+        assert_eq!(string.parse::<TestSnowflake>().unwrap(), flake);
+        assert_eq!(format!("{:>50}", flake), format!("{:>50}", flake.id()));
+
+        assert!(matches!(format!(" {}", string).parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!(format!("{} ", string).parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("".parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("-1".parse::<TestSnowflake>(), Err(error::Error::InvalidId)));
+    }
+
+    #[test]
+    fn radix_formatting() {
+        let flake = TestSnowflake::from_parts(1, 1, 1, 1, 1).unwrap();
+
+        assert_eq!(format!("{:x}", flake), "111101");
+        assert_eq!(format!("{:#X}", flake), "0x111101");
+        assert_eq!(format!("{:o}", flake), "4210401");
+        assert_eq!(format!("{:#o}", flake), "0o4210401");
+        assert_eq!(format!("{:b}", flake), "100010001000100000001");
+        assert_eq!(format!("{:#066b}", flake), "0b0000000000000000000000000000000000000000000100010001000100000001");
+        assert_eq!(format!("{:>#12x}", flake), "    0x111101");
+    }
+
+    #[test]
+    fn fmt_layout() {
+        let flake = TestSnowflake::from_parts(5, 2, 3, 6, 4).unwrap();
+        let expected = [
+            "0 | 0000000000000000000000000000000000000000101 | 0010 | 0011 | 0110 | 00000100",
+            "  | ts                                          | pid  | sid  | tid  | seq",
+            "  | 5                                           | 2    | 3    | 6    | 4",
+        ];
+
+        assert_eq!(flake.fmt_layout(), expected.join("\n"));
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_ext {
+        use super::*;
+
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        #[derive(Serialize, Deserialize)]
+        struct IdFlake {
+            id: TestSnowflake,
+        }
+
+        #[cfg(not(feature = "serde-human-string"))]
+        #[test]
+        fn to_int() {
+            let obj = IdFlake {
+                id: TestSnowflake::from_parts(1, 1, 1, 1, 1).unwrap(),
+            };
+
+            match serde_json::to_string(&obj) {
+                Ok(json_string) => {
+                    assert_eq!(
+                        json_string,
+                        String::from("{\"id\":1118465}"),
+                        "invalid json string"
+                    );
+                },
+                Err(err) => {
+                    panic!("failed to create json string. {:#?}", err);
+                }
+            }
+        }
+
+        #[test]
+        fn from_int() {
+            let json_str = "{\"id\":1118465}";
+
+            match serde_json::from_str::<IdFlake>(json_str) {
+                Ok(obj) => {
+                    assert_eq!(
+                        obj.id,
+                        TestSnowflake::from_parts(1, 1, 1, 1, 1).unwrap(),
+                        "invalid parsed id"
+                    );
+                },
+                Err(err) => {
+                    panic!("failed to parse json string. {:#?}", err);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! juniper GraphQL scalars for snowflakes
+//!
+//! the i64 snowflakes are exposed as a `SnowflakeID` scalar that is written
+//! as a decimal string since GraphQL integers are only 32 bits. input values
+//! can be either a string or an integer. all of the snowflake types use the
+//! same scalar name so a schema should only use one snowflake type.
+//!
+//! ```rust
+//! use juniper::{graphql_object, graphql_value, EmptyMutation, EmptySubscription, RootNode, Variables};
+//!
+//! type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! struct Query;
+//!
+//! #[graphql_object]
+//! impl Query {
+//!     fn next(id: MyFlake) -> MyFlake {
+//!         MyFlake::from_parts(id.timestamp(), id.primary_id(), id.sequence() + 1).unwrap()
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+//! let (value, _) = juniper::execute_sync(
+//!     r#"{ next(id: "1052673") }"#,
+//!     None,
+//!     &schema,
+//!     &Variables::new(),
+//!     &(),
+//! ).unwrap();
+//!
+//! assert_eq!(value, graphql_value!({ "next": "1052674" }));
+//! ```
+
+use juniper::{graphql_scalar, InputValue, ScalarValue, Value};
+
+use crate::i64::SegmentBits;
+
+/// the scalar impls are generated on the aliased types so the aliases
+/// themselves are never used
+#[allow(dead_code)]
+mod aliases {
+    use super::*;
+
+    #[graphql_scalar(name = "SnowflakeID", with = scalar, parse_token(String, i32))]
+    type SingleIdScalar<const TS: u8, const PID: u8, const SEQ: u8> = crate::i64::SingleIdFlake<TS, PID, SEQ>;
+
+    #[graphql_scalar(name = "SnowflakeID", with = scalar, parse_token(String, i32))]
+    type SingleIdSecScalar<const TS: u8, const PID: u8, const SEQ: u8> = crate::i64::SingleIdFlakeSec<TS, PID, SEQ>;
+
+    #[graphql_scalar(name = "SnowflakeID", with = scalar, parse_token(String, i32))]
+    type DualIdScalar<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> = crate::i64::DualIdFlake<TS, PID, SID, SEQ>;
+
+    #[graphql_scalar(name = "SnowflakeID", with = scalar, parse_token(String, i32))]
+    type TripleIdScalar<const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8> = crate::i64::TripleIdFlake<TS, PID, SID, TID, SEQ>;
+
+    #[graphql_scalar(name = "SnowflakeID", with = scalar, parse_token(String, i32), where(B: SegmentBits<N>))]
+    type NIdScalar<const TS: u8, const SEQ: u8, const N: usize, B> = crate::i64::NIdFlake<TS, SEQ, N, B>;
+}
+
+mod scalar {
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    use super::*;
+
+    pub(super) fn to_output<S, F>(flake: &F) -> Value<S>
+    where
+        S: ScalarValue,
+        F: Display,
+    {
+        Value::scalar(flake.to_string())
+    }
+
+    pub(super) fn from_input<S, F>(value: &InputValue<S>) -> Result<F, String>
+    where
+        S: ScalarValue,
+        F: FromStr,
+        F::Err: Display,
+    {
+        if let Some(string) = value.as_string_value() {
+            string.parse()
+                .map_err(|err| format!("invalid snowflake id \"{}\": {}", string, err))
+        } else if let Some(int) = value.as_int_value() {
+            int.to_string()
+                .parse()
+                .map_err(|err| format!("invalid snowflake id {}: {}", int, err))
+        } else {
+            Err(format!("expected a snowflake id as a string or integer, found: {}", value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use juniper::{
+        graphql_object,
+        graphql_value,
+        DefaultScalarValue,
+        EmptyMutation,
+        EmptySubscription,
+        FromInputValue,
+        InputValue,
+        RootNode,
+        ToInputValue,
+        Variables,
+    };
+
+    type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+    type I64DID = crate::i64::DualIdFlake<43, 4, 4, 12>;
+
+    type Input = InputValue<DefaultScalarValue>;
+
+    struct Record {
+        id: I64SID,
+        parent: Option<I64DID>,
+    }
+
+    #[graphql_object]
+    impl Record {
+        fn id(&self) -> I64SID {
+            self.id
+        }
+
+        fn parent(&self) -> Option<I64DID> {
+            self.parent
+        }
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn record(id: I64SID) -> Record {
+            Record {
+                id,
+                parent: I64DID::from_parts(id.timestamp(), 1, 1, 1).ok(),
+            }
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation, EmptySubscription>;
+
+    fn execute(query: &str, vars: Variables) -> Result<juniper::Value, String> {
+        let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+
+        match juniper::execute_sync(query, None, &schema, &vars, &()) {
+            Ok((value, errors)) if errors.is_empty() => Ok(value),
+            Ok((_, errors)) => Err(format!("{:?}", errors)),
+            Err(err) => Err(format!("{:?}", err)),
+        }
+    }
+
+    #[test]
+    fn schema() {
+        let id = I64SID::from_parts(1, 1, 1).unwrap();
+        let parent = I64DID::from_parts(1, 1, 1, 1).unwrap();
+        let expected = graphql_value!({
+            "record": {
+                "id": "1052673",
+                "parent": (parent.id().to_string()),
+            }
+        });
+
+        let value = execute(r#"{ record(id: "1052673") { id parent } }"#, Variables::new()).unwrap();
+        assert_eq!(value, expected);
+
+        let value = execute(r#"{ record(id: 1052673) { id parent } }"#, Variables::new()).unwrap();
+        assert_eq!(value, expected);
+
+        let mut vars = Variables::new();
+        vars.insert("id".to_owned(), id.to_input_value());
+
+        let value = execute(
+            r#"query Record($id: SnowflakeID!) { record(id: $id) { id parent } }"#,
+            vars
+        ).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn input_values() {
+        let id = I64SID::from_parts(1, 1, 1).unwrap();
+
+        let input: Input = id.to_input_value();
+
+        assert_eq!(input, Input::scalar("1052673".to_owned()));
+        assert_eq!(I64SID::from_input_value(&input).unwrap(), id);
+        assert_eq!(I64SID::from_input_value(&Input::scalar(1052673)).unwrap(), id);
+
+        let err = I64SID::from_input_value(&Input::scalar("abc".to_owned())).unwrap_err();
+        assert!(err.message().contains("\"abc\""), "{}", err.message());
+
+        let err = I64SID::from_input_value(&Input::scalar(-1)).unwrap_err();
+        assert!(err.message().contains("-1"), "{}", err.message());
+
+        let err = I64SID::from_input_value(&Input::scalar(true)).unwrap_err();
+        assert!(err.message().contains("true"), "{}", err.message());
+    }
+
+    #[test]
+    fn invalid_query() {
+        let err = execute(r#"{ record(id: "-5") { id } }"#, Variables::new()).unwrap_err();
+
+        assert!(err.contains("-5"), "{}", err);
+    }
+}
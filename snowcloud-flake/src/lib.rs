@@ -1,12 +1,388 @@
+use std::cmp::Ordering;
+use std::time::{Duration, SystemTime};
+
+use snowcloud_core::traits;
+
 pub mod error;
 
 #[cfg(feature = "serde")]
 pub mod serde_ext;
 #[cfg(feature = "postgres")]
 mod pg;
+#[cfg(feature = "time")]
+pub mod time_ext;
+#[cfg(feature = "uuid")]
+pub mod uuid_ext;
+#[cfg(feature = "schemars")]
+mod schemars_ext;
+#[cfg(feature = "sqlx")]
+pub mod sqlx_ext;
+#[cfg(feature = "mysql")]
+pub mod mysql_ext;
+#[cfg(feature = "sea-orm")]
+pub mod sea_orm_ext;
+#[cfg(feature = "scylla")]
+pub mod scylla_ext;
+#[cfg(feature = "bson")]
+pub mod bson_ext;
+#[cfg(feature = "juniper")]
+pub mod juniper_ext;
 
 mod segments;
 
 pub mod i64;
 pub mod u64;
+pub mod u32;
+pub mod i128;
+pub mod u128;
+pub mod presets;
+pub mod dynamic;
+pub mod migrate;
+pub mod opaque;
+pub mod shard;
+pub mod encoding;
 pub use segments::Segments;
+
+/// compares when two snowflakes were created when they use different epochs
+///
+/// the snowflakes do not have to be the same type. only the creation time is
+/// compared so snowflakes created in the same millisecond are equal.
+///
+/// ```rust
+/// use std::cmp::Ordering;
+///
+/// use snowcloud::flake::compare_across_epochs;
+///
+/// type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+///
+/// let a = MyFlake::from_parts(1000, 1, 1).unwrap();
+/// let b = MyFlake::from_parts(10, 1, 1).unwrap();
+///
+/// // b has the larger epoch so it was created after a
+/// let ord = compare_across_epochs(&a, 1679082337000, &b, 1679082338000).unwrap();
+///
+/// assert_eq!(ord, Ordering::Less);
+/// ```
+pub fn compare_across_epochs<A, B>(a: &A, epoch_a: u64, b: &B, epoch_b: u64) -> error::Result<Ordering>
+where
+    A: traits::UnixMillis<Error = error::Error>,
+    B: traits::UnixMillis<Error = error::Error>,
+{
+    Ok(a.unix_millis(epoch_a)?.cmp(&b.unix_millis(epoch_b)?))
+}
+
+/// converts a time range into the first and last tick of a snowflake
+///
+/// `start` is inclusive and `end` is exclusive. the ticks are clamped to 0
+/// and `max` with an error if nothing in the range can be represented
+pub(crate) fn tick_range<F>(epoch_millis: u64, start: SystemTime, end: SystemTime, max: u64) -> error::Result<(u64, u64)>
+where
+    F: traits::FromIdGenerator
+{
+    let Some(epoch) = SystemTime::UNIX_EPOCH.checked_add(Duration::from_millis(epoch_millis)) else {
+        return Err(error::Error::EpochInvalid);
+    };
+
+    if end <= start {
+        return Err(error::Error::EpochInvalid);
+    }
+
+    // anything that ends at or before the epoch has no ids
+    let end_dur = match end.duration_since(epoch) {
+        Ok(dur) if !dur.is_zero() => dur,
+        _ => return Err(error::Error::EpochInvalid),
+    };
+
+    let first = start.duration_since(epoch)
+        .map(|dur| F::ticks(&dur))
+        .unwrap_or(0);
+
+    if first > max {
+        return Err(error::Error::EpochInvalid);
+    }
+
+    // the tick of the last moment before the end
+    let last = F::ticks(&(end_dur - Duration::from_nanos(1)));
+
+    Ok((first, last.min(max)))
+}
+
+/// converts a bucket into the first and last tick that start inside of it
+///
+/// buckets are `bucket_millis` wide starting at the epoch. the last tick is
+/// clamped to `max` with an error if the bucket size is 0, the bucket starts
+/// after `max`, or no tick starts inside of the bucket
+pub(crate) fn bucket_ticks<F>(bucket: u64, bucket_millis: u64, max: u64) -> error::Result<(u64, u64)>
+where
+    F: traits::FromIdGenerator
+{
+    if bucket_millis == 0 {
+        return Err(error::Error::BucketInvalid);
+    }
+
+    // the first tick that starts at or after the given milliseconds
+    let tick_after = |millis: u64| if millis == 0 {
+        0
+    } else {
+        F::ticks(&(Duration::from_millis(millis) - Duration::from_nanos(1))) + 1
+    };
+
+    let start = bucket.checked_mul(bucket_millis)
+        .ok_or(error::Error::EpochInvalid)?;
+    let first = tick_after(start);
+
+    if first > max {
+        return Err(error::Error::EpochInvalid);
+    }
+
+    let last = match start.checked_add(bucket_millis) {
+        Some(end) => tick_after(end) - 1,
+        None => u64::MAX,
+    };
+
+    if last < first {
+        return Err(error::Error::BucketInvalid);
+    }
+
+    Ok((first, last.min(max)))
+}
+
+/// iterates the ticks of every multiple of `step` since the unix epoch
+/// between the given times
+///
+/// `start` is inclusive and `end` is exclusive. boundaries before the epoch
+/// are skipped and iteration stops once a tick is larger than `max`
+pub(crate) fn boundary_ticks<F>(epoch_millis: u64, start: SystemTime, end: SystemTime, step: Duration, max: u64) -> impl Iterator<Item = u64>
+where
+    F: traits::FromIdGenerator
+{
+    let since_unix = |time: SystemTime| {
+        time.duration_since(SystemTime::UNIX_EPOCH).unwrap_or(Duration::ZERO)
+    };
+
+    let epoch = Duration::from_millis(epoch_millis);
+    let start = since_unix(start).max(epoch);
+    let end = since_unix(end);
+    let step_nanos = step.as_nanos();
+
+    // first multiple of step that is not before the start
+    let first = start.as_nanos()
+        .checked_div(step_nanos)
+        .map(|count| if count * step_nanos < start.as_nanos() { count + 1 } else { count })
+        .and_then(|count| u64::try_from(count * step_nanos).ok())
+        .map(Duration::from_nanos);
+
+    std::iter::successors(first, move |boundary| boundary.checked_add(step))
+        .take_while(move |boundary| *boundary < end)
+        .map(move |boundary| F::ticks(&(boundary - epoch)))
+        .take_while(move |tick| *tick <= max)
+}
+
+/// checks the total bits of a layout against the bits available to it
+///
+/// with the `relaxed-layout` feature the layout only has to fit in the
+/// available bits otherwise it must use all of them
+pub(crate) const fn layout_fits(total: u32, max: u32) -> bool {
+    if cfg!(feature = "relaxed-layout") {
+        total <= max
+    } else {
+        total == max
+    }
+}
+
+/// renders the bits of an id split into the given parts along with the label
+/// and value of each part
+///
+/// parts are given from the highest bits to the lowest. bits of the base type
+/// that are not used by any part are shown first without a label
+pub(crate) fn layout_diagram(id: u128, base_bits: u32, parts: &[(&str, u8)]) -> String {
+    let used: u32 = parts.iter().map(|(_, bits)| *bits as u32).sum();
+    let mut columns = Vec::with_capacity(parts.len() + 1);
+    let mut shift = used;
+
+    if base_bits > used {
+        let bits = (base_bits - used) as usize;
+
+        columns.push([format!("{:0bits$b}", id >> used), String::new(), String::new()]);
+    }
+
+    for (label, bits) in parts {
+        shift -= *bits as u32;
+
+        let value = (id >> shift) & ((1 << *bits) - 1);
+        let bits = *bits as usize;
+
+        columns.push([format!("{:0bits$b}", value), label.to_string(), value.to_string()]);
+    }
+
+    let widths: Vec<usize> = columns.iter()
+        .map(|column| column.iter().map(String::len).max().unwrap_or(0))
+        .collect();
+
+    let lines: Vec<String> = (0..3)
+        .map(|row| {
+            let cells: Vec<String> = columns.iter()
+                .zip(&widths)
+                .map(|(column, width)| format!("{:width$}", column[row]))
+                .collect();
+
+            cells.join(" | ").trim_end().to_owned()
+        })
+        .collect();
+
+    lines.join("\n")
+}
+
+/// deterministic pseudo random bits for tests that need ids from across the
+/// full range of an integer
+#[cfg(test)]
+pub(crate) fn random_bits(count: usize) -> impl Iterator<Item = u128> {
+    let mut state: u64 = 17;
+    let mut next = move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        state
+    };
+
+    (0..count).map(move |_| (next() as u128) << 64 | next() as u128)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EPOCH_A: u64 = 1679082337000;
+    // a year after EPOCH_A
+    const EPOCH_B: u64 = EPOCH_A + 365 * 24 * 60 * 60 * 1000;
+
+    #[test]
+    fn unix_millis() {
+        let flake = i64::SingleIdFlake::<43, 8, 12>::from_parts(1000, 1, 1).unwrap();
+
+        assert_eq!(flake.unix_millis(EPOCH_A).unwrap(), EPOCH_A + 1000);
+        assert!(matches!(flake.unix_millis(u64::MAX), Err(error::Error::EpochInvalid)));
+
+        let flake = i64::SingleIdFlakeSec::<32, 27, 4>::from_parts(2, 1, 1).unwrap();
+
+        assert_eq!(flake.unix_millis(EPOCH_A).unwrap(), EPOCH_A + 2000);
+
+        let flake = u64::SingleIdFlakeMicros::<52, 4, 8>::from_parts(2999, 1, 1).unwrap();
+
+        assert_eq!(flake.unix_millis(EPOCH_A).unwrap(), EPOCH_A + 2);
+
+        let flake = u128::SingleIdFlake::<72, 52, 4>::from_parts(u64::MAX as u128 + 1, 1, 1).unwrap();
+
+        assert!(matches!(flake.unix_millis(0), Err(error::Error::EpochInvalid)));
+    }
+
+    #[test]
+    fn interleaved_epochs() {
+        type FlakeA = i64::SingleIdFlake<43, 8, 12>;
+        type FlakeB = u64::DualIdFlake<44, 4, 4, 12>;
+
+        let year = EPOCH_B - EPOCH_A;
+
+        // ids from both systems that were created in the same period of time
+        let a: Vec<FlakeA> = [year, year + 2, year + 4, year + 6]
+            .into_iter()
+            .map(|tsm| FlakeA::from_parts(tsm as i64, 1, 0).unwrap())
+            .collect();
+        let b: Vec<FlakeB> = [1, 3, 5]
+            .into_iter()
+            .map(|tsm| FlakeB::from_parts(tsm, 1, 1, 0).unwrap())
+            .collect();
+
+        let mut merged = Vec::new();
+        let mut a_iter = a.iter().peekable();
+        let mut b_iter = b.iter().peekable();
+
+        loop {
+            match (a_iter.peek(), b_iter.peek()) {
+                (Some(next_a), Some(next_b)) => {
+                    let ord = compare_across_epochs(*next_a, EPOCH_A, *next_b, EPOCH_B).unwrap();
+
+                    if ord == Ordering::Greater {
+                        merged.push(b_iter.next().unwrap().unix_millis(EPOCH_B).unwrap());
+                    } else {
+                        merged.push(a_iter.next().unwrap().unix_millis(EPOCH_A).unwrap());
+                    }
+                },
+                (Some(_), None) => merged.push(a_iter.next().unwrap().unix_millis(EPOCH_A).unwrap()),
+                (None, Some(_)) => merged.push(b_iter.next().unwrap().unix_millis(EPOCH_B).unwrap()),
+                (None, None) => break,
+            }
+        }
+
+        let expected: Vec<u64> = (0..7).map(|offset| EPOCH_B + offset).collect();
+
+        assert_eq!(merged, expected);
+
+        let same = FlakeB::from_parts(0, 1, 1, 0).unwrap();
+
+        assert_eq!(compare_across_epochs(&a[0], EPOCH_A, &same, EPOCH_B).unwrap(), Ordering::Equal);
+        assert!(compare_across_epochs(&a[0], u64::MAX, &same, EPOCH_B).is_err());
+    }
+
+    #[test]
+    fn flakes_are_copy() {
+        fn assert_copy<T: Copy>() {}
+
+        struct Bits;
+
+        impl i64::SegmentBits<2> for Bits {
+            const BITS: [u8; 2] = [4, 4];
+        }
+
+        assert_copy::<i64::SingleIdFlake<43, 8, 12>>();
+        assert_copy::<i64::DualIdFlake<43, 4, 4, 12>>();
+        assert_copy::<i64::TripleIdFlake<43, 3, 3, 2, 12>>();
+        assert_copy::<i64::NIdFlake<43, 12, 2, Bits>>();
+        assert_copy::<i64::SingleIdFlakeSec<32, 27, 4>>();
+        assert_copy::<u64::SingleIdFlake<44, 8, 12>>();
+        assert_copy::<u64::DualIdFlake<44, 4, 4, 12>>();
+        assert_copy::<u64::TripleIdFlake<44, 3, 3, 2, 12>>();
+        assert_copy::<u64::SingleIdFlakeMicros<52, 4, 8>>();
+        assert_copy::<u32::SingleIdFlake<20, 4, 8>>();
+        assert_copy::<i128::SingleIdFlake<64, 51, 12>>();
+        assert_copy::<i128::DualIdFlake<64, 26, 25, 12>>();
+        assert_copy::<u128::SingleIdFlake<64, 60, 4>>();
+        assert_copy::<u128::DualIdFlake<64, 30, 30, 4>>();
+        assert_copy::<presets::Sonyflake>();
+    }
+
+    #[test]
+    fn max_uses_all_bits() {
+        struct Bits;
+
+        impl i64::SegmentBits<3> for Bits {
+            const BITS: [u8; 3] = [3, 3, 2];
+        }
+
+        assert_eq!(i64::DualIdFlake::<43, 4, 4, 12>::MAX.id(), ::std::primitive::i64::MAX);
+        assert_eq!(i64::TripleIdFlake::<43, 3, 3, 2, 12>::MAX.id(), ::std::primitive::i64::MAX);
+        assert_eq!(i64::NIdFlake::<43, 12, 3, Bits>::MAX.id(), ::std::primitive::i64::MAX);
+        assert_eq!(i64::SingleIdFlakeSec::<32, 27, 4>::MAX.id(), ::std::primitive::i64::MAX);
+        assert_eq!(u64::DualIdFlake::<44, 4, 4, 12>::MAX.id(), ::std::primitive::u64::MAX);
+        assert_eq!(presets::Sonyflake::MAX.id(), ::std::primitive::i64::MAX as ::std::primitive::u64);
+
+        assert!(i64::DualIdFlake::<43, 4, 4, 12>::NIL.is_nil());
+        assert!(presets::Sonyflake::NIL.is_nil());
+    }
+
+    #[test]
+    fn full_layout_fits() {
+        assert!(layout_fits(63, 63));
+        assert!(!layout_fits(64, 63));
+    }
+
+    #[cfg(not(feature = "relaxed-layout"))]
+    #[test]
+    fn partial_layout_does_not_fit() {
+        assert!(!layout_fits(62, 63));
+    }
+
+    #[cfg(feature = "relaxed-layout")]
+    #[test]
+    fn partial_layout_fits() {
+        assert!(layout_fits(62, 63));
+    }
+}
@@ -0,0 +1,247 @@
+//! converting ids between layouts and epochs
+//!
+//! useful when moving from one snowflake layout to another and existing ids
+//! need to be encoded with the new layout. timestamps are expected to be in
+//! milliseconds for both layouts.
+//!
+//! ```rust
+//! use snowcloud::flake::dynamic::Layout;
+//! use snowcloud::flake::migrate::Converter;
+//!
+//! type OldFlake = snowcloud::flake::i64::SingleIdFlake<41, 10, 12>;
+//! type NewFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! let converter = Converter::new(
+//!     Layout::from_flake::<OldFlake>().unwrap(),
+//!     1288834974657,
+//!     Layout::from_flake::<NewFlake>().unwrap(),
+//!     1420070400000,
+//! );
+//!
+//! let old = OldFlake::from_parts(200000000000, 5, 10).unwrap();
+//! let new = NewFlake::try_from(&converter.convert(old.id()).unwrap()).unwrap();
+//!
+//! assert_eq!(new.timestamp(), 200000000000 + 1288834974657 - 1420070400000);
+//! assert_eq!(new.primary_id(), 5);
+//! assert_eq!(new.sequence(), 10);
+//! ```
+
+use crate::error;
+use crate::dynamic::{self, Layout};
+
+type IdMap = Box<dyn Fn(&[i64]) -> Option<Vec<i64>> + Send + Sync>;
+
+/// converts ids from a source layout and epoch to a target layout and epoch
+///
+/// the timestamp is moved to the target epoch, the id segments are copied or
+/// passed to the id map if one is provided, and the sequence is kept as is.
+/// conversion does not access the system clock.
+pub struct Converter {
+    source: Layout,
+    source_epoch: u64,
+    target: Layout,
+    target_epoch: u64,
+    id_map: Option<IdMap>,
+}
+
+impl Converter {
+    /// creates a new Converter. epochs are in milliseconds
+    pub fn new(source: Layout, source_epoch: u64, target: Layout, target_epoch: u64) -> Self {
+        Converter {
+            source,
+            source_epoch,
+            target,
+            target_epoch,
+            id_map: None,
+        }
+    }
+
+    /// sets the function used to map the id segments of the source layout to
+    /// the id segments of the target layout
+    ///
+    /// returning `None` will cause the conversion to fail with
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid)
+    pub fn with_id_map<F>(mut self, id_map: F) -> Self
+    where
+        F: Fn(&[i64]) -> Option<Vec<i64>> + Send + Sync + 'static
+    {
+        self.id_map = Some(Box::new(id_map));
+        self
+    }
+
+    /// returns the source layout
+    pub fn source(&self) -> &Layout {
+        &self.source
+    }
+
+    /// returns the target layout
+    pub fn target(&self) -> &Layout {
+        &self.target
+    }
+
+    /// converts the given id to the target layout
+    ///
+    /// returns [`InvalidId`](crate::error::Error::InvalidId) if the id is
+    /// negative,
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) if the timestamp
+    /// is before the target epoch or does not fit in the target layout, and
+    /// the same errors as [`encode`](crate::dynamic::encode) if any other part
+    /// does not fit
+    pub fn convert(&self, id: i64) -> error::Result<i64> {
+        if id < 0 {
+            return Err(error::Error::InvalidId);
+        }
+
+        let mut parts = dynamic::decode(id, &self.source);
+
+        let Some(unix) = self.source_epoch.checked_add(parts.timestamp as u64) else {
+            return Err(error::Error::EpochInvalid);
+        };
+        let Some(timestamp) = unix.checked_sub(self.target_epoch) else {
+            return Err(error::Error::EpochInvalid);
+        };
+        let Ok(timestamp) = i64::try_from(timestamp) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        parts.timestamp = timestamp;
+
+        if let Some(id_map) = &self.id_map {
+            let Some(segments) = id_map(&parts.segments) else {
+                return Err(error::Error::IdSegInvalid);
+            };
+
+            parts.segments = segments;
+        }
+
+        dynamic::encode(parts, &self.target)
+    }
+}
+
+impl std::fmt::Debug for Converter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Converter")
+            .field("source", &self.source)
+            .field("source_epoch", &self.source_epoch)
+            .field("target", &self.target)
+            .field("target_epoch", &self.target_epoch)
+            .field("id_map", &self.id_map.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::i64::SingleIdFlake;
+
+    type OldFlake = SingleIdFlake<41, 10, 12>;
+    type NewFlake = SingleIdFlake<43, 8, 12>;
+
+    const OLD_EPOCH: u64 = 1288834974657;
+    const NEW_EPOCH: u64 = 1420070400000;
+
+    fn forward() -> Converter {
+        Converter::new(
+            Layout::from_flake::<OldFlake>().unwrap(),
+            OLD_EPOCH,
+            Layout::from_flake::<NewFlake>().unwrap(),
+            NEW_EPOCH,
+        )
+    }
+
+    fn backward() -> Converter {
+        Converter::new(
+            Layout::from_flake::<NewFlake>().unwrap(),
+            NEW_EPOCH,
+            Layout::from_flake::<OldFlake>().unwrap(),
+            OLD_EPOCH,
+        )
+    }
+
+    #[test]
+    fn round_trip() {
+        let forward = forward();
+        let backward = backward();
+        let offset = (NEW_EPOCH - OLD_EPOCH) as i64;
+
+        let values = [
+            // first millisecond of the new epoch
+            (offset, 0, 0),
+            (offset + 1, 1, 1),
+            (200000000000, 200, 3000),
+            // last millisecond of the old layout
+            (OldFlake::MAX_TIMESTAMP, NewFlake::MAX_PRIMARY_ID, OldFlake::MAX_SEQUENCE),
+        ];
+
+        for (tsm, pid, seq) in values {
+            let old = OldFlake::from_parts(tsm, pid, seq).unwrap();
+            let new = NewFlake::try_from(&forward.convert(old.id()).unwrap()).unwrap();
+
+            assert_eq!(new.timestamp(), tsm - offset, "invalid timestamp for {:?}", old);
+            assert_eq!(new.primary_id(), pid, "invalid primary id for {:?}", old);
+            assert_eq!(new.sequence(), seq, "invalid sequence for {:?}", old);
+
+            assert_eq!(backward.convert(new.id()).unwrap(), old.id(), "failed round trip for {:?}", old);
+        }
+    }
+
+    #[test]
+    fn before_target_epoch() {
+        let offset = (NEW_EPOCH - OLD_EPOCH) as i64;
+        let old = OldFlake::from_parts(offset - 1, 1, 1).unwrap();
+
+        assert!(matches!(forward().convert(old.id()), Err(error::Error::EpochInvalid)));
+    }
+
+    #[test]
+    fn timestamp_overflow() {
+        let new = NewFlake::from_parts(NewFlake::MAX_TIMESTAMP, 1, 1).unwrap();
+
+        assert!(matches!(backward().convert(new.id()), Err(error::Error::EpochInvalid)));
+
+        let converter = Converter::new(
+            Layout::from_flake::<NewFlake>().unwrap(),
+            u64::MAX,
+            Layout::from_flake::<NewFlake>().unwrap(),
+            0,
+        );
+
+        assert!(matches!(converter.convert(new.id()), Err(error::Error::EpochInvalid)));
+    }
+
+    #[test]
+    fn primary_id_overflow() {
+        let old = OldFlake::from_parts(200000000000, 300, 1).unwrap();
+
+        assert!(matches!(forward().convert(old.id()), Err(error::Error::IdSegInvalid)));
+
+        let mapped = forward().with_id_map(|ids| Some(vec![ids[0] % 100]));
+        let new = NewFlake::try_from(&mapped.convert(old.id()).unwrap()).unwrap();
+
+        assert_eq!(new.primary_id(), 0);
+
+        let rejected = forward().with_id_map(|_| None);
+
+        assert!(matches!(rejected.convert(old.id()), Err(error::Error::IdSegInvalid)));
+    }
+
+    #[test]
+    fn sequence_overflow() {
+        let converter = Converter::new(
+            Layout::from_flake::<NewFlake>().unwrap(),
+            NEW_EPOCH,
+            Layout::new(45, vec![8], 10).unwrap(),
+            NEW_EPOCH,
+        );
+        let new = NewFlake::from_parts(1, 1, 1024).unwrap();
+
+        assert!(matches!(converter.convert(new.id()), Err(error::Error::SequenceInvalid)));
+    }
+
+    #[test]
+    fn negative_id() {
+        assert!(matches!(forward().convert(-1), Err(error::Error::InvalidId)));
+    }
+}
@@ -0,0 +1,214 @@
+//! mysql_common value conversions for snowflakes
+//!
+//! i64 snowflakes convert to `Value::Int` for `BIGINT` columns and u64
+//! snowflakes convert to `Value::UInt` for `BIGINT UNSIGNED` columns.
+//! [`ToValue`](mysql_common::value::convert::ToValue) is provided by
+//! mysql_common for anything that converts into a
+//! [`Value`](mysql_common::Value).
+//!
+//! reading a value goes through the conversion for the base type first so
+//! `Int`, `UInt`, and the `Bytes` returned by the text protocol are all
+//! accepted as long as the number fits in the base type and is a valid id
+//! for the layout. since the impls use
+//! [`FromValue`](mysql_common::value::convert::FromValue) snowflakes can
+//! also be read directly from rows or as part of a tuple.
+//!
+//! ```rust
+//! use mysql_common::Value;
+//! use mysql_common::value::convert::{from_value_opt, ToValue};
+//!
+//! type MyFlake = snowcloud::u64::SingleIdFlake<44, 8, 12>;
+//!
+//! let flake = MyFlake::from_parts(1, 1, 1).unwrap();
+//! let value = flake.to_value();
+//!
+//! assert_eq!(value, Value::UInt(flake.id()));
+//! assert_eq!(from_value_opt::<MyFlake>(value).unwrap(), flake);
+//! ```
+
+use mysql_common::Value;
+use mysql_common::value::convert::{FromValue, FromValueError, ParseIrOpt};
+
+use crate::i64::SegmentBits;
+
+/// intermediate result when converting a [`Value`] into a snowflake
+///
+/// the original value is kept so that it can be placed back into a row if
+/// another column fails to convert.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlakeIr<F> {
+    flake: F,
+    value: Value,
+}
+
+impl<F> From<FlakeIr<F>> for Value {
+    fn from(ir: FlakeIr<F>) -> Self {
+        ir.value
+    }
+}
+
+/// runs the conversion for the base type and then attempts to create the
+/// snowflake from it
+fn parse<T, F>(value: Value, to_flake: impl FnOnce(T) -> Option<F>) -> Result<FlakeIr<F>, FromValueError>
+where
+    T: FromValue<Intermediate = ParseIrOpt<T>> + Into<Value> + Copy,
+{
+    let ir = T::get_intermediate(value)?;
+    let int = match &ir {
+        ParseIrOpt::Ready(int) | ParseIrOpt::Parsed(int, _) => *int,
+    };
+    let value = ir.rollback();
+
+    match to_flake(int) {
+        Some(flake) => Ok(FlakeIr { flake, value }),
+        None => Err(FromValueError(value)),
+    }
+}
+
+macro_rules! mysql_impls {
+    ($base:ty, $variant:ident, [$($gen:tt)*] $flake:ty $(where $($bound:tt)*)?) => {
+        impl<$($gen)*> From<$flake> for Value $(where $($bound)*)? {
+            fn from(flake: $flake) -> Self {
+                Value::$variant(flake.id())
+            }
+        }
+
+        impl<$($gen)*> TryFrom<Value> for FlakeIr<$flake> $(where $($bound)*)? {
+            type Error = FromValueError;
+
+            fn try_from(value: Value) -> Result<Self, Self::Error> {
+                parse::<$base, _>(value, |int| <$flake>::try_from(&int).ok())
+            }
+        }
+
+        impl<$($gen)*> From<FlakeIr<$flake>> for $flake $(where $($bound)*)? {
+            fn from(ir: FlakeIr<$flake>) -> Self {
+                ir.flake
+            }
+        }
+
+        impl<$($gen)*> FromValue for $flake $(where $($bound)*)? {
+            type Intermediate = FlakeIr<$flake>;
+        }
+    };
+}
+
+mysql_impls!(i64, Int, [const TS: u8, const PID: u8, const SEQ: u8] crate::i64::SingleIdFlake<TS, PID, SEQ>);
+mysql_impls!(i64, Int, [const TS: u8, const PID: u8, const SEQ: u8] crate::i64::SingleIdFlakeSec<TS, PID, SEQ>);
+mysql_impls!(i64, Int, [const TS: u8, const PID: u8, const SID: u8, const SEQ: u8] crate::i64::DualIdFlake<TS, PID, SID, SEQ>);
+mysql_impls!(i64, Int, [const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8] crate::i64::TripleIdFlake<TS, PID, SID, TID, SEQ>);
+mysql_impls!(i64, Int, [const TS: u8, const SEQ: u8, const N: usize, B] crate::i64::NIdFlake<TS, SEQ, N, B> where B: SegmentBits<N>);
+
+mysql_impls!(u64, UInt, [const TS: u8, const PID: u8, const SEQ: u8] crate::u64::SingleIdFlake<TS, PID, SEQ>);
+mysql_impls!(u64, UInt, [const TS: u8, const PID: u8, const SEQ: u8] crate::u64::SingleIdFlakeMicros<TS, PID, SEQ>);
+mysql_impls!(u64, UInt, [const TS: u8, const PID: u8, const SID: u8, const SEQ: u8] crate::u64::DualIdFlake<TS, PID, SID, SEQ>);
+mysql_impls!(u64, UInt, [const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8] crate::u64::TripleIdFlake<TS, PID, SID, TID, SEQ>);
+mysql_impls!(u64, UInt, [] crate::presets::Sonyflake);
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use mysql_common::Value;
+    use mysql_common::constants::ColumnType;
+    use mysql_common::packets::Column;
+    use mysql_common::row::{new_row, convert::from_row_opt};
+    use mysql_common::value::convert::{from_value_opt, ToValue};
+
+    type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+    type I64DID = crate::i64::DualIdFlake<43, 4, 4, 12>;
+    type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+    type U64DID = crate::u64::DualIdFlake<44, 4, 4, 12>;
+
+    #[test]
+    fn to_value() {
+        let flake = I64SID::from_parts(1, 1, 1).unwrap();
+        assert_eq!(flake.to_value(), Value::Int(flake.id()));
+
+        let flake = I64DID::from_parts(1, 1, 1, 1).unwrap();
+        assert_eq!(flake.to_value(), Value::Int(flake.id()));
+
+        let flake = U64SID::MAX;
+        assert_eq!(flake.to_value(), Value::UInt(u64::MAX));
+
+        let flake = U64DID::from_parts(1, 1, 1, 1).unwrap();
+        assert_eq!(flake.to_value(), Value::UInt(flake.id()));
+    }
+
+    #[test]
+    fn from_value() {
+        let flake = I64SID::from_parts(1, 1, 1).unwrap();
+
+        assert_eq!(from_value_opt::<I64SID>(flake.to_value()).unwrap(), flake);
+        assert_eq!(from_value_opt::<I64SID>(Value::UInt(flake.id() as u64)).unwrap(), flake);
+        assert_eq!(
+            from_value_opt::<I64SID>(Value::Bytes(flake.id().to_string().into_bytes())).unwrap(),
+            flake
+        );
+
+        let flake = I64DID::MAX;
+        assert_eq!(from_value_opt::<I64DID>(flake.to_value()).unwrap(), flake);
+
+        let flake = U64SID::MAX;
+
+        assert_eq!(from_value_opt::<U64SID>(flake.to_value()).unwrap(), flake);
+        assert_eq!(
+            from_value_opt::<U64SID>(Value::Bytes(flake.id().to_string().into_bytes())).unwrap(),
+            flake
+        );
+
+        let flake = U64DID::from_parts(1, 1, 1, 1).unwrap();
+
+        assert_eq!(from_value_opt::<U64DID>(flake.to_value()).unwrap(), flake);
+        assert_eq!(from_value_opt::<U64DID>(Value::Int(flake.id() as i64)).unwrap(), flake);
+    }
+
+    #[test]
+    fn invalid() {
+        let checks = [
+            Value::Int(-1),
+            Value::UInt(u64::MAX),
+            Value::Bytes(b"-1".to_vec()),
+            Value::Bytes(b"not a number".to_vec()),
+            Value::NULL,
+            Value::Double(1.0),
+        ];
+
+        for value in checks.clone() {
+            let err = from_value_opt::<I64SID>(value.clone()).unwrap_err();
+
+            // the original value is returned on failure
+            assert_eq!(err.0, value);
+        }
+
+        for value in checks {
+            if matches!(value, Value::UInt(_)) {
+                continue;
+            }
+
+            let err = from_value_opt::<U64SID>(value.clone()).unwrap_err();
+
+            assert_eq!(err.0, value);
+        }
+
+    }
+
+    #[test]
+    fn rows() {
+        let columns: Arc<[Column]> = Arc::from(vec![
+            Column::new(ColumnType::MYSQL_TYPE_LONGLONG),
+            Column::new(ColumnType::MYSQL_TYPE_LONGLONG),
+        ]);
+        let record = (I64SID::from_parts(1, 1, 1).unwrap(), U64SID::MAX);
+
+        let row = new_row(vec![record.0.to_value(), record.1.to_value()], columns.clone());
+
+        assert_eq!(from_row_opt::<(I64SID, U64SID)>(row).unwrap(), record);
+
+        // a failed conversion puts the values back into the row
+        let row = new_row(vec![record.0.to_value(), Value::Int(-1)], columns);
+        let err = from_row_opt::<(I64SID, U64SID)>(row).unwrap_err();
+
+        assert_eq!(err.0.unwrap(), vec![record.0.to_value(), Value::Int(-1)]);
+    }
+}
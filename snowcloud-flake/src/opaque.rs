@@ -0,0 +1,179 @@
+//! reversible scrambling of ids
+//!
+//! sequential snowflakes show how many ids were created and when to anyone
+//! that can see two of them. a [`Permuter`] maps an id to another id that
+//! looks random and can only be mapped back with the same key. scrambled ids
+//! are still unique and non-negative ids stay non-negative so they fit in
+//! the same column as the original.
+//!
+//! this hides the order of ids from casual observation. it is not
+//! encryption and should not be used to protect sensitive data.
+//!
+//! ```rust
+//! use snowcloud::flake::opaque::Permuter;
+//!
+//! type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! let permuter = Permuter::new(0x5eed);
+//! let flake = MyFlake::from_parts(1000, 1, 1).unwrap();
+//!
+//! let opaque = flake.to_opaque(&permuter);
+//!
+//! assert_ne!(opaque, flake.id());
+//! assert_eq!(MyFlake::from_opaque(opaque, &permuter).unwrap(), flake);
+//! ```
+
+use std::fmt;
+
+/// total rounds of the feistel network
+const ROUNDS: usize = 8;
+
+/// splitmix64 finalizer
+#[inline]
+pub(crate) fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+#[inline]
+fn round(half: u32, key: u64) -> u32 {
+    (mix(half as u64 ^ key) >> 32) as u32
+}
+
+/// keyed permutation of the 63 bits available to an i64 id
+///
+/// uses a balanced feistel network over 64 bits and repeats it until the
+/// result fits in 63 bits. the sign bit is left as is so every i64 maps to
+/// exactly one other i64.
+#[derive(Clone)]
+pub struct Permuter {
+    keys: [u64; ROUNDS],
+}
+
+impl Permuter {
+    /// creates a permuter from the given key
+    pub fn new(key: u64) -> Self {
+        let mut state = key;
+        let keys = std::array::from_fn(|_| {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            mix(state)
+        });
+
+        Permuter { keys }
+    }
+
+    fn forward(&self, value: u64) -> u64 {
+        let mut left = (value >> 32) as u32;
+        let mut right = value as u32;
+
+        for key in self.keys {
+            (left, right) = (right, left ^ round(right, key));
+        }
+
+        (left as u64) << 32 | right as u64
+    }
+
+    fn backward(&self, value: u64) -> u64 {
+        let mut left = (value >> 32) as u32;
+        let mut right = value as u32;
+
+        for key in self.keys.iter().rev() {
+            (left, right) = (right ^ round(left, *key), left);
+        }
+
+        (left as u64) << 32 | right as u64
+    }
+
+    /// scrambles the given id
+    pub fn encode(&self, id: i64) -> i64 {
+        let mut value = (id & i64::MAX) as u64;
+
+        // the walk will come back to a value in range since the start of
+        // the cycle is in range
+        loop {
+            value = self.forward(value);
+
+            if value <= i64::MAX as u64 {
+                break;
+            }
+        }
+
+        value as i64 | (id & i64::MIN)
+    }
+
+    /// recovers the id given to [`encode`](Permuter::encode)
+    pub fn decode(&self, id: i64) -> i64 {
+        let mut value = (id & i64::MAX) as u64;
+
+        loop {
+            value = self.backward(value);
+
+            if value <= i64::MAX as u64 {
+                break;
+            }
+        }
+
+        value as i64 | (id & i64::MIN)
+    }
+}
+
+impl fmt::Debug for Permuter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Permuter").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn round_trip() {
+        let permuter = Permuter::new(42);
+
+        for id in (0..10_000).chain(i64::MAX - 10_000..=i64::MAX).chain(i64::MIN..i64::MIN + 10_000) {
+            let encoded = permuter.encode(id);
+
+            assert_eq!(encoded < 0, id < 0, "sign changed for {}", id);
+            assert_eq!(permuter.decode(encoded), id, "failed round trip for {}", id);
+        }
+    }
+
+    #[test]
+    fn no_collisions() {
+        let permuter = Permuter::new(42);
+        let total = 2_000_000;
+        let mut seen = HashSet::with_capacity(total);
+
+        for id in 0..total as i64 {
+            assert!(seen.insert(permuter.encode(id)), "collision for {}", id);
+        }
+    }
+
+    #[test]
+    fn different_keys() {
+        let a = Permuter::new(1);
+        let b = Permuter::new(2);
+
+        assert_ne!(a.encode(1000), b.encode(1000));
+        assert_ne!(b.decode(a.encode(1000)), 1000);
+    }
+
+    #[test]
+    fn stable_test_vector() {
+        let permuter = Permuter::new(0x5eed);
+        let expected = [
+            (0, 1043081055261389601),
+            (1, 878851511726215755),
+            (1679082337000, 3025972167435068296),
+            (i64::MAX, 7786024813063536293),
+        ];
+
+        for (id, opaque) in expected {
+            assert_eq!(permuter.encode(id), opaque, "permutation changed for {}", id);
+        }
+    }
+}
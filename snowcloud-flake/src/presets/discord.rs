@@ -0,0 +1,131 @@
+//! Discord snowflake layout
+//!
+//! 42 bit timestamp in milliseconds since [`EPOCH_MILLIS`], 5 bit internal
+//! worker id, 5 bit internal process id, and 12 bit increment.
+//!
+//! ```rust
+//! use snowcloud::presets::discord;
+//!
+//! // example id from the Discord api documentation
+//! let parts = discord::decode(175928847299117063);
+//!
+//! assert_eq!(parts.worker_id, 1);
+//! assert_eq!(parts.process_id, 0);
+//! assert_eq!(parts.increment, 7);
+//!
+//! println!("{:?}", discord::created_at(175928847299117063));
+//! ```
+
+use std::time::{Duration, SystemTime};
+
+use crate::u64::DualIdFlake;
+
+/// snowflake using the Discord layout. the primary id is the worker id and
+/// the secondary id is the process id
+///
+/// generators should be created with [`EPOCH_MILLIS`] as the epoch
+pub type DiscordFlake = DualIdFlake<42, 5, 5, 12>;
+
+/// 2015-01-01T00:00:00Z in milliseconds
+pub const EPOCH_MILLIS: u64 = 1420070400000;
+
+/// the individual parts of a Discord snowflake
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parts {
+    /// milliseconds since [`EPOCH_MILLIS`]
+    pub timestamp: u64,
+    pub worker_id: u64,
+    pub process_id: u64,
+    pub increment: u64,
+}
+
+/// splits an id into its parts
+pub fn decode(id: u64) -> Parts {
+    Parts {
+        timestamp: (id & DiscordFlake::TIMESTAMP_MASK) >> DiscordFlake::TIMESTAMP_SHIFT,
+        worker_id: (id & DiscordFlake::PRIMARY_ID_MASK) >> DiscordFlake::PRIMARY_ID_SHIFT,
+        process_id: (id & DiscordFlake::SECONDARY_ID_MASK) >> DiscordFlake::SECONDARY_ID_SHIFT,
+        increment: id & DiscordFlake::SEQUENCE_MASK,
+    }
+}
+
+/// returns when the id was created
+pub fn created_at(id: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_millis(EPOCH_MILLIS + decode(id).timestamp)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// (id, unix milliseconds, worker id, process id, increment)
+    const VECTORS: [(u64, u64, u64, u64, u64); 3] = [
+        // example from the Discord api documentation, 2016-04-30T11:18:25.796Z
+        (175928847299117063, 1462015105796, 1, 0, 7),
+        // message ids along with the `timestamp` of the message from the
+        // gateway payloads in serenity 0.5.14 `tests/resources`.
+        // message_footer_1.json, 2017-06-10T21:09:39.366Z
+        (323207107669393420, 1497128979366, 2, 0, 12),
+        // message_create_1.json, 2017-04-15T21:26:33.210Z
+        (302917639565475840, 1492291593210, 0, 0, 0),
+    ];
+
+    /// (id, unix milliseconds) for ids that were created shortly before the
+    /// given time
+    ///
+    /// from serenity 0.5.14 `tests/resources/guild_create_1.json`
+    const JOINED: [(u64, u64); 2] = [
+        // the "Discord API" guild and the `joined_at` of its owner,
+        // 2015-08-13T13:54:05.701Z
+        (81384788765712384, 1439474045701),
+        // a member and its `joined_at`, 2016-04-06T14:13:43.388Z
+        (167275652439998464, 1459952023388),
+    ];
+
+    #[test]
+    fn decode_vectors() {
+        for (id, millis, worker_id, process_id, increment) in VECTORS {
+            let parts = decode(id);
+
+            assert_eq!(parts, Parts {
+                timestamp: millis - EPOCH_MILLIS,
+                worker_id,
+                process_id,
+                increment,
+            }, "invalid parts for {}", id);
+            assert_eq!(
+                created_at(id),
+                SystemTime::UNIX_EPOCH + Duration::from_millis(millis),
+                "invalid created at for {}",
+                id
+            );
+
+            let flake = DiscordFlake::from_parts(parts.timestamp, worker_id, process_id, increment).unwrap();
+
+            assert_eq!(flake.id(), id, "invalid id from parts {:?}", flake);
+        }
+    }
+
+    #[test]
+    fn created_before_joined() {
+        for (id, millis) in JOINED {
+            let joined_at = SystemTime::UNIX_EPOCH + Duration::from_millis(millis);
+            let elapsed = joined_at.duration_since(created_at(id))
+                .unwrap_or_else(|_| panic!("{} created after {}", id, millis));
+
+            assert!(elapsed < Duration::from_secs(1), "{} created {:?} before {}", id, elapsed, millis);
+        }
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(decode(0), Parts { timestamp: 0, worker_id: 0, process_id: 0, increment: 0 });
+        assert_eq!(created_at(0), SystemTime::UNIX_EPOCH + Duration::from_millis(EPOCH_MILLIS));
+        assert_eq!(decode(u64::MAX), Parts {
+            timestamp: (1 << 42) - 1,
+            worker_id: 31,
+            process_id: 31,
+            increment: 4095,
+        });
+    }
+}
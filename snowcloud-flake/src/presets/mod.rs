@@ -0,0 +1,16 @@
+//! snowflakes that reproduce layouts used by other implementations
+//!
+//! ids created with these can be exchanged with the original implementation
+//! as long as the same epoch and machine ids are used
+
+mod sonyflake;
+#[cfg(feature = "uuid")]
+mod uuid7;
+pub mod twitter;
+pub mod discord;
+
+pub use sonyflake::Sonyflake;
+#[cfg(feature = "uuid")]
+pub use uuid7::Uuid7Flake;
+pub use twitter::TwitterFlake;
+pub use discord::DiscordFlake;
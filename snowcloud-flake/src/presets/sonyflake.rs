@@ -0,0 +1,1162 @@
+use std::hash::Hasher;
+use std::num::NonZeroU64;
+use std::ops::RangeInclusive;
+use std::time::{Duration, SystemTime};
+
+use snowcloud_core::traits;
+
+#[cfg(feature = "serde")]
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{de, ser};
+
+use crate::error;
+use crate::Segments;
+
+/// u64 Snowflake compatible with [Sonyflake](https://github.com/sony/sonyflake)
+///
+/// the layout matches the one used by the Go implementation with a 39 bit
+/// timestamp in units of 10 milliseconds, 8 bit sequence, and 16 bit machine
+/// id. unlike the other snowflakes the sequence comes before the machine id:
+///
+/// ```text
+///  0111111111111111111111111111111111111111 - 11111111 - 1111111111111111
+///  |                                      |   |      |   |              |
+/// 64                                     25  24     17  16              1
+///                                  timestamp         |                  |
+///                                             sequence                  |
+///                                                              machine id
+/// ```
+///
+/// the top bit is never set so any id can also be stored as an `i64`.
+///
+/// # Timestamp
+///
+/// timestamp is the number of 10 millisecond ticks since the epoch. the Go
+/// implementation uses [`EPOCH`](Sonyflake::EPOCH) by default which gives
+/// about 174 years of ids. generators will only reset the sequence once per
+/// tick and will estimate the time until the next tick once the max sequence
+/// has been reached.
+///
+/// # Sequence
+///
+/// the count of ids generated in the same tick. the Go implementation starts
+/// at 0 for each tick while the generators here start at 1 so a tick can hold
+/// one less id.
+///
+/// # Machine Id
+///
+/// any 16 bit value. the Go implementation defaults to the lower 16 bits of
+/// the private ip address of the machine.
+///
+/// # De/Serialize
+///
+/// with the `serde` feature you can de/serialize a snowflake to and from an
+/// [`u64`](core::primitive::u64)
+///
+/// # Example Usage
+///
+/// ```rust
+/// use snowcloud::presets::Sonyflake;
+///
+/// type MyCloud = snowcloud::Generator<Sonyflake>;
+///
+/// let mut cloud = MyCloud::new(Sonyflake::EPOCH, 0x0a01u64)
+///     .expect("failed to create MyCloud");
+/// let flake = cloud.next_id()
+///     .expect("failed to create snowflake");
+///
+/// println!("{}", flake.id());
+///
+/// // decode an id created by the Go implementation
+/// let flake = Sonyflake::try_from(&452234449204349441)
+///     .expect("invalid sonyflake id");
+///
+/// assert_eq!(flake.timestamp(), 26955273700);
+/// assert_eq!(flake.sequence(), 5);
+/// assert_eq!(flake.machine_id(), 0x0a01);
+/// ```
+#[derive(Eq, Clone, Copy)]
+pub struct Sonyflake {
+    pub(crate) dur: Option<Duration>,
+    pub(crate) tsm: u64,
+    pub(crate) seq: u64,
+    pub(crate) mid: u64,
+}
+
+impl Sonyflake {
+    /// default epoch of the Go implementation, 2014-09-01T00:00:00Z, in
+    /// milliseconds
+    pub const EPOCH: u64 = 1409529600000;
+
+    /// length of a single tick of the timestamp
+    pub const TICK: Duration = Duration::from_millis(10);
+
+    /// max value that a timestamp can be. `(1 << 39) - 1`
+    pub const MAX_TIMESTAMP: u64 = (1 << 39) - 1;
+    /// max value a sequence can be. `(1 << 8) - 1`
+    pub const MAX_SEQUENCE: u64 = (1 << 8) - 1;
+    /// max value that a machine id can be. `(1 << 16) - 1`
+    pub const MAX_MACHINE_ID: u64 = (1 << 16) - 1;
+
+    /// total bits to shift the timestamp. `8 + 16`
+    pub const TIMESTAMP_SHIFT: u64 = 8 + 16;
+    /// total bits to shift the sequence. `16`
+    pub const SEQUENCE_SHIFT: u64 = 16;
+
+    /// bit mask for timestamp. `Self::MAX_TIMESTAMP << Self::TIMESTAMP_SHIFT`
+    pub const TIMESTAMP_MASK: u64 = Self::MAX_TIMESTAMP << Self::TIMESTAMP_SHIFT;
+    /// bit mask for sequence. `Self::MAX_SEQUENCE << Self::SEQUENCE_SHIFT`
+    pub const SEQUENCE_MASK: u64 = Self::MAX_SEQUENCE << Self::SEQUENCE_SHIFT;
+    /// bit mask for machine id. `Self::MAX_MACHINE_ID`
+    pub const MACHINE_ID_MASK: u64 = Self::MAX_MACHINE_ID;
+
+    /// snowflake with every part set to 0
+    pub const NIL: Self = Self { dur: None, tsm: 0, seq: 0, mid: 0 };
+    /// smallest possible snowflake. same as `Self::NIL`
+    pub const MIN: Self = Self::NIL;
+    /// largest possible snowflake with every part set to its max value
+    pub const MAX: Self = Self {
+        dur: None,
+        tsm: Self::MAX_TIMESTAMP,
+        seq: Self::MAX_SEQUENCE,
+        mid: Self::MAX_MACHINE_ID,
+    };
+
+    /// returns the time since the epoch that the snowflake was generated at
+    ///
+    /// only snowflakes from a generator will have this. anything created
+    /// from parts or an integer will be `None` and only the 10 millisecond
+    /// precision of the timestamp is available
+    pub fn duration(&self) -> Option<&Duration> {
+        self.dur.as_ref()
+    }
+
+    /// returns timestamp in ticks of 10 milliseconds
+    ///
+    /// this is the `time` value given by `sonyflake.Decompose`
+    pub fn timestamp(&self) -> u64 {
+        self.tsm
+    }
+
+    /// returns the unix timestamp in milliseconds of when the snowflake was
+    /// created using the given epoch in milliseconds. usually this will be
+    /// [`EPOCH`](Sonyflake::EPOCH)
+    ///
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the result does not fit in a u64
+    pub fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        self.tsm.checked_mul(10)
+            .and_then(|tsm| epoch.checked_add(tsm))
+            .ok_or(error::Error::EpochInvalid)
+    }
+
+    /// returns sequence
+    pub fn sequence(&self) -> u64 {
+        self.seq
+    }
+
+    /// returns machine id
+    pub fn machine_id(&self) -> u64 {
+        self.mid
+    }
+
+    /// extracts the timestamp from an id without any validation
+    #[inline]
+    pub const fn timestamp_of(id: u64) -> u64 {
+        (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT
+    }
+
+    /// extracts the sequence from an id without any validation
+    #[inline]
+    pub const fn sequence_of(id: u64) -> u64 {
+        (id & Self::SEQUENCE_MASK) >> Self::SEQUENCE_SHIFT
+    }
+
+    /// extracts the machine id from an id without any validation
+    #[inline]
+    pub const fn machine_id_of(id: u64) -> u64 {
+        id & Self::MACHINE_ID_MASK
+    }
+
+    /// returns the time since the epoch that the id was created at
+    ///
+    /// same as `sonyflake.ElapsedTime` from the Go implementation
+    pub fn elapsed_time(&self) -> Duration {
+        Duration::from_millis(self.tsm * 10)
+    }
+
+    /// generates a Snowflake from the provided parts
+    ///
+    /// checks will be performed on each part to ensure that they are
+    /// valid for the given Snowflake.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the machine id is invalid
+    pub fn from_parts(tsm: u64, seq: u64, mid: u64) -> error::Result<Self> {
+        if tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        if seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        if mid > Self::MAX_MACHINE_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        Ok(Self { dur: None, tsm, seq, mid })
+    }
+
+    /// generates a Snowflake from the timestamp, id segments, and sequence
+    ///
+    /// the ids can be anything that converts into the same segments given to
+    /// a generator like a single value, a tuple, or
+    /// [`Segments`](crate::Segments). the same checks as
+    /// [`from_parts`](Self::from_parts) will be performed
+    pub fn from_segments<I>(tsm: u64, ids: I, seq: u64) -> error::Result<Self>
+    where
+        I: Into<Segments<u64, 1>>
+    {
+        let ids = ids.into();
+
+        Self::from_parts(tsm, seq, ids[0])
+    }
+
+    /// generates the smallest Snowflake for the given timestamp
+    ///
+    /// every other part is set to 0. useful as the lower bound when
+    /// searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn first_for_timestamp(tsm: u64) -> error::Result<Self> {
+        if tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::NIL })
+    }
+
+    /// generates the largest Snowflake for the given timestamp
+    ///
+    /// every other part is set to its max value. useful as the upper bound
+    /// when searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn last_for_timestamp(tsm: u64) -> error::Result<Self> {
+        if tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::MAX })
+    }
+
+    /// returns the range of ids that were created between the given times
+    /// using the epoch in milliseconds
+    ///
+    /// `start` is inclusive and `end` is exclusive. the returned range is
+    /// inclusive so it can be used directly with something like
+    /// `WHERE id BETWEEN $1 AND $2`. a `start` before the epoch or an `end`
+    /// after the max timestamp will be clamped to what the snowflake can
+    /// represent. [`EpochInvalid`](crate::error::Error::EpochInvalid) will be
+    /// returned if none of the time range can be represented
+    pub fn id_range_for(epoch_millis: u64, start: SystemTime, end: SystemTime) -> error::Result<RangeInclusive<u64>> {
+        let (first, last) = crate::tick_range::<Self>(epoch_millis, start, end, Self::MAX_TIMESTAMP)?;
+
+        Ok(Self::first_for_timestamp(first)?.id()..=Self::last_for_timestamp(last)?.id())
+    }
+
+    /// returns the first id of every boundary between the given times using
+    /// the epoch in milliseconds
+    ///
+    /// boundaries are multiples of `step` since the unix epoch so a step of
+    /// one day will give the first id of each day in UTC. `start` is
+    /// inclusive and `end` is exclusive. boundaries that are before the epoch
+    /// or after the max timestamp are skipped and a `step` of 0 will not
+    /// produce any ids. the ids are calculated as the iterator is consumed
+    pub fn id_partition_boundaries(epoch_millis: u64, start: SystemTime, end: SystemTime, step: Duration) -> impl Iterator<Item = u64> {
+        crate::boundary_ticks::<Self>(epoch_millis, start, end, step, Self::MAX_TIMESTAMP)
+            .filter_map(|tick| Self::first_for_timestamp(tick).ok())
+            .map(|flake| flake.id())
+    }
+
+    /// splits the current Snowflake into its individual parts
+    ///
+    /// the parts are in the same order as the bits of the id
+    pub fn into_parts(self) -> (u64, u64, u64) {
+        (self.tsm, self.seq, self.mid)
+    }
+
+    /// generates the unique id
+    pub fn id(&self) -> u64 {
+        (self.tsm << Self::TIMESTAMP_SHIFT) | (self.seq << Self::SEQUENCE_SHIFT) | self.mid
+    }
+
+    /// renders the bits of the id split at each part with the name and
+    /// value of each part underneath
+    ///
+    /// useful for checking that a layout places the parts where they are
+    /// expected to be
+    pub fn fmt_layout(&self) -> String {
+        crate::layout_diagram(u128::from(self.id()), 64, &[("ts", 39), ("seq", 8), ("mid", 16)])
+    }
+
+    /// encodes the id as base58
+    ///
+    /// see [`encoding::base58`](crate::encoding::base58) for more information
+    pub fn to_base58(&self) -> String {
+        crate::encoding::base58::encode(self)
+    }
+
+    /// decodes a base58 string created by [`to_base58`](Self::to_base58)
+    pub fn from_base58(string: &str) -> error::Result<Self> {
+        crate::encoding::base58::decode(string)
+    }
+
+    /// encodes the id as base62
+    ///
+    /// see [`encoding::base62`](crate::encoding::base62) for more information
+    pub fn to_base62(&self) -> String {
+        crate::encoding::base62::encode(self)
+    }
+
+    /// decodes a base62 string created by [`to_base62`](Self::to_base62)
+    pub fn from_base62(string: &str) -> error::Result<Self> {
+        crate::encoding::base62::decode(string)
+    }
+
+    /// encodes the id as fixed width crockford base32
+    ///
+    /// see [`encoding::crockford`](crate::encoding::crockford) for more
+    /// information
+    pub fn to_crockford(&self) -> String {
+        crate::encoding::crockford::encode_fixed(self)
+    }
+
+    /// decodes a crockford base32 string created by
+    /// [`to_crockford`](Self::to_crockford)
+    pub fn from_crockford(string: &str) -> error::Result<Self> {
+        crate::encoding::crockford::decode(string)
+    }
+
+    /// encodes the big endian bytes of the id as unpadded base64url
+    ///
+    /// see [`encoding::base64url`](crate::encoding::base64url) for more
+    /// information
+    pub fn to_base64url(&self) -> String {
+        crate::encoding::base64url::encode(self)
+    }
+
+    /// decodes a base64url string created by
+    /// [`to_base64url`](Self::to_base64url)
+    pub fn from_base64url(string: &str) -> error::Result<Self> {
+        crate::encoding::base64url::decode(string)
+    }
+
+    /// prints the id in the given radix using lowercase letters and no
+    /// prefix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn to_string_radix(&self, radix: u32) -> String {
+        crate::encoding::to_string_radix(self.id() as u128, radix)
+    }
+
+    /// parses a snowflake from an id in the given radix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn from_str_radix(string: &str, radix: u32) -> error::Result<Self> {
+        let id = u64::from_str_radix(string, radix).map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+
+    /// parses a snowflake from an id that may start with a `0x`, `0o`, or
+    /// `0b` prefix
+    ///
+    /// strings without a prefix are parsed as decimal
+    pub fn parse_prefixed(string: &str) -> error::Result<Self> {
+        Self::try_from(&crate::encoding::parse_prefixed::<u64>(string)?)
+    }
+
+    /// returns the id if it is not zero
+    ///
+    /// only [`NIL`](Self::NIL) will return `None`
+    pub fn id_nonzero(&self) -> Option<NonZeroU64> {
+        NonZeroU64::new(self.id())
+    }
+
+    /// checks if every part of the snowflake is 0
+    pub fn is_nil(&self) -> bool {
+        self == &Self::NIL
+    }
+
+    /// returns the snowflake that comes after this one
+    ///
+    /// same as adding 1 to the id so the sequence will carry over into the
+    /// id segments and timestamp. `None` will be returned if this is
+    /// [`MAX`](Self::MAX)
+    pub fn successor(&self) -> Option<Self> {
+        if *self == Self::MAX {
+            return None;
+        }
+
+        Self::try_from(&(self.id() + 1)).ok()
+    }
+
+    /// returns the snowflake that comes before this one
+    ///
+    /// same as subtracting 1 from the id. `None` will be returned if this is
+    /// [`MIN`](Self::MIN)
+    pub fn predecessor(&self) -> Option<Self> {
+        if *self == Self::MIN {
+            return None;
+        }
+
+        Self::try_from(&(self.id() - 1)).ok()
+    }
+
+    /// returns the snowflake with the machine id replaced
+    ///
+    /// the duration is kept as is.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the machine id is invalid
+    pub fn with_machine_id(self, mid: u64) -> error::Result<Self> {
+        if mid > Self::MAX_MACHINE_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        Ok(Self { mid, ..self })
+    }
+
+    /// returns the snowflake with the sequence replaced
+    ///
+    /// the duration is kept as is.
+    /// [`SequenceInvalid`](crate::error::Error::SequenceInvalid) will be returned if
+    /// the sequence is invalid
+    pub fn with_sequence(self, seq: u64) -> error::Result<Self> {
+        if seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        Ok(Self { seq, ..self })
+    }
+
+    /// returns a copy of the snowflake with the sequence set to 0
+    ///
+    /// snowflakes created in the same tick will be equal which hides the
+    /// order they were created in. the machine id will also be set to 0 if
+    /// `clear_ids` is true. the result is only meant for exporting and is
+    /// not checked against the generator rules for machine id
+    pub fn anonymize(&self, clear_ids: bool) -> Self {
+        if clear_ids {
+            Self { tsm: self.tsm, ..Self::NIL }
+        } else {
+            Self { tsm: self.tsm, mid: self.mid, ..Self::NIL }
+        }
+    }
+
+    /// returns the milliseconds since the epoch rounded down to the start of
+    /// the given bucket
+    ///
+    /// a bucket shorter than a millisecond will return the milliseconds
+    /// unchanged
+    pub fn bucket_millis(&self, bucket: Duration) -> u64 {
+        let millis = self.tsm.saturating_mul(10);
+        let bucket = u64::try_from(bucket.as_millis()).unwrap_or(u64::MAX);
+
+        if bucket == 0 {
+            millis
+        } else {
+            millis - millis % bucket
+        }
+    }
+
+    /// returns the shard that the id belongs to out of the given number of
+    /// shards
+    ///
+    /// the full id is hashed before picking the shard so ids spread evenly
+    /// even when most of them have a small sequence. the result is stable
+    /// across versions, see [`shard`](crate::shard) for the algorithm. panics
+    /// if `shards` is 0
+    pub fn shard(&self, shards: u32) -> u32 {
+        crate::shard::shard_u64(self.id(), shards)
+    }
+
+    /// returns the shard of the machine id out of the given number of shards
+    ///
+    /// the machine id is used as is without hashing so every id with the same
+    /// machine id will be in the same shard. panics if `shards` is 0
+    pub fn shard_by_machine(&self, shards: u32) -> u32 {
+        crate::shard::shard_segment(self.mid as u128, shards)
+    }
+
+    /// attempts to generated a snowflake from the given u64
+    ///
+    /// the top bit of the integer must not be set
+    pub fn try_from(id: &u64) -> error::Result<Self> {
+        if *id >> 63 != 0 {
+            return Err(error::Error::InvalidId);
+        }
+
+        Ok(Self {
+            dur: None,
+            tsm: (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT,
+            seq: (id & Self::SEQUENCE_MASK) >> Self::SEQUENCE_SHIFT,
+            mid: id & Self::MACHINE_ID_MASK,
+        })
+    }
+
+    /// returns the id as big endian bytes
+    ///
+    /// the bytes will sort in the same order as the ids which makes them
+    /// useful as keys in ordered byte stores
+    pub fn to_be_bytes(&self) -> [u8; 8] {
+        self.id().to_be_bytes()
+    }
+
+    /// returns the id as little endian bytes
+    pub fn to_le_bytes(&self) -> [u8; 8] {
+        self.id().to_le_bytes()
+    }
+
+    /// creates a snowflake from the big endian bytes of an id
+    pub fn from_be_bytes(bytes: [u8; 8]) -> error::Result<Self> {
+        Self::try_from(&u64::from_be_bytes(bytes))
+    }
+
+    /// writes the big endian bytes of the id to the given writer
+    pub fn write_be(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&self.to_be_bytes())
+    }
+}
+
+impl traits::Id for Sonyflake {
+    type BaseType = u64;
+
+    fn id(&self) -> Self::BaseType {
+        Sonyflake::id(self)
+    }
+}
+
+impl traits::UnixMillis for Sonyflake {
+    type Error = error::Error;
+
+    fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        Sonyflake::unix_millis(self, epoch)
+    }
+}
+
+impl From<Sonyflake> for u64 {
+    #[inline(always)]
+    fn from(flake: Sonyflake) -> u64 {
+        flake.id()
+    }
+}
+
+impl From<&Sonyflake> for u64 {
+    #[inline(always)]
+    fn from(flake: &Sonyflake) -> u64 {
+        flake.id()
+    }
+}
+
+impl TryFrom<u64> for Sonyflake {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: u64) -> Result<Self, Self::Error> {
+        Sonyflake::try_from(&id)
+    }
+}
+
+impl TryFrom<&u64> for Sonyflake {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: &u64) -> Result<Self, Self::Error> {
+        Sonyflake::try_from(id)
+    }
+}
+
+impl TryFrom<Sonyflake> for NonZeroU64 {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(flake: Sonyflake) -> Result<Self, Self::Error> {
+        flake.id_nonzero().ok_or(error::Error::InvalidId)
+    }
+}
+
+impl TryFrom<NonZeroU64> for Sonyflake {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: NonZeroU64) -> Result<Self, Self::Error> {
+        Self::try_from(&id.get())
+    }
+}
+
+impl std::cmp::PartialEq for Sonyflake {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.tsm == rhs.tsm && self.seq == rhs.seq && self.mid == rhs.mid
+    }
+}
+
+impl std::cmp::PartialEq<u64> for Sonyflake {
+    fn eq(&self, rhs: &u64) -> bool {
+        self.id() == *rhs
+    }
+}
+
+impl std::cmp::PartialOrd for Sonyflake {
+    fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl std::cmp::Ord for Sonyflake {
+    /// compares the ids of both snowflakes which orders them by timestamp
+    /// first followed by the id segments and then the sequence
+    fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
+        self.id().cmp(&rhs.id())
+    }
+}
+
+impl std::hash::Hash for Sonyflake {
+    /// hashes the id of the snowflake so the hash will be the same as the
+    /// hash of the u64 it is stored as
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
+impl std::fmt::Debug for Sonyflake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = self.id();
+
+        f.debug_struct("Sonyflake")
+            .field("id", &id)
+            .field("dur", &self.dur)
+            .field("tsm", &self.tsm)
+            .field("seq", &self.seq)
+            .field("mid", &self.mid)
+            .finish()
+    }
+}
+
+impl std::fmt::Display for Sonyflake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.id(), f)
+    }
+}
+
+impl std::fmt::LowerHex for Sonyflake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&self.id(), f)
+    }
+}
+
+impl std::fmt::UpperHex for Sonyflake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::UpperHex::fmt(&self.id(), f)
+    }
+}
+
+impl std::fmt::Binary for Sonyflake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Binary::fmt(&self.id(), f)
+    }
+}
+
+impl std::fmt::Octal for Sonyflake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Octal::fmt(&self.id(), f)
+    }
+}
+
+impl std::str::FromStr for Sonyflake {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.parse::<u64>().map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl Sonyflake {
+    /// places the id into a version 8 uuid
+    ///
+    /// see [`uuid_ext`](crate::uuid_ext) for the layout of the uuid
+    pub fn to_uuid(&self) -> uuid::Uuid {
+        crate::uuid_ext::from_id(self.id())
+    }
+
+    /// creates a snowflake from a uuid created by [`to_uuid`](Self::to_uuid)
+    ///
+    /// the uuid must be version 8 with only the bits used by the id set
+    pub fn from_uuid(uuid: uuid::Uuid) -> error::Result<Self> {
+        let id = crate::uuid_ext::to_id(&uuid).ok_or(error::Error::InvalidId)?;
+
+        Self::try_from(&id)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<Sonyflake> for uuid::Uuid {
+    fn from(flake: Sonyflake) -> Self {
+        flake.to_uuid()
+    }
+}
+
+impl traits::FromIdGenerator for Sonyflake {
+    type IdSegType = Segments<u64, 1>;
+    type Builder = Builder;
+
+    const LAYOUT_BITS: u32 = 39 + 8 + 16;
+    const MAX_LAYOUT_BITS: u32 = 63;
+
+    fn valid_id(v: &Self::IdSegType) -> bool {
+        *v.primary() <= Self::MAX_MACHINE_ID
+    }
+
+    fn valid_epoch(e: &u64) -> bool {
+        *e / 10 <= Self::MAX_TIMESTAMP
+    }
+
+    fn builder(ids: &Self::IdSegType) -> Self::Builder {
+        Builder {
+            dur: Duration::new(0,0),
+            ts: 0,
+            seq: 0,
+            mid: *ids.primary(),
+        }
+    }
+
+    fn ticks(dur: &Duration) -> u64 {
+        (dur.as_millis() / 10) as u64
+    }
+
+    fn next_tick(dur: &Duration) -> Duration {
+        Duration::from_nanos((10_000_000 - (dur.subsec_nanos() % 10_000_000)) as u64)
+    }
+}
+
+impl traits::FlakeParts for Sonyflake {
+    fn id_segs(&self) -> Self::IdSegType {
+        Segments::from([self.mid])
+    }
+
+    fn timestamp_ticks(&self) -> u64 {
+        self.tsm
+    }
+}
+
+pub struct Builder {
+    dur: Duration,
+    ts: u64,
+    seq: u64,
+    mid: u64,
+}
+
+impl traits::IdBuilder for Builder {
+    type Output = Sonyflake;
+
+    fn with_ts(&mut self, ts: u64) -> bool {
+        if ts > Sonyflake::MAX_TIMESTAMP {
+            false
+        } else {
+            self.ts = ts;
+            true
+        }
+    }
+
+    fn with_seq(&mut self, seq: u64) -> bool {
+        if seq > Sonyflake::MAX_SEQUENCE {
+            false
+        } else {
+            self.seq = seq;
+            true
+        }
+    }
+
+    fn with_dur(&mut self, dur: Duration) {
+        self.dur = dur;
+    }
+
+    fn build(self) -> Self::Output {
+        Sonyflake {
+            dur: Some(self.dur),
+            tsm: self.ts,
+            seq: self.seq,
+            mid: self.mid,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ser::Serialize for Sonyflake {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer
+    {
+        let id = self.id();
+
+        #[cfg(feature = "serde-human-string")]
+        if serializer.is_human_readable() {
+            return serializer.collect_str(&id);
+        }
+
+        serializer.serialize_u64(id)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct NumVisitor {}
+
+#[cfg(feature = "serde")]
+impl<'de> de::Visitor<'de> for NumVisitor {
+    type Value = Sonyflake;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "integer from 0 to i64::MAX")
+    }
+
+    fn visit_i64<E>(self, i: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        if i < 0 {
+            return Err(E::invalid_value(de::Unexpected::Signed(i), &self));
+        }
+
+        let Ok(flake) = Sonyflake::try_from(&(i as u64)) else {
+            return Err(E::invalid_value(de::Unexpected::Signed(i), &self));
+        };
+
+        Ok(flake)
+    }
+
+    fn visit_f64<E>(self, f: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        self.visit_i64(crate::serde_ext::float_to_int(f)?)
+    }
+
+    fn visit_u64<E>(self, u: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(flake) = Sonyflake::try_from(&u) else {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(u), &self));
+        };
+
+        Ok(flake)
+    }
+
+    #[cfg(feature = "serde-human-string")]
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(flake) = s.parse() else {
+            return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+        };
+
+        Ok(flake)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::Deserialize<'de> for Sonyflake {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        #[cfg(feature = "serde-human-string")]
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(NumVisitor {});
+        }
+
+        deserializer.deserialize_u64(NumVisitor {})
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unusual_byte_groupings)]
+mod test {
+    use super::*;
+
+    use traits::FromIdGenerator;
+
+    /// (id, time, sequence, machine id) worked out by hand from the bit layout
+    /// documented by `sony/sonyflake`. these are not ids created by the Go
+    /// library so they only check that the parts end up in the documented
+    /// bits
+    const KNOWN_IDS: [(u64, u64, u64, u64); 5] = [
+        (16777217, 1, 0, 1),
+        (16842753, 1, 1, 1),
+        (452234449204021761, 26955273700, 0, 0x0a01),
+        (452234449204349441, 26955273700, 5, 0x0a01),
+        (483135502434697215, 28797120000, 255, 0xffff),
+    ];
+
+    #[test]
+    fn from_segments() {
+        assert_eq!(
+            Sonyflake::from_segments(1, 0x0a01, 5).unwrap(),
+            Sonyflake::from_parts(1, 5, 0x0a01).unwrap()
+        );
+    }
+
+    #[test]
+    fn with_modifiers() {
+        let flake = Sonyflake::from_parts(1, 1, 1).unwrap()
+            .with_machine_id(0x0a01).unwrap()
+            .with_sequence(5).unwrap();
+
+        assert_eq!(flake.into_parts(), (1, 5, 0x0a01));
+
+        assert!(matches!(flake.with_machine_id(Sonyflake::MAX_MACHINE_ID + 1), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(flake.with_sequence(Sonyflake::MAX_SEQUENCE + 1), Err(error::Error::SequenceInvalid)));
+    }
+
+    #[test]
+    fn properly_calculated_consts() {
+        let max_timestamp: u64 = 0b111111111111111111111111111111111111111;
+        let max_sequence: u64 = 0b11111111;
+        let max_machine_id: u64 = 0b1111111111111111;
+
+        let timestamp_shift: u64 = 8 + 16;
+        let sequence_shift: u64 = 16;
+
+        let timestamp_mask: u64 =  0b0_111111111111111111111111111111111111111_00000000_0000000000000000;
+        let sequence_mask: u64 =   0b0_000000000000000000000000000000000000000_11111111_0000000000000000;
+        let machine_id_mask: u64 = 0b0_000000000000000000000000000000000000000_00000000_1111111111111111;
+
+        assert_eq!(Sonyflake::MAX_TIMESTAMP, max_timestamp, "invalid max timestamp");
+        assert_eq!(Sonyflake::MAX_SEQUENCE, max_sequence, "invalid max sequence");
+        assert_eq!(Sonyflake::MAX_MACHINE_ID, max_machine_id, "invalid max machine id");
+
+        assert_eq!(Sonyflake::TIMESTAMP_SHIFT, timestamp_shift, "invalid timestamp shift");
+        assert_eq!(Sonyflake::SEQUENCE_SHIFT, sequence_shift, "invalid sequence shift");
+
+        assert_eq!(Sonyflake::TIMESTAMP_MASK, timestamp_mask, "invalid timestamp mask");
+        assert_eq!(Sonyflake::SEQUENCE_MASK, sequence_mask, "invalid sequence mask");
+        assert_eq!(Sonyflake::MACHINE_ID_MASK, machine_id_mask, "invalid machine id mask");
+    }
+
+    #[test]
+    fn known_ids() {
+        for (id, time, seq, mid) in KNOWN_IDS {
+            let flake = Sonyflake::try_from(&id).unwrap();
+
+            assert_eq!(flake.timestamp(), time, "invalid time for {}", id);
+            assert_eq!(flake.sequence(), seq, "invalid sequence for {}", id);
+            assert_eq!(flake.machine_id(), mid, "invalid machine id for {}", id);
+            assert_eq!(flake.elapsed_time(), Duration::from_millis(time * 10), "invalid elapsed time for {}", id);
+
+            let from_parts = Sonyflake::from_parts(time, seq, mid).unwrap();
+
+            assert_eq!(from_parts.id(), id, "invalid id from parts {:?}", from_parts);
+        }
+    }
+
+    #[test]
+    fn top_bit_is_invalid() {
+        assert!(Sonyflake::try_from(&(1 << 63)).is_err());
+    }
+
+    #[test]
+    fn ticks_in_ten_millis() {
+        // 2023-03-17T19:45:37.009Z from the epoch
+        let dur = Duration::from_millis(1679082337009 - Sonyflake::EPOCH);
+
+        assert_eq!(Sonyflake::ticks(&dur), 26955273700);
+        assert_eq!(Sonyflake::next_tick(&dur), Duration::from_millis(1));
+        assert_eq!(Sonyflake::next_tick(&Duration::from_millis(20)), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn extract_helpers() {
+        for bits in crate::random_bits(1000) {
+            let id = bits as u64;
+            let Ok(flake) = Sonyflake::try_from(&id) else {
+                continue;
+            };
+
+            assert_eq!(Sonyflake::timestamp_of(id), flake.timestamp());
+            assert_eq!(Sonyflake::sequence_of(id), flake.sequence());
+            assert_eq!(Sonyflake::machine_id_of(id), flake.machine_id());
+        }
+    }
+
+    #[test]
+    fn be_bytes() {
+        let flakes = [
+            Sonyflake::NIL,
+            Sonyflake::try_from(&1).unwrap(),
+            Sonyflake::try_from(&256).unwrap(),
+            Sonyflake::MAX,
+        ];
+
+        for flake in flakes {
+            let mut written = Vec::new();
+            flake.write_be(&mut written).unwrap();
+
+            assert_eq!(Sonyflake::from_be_bytes(flake.to_be_bytes()).unwrap(), flake);
+            assert_eq!(flake.to_le_bytes(), flake.id().to_le_bytes());
+            assert_eq!(written, flake.to_be_bytes());
+        }
+
+        assert!(flakes.windows(2).all(|pair| pair[0].to_be_bytes() < pair[1].to_be_bytes()));
+    }
+
+    #[test]
+    fn nonzero() {
+        assert_eq!(Sonyflake::NIL.id_nonzero(), None);
+        assert!(matches!(NonZeroU64::try_from(Sonyflake::NIL), Err(error::Error::InvalidId)));
+
+        let flake = Sonyflake::MAX;
+        let id = flake.id_nonzero().unwrap();
+
+        assert_eq!(id.get(), flake.id());
+        assert_eq!(NonZeroU64::try_from(flake).unwrap(), id);
+
+        let back: Sonyflake = id.try_into().unwrap();
+
+        assert_eq!(back, flake);
+    }
+
+    #[test]
+    fn anonymize() {
+        let first = Sonyflake::MAX.with_sequence(1).unwrap();
+
+        assert_ne!(first, Sonyflake::MAX);
+        assert_eq!(first.anonymize(false), Sonyflake::MAX.anonymize(false));
+        assert_eq!(first.anonymize(false).sequence(), 0);
+        assert_eq!(
+            Sonyflake::first_for_timestamp(500).unwrap().anonymize(true),
+            Sonyflake::last_for_timestamp(500).unwrap().anonymize(true)
+        );
+    }
+
+    #[test]
+    fn bucket_millis() {
+        let bucket = Duration::from_secs(10);
+        let first = Sonyflake::first_for_timestamp(2500).unwrap();
+        let last = Sonyflake::last_for_timestamp(2900).unwrap();
+        let next = Sonyflake::first_for_timestamp(3500).unwrap();
+
+        assert_eq!(first.bucket_millis(bucket), 20_000);
+        assert_eq!(first.bucket_millis(bucket), last.bucket_millis(bucket));
+        assert_ne!(first.bucket_millis(bucket), next.bucket_millis(bucket));
+        assert_eq!(first.bucket_millis(Duration::ZERO), 25_000);
+    }
+
+    #[test]
+    fn shard() {
+        let first = Sonyflake::first_for_timestamp(5000).unwrap();
+        let next = first.with_sequence(1).unwrap();
+
+        assert_eq!(first.shard(16), crate::shard::shard_u64(first.id(), 16));
+        assert!(next.shard(7) < 7);
+        assert_eq!(first.shard_by_machine(4), next.shard_by_machine(4));
+        assert_eq!(Sonyflake::MAX.shard_by_machine(3), (Sonyflake::MAX.machine_id() % 3) as u32);
+    }
+
+    #[test]
+    fn display_and_from_str() {
+        let flake = Sonyflake::MAX.with_sequence(1).unwrap();
+        let string = flake.to_string();
+
+        assert_eq!(string, flake.id().to_string());
+        assert_eq!(string.parse::<Sonyflake>().unwrap(), flake);
+        assert_eq!(format!("{:>50}", flake), format!("{:>50}", flake.id()));
+
+        assert!(matches!(format!(" {}", string).parse::<Sonyflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!(format!("{} ", string).parse::<Sonyflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("".parse::<Sonyflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("-1".parse::<Sonyflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!(format!("{}0", u64::MAX).parse::<Sonyflake>(), Err(error::Error::ParseInt(_))));
+    }
+
+    #[test]
+    fn radix_formatting() {
+        let flake = Sonyflake::from_parts(1, 1, 1).unwrap();
+
+        assert_eq!(format!("{:x}", flake), "1010001");
+        assert_eq!(format!("{:#X}", flake), "0x1010001");
+        assert_eq!(format!("{:o}", flake), "100200001");
+        assert_eq!(format!("{:#o}", flake), "0o100200001");
+        assert_eq!(format!("{:b}", flake), "1000000010000000000000001");
+        assert_eq!(format!("{:#066b}", flake), "0b0000000000000000000000000000000000000001000000010000000000000001");
+        assert_eq!(format!("{:>#12x}", flake), "   0x1010001");
+    }
+
+    #[test]
+    fn fmt_layout() {
+        let flake = Sonyflake::from_parts(5, 4, 2).unwrap();
+        let expected = [
+            "0 | 000000000000000000000000000000000000101 | 00000100 | 0000000000000010",
+            "  | ts                                      | seq      | mid",
+            "  | 5                                       | 4        | 2",
+        ];
+
+        assert_eq!(flake.fmt_layout(), expected.join("\n"));
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_ext {
+        use super::*;
+
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        #[derive(Serialize, Deserialize)]
+        struct IdFlake {
+            id: Sonyflake,
+        }
+
+        #[cfg(not(feature = "serde-human-string"))]
+        #[test]
+        fn to_int() {
+            let obj = IdFlake {
+                id: Sonyflake::from_parts(1, 1, 1).unwrap(),
+            };
+
+            match serde_json::to_string(&obj) {
+                Ok(json_string) => {
+                    assert_eq!(
+                        json_string,
+                        String::from("{\"id\":16842753}"),
+                        "invalid json string"
+                    );
+                },
+                Err(err) => {
+                    panic!("failed to create json string. {:#?}", err);
+                }
+            }
+        }
+
+        #[test]
+        fn from_int() {
+            let json_str = "{\"id\":16842753}";
+
+            match serde_json::from_str::<IdFlake>(json_str) {
+                Ok(obj) => {
+                    assert_eq!(
+                        obj.id,
+                        Sonyflake::from_parts(1, 1, 1).unwrap(),
+                        "invalid parsed id"
+                    );
+                },
+                Err(err) => {
+                    panic!("failed to parse json string. {:#?}", err);
+                }
+            }
+        }
+    }
+}
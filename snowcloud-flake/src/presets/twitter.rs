@@ -0,0 +1,122 @@
+//! Twitter snowflake layout
+//!
+//! 41 bit timestamp in milliseconds since [`EPOCH_MILLIS`], 5 bit datacenter
+//! id, 5 bit worker id, and 12 bit sequence.
+//!
+//! ```rust
+//! use snowcloud::presets::twitter;
+//!
+//! // example tweet from the Twitter api v2 documentation
+//! let parts = twitter::decode(1212092628029698048);
+//!
+//! assert_eq!(parts.datacenter_id, 10);
+//! assert_eq!(parts.worker_id, 7);
+//! assert_eq!(parts.sequence, 0);
+//!
+//! println!("{:?}", twitter::created_at(1212092628029698048));
+//! ```
+
+use std::time::{Duration, SystemTime};
+
+use crate::i64::DualIdFlake;
+
+/// snowflake using the Twitter layout. the primary id is the datacenter id
+/// and the secondary id is the worker id. the top bit is never used so this is
+/// an `i64` snowflake
+///
+/// generators should be created with [`EPOCH_MILLIS`] as the epoch
+pub type TwitterFlake = DualIdFlake<41, 5, 5, 12>;
+
+/// 2010-11-04T01:42:54.657Z in milliseconds
+pub const EPOCH_MILLIS: u64 = 1288834974657;
+
+/// the individual parts of a Twitter snowflake
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parts {
+    /// milliseconds since [`EPOCH_MILLIS`]
+    pub timestamp: u64,
+    pub datacenter_id: u64,
+    pub worker_id: u64,
+    pub sequence: u64,
+}
+
+/// splits an id into its parts
+///
+/// the top bit of the id is ignored
+pub fn decode(id: u64) -> Parts {
+    let id = id as i64;
+
+    Parts {
+        timestamp: ((id & TwitterFlake::TIMESTAMP_MASK) >> TwitterFlake::TIMESTAMP_SHIFT) as u64,
+        datacenter_id: ((id & TwitterFlake::PRIMARY_ID_MASK) >> TwitterFlake::PRIMARY_ID_SHIFT) as u64,
+        worker_id: ((id & TwitterFlake::SECONDARY_ID_MASK) >> TwitterFlake::SECONDARY_ID_SHIFT) as u64,
+        sequence: (id & TwitterFlake::SEQUENCE_MASK) as u64,
+    }
+}
+
+/// returns when the id was created
+pub fn created_at(id: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_millis(EPOCH_MILLIS + decode(id).timestamp)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// (id, unix seconds, datacenter id, worker id, sequence)
+    ///
+    /// the `created_at` of a tweet only has seconds so the milliseconds of the
+    /// id are not checked against it
+    const VECTORS: [(u64, u64, u64, u64, u64); 4] = [
+        // example tweet from the Twitter api v2 documentation,
+        // 2019-12-31T19:26:16Z
+        (1212092628029698048, 1577820376, 10, 7, 0),
+        // tweets from the api responses in egg-mode 0.16.1 `sample_payloads`.
+        // sample-quote.json, Mon Oct 03 19:09:46 +0000 2016
+        (783021240093118465, 1475521786, 11, 1, 1),
+        // nullable_user_mention.json, Tue Jan 30 12:31:23 +0000 2018
+        (958316712847273984, 1517315483, 10, 15, 0),
+        // sample-image-alt-text.json, Sat Jan 05 15:57:43 +0000 2019
+        (1081580512102268928, 1546703863, 11, 20, 0),
+    ];
+
+    #[test]
+    fn decode_vectors() {
+        for (id, secs, datacenter_id, worker_id, sequence) in VECTORS {
+            let parts = decode(id);
+
+            assert_eq!(
+                (parts.datacenter_id, parts.worker_id, parts.sequence),
+                (datacenter_id, worker_id, sequence),
+                "invalid parts for {}",
+                id
+            );
+
+            let created = created_at(id).duration_since(SystemTime::UNIX_EPOCH).unwrap();
+
+            assert_eq!(created.as_secs(), secs, "invalid created at for {}", id);
+            assert_eq!(created.as_millis() as u64 - EPOCH_MILLIS, parts.timestamp, "invalid timestamp for {}", id);
+
+            let flake = TwitterFlake::from_parts(
+                parts.timestamp as i64,
+                datacenter_id as i64,
+                worker_id as i64,
+                sequence as i64
+            ).unwrap();
+
+            assert_eq!(flake.id() as u64, id, "invalid id from parts {:?}", flake);
+        }
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(decode(0), Parts { timestamp: 0, datacenter_id: 0, worker_id: 0, sequence: 0 });
+        assert_eq!(created_at(0), SystemTime::UNIX_EPOCH + Duration::from_millis(EPOCH_MILLIS));
+        assert_eq!(decode(i64::MAX as u64), Parts {
+            timestamp: (1 << 41) - 1,
+            datacenter_id: 31,
+            worker_id: 31,
+            sequence: 4095,
+        });
+    }
+}
@@ -0,0 +1,320 @@
+use std::time::Duration;
+
+use snowcloud_core::traits;
+
+use crate::error;
+
+/// version 7 uuid created by a generator
+///
+/// a UUIDv7 is a 48 bit unix timestamp in milliseconds followed by random
+/// bits. the 12 bits of `rand_a` are used as the sequence so the generators
+/// will create ids that are increasing within the same millisecond and the
+/// remaining 62 bits of `rand_b` are random:
+///
+/// ```text
+///  111111111111111111111111111111111111111111111111 - 0111 - 111111111111 - 10 - 11111...
+///  |                                              |   |  |   |          |   ||   |
+/// 128                                            81  80 77  76         65  64   62
+///                                        unix_ts_ms      |              |    |   |
+///                                                  version              |    |   |
+///                                                                sequence    |   |
+///                                                                      variant   |
+///                                                                           random
+/// ```
+///
+/// # Timestamp
+///
+/// the timestamp is always from the unix epoch so the generator must be
+/// given an epoch of 0.
+///
+/// # Sequence
+///
+/// the count of ids generated in the same millisecond starting at 1. ids
+/// created by the same generator are ordered by timestamp and then sequence
+/// so they are monotonic even within a millisecond.
+///
+/// # Random
+///
+/// 62 bits are filled from [`rand`] when the id is built.
+///
+/// # Example Usage
+///
+/// ```rust
+/// use snowcloud::presets::Uuid7Flake;
+///
+/// type MyCloud = snowcloud::Generator<Uuid7Flake>;
+///
+/// let mut cloud = MyCloud::new(0, ())
+///     .expect("failed to create MyCloud");
+/// let uuid: uuid::Uuid = cloud.next_id()
+///     .expect("failed to create uuid")
+///     .into();
+///
+/// assert_eq!(uuid.get_version_num(), 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Uuid7Flake {
+    tsm: u64,
+    seq: u64,
+    rand: u64,
+}
+
+impl Uuid7Flake {
+    /// max value that the timestamp can be
+    pub const MAX_TIMESTAMP: u64 = (1 << 48) - 1;
+
+    /// max value that the sequence can be
+    pub const MAX_SEQUENCE: u64 = (1 << 12) - 1;
+
+    /// max value of the random bits
+    pub const MAX_RANDOM: u64 = (1 << 62) - 1;
+
+    /// the version value of the uuid
+    const VERSION: u128 = 7;
+
+    /// the variant value of the uuid
+    const VARIANT: u128 = 0b10;
+
+    /// creates a flake from the given parts
+    ///
+    /// returns an error if any of the parts are too large
+    pub fn from_parts(tsm: u64, seq: u64, rand: u64) -> error::Result<Self> {
+        if tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        if seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        if rand > Self::MAX_RANDOM {
+            return Err(error::Error::InvalidId);
+        }
+
+        Ok(Uuid7Flake { tsm, seq, rand })
+    }
+
+    /// returns the unix timestamp in milliseconds
+    pub fn timestamp(&self) -> u64 {
+        self.tsm
+    }
+
+    /// returns the sequence stored in `rand_a`
+    pub fn sequence(&self) -> u64 {
+        self.seq
+    }
+
+    /// returns the random bits stored in `rand_b`
+    pub fn random(&self) -> u64 {
+        self.rand
+    }
+
+    /// returns the 128 bits of the uuid
+    pub fn id(&self) -> u128 {
+        (self.tsm as u128) << 80
+            | Self::VERSION << 76
+            | (self.seq as u128) << 64
+            | Self::VARIANT << 62
+            | self.rand as u128
+    }
+
+    /// returns the id as a uuid
+    pub fn to_uuid(&self) -> uuid::Uuid {
+        uuid::Uuid::from_u128(self.id())
+    }
+
+    /// creates a flake from a version 7 uuid
+    ///
+    /// any version 7 uuid with the RFC 9562 variant is accepted since every
+    /// bit of `rand_a` is a valid sequence
+    pub fn from_uuid(uuid: uuid::Uuid) -> error::Result<Self> {
+        Self::try_from(&uuid.as_u128())
+    }
+
+    /// attempts to create a flake from the given u128
+    ///
+    /// the version and variant bits must be set
+    pub fn try_from(id: &u128) -> error::Result<Self> {
+        if (id >> 76) & 0xf != Self::VERSION || (id >> 62) & 0b11 != Self::VARIANT {
+            return Err(error::Error::InvalidId);
+        }
+
+        Ok(Uuid7Flake {
+            tsm: (id >> 80) as u64,
+            seq: ((id >> 64) & 0xfff) as u64,
+            rand: (*id as u64) & Self::MAX_RANDOM,
+        })
+    }
+}
+
+impl traits::Id for Uuid7Flake {
+    type BaseType = u128;
+
+    fn id(&self) -> Self::BaseType {
+        Uuid7Flake::id(self)
+    }
+}
+
+impl TryFrom<u128> for Uuid7Flake {
+    type Error = error::Error;
+
+    fn try_from(id: u128) -> Result<Self, Self::Error> {
+        Uuid7Flake::try_from(&id)
+    }
+}
+
+impl From<Uuid7Flake> for uuid::Uuid {
+    fn from(flake: Uuid7Flake) -> Self {
+        flake.to_uuid()
+    }
+}
+
+impl TryFrom<uuid::Uuid> for Uuid7Flake {
+    type Error = error::Error;
+
+    fn try_from(uuid: uuid::Uuid) -> Result<Self, Self::Error> {
+        Uuid7Flake::from_uuid(uuid)
+    }
+}
+
+impl traits::FromIdGenerator for Uuid7Flake {
+    type IdSegType = ();
+    type Builder = Builder;
+
+    const LAYOUT_BITS: u32 = 48 + 12 + 62;
+    // the version and variant use the other 6 bits
+    const MAX_LAYOUT_BITS: u32 = 122;
+
+    fn valid_id(_: &Self::IdSegType) -> bool {
+        true
+    }
+
+    fn valid_epoch(e: &u64) -> bool {
+        *e == 0
+    }
+
+    fn builder(_: &Self::IdSegType) -> Self::Builder {
+        Builder {
+            ts: 0,
+            seq: 0,
+        }
+    }
+}
+
+impl traits::FlakeParts for Uuid7Flake {
+    fn id_segs(&self) -> Self::IdSegType {}
+
+    fn timestamp_ticks(&self) -> u64 {
+        self.tsm
+    }
+}
+
+pub struct Builder {
+    ts: u64,
+    seq: u64,
+}
+
+impl traits::IdBuilder for Builder {
+    type Output = Uuid7Flake;
+
+    fn with_ts(&mut self, ts: u64) -> bool {
+        if ts > Uuid7Flake::MAX_TIMESTAMP {
+            false
+        } else {
+            self.ts = ts;
+            true
+        }
+    }
+
+    fn with_seq(&mut self, seq: u64) -> bool {
+        if seq > Uuid7Flake::MAX_SEQUENCE {
+            false
+        } else {
+            self.seq = seq;
+            true
+        }
+    }
+
+    fn with_dur(&mut self, _dur: Duration) {}
+
+    fn build(self) -> Self::Output {
+        Uuid7Flake {
+            tsm: self.ts,
+            seq: self.seq,
+            rand: rand::random::<u64>() & Uuid7Flake::MAX_RANDOM,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use traits::{FromIdGenerator, IdBuilder};
+
+    #[test]
+    fn version_and_variant() {
+        let flake = Uuid7Flake::from_parts(1679082337000, 1, 0).unwrap();
+        let uuid = flake.to_uuid();
+
+        assert_eq!(uuid.to_string(), "0186f11b-32e8-7001-8000-000000000000");
+        assert_eq!(uuid.get_version_num(), 7);
+        assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+
+        let max = Uuid7Flake::from_parts(Uuid7Flake::MAX_TIMESTAMP, Uuid7Flake::MAX_SEQUENCE, Uuid7Flake::MAX_RANDOM).unwrap();
+
+        assert_eq!(max.to_uuid().to_string(), "ffffffff-ffff-7fff-bfff-ffffffffffff");
+        assert_eq!(Uuid7Flake::from_uuid(max.to_uuid()).unwrap(), max);
+        assert_eq!(Uuid7Flake::from_uuid(uuid).unwrap(), flake);
+    }
+
+    #[test]
+    fn invalid() {
+        let invalid = [
+            // version 4
+            "0186f11b-32e8-4001-8000-000000000000",
+            // version 8
+            "0186f11b-32e8-8001-8000-000000000000",
+            // microsoft variant
+            "0186f11b-32e8-7001-c000-000000000000",
+            "00000000-0000-0000-0000-000000000000",
+        ];
+
+        for string in invalid {
+            let uuid = uuid::Uuid::parse_str(string).unwrap();
+
+            assert!(matches!(Uuid7Flake::from_uuid(uuid), Err(error::Error::InvalidId)), "accepted {}", string);
+        }
+
+        assert!(matches!(Uuid7Flake::from_parts(1 << 48, 0, 0), Err(error::Error::EpochInvalid)));
+        assert!(matches!(Uuid7Flake::from_parts(0, 1 << 12, 0), Err(error::Error::SequenceInvalid)));
+        assert!(matches!(Uuid7Flake::from_parts(0, 0, 1 << 62), Err(error::Error::InvalidId)));
+    }
+
+    #[test]
+    fn builder() {
+        assert!(Uuid7Flake::valid_layout());
+        assert!(Uuid7Flake::valid_epoch(&0));
+        assert!(!Uuid7Flake::valid_epoch(&1679082337000));
+
+        let mut builder = Uuid7Flake::builder(&());
+
+        assert!(!builder.with_ts(Uuid7Flake::MAX_TIMESTAMP + 1));
+        assert!(!builder.with_seq(Uuid7Flake::MAX_SEQUENCE + 1));
+        assert!(builder.with_ts(1679082337000));
+        assert!(builder.with_seq(5));
+
+        let first = builder.build();
+
+        assert_eq!(first.timestamp(), 1679082337000);
+        assert_eq!(first.sequence(), 5);
+        assert!(first.random() <= Uuid7Flake::MAX_RANDOM);
+
+        let mut builder = Uuid7Flake::builder(&());
+        builder.with_ts(1679082337000);
+        builder.with_seq(5);
+
+        // the random bits are filled for every id
+        assert_ne!(builder.build().random(), first.random());
+    }
+}
@@ -0,0 +1,141 @@
+//! json schemas for snowflakes
+//!
+//! snowflakes are described as integers from 0 to the largest id allowed by
+//! the layout. with the `serde-human-string` feature they are described as
+//! strings of decimal digits instead to match what is written to json.
+//! schema numbers are floats so the maximum of a large layout is rounded.
+//!
+//! ```rust
+//! type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! let schema = schemars::schema_for!(MyFlake);
+//! let json = serde_json::to_value(&schema).unwrap();
+//!
+//! # #[cfg(not(feature = "serde-human-string"))]
+//! assert_eq!(json["maximum"], MyFlake::MAX.id() as f64);
+//! ```
+
+use schemars::schema::{Schema, SchemaObject, InstanceType};
+#[cfg(not(feature = "serde-human-string"))]
+use schemars::schema::NumberValidation;
+#[cfg(feature = "serde-human-string")]
+use schemars::schema::StringValidation;
+
+/// creates the schema for an id from 0 to the given max
+#[cfg(not(feature = "serde-human-string"))]
+pub(crate) fn id_schema(max: u64, format: &str) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::Integer.into()),
+        format: Some(format.to_owned()),
+        number: Some(Box::new(NumberValidation {
+            minimum: Some(0.0),
+            maximum: Some(max as f64),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }.into()
+}
+
+/// creates the schema for a decimal string of an id from 0 to the given max
+#[cfg(feature = "serde-human-string")]
+pub(crate) fn id_schema(max: u64, _format: &str) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        string: Some(Box::new(StringValidation {
+            pattern: Some(format!("^[0-9]{{1,{}}}$", max.to_string().len())),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }.into()
+}
+
+#[cfg(test)]
+mod test {
+    use schemars::schema_for;
+
+    use crate::Segments;
+
+    type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+    type I64DID = crate::i64::DualIdFlake<43, 4, 4, 12>;
+    type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+    type U64DID = crate::u64::DualIdFlake<44, 4, 4, 12>;
+
+    fn schema_json<T: schemars::JsonSchema>() -> serde_json::Value {
+        serde_json::to_value(schema_for!(T)).unwrap()
+    }
+
+    #[cfg(not(feature = "serde-human-string"))]
+    #[test]
+    fn integer_bounds() {
+        let json = schema_json::<I64SID>();
+        let max = I64SID::MAX_TIMESTAMP << I64SID::TIMESTAMP_SHIFT
+            | I64SID::MAX_PRIMARY_ID << I64SID::PRIMARY_ID_SHIFT
+            | I64SID::MAX_SEQUENCE;
+
+        assert_eq!(json["type"], "integer");
+        assert_eq!(json["format"], "int64");
+        assert_eq!(json["minimum"], 0.0);
+        assert_eq!(json["maximum"], max as f64);
+
+        let json = schema_json::<crate::i64::SingleIdFlake<20, 31, 12>>();
+
+        assert_eq!(json["maximum"], ((1u64 << 63) - 1) as f64);
+
+        let json = schema_json::<I64DID>();
+
+        assert_eq!(json["maximum"], I64DID::MAX.id() as f64);
+
+        let json = schema_json::<U64SID>();
+        let max = U64SID::MAX_TIMESTAMP << U64SID::TIMESTAMP_SHIFT
+            | U64SID::MAX_PRIMARY_ID << U64SID::PRIMARY_ID_SHIFT
+            | U64SID::MAX_SEQUENCE;
+
+        assert_eq!(json["format"], "uint64");
+        assert_eq!(json["maximum"], max as f64);
+
+        let json = schema_json::<U64DID>();
+
+        assert_eq!(json["maximum"], U64DID::MAX.id() as f64);
+    }
+
+    #[cfg(feature = "serde-human-string")]
+    #[test]
+    fn string_pattern() {
+        let json = schema_json::<I64SID>();
+
+        assert_eq!(json["type"], "string");
+        assert_eq!(json["pattern"], "^[0-9]{1,19}$");
+
+        let json = schema_json::<I64DID>();
+
+        assert_eq!(json["pattern"], "^[0-9]{1,19}$");
+
+        for json in [schema_json::<U64SID>(), schema_json::<U64DID>()] {
+            assert_eq!(json["type"], "string");
+            assert_eq!(json["pattern"], "^[0-9]{1,20}$");
+        }
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(
+            serde_json::to_value(schema_for!(I64SID)).unwrap()["title"],
+            "SingleIdFlake_43_8_12"
+        );
+        assert_eq!(
+            serde_json::to_value(schema_for!(U64DID)).unwrap()["title"],
+            "DualIdFlake_44_4_4_12"
+        );
+    }
+
+    #[test]
+    fn segments() {
+        let json = schema_json::<Segments<i64, 2>>();
+
+        assert_eq!(json["type"], "array");
+        assert_eq!(json["minItems"], 2);
+        assert_eq!(json["maxItems"], 2);
+        assert_eq!(json["items"]["type"], "integer");
+        assert_eq!(json["items"]["format"], "int64");
+    }
+}
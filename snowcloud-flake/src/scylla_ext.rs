@@ -0,0 +1,209 @@
+//! ScyllaDB / Cassandra values for snowflakes
+//!
+//! snowflakes are written and read as CQL `bigint` with both the
+//! [`Value`](scylla_cql::frame::value::Value) and
+//! [`SerializeCql`](scylla_cql::types::serialize::value::SerializeCql)
+//! serialization traits along with
+//! [`FromCqlVal`](scylla_cql::frame::response::cql_to_rust::FromCqlVal).
+//! `Option<Flake>` is handled by the impls in the driver so a `None` is
+//! written as null and a null column is read as `None`.
+//!
+//! i64 snowflakes are written as is and will fail to convert with `BadVal`
+//! if the value read is not a valid id for the layout.
+//!
+//! u64 snowflakes are also written as `bigint` since CQL does not have an
+//! unsigned 64 bit integer. the bits of the id are reinterpreted so the full
+//! range will round trip but ids with the top bit set will be negative
+//! numbers in the database. clustering columns and range queries will order
+//! those ids before the other ids so use a layout that leaves the top bit
+//! unused if the order matters.
+//!
+//! ```rust,ignore
+//! type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! session.query("insert into records (id, name) values (?, ?)", (flake, name)).await?;
+//!
+//! let rows = session.query("select id, name from records", &[]).await?;
+//!
+//! for row in rows.rows_typed::<(MyFlake, String)>()? {
+//!     let (id, name) = row?;
+//! }
+//! ```
+
+use scylla_cql::frame::response::cql_to_rust::{FromCqlVal, FromCqlValError};
+use scylla_cql::frame::response::result::{ColumnType, CqlValue};
+use scylla_cql::frame::value::{Value, ValueTooBig};
+use scylla_cql::types::serialize::{CellWriter, SerializationError};
+use scylla_cql::types::serialize::value::SerializeCql;
+use scylla_cql::types::serialize::writers::WrittenCellProof;
+
+use crate::i64::SegmentBits;
+
+/// conversion between the base type of a snowflake and a CQL bigint
+trait BigInt: Sized {
+    fn to_bigint(self) -> i64;
+
+    fn from_bigint(int: i64) -> Self;
+}
+
+impl BigInt for i64 {
+    #[inline]
+    fn to_bigint(self) -> i64 {
+        self
+    }
+
+    #[inline]
+    fn from_bigint(int: i64) -> Self {
+        int
+    }
+}
+
+impl BigInt for u64 {
+    #[inline]
+    fn to_bigint(self) -> i64 {
+        self as i64
+    }
+
+    #[inline]
+    fn from_bigint(int: i64) -> Self {
+        int as u64
+    }
+}
+
+macro_rules! scylla_impls {
+    ($base:ty, [$($gen:tt)*] $flake:ty $(where $($bound:tt)*)?) => {
+        impl<$($gen)*> Value for $flake $(where $($bound)*)? {
+            fn serialize(&self, buf: &mut Vec<u8>) -> Result<(), ValueTooBig> {
+                Value::serialize(&self.id().to_bigint(), buf)
+            }
+        }
+
+        impl<$($gen)*> SerializeCql for $flake $(where $($bound)*)? {
+            fn serialize<'b>(
+                &self,
+                typ: &ColumnType,
+                writer: CellWriter<'b>
+            ) -> Result<WrittenCellProof<'b>, SerializationError> {
+                SerializeCql::serialize(&self.id().to_bigint(), typ, writer)
+            }
+        }
+
+        impl<$($gen)*> FromCqlVal<CqlValue> for $flake $(where $($bound)*)? {
+            fn from_cql(cql_val: CqlValue) -> Result<Self, FromCqlValError> {
+                let int = <$base>::from_bigint(i64::from_cql(cql_val)?);
+
+                <$flake>::try_from(&int).map_err(|_| FromCqlValError::BadVal)
+            }
+        }
+    };
+}
+
+scylla_impls!(i64, [const TS: u8, const PID: u8, const SEQ: u8] crate::i64::SingleIdFlake<TS, PID, SEQ>);
+scylla_impls!(i64, [const TS: u8, const PID: u8, const SEQ: u8] crate::i64::SingleIdFlakeSec<TS, PID, SEQ>);
+scylla_impls!(i64, [const TS: u8, const PID: u8, const SID: u8, const SEQ: u8] crate::i64::DualIdFlake<TS, PID, SID, SEQ>);
+scylla_impls!(i64, [const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8] crate::i64::TripleIdFlake<TS, PID, SID, TID, SEQ>);
+scylla_impls!(i64, [const TS: u8, const SEQ: u8, const N: usize, B] crate::i64::NIdFlake<TS, SEQ, N, B> where B: SegmentBits<N>);
+
+scylla_impls!(u64, [const TS: u8, const PID: u8, const SEQ: u8] crate::u64::SingleIdFlake<TS, PID, SEQ>);
+scylla_impls!(u64, [const TS: u8, const PID: u8, const SEQ: u8] crate::u64::SingleIdFlakeMicros<TS, PID, SEQ>);
+scylla_impls!(u64, [const TS: u8, const PID: u8, const SID: u8, const SEQ: u8] crate::u64::DualIdFlake<TS, PID, SID, SEQ>);
+scylla_impls!(u64, [const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8] crate::u64::TripleIdFlake<TS, PID, SID, TID, SEQ>);
+scylla_impls!(u64, [] crate::presets::Sonyflake);
+
+#[cfg(test)]
+mod test {
+    use scylla_cql::frame::response::cql_to_rust::{FromCqlVal, FromCqlValError};
+    use scylla_cql::frame::response::result::{deser_cql_value, ColumnType, CqlValue};
+    use scylla_cql::frame::value::Value;
+    use scylla_cql::types::serialize::CellWriter;
+    use scylla_cql::types::serialize::value::SerializeCql;
+
+    type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+    type I64DID = crate::i64::DualIdFlake<43, 4, 4, 12>;
+    type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+
+    /// serializes with both traits and checks that they produce the same
+    /// cell
+    fn serialize<T: Value + SerializeCql>(value: &T) -> Vec<u8> {
+        let mut legacy = Vec::new();
+        let mut cell = Vec::new();
+
+        Value::serialize(value, &mut legacy).unwrap();
+        SerializeCql::serialize(value, &ColumnType::BigInt, CellWriter::new(&mut cell)).unwrap();
+
+        assert_eq!(legacy, cell);
+
+        legacy
+    }
+
+    /// reads a cell back into a CqlValue the same way the driver does
+    fn deserialize(cell: &[u8]) -> Option<CqlValue> {
+        let (len, mut value) = cell.split_at(4);
+
+        if i32::from_be_bytes(len.try_into().unwrap()) < 0 {
+            return None;
+        }
+
+        Some(deser_cql_value(&ColumnType::BigInt, &mut value).unwrap())
+    }
+
+    #[test]
+    fn round_trip() {
+        let flake = I64SID::from_parts(1, 1, 1).unwrap();
+        let cell = serialize(&flake);
+
+        assert_eq!(&cell[..4], &8i32.to_be_bytes());
+        assert_eq!(&cell[4..], &flake.id().to_be_bytes());
+        assert_eq!(I64SID::from_cql(deserialize(&cell).unwrap()).unwrap(), flake);
+
+        let flake = I64DID::MAX;
+        let cell = serialize(&flake);
+
+        assert_eq!(I64DID::from_cql(deserialize(&cell).unwrap()).unwrap(), flake);
+    }
+
+    #[test]
+    fn unsigned() {
+        let flake = U64SID::MAX;
+        let cell = serialize(&flake);
+
+        assert_eq!(&cell[4..], &[0xff; 8]);
+        assert_eq!(deserialize(&cell), Some(CqlValue::BigInt(-1)));
+        assert_eq!(U64SID::from_cql(deserialize(&cell).unwrap()).unwrap(), flake);
+
+        let flake = U64SID::from_parts(1, 1, 1).unwrap();
+        let cell = serialize(&flake);
+
+        assert_eq!(U64SID::from_cql(deserialize(&cell).unwrap()).unwrap(), flake);
+    }
+
+    #[test]
+    fn nulls() {
+        let flake = Some(I64SID::from_parts(1, 1, 1).unwrap());
+        let cell = serialize(&flake);
+
+        assert_eq!(<Option<I64SID>>::from_cql(deserialize(&cell)).unwrap(), flake);
+
+        let cell = serialize(&None::<I64SID>);
+
+        assert_eq!(&cell, &(-1i32).to_be_bytes());
+        assert_eq!(<Option<I64SID>>::from_cql(deserialize(&cell)).unwrap(), None);
+        assert_eq!(<Option<I64SID>>::from_cql(Some(CqlValue::Empty)).unwrap(), None);
+        assert_eq!(
+            <I64SID as FromCqlVal<Option<CqlValue>>>::from_cql(None),
+            Err(FromCqlValError::ValIsNull)
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(I64SID::from_cql(CqlValue::BigInt(-1)), Err(FromCqlValError::BadVal));
+        assert_eq!(I64SID::from_cql(CqlValue::Int(1)), Err(FromCqlValError::BadCqlType));
+        assert_eq!(U64SID::from_cql(CqlValue::Text("1".into())), Err(FromCqlValError::BadCqlType));
+
+        let flake = I64SID::from_parts(1, 1, 1).unwrap();
+        let mut cell = Vec::new();
+
+        assert!(SerializeCql::serialize(&flake, &ColumnType::Int, CellWriter::new(&mut cell)).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//! SeaORM values for snowflakes
+//!
+//! the i64 snowflakes are stored as `BIGINT` and can be used as column types
+//! including the primary key of an entity. the primary key must have
+//! `auto_increment = false` since the id is generated by the application and
+//! not the database, otherwise the id will be left out of inserts.
+//!
+//! ```rust,ignore
+//! use sea_orm::entity::prelude::*;
+//!
+//! type RecordId = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+//! #[sea_orm(table_name = "records")]
+//! pub struct Model {
+//!     #[sea_orm(primary_key, auto_increment = false)]
+//!     pub id: RecordId,
+//!     pub name: String,
+//! }
+//!
+//! let found = Entity::find_by_id(id).one(&db).await?;
+//! ```
+//!
+//! values read from the database that are not valid ids for the layout will
+//! fail to convert.
+
+use sea_orm::{ColIdx, DbErr, QueryResult, TryFromU64, TryGetError, TryGetable, Value};
+use sea_orm::sea_query::{ArrayType, ColumnType, Nullable, ValueType, ValueTypeErr};
+
+use crate::i64::SegmentBits;
+
+macro_rules! sea_orm_impls {
+    ([$($gen:tt)*] $flake:ty $(where $($bound:tt)*)?) => {
+        impl<$($gen)*> From<$flake> for Value $(where $($bound)*)? {
+            fn from(flake: $flake) -> Self {
+                Value::BigInt(Some(flake.id()))
+            }
+        }
+
+        impl<$($gen)*> Nullable for $flake $(where $($bound)*)? {
+            fn null() -> Value {
+                Value::BigInt(None)
+            }
+        }
+
+        impl<$($gen)*> ValueType for $flake $(where $($bound)*)? {
+            fn try_from(value: Value) -> Result<Self, ValueTypeErr> {
+                match value {
+                    Value::BigInt(Some(int)) => <$flake>::try_from(&int).map_err(|_| ValueTypeErr),
+                    _ => Err(ValueTypeErr),
+                }
+            }
+
+            fn type_name() -> String {
+                std::any::type_name::<Self>().to_owned()
+            }
+
+            fn array_type() -> ArrayType {
+                ArrayType::BigInt
+            }
+
+            fn column_type() -> ColumnType {
+                ColumnType::BigInteger
+            }
+        }
+
+        impl<$($gen)*> TryGetable for $flake $(where $($bound)*)? {
+            fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+                let int = i64::try_get_by(res, index)?;
+
+                <$flake>::try_from(&int).map_err(|err| TryGetError::DbErr(DbErr::TryIntoErr {
+                    from: "i64",
+                    into: std::any::type_name::<Self>(),
+                    source: Box::new(err),
+                }))
+            }
+        }
+
+        impl<$($gen)*> TryFromU64 for $flake $(where $($bound)*)? {
+            fn try_from_u64(n: u64) -> Result<Self, DbErr> {
+                <i64 as TryFrom<u64>>::try_from(n)
+                    .map_err(Into::into)
+                    .and_then(|int| <$flake>::try_from(&int).map_err(Into::into))
+                    .map_err(|source| DbErr::TryIntoErr {
+                        from: "u64",
+                        into: std::any::type_name::<Self>(),
+                        source,
+                    })
+            }
+        }
+    };
+}
+
+sea_orm_impls!([const TS: u8, const PID: u8, const SEQ: u8] crate::i64::SingleIdFlake<TS, PID, SEQ>);
+sea_orm_impls!([const TS: u8, const PID: u8, const SEQ: u8] crate::i64::SingleIdFlakeSec<TS, PID, SEQ>);
+sea_orm_impls!([const TS: u8, const PID: u8, const SID: u8, const SEQ: u8] crate::i64::DualIdFlake<TS, PID, SID, SEQ>);
+sea_orm_impls!([const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8] crate::i64::TripleIdFlake<TS, PID, SID, TID, SEQ>);
+sea_orm_impls!([const TS: u8, const SEQ: u8, const N: usize, B] crate::i64::NIdFlake<TS, SEQ, N, B> where B: SegmentBits<N>);
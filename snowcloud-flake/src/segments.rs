@@ -5,7 +5,7 @@ use std::fmt;
 /// container for storing id segments
 ///
 /// wrapper around an array with a fixed size
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Segments<T, const N: usize>([T; N]);
 
 impl<T, const N: usize> Segments<T, N> {
@@ -125,7 +125,158 @@ where
             write!(f, "{}", self.0[i])?;
         }
 
-        Ok(())
+        write!(f, ")")
     }
 }
 
+
+#[cfg(feature = "serde")]
+impl<T, const N: usize> serde::Serialize for Segments<T, N>
+where
+    T: serde::Serialize
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer
+    {
+        use serde::ser::SerializeTuple;
+
+        let mut tuple = serializer.serialize_tuple(N)?;
+
+        for seg in &self.0 {
+            tuple.serialize_element(seg)?;
+        }
+
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+struct SegmentsVisitor<T, const N: usize> {
+    phantom: std::marker::PhantomData<T>
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, const N: usize> serde::de::Visitor<'de> for SegmentsVisitor<T, N>
+where
+    T: serde::Deserialize<'de>
+{
+    type Value = Segments<T, N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "sequence of {} segments", N)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>
+    {
+        let mut segs = Vec::with_capacity(N);
+
+        while segs.len() < N {
+            let Some(seg) = seq.next_element()? else {
+                return Err(serde::de::Error::invalid_length(segs.len(), &self));
+            };
+
+            segs.push(seg);
+        }
+
+        if seq.next_element::<serde::de::IgnoredAny>()?.is_some() {
+            return Err(serde::de::Error::custom(crate::error::Error::TooManySegments));
+        }
+
+        let Ok(array) = segs.try_into() else {
+            unreachable!("collected exactly N segments");
+        };
+
+        Ok(Segments(array))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, const N: usize> serde::Deserialize<'de> for Segments<T, N>
+where
+    T: serde::Deserialize<'de>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>
+    {
+        deserializer.deserialize_tuple(N, SegmentsVisitor {
+            phantom: std::marker::PhantomData
+        })
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T, const N: usize> schemars::JsonSchema for Segments<T, N>
+where
+    T: schemars::JsonSchema
+{
+    fn schema_name() -> String {
+        format!("Segments_{}_{}", T::schema_name(), N)
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{SchemaObject, InstanceType, ArrayValidation};
+
+        SchemaObject {
+            instance_type: Some(InstanceType::Array.into()),
+            array: Some(Box::new(ArrayValidation {
+                items: Some(gen.subschema_for::<T>().into()),
+                min_items: Some(N as u32),
+                max_items: Some(N as u32),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }.into()
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        let one = Segments::<i64, 1>::from_parts(1);
+        let two = Segments::<i64, 2>::from_parts(1, 2);
+        let three = Segments::<u64, 3>::from_parts(1, 2, 3);
+
+        assert_eq!(serde_json::to_string(&one).unwrap(), "[1]");
+        assert_eq!(serde_json::to_string(&two).unwrap(), "[1,2]");
+        assert_eq!(serde_json::to_string(&three).unwrap(), "[1,2,3]");
+
+        assert_eq!(serde_json::from_str::<Segments<i64, 1>>("[1]").unwrap(), one);
+        assert_eq!(serde_json::from_str::<Segments<i64, 2>>("[1,2]").unwrap(), two);
+        assert_eq!(serde_json::from_str::<Segments<u64, 3>>("[1,2,3]").unwrap(), three);
+
+        let bytes = bincode::serialize(&one).unwrap();
+
+        assert_eq!(bytes.len(), 8);
+        assert_eq!(bincode::deserialize::<Segments<i64, 1>>(&bytes).unwrap(), one);
+
+        let bytes = bincode::serialize(&two).unwrap();
+
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(bincode::deserialize::<Segments<i64, 2>>(&bytes).unwrap(), two);
+
+        let bytes = bincode::serialize(&three).unwrap();
+
+        assert_eq!(bytes.len(), 24);
+        assert_eq!(bincode::deserialize::<Segments<u64, 3>>(&bytes).unwrap(), three);
+    }
+
+    #[test]
+    fn serde_wrong_length() {
+        let err = serde_json::from_str::<Segments<i64, 2>>("[1,2,3]").unwrap_err();
+
+        assert!(err.to_string().contains("too many segments"), "{}", err);
+
+        let err = serde_json::from_str::<Segments<i64, 3>>("[1,2]").unwrap_err();
+
+        assert!(err.to_string().contains("invalid length 2, expected sequence of 3 segments"), "{}", err);
+
+        assert!(bincode::deserialize::<Segments<i64, 2>>(&[0u8; 8]).is_err());
+    }
+}
@@ -1,7 +1,20 @@
 //! additional serde options for de/serializing a snowflake
 //!
 //! provides one module for converting a snowflake to a string if something
-//! cannot handle working with 64 bit signed integers (javascript).
+//! cannot handle working with 64 bit signed integers (javascript). the other
+//! modules cover different representations:
+//!
+//! - [`padded_string_id`] pads the string so that sorting the strings sorts
+//!   the ids
+//! - [`hex_string_id`] writes fixed width hex strings
+//! - [`bytes_id`] writes big endian bytes
+//! - [`fixed_bytes_id`] writes big endian bytes that are always the same
+//!   size in binary formats
+//! - [`any_id`] accepts either integers or strings
+//! - [`option_number_id`] handles optional integer ids
+//! - [`parts_id`] writes the named parts of a snowflake
+//! - [`split_u32_id`] writes the id as two u32 values
+//! - [`with_epoch`] writes the epoch of the generator along with the id
 //!
 //! ```rust
 //! use serde::{Serialize, Deserialize};
@@ -28,7 +41,7 @@ use std::fmt;
 use std::marker::PhantomData;
 use core::convert::TryFrom;
 
-use serde::{ser, de};
+use serde::de;
 
 use snowcloud_core::traits;
 
@@ -51,9 +64,89 @@ macro_rules! from_str_radix {
     };
 }
 
+from_str_radix!(u32);
 from_str_radix!(i64);
 from_str_radix!(u64);
+from_str_radix!(i128);
+from_str_radix!(u128);
+
+/// converts an integer to and from big endian bytes
+pub trait BeBytes: Sized {
+    /// returns the big endian bytes of the integer
+    fn to_be_vec(&self) -> Vec<u8>;
+
+    /// attempts to create the integer from a slice of big endian bytes.
+    /// returns None if the slice is not the size of the integer
+    fn from_be_slice(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! be_bytes {
+    ($t:ty) => {
+        impl BeBytes for $t {
+            #[inline]
+            fn to_be_vec(&self) -> Vec<u8> {
+                self.to_be_bytes().to_vec()
+            }
+
+            #[inline]
+            fn from_be_slice(bytes: &[u8]) -> Option<Self> {
+                Some(<$t>::from_be_bytes(bytes.try_into().ok()?))
+            }
+        }
+    };
+}
+
+be_bytes!(u32);
+be_bytes!(i64);
+be_bytes!(u64);
+be_bytes!(i128);
+be_bytes!(u128);
+
+/// number of decimal digits needed for the largest value of an integer
+pub trait DecimalWidth {
+    const DECIMAL_WIDTH: usize;
+}
+
+macro_rules! decimal_width {
+    ($t:ty, $w:literal) => {
+        impl DecimalWidth for $t {
+            const DECIMAL_WIDTH: usize = $w;
+        }
+    };
+}
+
+decimal_width!(u32, 10);
+decimal_width!(i64, 19);
+decimal_width!(u64, 20);
+decimal_width!(i128, 39);
+decimal_width!(u128, 39);
+
+
+/// largest integer that an f64 can hold without rounding, 2^53
+const MAX_SAFE_FLOAT: f64 = 9007199254740992.0;
+
+/// converts a float given to a visitor into an integer
+///
+/// only floats that are exact integers of 2^53 or less are accepted since
+/// anything larger may have been rounded before reaching the visitor.
+pub(crate) fn float_to_int<E>(v: f64) -> Result<i64, E>
+where
+    E: de::Error
+{
+    if v.fract() != 0.0 || !v.is_finite() {
+        return Err(E::custom(format_args!(
+            "floating point id {} is not an integer. ids must be sent as integers or strings", v
+        )));
+    }
+
+    if v.abs() > MAX_SAFE_FLOAT {
+        return Err(E::custom(format_args!(
+            "floating point id {} is larger than 2^53 and may have lost precision. ids must be sent as integers or strings", v
+        )));
+    }
 
+    Ok(v as i64)
+}
 
 /// visitor for deserializing a string to a snowflake
 pub struct StringVisitor<F> {
@@ -176,6 +269,8 @@ pub mod string_id {
         type I64DID = crate::i64::DualIdFlake<43, 4, 4, 12>;
         type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
         type U64DID = crate::u64::DualIdFlake<44, 4, 4, 12>;
+        type I128SID = crate::i128::SingleIdFlake<63, 32, 32>;
+        type U128SID = crate::u128::SingleIdFlake<64, 32, 32>;
 
         #[derive(Serialize, Deserialize)]
         struct I64SIDJson {
@@ -225,6 +320,30 @@ pub mod string_id {
             }
         }
 
+        #[derive(Serialize, Deserialize)]
+        struct I128SIDJson {
+            #[serde(with = "string_id")]
+            id: I128SID
+        }
+
+        impl I128SIDJson {
+            fn from_parts(ts: i128, seq: i128, pid: i128) -> Self {
+                Self { id: I128SID::from_parts(ts, pid, seq).unwrap() }
+            }
+        }
+
+        #[derive(Serialize, Deserialize)]
+        struct U128SIDJson {
+            #[serde(with = "string_id")]
+            id: U128SID
+        }
+
+        impl U128SIDJson {
+            fn from_parts(ts: u128, seq: u128, pid: u128) -> Self {
+                Self { id: U128SID::from_parts(ts, pid, seq).unwrap() }
+            }
+        }
+
         macro_rules! string_test {
             ($to_string:ident, $from_string:ident, $type:path, $expected:literal, $ts:literal, $seq:literal, $($id:literal),+) => {
                 #[test]
@@ -294,6 +413,22 @@ pub mod string_id {
             "{\"id\":\"1118209\"}",
             1, 1, 1, 1
         );
+
+        string_test!(
+            to_string_i128_single_id_seg,
+            from_string_i128_single_id_seg,
+            I128SIDJson,
+            "{\"id\":\"18446744078004518913\"}",
+            1, 1, 1
+        );
+
+        string_test!(
+            to_string_u128_single_id_seg,
+            from_string_u128_single_id_seg,
+            U128SIDJson,
+            "{\"id\":\"18446744078004518913\"}",
+            1, 1, 1
+        );
     }
 }
 
@@ -336,3 +471,2046 @@ pub mod option_string_id {
         })
     }
 }
+
+/// de/serializes an optional snowflake to its integer
+///
+/// structured to be used in `#[serde(with = "option_number_id")]`. `None`
+/// is written as null and null or unit values are read as `None`. pair it
+/// with `#[serde(default)]` so that missing fields are also `None`.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::option_number_id;
+///
+/// type I64SID = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(default, with = "option_number_id", skip_serializing_if = "Option::is_none")]
+///     id: Option<I64SID>,
+/// }
+///
+/// let present: MyStruct = serde_json::from_str("{\"id\":1052673}").unwrap();
+/// let null: MyStruct = serde_json::from_str("{\"id\":null}").unwrap();
+/// let absent: MyStruct = serde_json::from_str("{}").unwrap();
+///
+/// assert_eq!(present.id, Some(I64SID::from_parts(1, 1, 1).unwrap()));
+/// assert_eq!(null.id, None);
+/// assert_eq!(absent.id, None);
+/// ```
+pub mod option_number_id {
+    use core::convert::TryFrom;
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::{ser, de};
+    use snowcloud_core::traits;
+
+    /// serializes a given optional snowflake to an integer or null
+    pub fn serialize<F, S>(flake: &Option<F>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: traits::Id,
+        F::BaseType: ser::Serialize,
+        S: ser::Serializer
+    {
+        match flake {
+            Some(ref v) => serializer.serialize_some(&v.id()),
+            None => serializer.serialize_none()
+        }
+    }
+
+    /// deserializes a given integer or null to an optional snowflake
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<Option<F>, D::Error>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: de::Deserialize<'de>,
+        D: de::Deserializer<'de>
+    {
+        deserializer.deserialize_option(OptionNumVisitor {
+            phantom: PhantomData
+        })
+    }
+
+    /// visitor for deserializing an integer or null to an optional snowflake
+    pub struct OptionNumVisitor<F> {
+        phantom: PhantomData<F>
+    }
+
+    impl<'de, F> de::Visitor<'de> for OptionNumVisitor<F>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: de::Deserialize<'de>
+    {
+        type Value = Option<F>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "null or integer within the valid range of the Id")
+        }
+
+        fn visit_some<D>(self, d: D) -> Result<Self::Value, D::Error>
+        where
+            D: de::Deserializer<'de>
+        {
+            let num: F::BaseType = de::Deserialize::deserialize(d)?;
+
+            let Ok(flake) = TryFrom::try_from(num) else {
+                return Err(de::Error::invalid_value(de::Unexpected::Other("integer"), &self));
+            };
+
+            Ok(Some(flake))
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E>
+        where
+            E: de::Error
+        {
+            Ok(None)
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: de::Error
+        {
+            Ok(None)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        use crate::serde_ext::option_number_id;
+
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+        type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct I64SIDJson {
+            #[serde(default, with = "option_number_id", skip_serializing_if = "Option::is_none")]
+            id: Option<I64SID>,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U64SIDJson {
+            #[serde(default, with = "option_number_id", skip_serializing_if = "Option::is_none")]
+            id: Option<U64SID>,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U64SIDBin {
+            #[serde(with = "option_number_id")]
+            id: Option<U64SID>,
+        }
+
+        #[test]
+        fn json() {
+            let present = I64SIDJson { id: Some(I64SID::from_parts(1, 1, 1).unwrap()) };
+            let absent = I64SIDJson { id: None };
+
+            assert_eq!(serde_json::to_string(&present).unwrap(), "{\"id\":1052673}");
+            assert_eq!(serde_json::to_string(&absent).unwrap(), "{}");
+
+            assert_eq!(serde_json::from_str::<I64SIDJson>("{\"id\":1052673}").unwrap(), present);
+            assert_eq!(serde_json::from_str::<I64SIDJson>("{\"id\":null}").unwrap(), absent);
+            assert_eq!(serde_json::from_str::<I64SIDJson>("{}").unwrap(), absent);
+
+            let present = U64SIDJson { id: Some(U64SID::MAX) };
+
+            assert_eq!(serde_json::to_string(&present).unwrap(), "{\"id\":18446744073709551615}");
+            assert_eq!(serde_json::from_str::<U64SIDJson>("{\"id\":18446744073709551615}").unwrap(), present);
+            assert_eq!(serde_json::from_str::<U64SIDJson>("{\"id\":null}").unwrap(), U64SIDJson { id: None });
+            assert_eq!(serde_json::from_str::<U64SIDJson>("{}").unwrap(), U64SIDJson { id: None });
+        }
+
+        #[test]
+        fn binary() {
+            for obj in [U64SIDBin { id: Some(U64SID::from_parts(1, 1, 1).unwrap()) }, U64SIDBin { id: None }] {
+                let bytes = bincode::serialize(&obj).unwrap();
+
+                assert_eq!(bincode::deserialize::<U64SIDBin>(&bytes).unwrap(), obj);
+            }
+
+            assert_eq!(bincode::serialize(&U64SIDBin { id: None }).unwrap(), [0]);
+        }
+
+        #[test]
+        fn invalid() {
+            for json in ["{\"id\":-1}", "{\"id\":\"1052673\"}", "{\"id\":9223372036854775808}"] {
+                assert!(serde_json::from_str::<I64SIDJson>(json).is_err(), "parsed {}", json);
+            }
+        }
+    }
+}
+
+/// de/serializes a snowflake to a string that can also be hex, octal, or
+/// binary
+///
+/// structured to be used in `#[serde(with = "flexible_string_id")]`.
+/// serializes the same as [`string_id`] but deserializing will also accept
+/// strings with a `0x`, `0o`, or `0b` prefix for ids written by hand.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::flexible_string_id;
+///
+/// type I64SID = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(with = "flexible_string_id")]
+///     id: I64SID,
+/// }
+///
+/// let my_struct: MyStruct = serde_json::from_str("{\"id\":\"0x101001\"}").unwrap();
+///
+/// assert_eq!(my_struct.id, I64SID::from_parts(1, 1, 1).unwrap());
+/// ```
+pub mod flexible_string_id {
+    use core::convert::TryFrom;
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::{ser, de};
+    use snowcloud_core::traits;
+
+    use crate::encoding::Encodable;
+
+    /// serializes a given snowflake to a decimal string
+    pub fn serialize<F, S>(flake: &F, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: traits::Id,
+        F::BaseType: ToString,
+        S: ser::Serializer
+    {
+        super::string_id::serialize(flake, serializer)
+    }
+
+    /// deserializes a given decimal or prefixed string to a snowflake
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: Encodable,
+        D: de::Deserializer<'de>
+    {
+        deserializer.deserialize_str(FlexibleStringVisitor {
+            phantom: PhantomData
+        })
+    }
+
+    /// visitor for deserializing a decimal or prefixed string to a snowflake
+    pub struct FlexibleStringVisitor<F> {
+        phantom: PhantomData<F>
+    }
+
+    impl<'de, F> de::Visitor<'de> for FlexibleStringVisitor<F>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: Encodable
+    {
+        type Value = F;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "decimal, 0x hex, 0o octal, or 0b binary integer string within the valid range of the Id")
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let Ok(num) = crate::encoding::parse_prefixed(s) else {
+                return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+            };
+
+            let Ok(flake) = TryFrom::try_from(num) else {
+                return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+            };
+
+            Ok(flake)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        use crate::serde_ext::flexible_string_id;
+
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+        type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct I64SIDJson {
+            #[serde(with = "flexible_string_id")]
+            id: I64SID,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U64SIDJson {
+            #[serde(with = "flexible_string_id")]
+            id: U64SID,
+        }
+
+        #[test]
+        fn prefixed() {
+            let expected = I64SIDJson {
+                id: I64SID::from_parts(1, 1, 1).unwrap(),
+            };
+
+            assert_eq!(serde_json::to_string(&expected).unwrap(), "{\"id\":\"1052673\"}");
+
+            for string in ["1052673", "0x101001", "0X101001", "0o4010001", "0b100000001000000000001"] {
+                let json = format!("{{\"id\":\"{}\"}}", string);
+                let parsed: I64SIDJson = serde_json::from_str(&json)
+                    .expect("failed to parse json string");
+
+                assert_eq!(parsed, expected, "invalid parsed id for {}", string);
+            }
+
+            let parsed: U64SIDJson = serde_json::from_str("{\"id\":\"0xFFFFFFFFFFFFFFFF\"}")
+                .expect("failed to parse json string");
+
+            assert_eq!(parsed.id, U64SID::MAX);
+        }
+
+        #[test]
+        fn invalid() {
+            for string in ["", "0x", "0b102", "0xfg", "-1", "0x-1", "0xffffffffffffffff"] {
+                let json = format!("{{\"id\":\"{}\"}}", string);
+
+                assert!(serde_json::from_str::<I64SIDJson>(&json).is_err(), "parsed {}", string);
+            }
+        }
+    }
+}
+
+/// de/serializes a snowflake to a fixed width hex string
+///
+/// structured to be used in `#[serde(with = "hex_string_id")]`. the string
+/// is lowercase and padded to the bits of the base type, 16 characters for
+/// i64 and u64. deserializing accepts either case, an optional `0x` prefix,
+/// and shorter strings as long as they are whole bytes.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::hex_string_id;
+///
+/// type I64SID = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(with = "hex_string_id")]
+///     id: I64SID,
+/// }
+///
+/// let my_struct = MyStruct {
+///     id: I64SID::from_parts(1, 1, 1).unwrap(),
+/// };
+///
+/// let json_string = serde_json::to_string(&my_struct).unwrap();
+///
+/// assert_eq!(json_string, "{\"id\":\"0000000000101001\"}");
+/// ```
+pub mod hex_string_id {
+    use core::convert::TryFrom;
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::{ser, de};
+    use snowcloud_core::traits;
+
+    use crate::encoding::Encodable;
+    use super::FromStrRadix;
+
+    /// serializes a given snowflake to a fixed width lowercase hex string
+    pub fn serialize<F, S>(flake: &F, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: traits::Id,
+        F::BaseType: Encodable,
+        S: ser::Serializer
+    {
+        let id_str = format!(
+            "{:0width$x}",
+            flake.id().into_u128(),
+            width = (F::BaseType::BITS / 4) as usize
+        );
+
+        serializer.serialize_str(id_str.as_str())
+    }
+
+    /// deserializes a given hex string to a snowflake
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: Encodable + FromStrRadix,
+        D: de::Deserializer<'de>
+    {
+        deserializer.deserialize_str(HexVisitor {
+            phantom: PhantomData
+        })
+    }
+
+    /// visitor for deserializing a hex string to a snowflake
+    pub struct HexVisitor<F> {
+        phantom: PhantomData<F>
+    }
+
+    impl<'de, F> de::Visitor<'de> for HexVisitor<F>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: Encodable + FromStrRadix
+    {
+        type Value = F;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "hex string of at most {} characters within the valid range of the Id",
+                F::BaseType::BITS / 4
+            )
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let digits = s.strip_prefix("0x")
+                .or_else(|| s.strip_prefix("0X"))
+                .unwrap_or(s);
+
+            if digits.is_empty()
+                || digits.len() % 2 != 0
+                || digits.len() > (F::BaseType::BITS / 4) as usize
+            {
+                return Err(E::invalid_length(digits.len(), &self));
+            }
+
+            if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+            }
+
+            let Ok(num) = FromStrRadix::from_str_radix(digits, 16) else {
+                return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+            };
+
+            let Ok(flake) = TryFrom::try_from(num) else {
+                return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+            };
+
+            Ok(flake)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        use crate::serde_ext::hex_string_id;
+
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+        type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+        type U32SID = crate::u32::SingleIdFlake<22, 4, 6>;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct I64SIDJson {
+            #[serde(with = "hex_string_id")]
+            id: I64SID,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U64SIDJson {
+            #[serde(with = "hex_string_id")]
+            id: U64SID,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U32SIDJson {
+            #[serde(with = "hex_string_id")]
+            id: U32SID,
+        }
+
+        #[test]
+        fn boundaries() {
+            let expected = [
+                (I64SID::NIL, "{\"id\":\"0000000000000000\"}"),
+                (I64SID::from_parts(1, 1, 1).unwrap(), "{\"id\":\"0000000000101001\"}"),
+                (I64SID::MAX, "{\"id\":\"7fffffffffffffff\"}"),
+            ];
+
+            for (id, json) in expected {
+                let obj = I64SIDJson { id };
+
+                assert_eq!(serde_json::to_string(&obj).unwrap(), json);
+                assert_eq!(serde_json::from_str::<I64SIDJson>(json).unwrap(), obj);
+            }
+
+            let expected = [
+                (U64SID::NIL, "{\"id\":\"0000000000000000\"}"),
+                (U64SID::MAX, "{\"id\":\"ffffffffffffffff\"}"),
+            ];
+
+            for (id, json) in expected {
+                let obj = U64SIDJson { id };
+
+                assert_eq!(serde_json::to_string(&obj).unwrap(), json);
+                assert_eq!(serde_json::from_str::<U64SIDJson>(json).unwrap(), obj);
+            }
+
+            let obj = U32SIDJson { id: U32SID::from_parts(1, 1, 1).unwrap() };
+
+            assert_eq!(serde_json::to_string(&obj).unwrap(), "{\"id\":\"00000441\"}");
+        }
+
+        #[test]
+        fn relaxed_input() {
+            let expected = I64SIDJson { id: I64SID::from_parts(1, 1, 1).unwrap() };
+
+            for string in ["0x0000000000101001", "0X0000000000101001", "101001", "0x101001"] {
+                let json = format!("{{\"id\":\"{}\"}}", string);
+
+                assert_eq!(serde_json::from_str::<I64SIDJson>(&json).unwrap(), expected, "{}", string);
+            }
+
+            let parsed: U64SIDJson = serde_json::from_str("{\"id\":\"FFFFFFFFFFFFFFFF\"}").unwrap();
+
+            assert_eq!(parsed.id, U64SID::MAX);
+        }
+
+        #[test]
+        fn invalid() {
+            for string in [
+                "",
+                "0x",
+                "1",
+                "00000000001010010",
+                "000000000000101001",
+                "000000000010100g",
+                "+000000000101001",
+                "8000000000000000",
+                "0x0x101001",
+            ] {
+                let json = format!("{{\"id\":\"{}\"}}", string);
+
+                assert!(serde_json::from_str::<I64SIDJson>(&json).is_err(), "parsed {}", string);
+            }
+
+            assert!(serde_json::from_str::<I64SIDJson>("{\"id\":1052673}").is_err());
+        }
+    }
+}
+
+/// de/serializes a snowflake to a zero padded string
+///
+/// structured to be used in `#[serde(with = "padded_string_id")]`. the
+/// string is padded to the digits of the largest value of the base type, 19
+/// for i64 and 20 for u64, so sorting the strings will sort the ids.
+/// deserializing accepts padded and unpadded strings.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::padded_string_id;
+///
+/// type I64SID = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(with = "padded_string_id")]
+///     id: I64SID,
+/// }
+///
+/// let my_struct = MyStruct {
+///     id: I64SID::from_parts(1, 1, 1).unwrap(),
+/// };
+///
+/// let json_string = serde_json::to_string(&my_struct).unwrap();
+///
+/// assert_eq!(json_string, "{\"id\":\"0000000000001052673\"}");
+/// ```
+pub mod padded_string_id {
+    use core::convert::TryFrom;
+    use std::fmt::Display;
+    use std::marker::PhantomData;
+
+    use serde::{ser, de};
+    use snowcloud_core::traits;
+
+    use super::{DecimalWidth, FromStrRadix};
+    use super::StringVisitor;
+
+    /// serializes a given snowflake to a zero padded string
+    pub fn serialize<F, S>(flake: &F, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: traits::Id,
+        F::BaseType: Display + DecimalWidth,
+        S: ser::Serializer
+    {
+        let id_str = format!("{:0width$}", flake.id(), width = F::BaseType::DECIMAL_WIDTH);
+
+        serializer.serialize_str(id_str.as_str())
+    }
+
+    /// deserializes a given padded or unpadded string to a snowflake
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: FromStrRadix,
+        D: de::Deserializer<'de>
+    {
+        deserializer.deserialize_str(StringVisitor {
+            phantom: PhantomData
+        })
+    }
+
+    #[cfg(test)]
+    mod test {
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        use crate::serde_ext::padded_string_id;
+
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+        type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct I64SIDJson {
+            #[serde(with = "padded_string_id")]
+            id: I64SID,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U64SIDJson {
+            #[serde(with = "padded_string_id")]
+            id: U64SID,
+        }
+
+        #[test]
+        fn padding_width() {
+            let expected = [
+                (I64SID::NIL, "{\"id\":\"0000000000000000000\"}"),
+                (I64SID::from_parts(1, 1, 1).unwrap(), "{\"id\":\"0000000000001052673\"}"),
+                (I64SID::MAX, "{\"id\":\"9223372036854775807\"}"),
+            ];
+
+            for (id, json) in expected {
+                let obj = I64SIDJson { id };
+
+                assert_eq!(serde_json::to_string(&obj).unwrap(), json);
+                assert_eq!(serde_json::from_str::<I64SIDJson>(json).unwrap(), obj);
+            }
+
+            let expected = [
+                (U64SID::NIL, "{\"id\":\"00000000000000000000\"}"),
+                (U64SID::from_parts(1, 1, 1).unwrap(), "{\"id\":\"00000000000001052673\"}"),
+                (U64SID::MAX, "{\"id\":\"18446744073709551615\"}"),
+            ];
+
+            for (id, json) in expected {
+                let obj = U64SIDJson { id };
+
+                assert_eq!(serde_json::to_string(&obj).unwrap(), json);
+                assert_eq!(serde_json::from_str::<U64SIDJson>(json).unwrap(), obj);
+            }
+        }
+
+        #[test]
+        fn unpadded() {
+            let parsed: I64SIDJson = serde_json::from_str("{\"id\":\"1052673\"}")
+                .expect("failed to parse unpadded string");
+
+            assert_eq!(parsed.id, I64SID::from_parts(1, 1, 1).unwrap());
+
+            assert!(serde_json::from_str::<I64SIDJson>("{\"id\":\"\"}").is_err());
+            assert!(serde_json::from_str::<I64SIDJson>("{\"id\":\"18446744073709551615\"}").is_err());
+        }
+
+        #[test]
+        fn sorted() {
+            let mut objs: Vec<U64SIDJson> = crate::random_bits(1000)
+                .map(|bits| U64SIDJson { id: U64SID::try_from(&(bits as u64 >> (bits % 64) as u32)).unwrap() })
+                .collect();
+            let mut strings: Vec<String> = objs.iter()
+                .map(|obj| serde_json::to_string(obj).unwrap())
+                .collect();
+
+            objs.sort_by_key(|obj| obj.id.id());
+            strings.sort();
+
+            for (obj, string) in objs.iter().zip(strings.iter()) {
+                assert_eq!(serde_json::from_str::<U64SIDJson>(string).unwrap(), *obj);
+            }
+        }
+    }
+}
+
+/// de/serializes a snowflake to unpadded base64url
+///
+/// structured to be used in `#[serde(with = "base64_id")]`. uses the big
+/// endian bytes of the id, see
+/// [`encoding::base64url`](crate::encoding::base64url) for the format.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::base64_id;
+///
+/// type I64SID = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(with = "base64_id")]
+///     id: I64SID,
+/// }
+///
+/// let my_struct = MyStruct {
+///     id: I64SID::from_parts(1, 1, 1).unwrap(),
+/// };
+///
+/// let json_string = serde_json::to_string(&my_struct).unwrap();
+///
+/// assert_eq!(json_string, "{\"id\":\"AAAAAAAQEAE\"}");
+/// ```
+pub mod base64_id {
+    use core::convert::TryFrom;
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::{ser, de};
+    use snowcloud_core::traits;
+
+    use crate::encoding::{base64url, Encodable};
+
+    /// serializes a given snowflake to base64url
+    pub fn serialize<F, S>(flake: &F, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: traits::Id,
+        F::BaseType: Encodable,
+        S: ser::Serializer
+    {
+        serializer.serialize_str(base64url::encode(flake).as_str())
+    }
+
+    /// deserializes a base64url string to a snowflake
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: Encodable,
+        D: de::Deserializer<'de>
+    {
+        deserializer.deserialize_str(Base64Visitor {
+            phantom: PhantomData
+        })
+    }
+
+    /// visitor for deserializing a base64url string to a snowflake
+    pub struct Base64Visitor<F> {
+        phantom: PhantomData<F>
+    }
+
+    impl<'de, F> de::Visitor<'de> for Base64Visitor<F>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: Encodable
+    {
+        type Value = F;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "unpadded base64url string of the big endian bytes of the Id")
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            base64url::decode(s)
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(s), &self))
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        use crate::serde_ext::base64_id;
+
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+        type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct I64SIDJson {
+            #[serde(with = "base64_id")]
+            id: I64SID,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U64SIDJson {
+            #[serde(with = "base64_id")]
+            id: U64SID,
+        }
+
+        #[test]
+        fn round_trip() {
+            let obj = I64SIDJson {
+                id: I64SID::from_parts(1, 1, 1).unwrap(),
+            };
+            let expected = "{\"id\":\"AAAAAAAQEAE\"}";
+
+            let json_string = serde_json::to_string(&obj)
+                .expect("failed to create json string");
+
+            assert_eq!(json_string, expected, "invalid json string");
+
+            let parsed: I64SIDJson = serde_json::from_str(expected)
+                .expect("failed to parse json string");
+
+            assert_eq!(parsed, obj, "invalid parsed id");
+
+            let obj = U64SIDJson { id: U64SID::MAX };
+            let json_string = serde_json::to_string(&obj).unwrap();
+
+            assert_eq!(json_string, "{\"id\":\"__________8\"}");
+            assert_eq!(serde_json::from_str::<U64SIDJson>(&json_string).unwrap(), obj);
+        }
+
+        #[test]
+        fn invalid() {
+            assert!(serde_json::from_str::<I64SIDJson>("{\"id\":\"AAAAAAAQEAE=\"}").is_err());
+            assert!(serde_json::from_str::<I64SIDJson>("{\"id\":\"AAAAAAQEAE\"}").is_err());
+            assert!(serde_json::from_str::<I64SIDJson>("{\"id\":\"__________8\"}").is_err());
+            assert!(serde_json::from_str::<I64SIDJson>("{\"id\":1052673}").is_err());
+        }
+    }
+}
+
+/// de/serializes a snowflake to a fixed number of big endian bytes
+///
+/// structured to be used in `#[serde(with = "fixed_bytes_id")]`. the
+/// default impls write the id as an integer, which binary formats are free
+/// to encode how they want. bincode with its default options and postcard
+/// use variable length integers so the size depends on the value of the id.
+/// this writes the id as a tuple of bytes instead, 8 for i64 and u64
+/// snowflakes, which binary formats write without a length prefix so the
+/// size is always the same. unlike [`bytes_id`] there is no length prefix
+/// either.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::fixed_bytes_id;
+///
+/// type I64SID = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(with = "fixed_bytes_id")]
+///     id: I64SID,
+/// }
+///
+/// let my_struct = MyStruct {
+///     id: I64SID::from_parts(1, 1, 1).unwrap(),
+/// };
+///
+/// let json_string = serde_json::to_string(&my_struct).unwrap();
+///
+/// assert_eq!(json_string, "{\"id\":[0,0,0,0,0,16,16,1]}");
+/// ```
+pub mod fixed_bytes_id {
+    use core::convert::TryFrom;
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::{ser, de};
+    use serde::ser::SerializeTuple;
+    use snowcloud_core::traits;
+
+    use super::BeBytes;
+
+    /// serializes a given snowflake to a tuple of big endian bytes
+    pub fn serialize<F, S>(flake: &F, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: traits::Id,
+        F::BaseType: BeBytes,
+        S: ser::Serializer
+    {
+        let bytes = flake.id().to_be_vec();
+        let mut tuple = serializer.serialize_tuple(bytes.len())?;
+
+        for b in &bytes {
+            tuple.serialize_element(b)?;
+        }
+
+        tuple.end()
+    }
+
+    /// deserializes a tuple of big endian bytes to a snowflake
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: BeBytes,
+        D: de::Deserializer<'de>
+    {
+        deserializer.deserialize_tuple(std::mem::size_of::<F::BaseType>(), FixedBytesVisitor {
+            phantom: PhantomData
+        })
+    }
+
+    /// visitor for deserializing a tuple of bytes to a snowflake
+    pub struct FixedBytesVisitor<F> {
+        phantom: PhantomData<F>
+    }
+
+    impl<'de, F> de::Visitor<'de> for FixedBytesVisitor<F>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: BeBytes
+    {
+        type Value = F;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "{} big endian bytes within the valid range of the Id",
+                std::mem::size_of::<F::BaseType>()
+            )
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::SeqAccess<'de>
+        {
+            let size = std::mem::size_of::<F::BaseType>();
+            let mut bytes = Vec::with_capacity(size);
+
+            while bytes.len() < size {
+                let Some(b) = seq.next_element::<u8>()? else {
+                    return Err(de::Error::invalid_length(bytes.len(), &self));
+                };
+
+                bytes.push(b);
+            }
+
+            if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                return Err(de::Error::invalid_length(size + 1, &self));
+            }
+
+            let Some(num) = BeBytes::from_be_slice(&bytes) else {
+                return Err(de::Error::invalid_length(bytes.len(), &self));
+            };
+
+            let Ok(flake) = TryFrom::try_from(num) else {
+                return Err(de::Error::invalid_value(de::Unexpected::Bytes(&bytes), &self));
+            };
+
+            Ok(flake)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        use crate::serde_ext::fixed_bytes_id;
+
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+        type U32SID = crate::u32::SingleIdFlake<22, 4, 6>;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct I64SIDJson {
+            #[serde(with = "fixed_bytes_id")]
+            id: I64SID,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U32SIDJson {
+            #[serde(with = "fixed_bytes_id")]
+            id: U32SID,
+        }
+
+        #[test]
+        fn json() {
+            let obj = I64SIDJson { id: I64SID::from_parts(1, 1, 1).unwrap() };
+            let json = "{\"id\":[0,0,0,0,0,16,16,1]}";
+
+            assert_eq!(serde_json::to_string(&obj).unwrap(), json);
+            assert_eq!(serde_json::from_str::<I64SIDJson>(json).unwrap(), obj);
+
+            let obj = U32SIDJson { id: U32SID::from_parts(1, 1, 1).unwrap() };
+            let json = "{\"id\":[0,0,4,65]}";
+
+            assert_eq!(serde_json::to_string(&obj).unwrap(), json);
+            assert_eq!(serde_json::from_str::<U32SIDJson>(json).unwrap(), obj);
+        }
+
+        #[test]
+        fn invalid() {
+            for json in [
+                "{\"id\":[0,0,0,0,0,16,16]}",
+                "{\"id\":[0,0,0,0,0,16,16,1,0]}",
+                "{\"id\":[128,0,0,0,0,16,16,1]}",
+                "{\"id\":[0,0,0,0,0,16,16,256]}",
+                "{\"id\":1052673}",
+            ] {
+                assert!(serde_json::from_str::<I64SIDJson>(json).is_err(), "parsed {}", json);
+            }
+        }
+    }
+}
+
+/// de/serializes a snowflake to a pair of u32 values
+///
+/// structured to be used in `#[serde(with = "split_u32_id")]`. the id is
+/// written as `{"high": u32, "low": u32}` for clients that cannot handle
+/// large integers or strings but can do arithmetic on two smaller numbers.
+/// only i64 and u64 snowflakes are supported. for i64 snowflakes the top bit
+/// of `high` is the sign bit and must be clear.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::split_u32_id;
+///
+/// type I64SID = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(with = "split_u32_id")]
+///     id: I64SID,
+/// }
+///
+/// let my_struct = MyStruct {
+///     id: I64SID::from_parts(1 << 12, 1, 1).unwrap(),
+/// };
+///
+/// let json_string = serde_json::to_string(&my_struct).unwrap();
+///
+/// assert_eq!(json_string, "{\"id\":{\"high\":1,\"low\":4097}}");
+/// assert_eq!(split_u32_id::to_hi_lo(&my_struct.id), (1, 4097));
+/// ```
+pub mod split_u32_id {
+    use core::convert::TryFrom;
+
+    use serde::{ser, de, Serialize, Deserialize};
+    use snowcloud_core::traits;
+
+    use crate::error;
+
+    /// integers that can be split into two u32 values
+    pub trait SplitU32: Sized {
+        /// returns the high and low 32 bits of the integer
+        fn to_hi_lo(self) -> (u32, u32);
+
+        /// joins the high and low 32 bits into the integer. returns None if
+        /// the value is not valid for the integer
+        fn from_hi_lo(high: u32, low: u32) -> Option<Self>;
+    }
+
+    impl SplitU32 for u64 {
+        #[inline]
+        fn to_hi_lo(self) -> (u32, u32) {
+            ((self >> 32) as u32, self as u32)
+        }
+
+        #[inline]
+        fn from_hi_lo(high: u32, low: u32) -> Option<Self> {
+            Some((high as u64) << 32 | low as u64)
+        }
+    }
+
+    impl SplitU32 for i64 {
+        #[inline]
+        fn to_hi_lo(self) -> (u32, u32) {
+            (self as u64).to_hi_lo()
+        }
+
+        #[inline]
+        fn from_hi_lo(high: u32, low: u32) -> Option<Self> {
+            // a set sign bit would be a negative id
+            if high & 0x8000_0000 != 0 {
+                return None;
+            }
+
+            Some(u64::from_hi_lo(high, low)? as i64)
+        }
+    }
+
+    /// returns the high and low 32 bits of a snowflake
+    pub fn to_hi_lo<F>(flake: &F) -> (u32, u32)
+    where
+        F: traits::Id,
+        F::BaseType: SplitU32,
+    {
+        flake.id().to_hi_lo()
+    }
+
+    /// creates a snowflake from the high and low 32 bits of its id
+    ///
+    /// returns [`InvalidId`](crate::error::Error::InvalidId) if the joined
+    /// value is negative or not a valid snowflake
+    pub fn from_hi_lo<F>(high: u32, low: u32) -> error::Result<F>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: SplitU32,
+    {
+        let Some(num) = F::BaseType::from_hi_lo(high, low) else {
+            return Err(error::Error::InvalidId);
+        };
+
+        F::try_from(num).map_err(|_| error::Error::InvalidId)
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct HiLo {
+        high: u32,
+        low: u32,
+    }
+
+    /// serializes a given snowflake to a high and low pair
+    pub fn serialize<F, S>(flake: &F, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: traits::Id,
+        F::BaseType: SplitU32,
+        S: ser::Serializer
+    {
+        let (high, low) = to_hi_lo(flake);
+
+        HiLo { high, low }.serialize(serializer)
+    }
+
+    /// deserializes a given high and low pair to a snowflake
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: SplitU32,
+        D: de::Deserializer<'de>
+    {
+        let HiLo { high, low } = HiLo::deserialize(deserializer)?;
+
+        from_hi_lo(high, low).map_err(|_| de::Error::custom(format_args!(
+            "high {} and low {} are not a valid id. the sign bit of high must be clear for signed ids",
+            high,
+            low
+        )))
+    }
+
+    #[cfg(test)]
+    mod test {
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        use crate::serde_ext::split_u32_id;
+
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+        type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct I64SIDJson {
+            #[serde(with = "split_u32_id")]
+            id: I64SID,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U64SIDJson {
+            #[serde(with = "split_u32_id")]
+            id: U64SID,
+        }
+
+        #[test]
+        fn round_trip() {
+            let expected = [
+                (I64SID::NIL, "{\"id\":{\"high\":0,\"low\":0}}"),
+                (I64SID::from_parts(1, 1, 1).unwrap(), "{\"id\":{\"high\":0,\"low\":1052673}}"),
+                (I64SID::MAX, "{\"id\":{\"high\":2147483647,\"low\":4294967295}}"),
+            ];
+
+            for (id, json) in expected {
+                let obj = I64SIDJson { id };
+
+                assert_eq!(serde_json::to_string(&obj).unwrap(), json);
+                assert_eq!(serde_json::from_str::<I64SIDJson>(json).unwrap(), obj);
+            }
+
+            let json = "{\"id\":{\"high\":4294967295,\"low\":4294967295}}";
+            let obj = U64SIDJson { id: U64SID::MAX };
+
+            assert_eq!(serde_json::to_string(&obj).unwrap(), json);
+            assert_eq!(serde_json::from_str::<U64SIDJson>(json).unwrap(), obj);
+
+            assert_eq!(split_u32_id::to_hi_lo(&U64SID::MAX), (u32::MAX, u32::MAX));
+            assert_eq!(split_u32_id::from_hi_lo::<U64SID>(u32::MAX, u32::MAX).unwrap(), U64SID::MAX);
+        }
+
+        #[test]
+        fn sign_bit() {
+            assert!(matches!(
+                split_u32_id::from_hi_lo::<I64SID>(0x8000_0000, 0),
+                Err(crate::error::Error::InvalidId)
+            ));
+            assert!(split_u32_id::from_hi_lo::<I64SID>(u32::MAX, u32::MAX).is_err());
+
+            let err = serde_json::from_str::<I64SIDJson>("{\"id\":{\"high\":2147483648,\"low\":0}}")
+                .unwrap_err();
+
+            assert!(err.to_string().contains("sign bit of high must be clear"), "{}", err);
+        }
+
+        #[test]
+        fn invalid() {
+            for json in [
+                "{\"id\":{\"high\":-1,\"low\":0}}",
+                "{\"id\":{\"high\":0,\"low\":-1}}",
+                "{\"id\":{\"high\":4294967296,\"low\":0}}",
+                "{\"id\":{\"high\":0}}",
+                "{\"id\":{\"high\":0,\"low\":0,\"sign\":0}}",
+                "{\"id\":1052673}",
+            ] {
+                assert!(serde_json::from_str::<I64SIDJson>(json).is_err(), "parsed {}", json);
+            }
+        }
+    }
+}
+
+/// de/serializes a snowflake to big endian bytes
+///
+/// structured to be used in `#[serde(with = "bytes_id")]`. formats that do
+/// not have a bytes type (json) will use a sequence of u8 values instead.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::bytes_id;
+///
+/// type U128SID = snowcloud::u128::SingleIdFlake<64, 32, 32>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(with = "bytes_id")]
+///     id: U128SID,
+/// }
+///
+/// let my_struct = MyStruct {
+///     id: U128SID::from_parts(1, 1, 1).unwrap(),
+/// };
+///
+/// let json_string = serde_json::to_string(&my_struct).unwrap();
+///
+/// println!("{}", json_string);
+/// ```
+pub mod bytes_id {
+    use core::convert::TryFrom;
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::{ser, de};
+    use snowcloud_core::traits;
+
+    use super::BeBytes;
+
+    /// serializes a given snowflake to big endian bytes
+    pub fn serialize<F, S>(flake: &F, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: traits::Id,
+        F::BaseType: BeBytes,
+        S: ser::Serializer
+    {
+        serializer.serialize_bytes(&flake.id().to_be_vec())
+    }
+
+    /// deserializes big endian bytes to a snowflake
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: BeBytes,
+        D: de::Deserializer<'de>
+    {
+        deserializer.deserialize_bytes(BytesVisitor {
+            phantom: PhantomData
+        })
+    }
+
+    /// visitor for deserializing bytes to a snowflake
+    pub struct BytesVisitor<F> {
+        phantom: PhantomData<F>
+    }
+
+    impl<'de, F> de::Visitor<'de> for BytesVisitor<F>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: BeBytes
+    {
+        type Value = F;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "big endian bytes within the valid range of the Id")
+        }
+
+        fn visit_bytes<E>(self, b: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let Some(num) = BeBytes::from_be_slice(b) else {
+                return Err(E::invalid_length(b.len(), &self));
+            };
+
+            let Ok(flake) = TryFrom::try_from(num) else {
+                return Err(E::invalid_value(de::Unexpected::Bytes(b), &self));
+            };
+
+            Ok(flake)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::SeqAccess<'de>
+        {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(16));
+
+            while let Some(b) = seq.next_element::<u8>()? {
+                bytes.push(b);
+            }
+
+            self.visit_bytes(&bytes)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        use crate::serde_ext::bytes_id;
+
+        type U128SID = crate::u128::SingleIdFlake<64, 32, 32>;
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U128SIDJson {
+            #[serde(with = "bytes_id")]
+            id: U128SID,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct I64SIDJson {
+            #[serde(with = "bytes_id")]
+            id: I64SID,
+        }
+
+        #[test]
+        fn u128_bytes() {
+            let obj = U128SIDJson {
+                id: U128SID::from_parts(1, 1, 1).unwrap(),
+            };
+            let expected = "{\"id\":[0,0,0,0,0,0,0,1,0,0,0,1,0,0,0,1]}";
+
+            let json_string = serde_json::to_string(&obj)
+                .expect("failed to create json string");
+
+            assert_eq!(json_string, expected, "invalid json string");
+
+            let parsed: U128SIDJson = serde_json::from_str(expected)
+                .expect("failed to parse json string");
+
+            assert_eq!(parsed, obj, "invalid parsed id");
+        }
+
+        #[test]
+        fn i64_bytes() {
+            let obj = I64SIDJson {
+                id: I64SID::from_parts(1, 1, 1).unwrap(),
+            };
+            let expected = "{\"id\":[0,0,0,0,0,16,16,1]}";
+
+            let json_string = serde_json::to_string(&obj)
+                .expect("failed to create json string");
+
+            assert_eq!(json_string, expected, "invalid json string");
+
+            let parsed: I64SIDJson = serde_json::from_str(expected)
+                .expect("failed to parse json string");
+
+            assert_eq!(parsed, obj, "invalid parsed id");
+        }
+
+        #[test]
+        fn invalid_length() {
+            let result = serde_json::from_str::<U128SIDJson>("{\"id\":[0,0,0,1]}");
+
+            assert!(result.is_err(), "parsed id from invalid length bytes");
+        }
+    }
+}
+
+/// de/serializes a snowflake to an integer while accepting integers or
+/// strings
+///
+/// structured to be used in `#[serde(with = "any_id")]`. serializes the
+/// same as the snowflake itself but deserializing will also accept base 10
+/// number strings for clients that cannot handle large integers
+/// (javascript). formats that are not self describing do not support
+/// `deserialize_any` so they will only read the integer.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::any_id;
+///
+/// type I64SID = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(with = "any_id")]
+///     id: I64SID,
+/// }
+///
+/// let from_num: MyStruct = serde_json::from_str("{\"id\":1052673}").unwrap();
+/// let from_str: MyStruct = serde_json::from_str("{\"id\":\"1052673\"}").unwrap();
+///
+/// assert_eq!(from_num.id, from_str.id);
+/// assert_eq!(serde_json::to_string(&from_str).unwrap(), "{\"id\":1052673}");
+/// ```
+pub mod any_id {
+    use core::convert::TryFrom;
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::{ser, de};
+    use snowcloud_core::traits;
+
+    use super::FromStrRadix;
+
+    /// serializes a given snowflake to its integer
+    pub fn serialize<F, S>(flake: &F, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: traits::Id,
+        F::BaseType: ser::Serialize,
+        S: ser::Serializer
+    {
+        ser::Serialize::serialize(&flake.id(), serializer)
+    }
+
+    /// deserializes a given integer or string to a snowflake
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: FromStrRadix + de::Deserialize<'de>
+            + TryFrom<i64> + TryFrom<u64> + TryFrom<i128> + TryFrom<u128>,
+        D: de::Deserializer<'de>
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(AnyVisitor {
+                phantom: PhantomData
+            })
+        } else {
+            let num: F::BaseType = de::Deserialize::deserialize(deserializer)?;
+
+            TryFrom::try_from(num).map_err(|_| de::Error::custom("integer is not within the valid range of the Id"))
+        }
+    }
+
+    /// visitor for deserializing an integer or string to a snowflake
+    pub struct AnyVisitor<F> {
+        phantom: PhantomData<F>
+    }
+
+    /// converts an integer from a visitor to a snowflake
+    fn from_int<F, N, E>(value: N, unexpected: de::Unexpected) -> Result<F, E>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: TryFrom<N>,
+        E: de::Error,
+    {
+        let Ok(num) = TryFrom::try_from(value) else {
+            return Err(E::invalid_value(unexpected, &"integer within the valid range of the Id"));
+        };
+
+        let Ok(flake) = TryFrom::try_from(num) else {
+            return Err(E::invalid_value(unexpected, &"integer within the valid range of the Id"));
+        };
+
+        Ok(flake)
+    }
+
+    impl<'de, F> de::Visitor<'de> for AnyVisitor<F>
+    where
+        F: traits::Id + TryFrom<F::BaseType>,
+        F::BaseType: FromStrRadix + TryFrom<i64> + TryFrom<u64> + TryFrom<i128> + TryFrom<u128>,
+    {
+        type Value = F;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "integer or integer string within the valid range of the Id")
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            from_int(v, de::Unexpected::Signed(v))
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            from_int(v, de::Unexpected::Unsigned(v))
+        }
+
+        fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            from_int(v, de::Unexpected::Other("i128"))
+        }
+
+        fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            from_int(v, de::Unexpected::Other("u128"))
+        }
+
+        fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            from_int(super::float_to_int(v)?, de::Unexpected::Float(v))
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let Ok(num) = FromStrRadix::from_str_radix(s, 10) else {
+                return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+            };
+
+            let Ok(flake) = TryFrom::try_from(num) else {
+                return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+            };
+
+            Ok(flake)
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        use crate::serde_ext::any_id;
+
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+        type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+        type U128SID = crate::u128::SingleIdFlake<64, 32, 32>;
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct I64SIDJson {
+            #[serde(with = "any_id")]
+            id: I64SID,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U64SIDJson {
+            #[serde(with = "any_id")]
+            id: U64SID,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct U128SIDJson {
+            #[serde(with = "any_id")]
+            id: U128SID,
+        }
+
+        #[test]
+        fn integer_or_string() {
+            let expected = I64SIDJson {
+                id: I64SID::from_parts(1, 1, 1).unwrap(),
+            };
+
+            assert_eq!(serde_json::to_string(&expected).unwrap(), "{\"id\":1052673}");
+
+            for json in ["{\"id\":1052673}", "{\"id\":\"1052673\"}", "{\"id\":1052673.0}"] {
+                let parsed: I64SIDJson = serde_json::from_str(json)
+                    .expect("failed to parse json string");
+
+                assert_eq!(parsed, expected, "invalid parsed id for {}", json);
+            }
+
+            let parsed: U64SIDJson = serde_json::from_str("{\"id\":18446744073709551615}")
+                .expect("failed to parse json string");
+
+            assert_eq!(parsed.id, U64SID::MAX);
+
+            let parsed: U128SIDJson = serde_json::from_str("{\"id\":\"18446744078004518913\"}")
+                .expect("failed to parse json string");
+
+            assert_eq!(parsed.id, U128SID::from_parts(1, 1, 1).unwrap());
+        }
+
+        #[test]
+        fn invalid() {
+            for json in [
+                "{\"id\":-1}",
+                "{\"id\":\"-1\"}",
+                "{\"id\":\"\"}",
+                "{\"id\":\"0x101001\"}",
+                "{\"id\":18446744073709551615}",
+                "{\"id\":1052673.5}",
+                "{\"id\":true}",
+                "{\"id\":null}",
+            ] {
+                assert!(serde_json::from_str::<I64SIDJson>(json).is_err(), "parsed {}", json);
+            }
+        }
+
+        #[test]
+        fn bincode() {
+            let obj = I64SIDJson {
+                id: I64SID::from_parts(1, 1, 1).unwrap(),
+            };
+
+            let bytes = bincode::serialize(&obj).unwrap();
+
+            assert_eq!(bytes, 1052673i64.to_le_bytes());
+            assert_eq!(bincode::deserialize::<I64SIDJson>(&bytes).unwrap(), obj);
+
+            let obj = U128SIDJson {
+                id: U128SID::from_parts(1, 1, 1).unwrap(),
+            };
+
+            let bytes = bincode::serialize(&obj).unwrap();
+
+            assert_eq!(bincode::deserialize::<U128SIDJson>(&bytes).unwrap(), obj);
+            assert!(bincode::deserialize::<I64SIDJson>(&(-1i64).to_le_bytes()).is_err());
+        }
+    }
+}
+
+/// de/serializes a snowflake to a struct of its named parts
+///
+/// structured to be used in `#[serde(with = "parts_id")]`. the snowflake is
+/// written as the named parts of the flake, e.g.
+/// [`SingleIdParts`](crate::i64::SingleIdParts), and deserializing will run
+/// the same checks as `from_parts`. unknown fields are rejected.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::parts_id;
+///
+/// type I64DID = snowcloud::i64::DualIdFlake<43, 4, 4, 12>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(with = "parts_id")]
+///     id: I64DID,
+/// }
+///
+/// let my_struct = MyStruct {
+///     id: I64DID::from_parts(1, 2, 3, 4).unwrap(),
+/// };
+///
+/// let json_string = serde_json::to_string(&my_struct).unwrap();
+///
+/// assert_eq!(
+///     json_string,
+///     "{\"id\":{\"timestamp\":1,\"primary_id\":2,\"secondary_id\":3,\"sequence\":4}}"
+/// );
+/// ```
+pub mod parts_id {
+    use serde::{ser, de};
+
+    use crate::error;
+
+    /// a snowflake that can be split into named parts
+    pub trait NamedParts: Sized {
+        type Parts;
+
+        /// splits the snowflake into its named parts
+        fn into_parts_named(self) -> Self::Parts;
+
+        /// creates the snowflake from named parts
+        fn from_parts_named(parts: Self::Parts) -> error::Result<Self>;
+    }
+
+    macro_rules! named_parts {
+        ($flake:ident, $parts:path, $($generic:ident),+) => {
+            impl<$(const $generic: u8),+> NamedParts for $flake<$($generic),+> {
+                type Parts = $parts;
+
+                #[inline]
+                fn into_parts_named(self) -> Self::Parts {
+                    $flake::into_parts_named(self)
+                }
+
+                #[inline]
+                fn from_parts_named(parts: Self::Parts) -> error::Result<Self> {
+                    $flake::from_parts_named(parts)
+                }
+            }
+        };
+    }
+
+    mod impls {
+        use super::NamedParts;
+        use crate::error;
+
+        mod i64 {
+            use super::*;
+            use crate::i64::{SingleIdFlake, DualIdFlake, TripleIdFlake};
+
+            named_parts!(SingleIdFlake, crate::i64::SingleIdParts, TS, PID, SEQ);
+            named_parts!(DualIdFlake, crate::i64::DualIdParts, TS, PID, SID, SEQ);
+            named_parts!(TripleIdFlake, crate::i64::TripleIdParts, TS, PID, SID, TID, SEQ);
+        }
+
+        mod u64 {
+            use super::*;
+            use crate::u64::{SingleIdFlake, DualIdFlake, TripleIdFlake};
+
+            named_parts!(SingleIdFlake, crate::u64::SingleIdParts, TS, PID, SEQ);
+            named_parts!(DualIdFlake, crate::u64::DualIdParts, TS, PID, SID, SEQ);
+            named_parts!(TripleIdFlake, crate::u64::TripleIdParts, TS, PID, SID, TID, SEQ);
+        }
+    }
+
+    /// serializes a given snowflake to its named parts
+    pub fn serialize<F, S>(flake: &F, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: NamedParts + Copy,
+        F::Parts: ser::Serialize,
+        S: ser::Serializer
+    {
+        ser::Serialize::serialize(&flake.into_parts_named(), serializer)
+    }
+
+    /// deserializes given named parts to a snowflake
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+    where
+        F: NamedParts,
+        F::Parts: de::Deserialize<'de>,
+        D: de::Deserializer<'de>
+    {
+        let parts = de::Deserialize::deserialize(deserializer)?;
+
+        F::from_parts_named(parts)
+            .map_err(|err| de::Error::custom(format_args!("invalid snowflake parts: {}", err)))
+    }
+
+    #[cfg(test)]
+    mod test {
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        use crate::serde_ext::parts_id;
+
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+        type I64DID = crate::i64::DualIdFlake<43, 4, 4, 12>;
+        type U64SID = crate::u64::SingleIdFlake<44, 8, 12>;
+        type U64DID = crate::u64::DualIdFlake<44, 4, 4, 12>;
+
+        macro_rules! parts_json {
+            ($name:ident, $flake:ty) => {
+                #[derive(Debug, PartialEq, Serialize, Deserialize)]
+                struct $name {
+                    #[serde(with = "parts_id")]
+                    id: $flake,
+                }
+            };
+        }
+
+        parts_json!(I64SIDJson, I64SID);
+        parts_json!(I64DIDJson, I64DID);
+        parts_json!(U64SIDJson, U64SID);
+        parts_json!(U64DIDJson, U64DID);
+
+        #[test]
+        fn round_trip() {
+            let single = "{\"id\":{\"timestamp\":1,\"primary_id\":2,\"sequence\":3}}";
+            let dual = "{\"id\":{\"timestamp\":1,\"primary_id\":2,\"secondary_id\":3,\"sequence\":4}}";
+
+            let obj = I64SIDJson { id: I64SID::from_parts(1, 2, 3).unwrap() };
+
+            assert_eq!(serde_json::to_string(&obj).unwrap(), single);
+            assert_eq!(serde_json::from_str::<I64SIDJson>(single).unwrap(), obj);
+
+            let obj = U64SIDJson { id: U64SID::from_parts(1, 2, 3).unwrap() };
+
+            assert_eq!(serde_json::to_string(&obj).unwrap(), single);
+            assert_eq!(serde_json::from_str::<U64SIDJson>(single).unwrap(), obj);
+
+            let obj = I64DIDJson { id: I64DID::from_parts(1, 2, 3, 4).unwrap() };
+
+            assert_eq!(serde_json::to_string(&obj).unwrap(), dual);
+            assert_eq!(serde_json::from_str::<I64DIDJson>(dual).unwrap(), obj);
+
+            let obj = U64DIDJson { id: U64DID::from_parts(1, 2, 3, 4).unwrap() };
+
+            assert_eq!(serde_json::to_string(&obj).unwrap(), dual);
+            assert_eq!(serde_json::from_str::<U64DIDJson>(dual).unwrap(), obj);
+        }
+
+        #[test]
+        fn out_of_range() {
+            let err = serde_json::from_str::<I64SIDJson>(
+                "{\"id\":{\"timestamp\":1,\"primary_id\":2,\"sequence\":4096}}"
+            ).unwrap_err();
+
+            assert!(err.to_string().contains("invalid snowflake parts: sequence invalid"), "{}", err);
+
+            let err = serde_json::from_str::<U64DIDJson>(
+                "{\"id\":{\"timestamp\":1,\"primary_id\":16,\"secondary_id\":3,\"sequence\":4}}"
+            ).unwrap_err();
+
+            assert!(err.to_string().contains("invalid snowflake parts: id seg invalid"), "{}", err);
+        }
+
+        #[test]
+        fn unknown_and_missing_fields() {
+            let err = serde_json::from_str::<I64SIDJson>(
+                "{\"id\":{\"timestamp\":1,\"primary_id\":2,\"secondary_id\":3,\"sequence\":4}}"
+            ).unwrap_err();
+
+            assert!(err.to_string().contains("unknown field `secondary_id`"), "{}", err);
+
+            let err = serde_json::from_str::<I64DIDJson>(
+                "{\"id\":{\"timestamp\":1,\"primary_id\":2,\"sequence\":4}}"
+            ).unwrap_err();
+
+            assert!(err.to_string().contains("missing field `secondary_id`"), "{}", err);
+
+            assert!(serde_json::from_str::<I64SIDJson>("{\"id\":1052673}").is_err());
+        }
+    }
+}
+
+/// de/serializes a snowflake along with the epoch it was created with
+///
+/// a snowflake only stores the time since the epoch of its generator, so
+/// [`EpochedId`](with_epoch::EpochedId) keeps the two together and is
+/// written as `{"epoch": 1679587200000, "id": 1052673}`. the id uses the
+/// normal serde impl of the snowflake. use
+/// [`expect_epoch`](with_epoch::EpochedId::expect_epoch) when reading ids
+/// to catch ids created with a different epoch.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+/// use snowcloud::serde_ext::with_epoch::{self, EpochedId};
+///
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+/// type MyCloud = snowcloud::Generator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     #[serde(with = "with_epoch")]
+///     id: EpochedId<MyFlake>,
+/// }
+///
+/// let mut cloud = MyCloud::new(START_TIME, 1).unwrap();
+/// let flake = cloud.next_id().unwrap();
+///
+/// let my_struct = MyStruct {
+///     id: EpochedId::new(&cloud, flake),
+/// };
+///
+/// let json_string = serde_json::to_string(&my_struct).unwrap();
+/// let parsed: MyStruct = serde_json::from_str(&json_string).unwrap();
+///
+/// assert_eq!(parsed.id.expect_epoch(START_TIME).unwrap(), flake);
+/// ```
+pub mod with_epoch {
+    use std::time::SystemTime;
+
+    use serde::{ser, de, Serialize, Deserialize};
+    use snowcloud_core::traits;
+
+    use crate::error;
+
+    /// a snowflake along with the epoch in milliseconds it was created with
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct EpochedId<F> {
+        pub epoch_millis: u64,
+        pub flake: F,
+    }
+
+    impl<F> EpochedId<F> {
+        /// pairs the snowflake with the epoch of the given generator
+        pub fn new<G>(generator: &G, flake: F) -> Self
+        where
+            G: traits::EpochMillis
+        {
+            EpochedId {
+                epoch_millis: generator.epoch_millis(),
+                flake,
+            }
+        }
+
+        /// pairs the snowflake with the given epoch in milliseconds
+        pub fn with_epoch_millis(epoch_millis: u64, flake: F) -> Self {
+            EpochedId { epoch_millis, flake }
+        }
+
+        /// returns when the snowflake was created using the stored epoch
+        pub fn created_at(&self) -> Option<SystemTime>
+        where
+            F: traits::UnixMillis
+        {
+            self.flake.created_at(self.epoch_millis)
+        }
+
+        /// returns the snowflake if it was created with the expected epoch
+        ///
+        /// returns [`EpochMismatch`](crate::error::Error::EpochMismatch) if
+        /// the epochs are different
+        pub fn expect_epoch(self, epoch_millis: u64) -> error::Result<F> {
+            if self.epoch_millis != epoch_millis {
+                return Err(error::Error::EpochMismatch);
+            }
+
+            Ok(self.flake)
+        }
+
+        /// returns the snowflake without checking the epoch
+        pub fn into_flake(self) -> F {
+            self.flake
+        }
+    }
+
+    #[derive(Serialize)]
+    struct EpochedIdRef<'a, F> {
+        epoch: u64,
+        id: &'a F,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct EpochedIdOwned<F> {
+        epoch: u64,
+        id: F,
+    }
+
+    impl<F> Serialize for EpochedId<F>
+    where
+        F: Serialize
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer
+        {
+            EpochedIdRef {
+                epoch: self.epoch_millis,
+                id: &self.flake,
+            }.serialize(serializer)
+        }
+    }
+
+    impl<'de, F> Deserialize<'de> for EpochedId<F>
+    where
+        F: Deserialize<'de>
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>
+        {
+            let owned = EpochedIdOwned::deserialize(deserializer)?;
+
+            Ok(EpochedId {
+                epoch_millis: owned.epoch,
+                flake: owned.id,
+            })
+        }
+    }
+
+    /// serializes a given snowflake and epoch
+    pub fn serialize<F, S>(epoched: &EpochedId<F>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        F: Serialize,
+        S: ser::Serializer
+    {
+        epoched.serialize(serializer)
+    }
+
+    /// deserializes a given snowflake and epoch
+    pub fn deserialize<'de, F, D>(deserializer: D) -> Result<EpochedId<F>, D::Error>
+    where
+        F: Deserialize<'de>,
+        D: de::Deserializer<'de>
+    {
+        EpochedId::deserialize(deserializer)
+    }
+
+    #[cfg(test)]
+    mod test {
+        use std::time::{Duration, SystemTime};
+
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+        use snowcloud_core::traits;
+
+        use super::EpochedId;
+        use crate::serde_ext::with_epoch;
+
+        type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+
+        const EPOCH: u64 = 1679587200000;
+
+        struct TestGenerator;
+
+        impl traits::EpochMillis for TestGenerator {
+            fn epoch_millis(&self) -> u64 {
+                EPOCH
+            }
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct I64SIDJson {
+            #[serde(with = "with_epoch")]
+            id: EpochedId<I64SID>,
+        }
+
+        #[test]
+        fn json_shape() {
+            let obj = I64SIDJson {
+                id: EpochedId::new(&TestGenerator, I64SID::from_parts(1, 1, 1).unwrap()),
+            };
+            let json_string = serde_json::to_string(&obj).unwrap();
+
+            #[cfg(not(feature = "serde-human-string"))]
+            assert_eq!(json_string, "{\"id\":{\"epoch\":1679587200000,\"id\":1052673}}");
+            #[cfg(feature = "serde-human-string")]
+            assert_eq!(json_string, "{\"id\":{\"epoch\":1679587200000,\"id\":\"1052673\"}}");
+
+            assert_eq!(serde_json::from_str::<I64SIDJson>(&json_string).unwrap(), obj);
+
+            assert!(serde_json::from_str::<I64SIDJson>("{\"id\":{\"id\":1052673}}").is_err());
+            assert!(serde_json::from_str::<I64SIDJson>(
+                "{\"id\":{\"epoch\":1679587200000,\"id\":1052673,\"other\":1}}"
+            ).is_err());
+        }
+
+        #[test]
+        fn created_at() {
+            let epoched = EpochedId::with_epoch_millis(EPOCH, I64SID::from_parts(1000, 1, 1).unwrap());
+            let expected = SystemTime::UNIX_EPOCH + Duration::from_millis(EPOCH + 1000);
+
+            assert_eq!(epoched.created_at(), Some(expected));
+        }
+
+        #[test]
+        fn epoch_mismatch() {
+            let flake = I64SID::from_parts(1000, 1, 1).unwrap();
+            let json_string = serde_json::to_string(&EpochedId::with_epoch_millis(EPOCH, flake)).unwrap();
+            let parsed: EpochedId<I64SID> = serde_json::from_str(&json_string).unwrap();
+
+            assert_eq!(parsed.expect_epoch(EPOCH).unwrap(), flake);
+            assert!(matches!(
+                parsed.expect_epoch(EPOCH + 1),
+                Err(crate::error::Error::EpochMismatch)
+            ));
+
+            // same id with a different epoch is a different time
+            let other = EpochedId::with_epoch_millis(EPOCH + 1, flake);
+
+            assert_ne!(parsed.created_at(), other.created_at());
+        }
+    }
+}
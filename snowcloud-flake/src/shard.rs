@@ -0,0 +1,163 @@
+//! stable shard selection for ids
+//!
+//! taking `id % shards` directly mostly depends on the sequence bits which
+//! are usually 0 or close to it so the lower shards will get most of the
+//! ids. the `shard` method on the snowflakes hashes the full id first so
+//! every bit of the id affects the result.
+//!
+//! the algorithm is part of the public api and will not change between
+//! versions since the result is expected to be stored or used to route
+//! between services running different versions:
+//!
+//! 1. the id is converted to its unsigned bits. ids smaller than 64 bits
+//!    are zero extended to a u64
+//! 2. a 64 bit id is hashed with the splitmix64 finalizer. a 128 bit id
+//!    hashes the low 64 bits, xors the result with the high 64 bits, and
+//!    hashes that again
+//! 3. the shard is the hash modulo the number of shards
+//!
+//! the `shard_by_primary` method skips the hash and uses the primary id
+//! modulo the number of shards so every id from the same primary id goes to
+//! the same shard. [`Sonyflake`](crate::presets::Sonyflake) does the same
+//! with `shard_by_machine`.
+//!
+//! ```rust
+//! type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! let flake = MyFlake::from_parts(1000, 5, 1).unwrap();
+//!
+//! assert_eq!(flake.shard(16), snowcloud::flake::shard::shard_u64(flake.id() as u64, 16));
+//! assert_eq!(flake.shard_by_primary(4), 1);
+//! ```
+
+use crate::opaque::mix;
+
+/// hashes a 64 bit id with the splitmix64 finalizer
+#[inline]
+pub fn hash_u64(id: u64) -> u64 {
+    mix(id)
+}
+
+/// hashes a 128 bit id by folding the high bits into the hash of the low
+/// bits
+#[inline]
+pub fn hash_u128(id: u128) -> u64 {
+    mix(mix(id as u64) ^ (id >> 64) as u64)
+}
+
+/// returns the shard of a 64 bit id
+///
+/// panics if `shards` is 0
+pub fn shard_u64(id: u64, shards: u32) -> u32 {
+    reduce(hash_u64(id), shards)
+}
+
+/// returns the shard of a 128 bit id
+///
+/// panics if `shards` is 0
+pub fn shard_u128(id: u128, shards: u32) -> u32 {
+    reduce(hash_u128(id), shards)
+}
+
+/// returns the shard of an id segment without hashing it
+///
+/// panics if `shards` is 0
+pub fn shard_segment(segment: u128, shards: u32) -> u32 {
+    assert!(shards != 0, "shards must be greater than 0");
+
+    (segment % shards as u128) as u32
+}
+
+#[inline]
+fn reduce(hash: u64, shards: u32) -> u32 {
+    assert!(shards != 0, "shards must be greater than 0");
+
+    (hash % shards as u64) as u32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type I64SID = crate::i64::SingleIdFlake<43, 8, 12>;
+
+    #[test]
+    fn stable_hashes() {
+        assert_eq!(hash_u64(0), 0);
+        assert_eq!(hash_u64(1), 0x5692161d100b05e5);
+        assert_eq!(hash_u64(1052673), 0xe55300878c9d3267);
+        assert_eq!(hash_u64(u64::MAX), 0xb4d055fcf2cbbd7b);
+
+        assert_eq!(hash_u128(0), 0);
+        assert_eq!(hash_u128(1), 0x7ab40e090f363a7d);
+        assert_eq!(hash_u128(1 << 64), 0x5692161d100b05e5);
+        assert_eq!(hash_u128(u128::MAX), 0xd6bdf7544574c9cb);
+    }
+
+    #[test]
+    fn stable_shards() {
+        assert_eq!(shard_u64(1, 16), 5);
+        assert_eq!(shard_u64(1052673, 16), 7);
+        assert_eq!(shard_u64(1052673, 10), 7);
+        assert_eq!(shard_u128(1052673, 16), shard_u64(hash_u64(1052673), 16));
+
+        assert_eq!(shard_segment(13, 4), 1);
+        assert_eq!(shard_segment(u128::MAX, 3), 0);
+
+        let expected = [10, 15, 14, 14, 12, 7, 11, 7];
+        let found: Vec<u32> = (0..8)
+            .map(|seq| I64SID::from_parts(1000, 1, seq).unwrap().shard(16))
+            .collect();
+
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn uniform() {
+        const SHARDS: u32 = 16;
+        const IDS: i64 = 1 << 16;
+
+        let mut counts = [0u64; SHARDS as usize];
+
+        // consecutive ids from a few ticks like a busy generator would make
+        for tsm in 0..16 {
+            for seq in 0..(IDS / 16) {
+                let flake = I64SID::from_parts(1000 + tsm, 1, seq).unwrap();
+
+                counts[flake.shard(SHARDS) as usize] += 1;
+            }
+        }
+
+        let expected = IDS as f64 / SHARDS as f64;
+        let chi_square: f64 = counts.iter()
+            .map(|count| {
+                let diff = *count as f64 - expected;
+
+                diff * diff / expected
+            })
+            .sum();
+
+        // 15 degrees of freedom. 37.7 is the 0.001 critical value
+        assert!(chi_square < 37.7, "chi square {} for {:?}", chi_square, counts);
+
+        // the first id of each tick always lands on the same shard with a
+        // plain modulo
+        let mut first = [0u64; SHARDS as usize];
+
+        for tsm in 0..64 {
+            let flake = I64SID::from_parts(1000 + tsm, 1, 0).unwrap();
+
+            assert_eq!(flake.id() % SHARDS as i64, 0);
+
+            first[flake.shard(SHARDS) as usize] += 1;
+        }
+
+        assert!(first.iter().filter(|count| **count != 0).count() > 8, "{:?}", first);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_shards() {
+        shard_u64(1, 0);
+    }
+}
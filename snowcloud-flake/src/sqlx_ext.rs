@@ -0,0 +1,137 @@
+//! sqlx Type, Encode, and Decode for snowflakes
+//!
+//! the impls are generic over the database and defer to the impls for `i64`
+//! so any driver that supports `i64` (`BIGINT` / `INT8` / `INTEGER`) will
+//! also accept snowflakes. the `sqlx-postgres`, `sqlx-mysql`, and
+//! `sqlx-sqlite` features only enable the matching sqlx driver.
+//!
+//! i64 snowflakes are written as is and will fail to decode if the value
+//! from the database is not a valid id for the layout.
+//!
+//! u64 snowflakes are also stored as `i64` since most databases do not have
+//! an unsigned 64 bit integer. the bits of the id are reinterpreted so the
+//! full range will round trip but ids with the top bit set will show up as
+//! negative numbers in the database and will sort before the other ids. if
+//! that is a problem use a layout that leaves the top bit unused.
+//!
+//! ```rust,ignore
+//! type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! let flake: MyFlake = sqlx::query_scalar("select id from records where id = $1")
+//!     .bind(flake)
+//!     .fetch_one(&pool)
+//!     .await?;
+//! ```
+//!
+//! for the compile time checked macros the column will be reported as `i64`
+//! so use a type override to get the snowflake back.
+//!
+//! ```rust,ignore
+//! let record = sqlx::query!(r#"select id as "id: MyFlake" from records"#)
+//!     .fetch_one(&pool)
+//!     .await?;
+//! ```
+
+use sqlx::{Database, Type, Encode, Decode};
+use sqlx::database::{HasArguments, HasValueRef};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+
+use crate::i64::SegmentBits;
+
+macro_rules! i64_impls {
+    ([$($gen:tt)*] $flake:ty $(where $($bound:tt)*)?) => {
+        impl<DB: Database, $($gen)*> Type<DB> for $flake
+        where
+            i64: Type<DB>,
+            $($($bound)*)?
+        {
+            fn type_info() -> DB::TypeInfo {
+                <i64 as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <i64 as Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB: Database, $($gen)*> Encode<'q, DB> for $flake
+        where
+            i64: Encode<'q, DB>,
+            $($($bound)*)?
+        {
+            fn encode_by_ref(&self, buf: &mut <DB as HasArguments<'q>>::ArgumentBuffer) -> IsNull {
+                self.id().encode_by_ref(buf)
+            }
+
+            fn size_hint(&self) -> usize {
+                self.id().size_hint()
+            }
+        }
+
+        impl<'r, DB: Database, $($gen)*> Decode<'r, DB> for $flake
+        where
+            i64: Decode<'r, DB>,
+            $($($bound)*)?
+        {
+            fn decode(value: <DB as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+                let int = <i64 as Decode<'r, DB>>::decode(value)?;
+
+                Self::try_from(&int).map_err(Into::into)
+            }
+        }
+    };
+}
+
+macro_rules! u64_impls {
+    ([$($gen:tt)*] $flake:ty) => {
+        impl<DB: Database, $($gen)*> Type<DB> for $flake
+        where
+            i64: Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <i64 as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <i64 as Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB: Database, $($gen)*> Encode<'q, DB> for $flake
+        where
+            i64: Encode<'q, DB>,
+        {
+            fn encode_by_ref(&self, buf: &mut <DB as HasArguments<'q>>::ArgumentBuffer) -> IsNull {
+                (self.id() as i64).encode_by_ref(buf)
+            }
+
+            fn size_hint(&self) -> usize {
+                (self.id() as i64).size_hint()
+            }
+        }
+
+        impl<'r, DB: Database, $($gen)*> Decode<'r, DB> for $flake
+        where
+            i64: Decode<'r, DB>,
+        {
+            fn decode(value: <DB as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+                let int = <i64 as Decode<'r, DB>>::decode(value)?;
+
+                Self::try_from(&(int as u64)).map_err(Into::into)
+            }
+        }
+    };
+}
+
+i64_impls!([const TS: u8, const PID: u8, const SEQ: u8] crate::i64::SingleIdFlake<TS, PID, SEQ>);
+i64_impls!([const TS: u8, const PID: u8, const SEQ: u8] crate::i64::SingleIdFlakeSec<TS, PID, SEQ>);
+i64_impls!([const TS: u8, const PID: u8, const SID: u8, const SEQ: u8] crate::i64::DualIdFlake<TS, PID, SID, SEQ>);
+i64_impls!([const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8] crate::i64::TripleIdFlake<TS, PID, SID, TID, SEQ>);
+i64_impls!([const TS: u8, const SEQ: u8, const N: usize, B] crate::i64::NIdFlake<TS, SEQ, N, B> where B: SegmentBits<N>);
+
+u64_impls!([const TS: u8, const PID: u8, const SEQ: u8] crate::u64::SingleIdFlake<TS, PID, SEQ>);
+u64_impls!([const TS: u8, const PID: u8, const SEQ: u8] crate::u64::SingleIdFlakeMicros<TS, PID, SEQ>);
+u64_impls!([const TS: u8, const PID: u8, const SID: u8, const SEQ: u8] crate::u64::DualIdFlake<TS, PID, SID, SEQ>);
+u64_impls!([const TS: u8, const PID: u8, const SID: u8, const TID: u8, const SEQ: u8] crate::u64::TripleIdFlake<TS, PID, SID, TID, SEQ>);
+u64_impls!([] crate::presets::Sonyflake);
@@ -0,0 +1,117 @@
+//! helpers for working with the [`time`] crate
+//!
+//! provides [`CreatedAt`] for any snowflake that implements
+//! [`UnixMillis`](snowcloud_core::traits::UnixMillis) along with converting
+//! an [`OffsetDateTime`] into an epoch that can be given to a generator.
+//!
+//! ```rust
+//! use time::macros::datetime;
+//! use snowcloud::flake::time_ext::{self, CreatedAt};
+//!
+//! type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! let epoch = time_ext::epoch_millis(datetime!(2023-03-17 19:45:37 UTC)).unwrap();
+//! let flake = MyFlake::from_parts(1000, 1, 1).unwrap();
+//!
+//! assert_eq!(
+//!     flake.created_at_rfc3339(epoch).unwrap(),
+//!     "2023-03-17T19:45:38Z"
+//! );
+//! ```
+
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use snowcloud_core::traits;
+
+/// converts a date time into an epoch in milliseconds
+///
+/// returns `None` if the date time is before the unix epoch or does not fit
+/// in a u64
+pub fn epoch_millis(dt: OffsetDateTime) -> Option<u64> {
+    u64::try_from(dt.unix_timestamp_nanos() / 1_000_000).ok()
+}
+
+/// formats a date time as an RFC3339 string
+///
+/// returns `None` if the date time cannot be represented by RFC3339 like a
+/// year after 9999
+pub fn to_rfc3339(dt: &OffsetDateTime) -> Option<String> {
+    dt.format(&Rfc3339).ok()
+}
+
+/// creation time of a snowflake as an [`OffsetDateTime`]
+pub trait CreatedAt {
+    /// returns when the snowflake was created using the given epoch in
+    /// milliseconds
+    ///
+    /// `None` will be returned if the time cannot be represented
+    fn created_at_odt(&self, epoch_millis: u64) -> Option<OffsetDateTime>;
+
+    /// returns when the snowflake was created as an RFC3339 string
+    fn created_at_rfc3339(&self, epoch_millis: u64) -> Option<String> {
+        to_rfc3339(&self.created_at_odt(epoch_millis)?)
+    }
+}
+
+impl<F> CreatedAt for F
+where
+    F: traits::UnixMillis,
+{
+    fn created_at_odt(&self, epoch_millis: u64) -> Option<OffsetDateTime> {
+        let millis = self.unix_millis(epoch_millis).ok()?;
+
+        OffsetDateTime::from_unix_timestamp_nanos(millis as i128 * 1_000_000).ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use time::macros::datetime;
+
+    use crate::i64::SingleIdFlake;
+    use crate::u64::DualIdFlake;
+
+    const EPOCH: u64 = 1679082337000;
+
+    #[test]
+    fn matches_unix_millis() {
+        let ids = [
+            0,
+            1052673,
+            3276799999999,
+            7042542035239350273,
+            i64::MAX,
+        ];
+
+        for id in ids {
+            let flake = SingleIdFlake::<43, 8, 12>::try_from(&id).unwrap();
+            let millis = flake.unix_millis(EPOCH).unwrap();
+            let odt = flake.created_at_odt(EPOCH).unwrap();
+
+            assert_eq!(epoch_millis(odt), Some(millis), "time and millis differ for {}", id);
+            assert_eq!(odt.millisecond() as u64, millis % 1_000, "invalid millisecond for {}", id);
+        }
+
+        let flake = DualIdFlake::<44, 4, 4, 12>::from_parts(1, 1, 1, 1).unwrap();
+
+        assert_eq!(epoch_millis(flake.created_at_odt(EPOCH).unwrap()), Some(EPOCH + 1));
+    }
+
+    #[test]
+    fn epoch_from_datetime() {
+        assert_eq!(epoch_millis(datetime!(2023-03-17 19:45:37 UTC)), Some(EPOCH));
+        assert_eq!(epoch_millis(datetime!(2023-03-17 21:45:37 +2)), Some(EPOCH));
+        assert_eq!(epoch_millis(datetime!(1969-12-31 23:59:59 UTC)), None);
+    }
+
+    #[test]
+    fn rfc3339() {
+        let flake = SingleIdFlake::<43, 8, 12>::from_parts(1, 1, 1).unwrap();
+
+        assert_eq!(flake.created_at_rfc3339(EPOCH).unwrap(), "2023-03-17T19:45:37.001Z");
+        assert_eq!(flake.created_at_rfc3339(u64::MAX), None);
+    }
+}
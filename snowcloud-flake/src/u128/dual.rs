@@ -0,0 +1,1191 @@
+use std::hash::Hasher;
+use std::ops::RangeInclusive;
+use std::time::{Duration, SystemTime};
+
+use snowcloud_core::traits;
+
+#[cfg(feature = "serde")]
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{de, ser};
+
+use crate::error;
+use crate::Segments;
+
+/// u128 Snowflake with 2 id segments
+///
+/// the format is as follows with a 64 bit timestamp, 16 bit primary id, 16
+/// bit secondary id, and 32 bit sequence:
+///
+/// ```text
+///  64 bits   - 16 bits    - 16 bits      - 32 bits
+///  |       |   |        |   |          |   |      |
+/// 128     65  64       49  48         33  32      1
+///  timestamp   primary id   secondary id   sequence
+/// ```
+///
+/// bit values for each segment can be specified by `TS`, `PID`, `SID`, and
+/// `SEQ`. the total amount of bits should equal 128.
+///
+/// Note: the total is checked at compile time once the snowflake is used and
+/// will fail to compile if it does not equal 128. the `relaxed-layout`
+/// feature will only require the total to be less than or equal to 128.
+///
+/// # Timestamp
+///
+/// timestamp is in milliseconds with a bit value specified by the `TS` const.
+/// the snowflake holds the duration value of when the snowflake was created
+/// and the timestamp will be pulled from that. generators provide the
+/// timestamp as a u64 so anything above 64 bits will not be used.
+///
+/// Note: when creating a snowflake outside of a generator the duration will
+/// only be as accurate as the provided ts.
+///
+/// # Primary Id
+///
+/// specified by the `PID` const. used to help differentiate ids outside of 
+/// the timestamp and sequence values. an example representation could be 
+/// different server ids if being used across multiple machines in a web 
+/// server.
+///
+/// # Secondary Id
+///
+/// specified by the `SID` const. similar to the primary but for more 
+/// distinction. example could different instances on a single server or a
+/// thread id
+///
+/// # Sequence
+///
+/// specified by the `SEQ` const. indicates the count of when the snowflake 
+/// was generated in the same millisecond.
+///
+/// # De/Serialize
+///
+/// with the `serde` feature you can de/serialize a snowflake to and from a
+/// base 10 string by default since most formats are not able to handle 128
+/// bit integers.
+///
+/// ```rust
+/// use serde::{Serialize, Deserialize};
+///
+/// type MyFlake = snowcloud::u128::DualIdFlake<64, 16, 16, 32>;
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct MyStruct {
+///     id: MyFlake
+/// }
+///
+/// let my_struct = MyStruct {
+///     id: MyFlake::from_parts(1, 1, 1, 1).unwrap(),
+/// };
+///
+/// let json_string = serde_json::to_string(&my_struct).unwrap();
+///
+/// println!("{}", json_string);
+/// ```
+///
+/// to store the id as bytes check out
+/// [`bytes_id`](crate::serde_ext::bytes_id)
+///
+/// # Example Usage
+///
+/// ```rust
+/// type MyFlake = snowcloud::u128::DualIdFlake<64, 16, 16, 32>;
+/// type MyCloud = snowcloud::Generator<MyFlake>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let mut cloud = MyCloud::new(START_TIME, (1u128, 1u128))
+///     .expect("failed to create MyCloud");
+/// let flake: MyFlake = cloud.next_id()
+///     .expect("failed to create snowflake");
+///
+/// let id: u128 = flake.into();
+/// println!("{}", id);
+///
+/// let and_back: MyFlake = id.try_into()
+///     .expect("invalid u128 was provided");
+/// println!("{:?}", and_back);
+/// ```
+#[derive(Eq, Clone, Copy)]
+pub struct DualIdFlake<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> {
+    pub(crate) dur: Option<Duration>,
+    pub(crate) tsm: u128,
+    pub(crate) pid: u128,
+    pub(crate) sid: u128,
+    pub(crate) seq: u128,
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> DualIdFlake<TS, PID, SID, SEQ> {
+    /// max value that a timestamp can be.
+    /// `(1 << TS as u128) - 1`
+    pub const MAX_TIMESTAMP: u128 = (1 << TS as u128) - 1;
+    /// max value that a primary id can be.
+    /// `(1 << PID as u128) - 1`
+    pub const MAX_PRIMARY_ID: u128 = (1 << PID as u128) - 1;
+    /// max value that a secondary id can be.
+    /// `(1 << SID as u128) - 1`
+    pub const MAX_SECONDARY_ID: u128 = (1 << SID as u128) - 1;
+    /// max value a sequence can be.
+    /// `(1 << SEQ as u128) - 1`
+    pub const MAX_SEQUENCE: u128 = (1 << SEQ as u128) - 1;
+
+    /// total bits to shift the timestamp.
+    /// `PID as u128 + SID as u128 + SEQ as u128`
+    pub const TIMESTAMP_SHIFT: u128 = PID as u128 + SID as u128 + SEQ as u128;
+    /// total bits to shift the primary id
+    /// `SID as u128 + SEQ as u128`
+    pub const PRIMARY_ID_SHIFT: u128 = SID as u128 + SEQ as u128;
+    /// total bits to shift the secondary id
+    /// `SEQ as u128`
+    pub const SECONDARY_ID_SHIFT: u128 = SEQ as u128;
+
+    /// bit mask for timestamp
+    /// `Self::MAX_TIMESTAMP << Self::TIMESTAMP_SHIFT`
+    pub const TIMESTAMP_MASK: u128 = Self::MAX_TIMESTAMP << Self::TIMESTAMP_SHIFT;
+    /// bit mask for primary id
+    /// `Self::MAX_PRIMARY_ID << Self::PRIMARY_ID_SHIFT`
+    pub const PRIMARY_ID_MASK: u128 = Self::MAX_PRIMARY_ID << Self::PRIMARY_ID_SHIFT;
+    /// bit mask for secondary id
+    /// `Self::MAX_SECONDARY_ID << Self::SECONDARY_ID_SHIFT`
+    pub const SECONDARY_ID_MASK: u128 = Self::MAX_SECONDARY_ID << Self::SECONDARY_ID_SHIFT;
+    /// bit mask for sequence
+    /// `Self::MAX_SEQUENCE`
+    pub const SEQUENCE_MASK: u128 = Self::MAX_SEQUENCE;
+
+    /// snowflake with every part set to 0
+    pub const NIL: Self = Self { dur: None, tsm: 0, pid: 0, sid: 0, seq: 0 };
+    /// smallest possible snowflake. same as `Self::NIL`
+    pub const MIN: Self = Self::NIL;
+    /// largest possible snowflake with every part set to its max value
+    pub const MAX: Self = Self {
+        dur: None,
+        tsm: Self::MAX_TIMESTAMP,
+        pid: Self::MAX_PRIMARY_ID,
+        sid: Self::MAX_SECONDARY_ID,
+        seq: Self::MAX_SEQUENCE,
+    };
+
+    /// fails to compile if the layout does not fit the available bits
+    const VALID_LAYOUT: () = assert!(
+        crate::layout_fits(
+            <Self as traits::FromIdGenerator>::LAYOUT_BITS,
+            <Self as traits::FromIdGenerator>::MAX_LAYOUT_BITS
+        ),
+        "DualIdFlake bit widths must sum to 128"
+    );
+
+    /// returns the time since the epoch that the snowflake was generated at
+    ///
+    /// only snowflakes from a generator will have this. anything created
+    /// from parts or an integer will be `None` and only the millisecond
+    /// precision of the timestamp is available
+    pub fn duration(&self) -> Option<&Duration> {
+        self.dur.as_ref()
+    }
+
+    /// returns timestamp
+    pub fn timestamp(&self) -> u128 {
+        self.tsm
+    }
+
+    /// returns the unix timestamp in milliseconds of when the snowflake was
+    /// created using the given epoch
+    ///
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the result does not fit in a u64
+    pub fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        u64::try_from(self.tsm).ok()
+            .and_then(|tsm| epoch.checked_add(tsm))
+            .ok_or(error::Error::EpochInvalid)
+    }
+
+    /// returns primary id
+    pub fn primary_id(&self) -> u128 {
+        self.pid
+    }
+
+    /// returns secondary id
+    pub fn secondary_id(&self) -> u128 {
+        self.sid
+    }
+
+    /// returns sequence
+    pub fn sequence(&self) -> u128 {
+        self.seq
+    }
+
+    /// extracts the timestamp from an id without any validation
+    #[inline]
+    pub const fn timestamp_of(id: u128) -> u128 {
+        (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT
+    }
+
+    /// extracts the primary id from an id without any validation
+    #[inline]
+    pub const fn primary_id_of(id: u128) -> u128 {
+        (id & Self::PRIMARY_ID_MASK) >> Self::PRIMARY_ID_SHIFT
+    }
+
+    /// extracts the secondary id from an id without any validation
+    #[inline]
+    pub const fn secondary_id_of(id: u128) -> u128 {
+        (id & Self::SECONDARY_ID_MASK) >> Self::SECONDARY_ID_SHIFT
+    }
+
+    /// extracts the sequence from an id without any validation
+    #[inline]
+    pub const fn sequence_of(id: u128) -> u128 {
+        id & Self::SEQUENCE_MASK
+    }
+
+    /// generates a Snowflake from the provided parts
+    ///
+    /// checks will be performed on each part to ensure that they are
+    /// valid for the given Snowflake. 
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the primary/secondary id is invalid
+    pub fn from_parts(tsm: u128, pid: u128, sid: u128, seq: u128) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        if pid > Self::MAX_PRIMARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        if sid > Self::MAX_SECONDARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        if seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        Ok(Self { dur: None, tsm, pid, sid, seq })
+    }
+
+    /// generates a Snowflake from the timestamp, id segments, and sequence
+    ///
+    /// the ids can be anything that converts into the same segments given to
+    /// a generator like a single value, a tuple, or
+    /// [`Segments`](crate::Segments). the same checks as
+    /// [`from_parts`](Self::from_parts) will be performed
+    pub fn from_segments<I>(tsm: u128, ids: I, seq: u128) -> error::Result<Self>
+    where
+        I: Into<Segments<u128, 2>>
+    {
+        let ids = ids.into();
+
+        Self::from_parts(tsm, ids[0], ids[1], seq)
+    }
+
+    /// generates the smallest Snowflake for the given timestamp
+    ///
+    /// every other part is set to 0. useful as the lower bound when
+    /// searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn first_for_timestamp(tsm: u128) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::NIL })
+    }
+
+    /// generates the largest Snowflake for the given timestamp
+    ///
+    /// every other part is set to its max value. useful as the upper bound
+    /// when searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn last_for_timestamp(tsm: u128) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::MAX })
+    }
+
+    /// returns the range of ids that were created between the given times
+    /// using the epoch in milliseconds
+    ///
+    /// `start` is inclusive and `end` is exclusive. the returned range is
+    /// inclusive so it can be used directly with something like
+    /// `WHERE id BETWEEN $1 AND $2`. a `start` before the epoch or an `end`
+    /// after the max timestamp will be clamped to what the snowflake can
+    /// represent. [`EpochInvalid`](crate::error::Error::EpochInvalid) will be
+    /// returned if none of the time range can be represented
+    pub fn id_range_for(epoch_millis: u64, start: SystemTime, end: SystemTime) -> error::Result<RangeInclusive<u128>> {
+        let (first, last) = crate::tick_range::<Self>(epoch_millis, start, end, u64::try_from(Self::MAX_TIMESTAMP).unwrap_or(u64::MAX))?;
+
+        Ok(Self::first_for_timestamp(first as u128)?.id()..=Self::last_for_timestamp(last as u128)?.id())
+    }
+
+    /// returns the first id of every boundary between the given times using
+    /// the epoch in milliseconds
+    ///
+    /// boundaries are multiples of `step` since the unix epoch so a step of
+    /// one day will give the first id of each day in UTC. `start` is
+    /// inclusive and `end` is exclusive. boundaries that are before the epoch
+    /// or after the max timestamp are skipped and a `step` of 0 will not
+    /// produce any ids. the ids are calculated as the iterator is consumed
+    pub fn id_partition_boundaries(epoch_millis: u64, start: SystemTime, end: SystemTime, step: Duration) -> impl Iterator<Item = u128> {
+        crate::boundary_ticks::<Self>(epoch_millis, start, end, step, u64::try_from(Self::MAX_TIMESTAMP).unwrap_or(u64::MAX))
+            .filter_map(|tick| Self::first_for_timestamp(tick as u128).ok())
+            .map(|flake| flake.id())
+    }
+
+    /// splits the current Snowflake into its individual parts
+    pub fn into_parts(self) -> (u128, u128, u128, u128) {
+        (self.tsm, self.pid, self.sid, self.seq)
+    }
+
+    /// generates the unique id
+    pub fn id(&self) -> u128 {
+        (self.tsm << Self::TIMESTAMP_SHIFT) | 
+        (self.pid << Self::PRIMARY_ID_SHIFT) | 
+        (self.sid << Self::SECONDARY_ID_SHIFT) |
+        self.seq
+    }
+
+    /// renders the bits of the id split at each part with the name and
+    /// value of each part underneath
+    ///
+    /// useful for checking that a layout places the parts where they are
+    /// expected to be
+    pub fn fmt_layout(&self) -> String {
+        crate::layout_diagram(self.id(), 128, &[("ts", TS), ("pid", PID), ("sid", SID), ("seq", SEQ)])
+    }
+
+    /// encodes the id as base58
+    ///
+    /// see [`encoding::base58`](crate::encoding::base58) for more information
+    pub fn to_base58(&self) -> String {
+        crate::encoding::base58::encode(self)
+    }
+
+    /// decodes a base58 string created by [`to_base58`](Self::to_base58)
+    pub fn from_base58(string: &str) -> error::Result<Self> {
+        crate::encoding::base58::decode(string)
+    }
+
+    /// encodes the id as base62
+    ///
+    /// see [`encoding::base62`](crate::encoding::base62) for more information
+    pub fn to_base62(&self) -> String {
+        crate::encoding::base62::encode(self)
+    }
+
+    /// decodes a base62 string created by [`to_base62`](Self::to_base62)
+    pub fn from_base62(string: &str) -> error::Result<Self> {
+        crate::encoding::base62::decode(string)
+    }
+
+    /// encodes the id as fixed width crockford base32
+    ///
+    /// see [`encoding::crockford`](crate::encoding::crockford) for more
+    /// information
+    pub fn to_crockford(&self) -> String {
+        crate::encoding::crockford::encode_fixed(self)
+    }
+
+    /// decodes a crockford base32 string created by
+    /// [`to_crockford`](Self::to_crockford)
+    pub fn from_crockford(string: &str) -> error::Result<Self> {
+        crate::encoding::crockford::decode(string)
+    }
+
+    /// encodes the big endian bytes of the id as unpadded base64url
+    ///
+    /// see [`encoding::base64url`](crate::encoding::base64url) for more
+    /// information
+    pub fn to_base64url(&self) -> String {
+        crate::encoding::base64url::encode(self)
+    }
+
+    /// decodes a base64url string created by
+    /// [`to_base64url`](Self::to_base64url)
+    pub fn from_base64url(string: &str) -> error::Result<Self> {
+        crate::encoding::base64url::decode(string)
+    }
+
+    /// prints the id in the given radix using lowercase letters and no
+    /// prefix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn to_string_radix(&self, radix: u32) -> String {
+        crate::encoding::to_string_radix(self.id(), radix)
+    }
+
+    /// parses a snowflake from an id in the given radix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn from_str_radix(string: &str, radix: u32) -> error::Result<Self> {
+        let id = u128::from_str_radix(string, radix).map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+
+    /// parses a snowflake from an id that may start with a `0x`, `0o`, or
+    /// `0b` prefix
+    ///
+    /// strings without a prefix are parsed as decimal
+    pub fn parse_prefixed(string: &str) -> error::Result<Self> {
+        Self::try_from(&crate::encoding::parse_prefixed::<u128>(string)?)
+    }
+
+    /// checks if every part of the snowflake is 0
+    pub fn is_nil(&self) -> bool {
+        self == &Self::NIL
+    }
+
+    /// returns the snowflake that comes after this one
+    ///
+    /// same as adding 1 to the id so the sequence will carry over into the
+    /// id segments and timestamp. `None` will be returned if this is
+    /// [`MAX`](Self::MAX)
+    pub fn successor(&self) -> Option<Self> {
+        if *self == Self::MAX {
+            return None;
+        }
+
+        Self::try_from(&(self.id() + 1)).ok()
+    }
+
+    /// returns the snowflake that comes before this one
+    ///
+    /// same as subtracting 1 from the id. `None` will be returned if this is
+    /// [`MIN`](Self::MIN)
+    pub fn predecessor(&self) -> Option<Self> {
+        if *self == Self::MIN {
+            return None;
+        }
+
+        Self::try_from(&(self.id() - 1)).ok()
+    }
+
+    /// returns the snowflake with the primary id replaced
+    ///
+    /// the duration is kept as is.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the primary id is invalid
+    pub fn with_primary_id(self, pid: u128) -> error::Result<Self> {
+        if pid > Self::MAX_PRIMARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        Ok(Self { pid, ..self })
+    }
+
+    /// returns the snowflake with the secondary id replaced
+    ///
+    /// the duration is kept as is.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the secondary id is invalid
+    pub fn with_secondary_id(self, sid: u128) -> error::Result<Self> {
+        if sid > Self::MAX_SECONDARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        Ok(Self { sid, ..self })
+    }
+
+    /// returns the snowflake with the sequence replaced
+    ///
+    /// the duration is kept as is.
+    /// [`SequenceInvalid`](crate::error::Error::SequenceInvalid) will be returned if
+    /// the sequence is invalid
+    pub fn with_sequence(self, seq: u128) -> error::Result<Self> {
+        if seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        Ok(Self { seq, ..self })
+    }
+
+    /// returns a copy of the snowflake with the sequence set to 0
+    ///
+    /// snowflakes created in the same tick will be equal which hides the
+    /// order they were created in. the ids will also be set to 0 if
+    /// `clear_ids` is true. the result is only meant for exporting and is
+    /// not checked against the generator rules for ids
+    pub fn anonymize(&self, clear_ids: bool) -> Self {
+        if clear_ids {
+            Self { tsm: self.tsm, ..Self::NIL }
+        } else {
+            Self { tsm: self.tsm, pid: self.pid, sid: self.sid, ..Self::NIL }
+        }
+    }
+
+    /// returns the milliseconds since the epoch rounded down to the start of
+    /// the given bucket
+    ///
+    /// a bucket shorter than a millisecond will return the milliseconds
+    /// unchanged
+    pub fn bucket_millis(&self, bucket: Duration) -> u128 {
+        let millis = self.tsm;
+        let bucket = bucket.as_millis();
+
+        if bucket == 0 {
+            millis
+        } else {
+            millis - millis % bucket
+        }
+    }
+
+    /// returns the shard that the id belongs to out of the given number of
+    /// shards
+    ///
+    /// the full id is hashed before picking the shard so ids spread evenly
+    /// even when most of them have a small sequence. the result is stable
+    /// across versions, see [`shard`](crate::shard) for the algorithm. panics
+    /// if `shards` is 0
+    pub fn shard(&self, shards: u32) -> u32 {
+        crate::shard::shard_u128(self.id(), shards)
+    }
+
+    /// returns the shard of the primary id out of the given number of shards
+    ///
+    /// the primary id is used as is without hashing so every id with the same
+    /// primary id will be in the same shard. panics if `shards` is 0
+    pub fn shard_by_primary(&self, shards: u32) -> u32 {
+        crate::shard::shard_segment(self.pid, shards)
+    }
+
+    /// returns the snowflake with the given milliseconds added to the
+    /// timestamp
+    ///
+    /// every other part is left untouched and the duration is cleared like
+    /// [`from_parts`](Self::from_parts).
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the new timestamp is less than 0 or greater than the max timestamp
+    pub fn add_millis(&self, delta: i64) -> error::Result<Self> {
+        let tsm = i128::try_from(self.tsm).ok()
+            .and_then(|tsm| tsm.checked_add(delta as i128))
+            .and_then(|tsm| u128::try_from(tsm).ok())
+            .filter(|tsm| *tsm <= Self::MAX_TIMESTAMP)
+            .ok_or(error::Error::EpochInvalid)?;
+
+        Ok(Self { dur: None, tsm, ..*self })
+    }
+
+    /// returns the snowflake with the given milliseconds subtracted from the
+    /// timestamp
+    ///
+    /// same as [`add_millis`](Self::add_millis) with a negated delta
+    pub fn sub_millis(&self, delta: i64) -> error::Result<Self> {
+        let Some(delta) = delta.checked_neg() else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        self.add_millis(delta)
+    }
+
+    /// returns the snowflake with the timestamp set to the given time using
+    /// the epoch in milliseconds
+    ///
+    /// every other part is left untouched.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the time is before the epoch or after the max timestamp
+    pub fn with_timestamp_offset_from(&self, epoch_millis: u64, time: SystemTime) -> error::Result<Self> {
+        let Ok(since_unix) = time.duration_since(SystemTime::UNIX_EPOCH) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        let Ok(current) = i128::try_from(self.tsm) else {
+            return Err(error::Error::EpochInvalid);
+        };
+        let target = since_unix.as_millis() as i128 - epoch_millis as i128;
+
+        let Ok(delta) = i64::try_from(target - current) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        self.add_millis(delta)
+    }
+
+    /// attempts to generated a snowflake from the given u128
+    pub fn try_from(id: &u128) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        Ok(Self {
+            dur: None,
+            tsm: (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT,
+            pid: (id & Self::PRIMARY_ID_MASK) >> Self::PRIMARY_ID_SHIFT,
+            sid: (id & Self::SECONDARY_ID_MASK) >> Self::SECONDARY_ID_SHIFT,
+            seq: id & Self::SEQUENCE_MASK,
+        })
+    }
+
+    /// returns the id as big endian bytes
+    ///
+    /// the bytes will sort in the same order as the ids which makes them
+    /// useful as keys in ordered byte stores
+    pub fn to_be_bytes(&self) -> [u8; 16] {
+        self.id().to_be_bytes()
+    }
+
+    /// returns the id as little endian bytes
+    pub fn to_le_bytes(&self) -> [u8; 16] {
+        self.id().to_le_bytes()
+    }
+
+    /// creates a snowflake from the big endian bytes of an id
+    pub fn from_be_bytes(bytes: [u8; 16]) -> error::Result<Self> {
+        Self::try_from(&u128::from_be_bytes(bytes))
+    }
+
+    /// writes the big endian bytes of the id to the given writer
+    pub fn write_be(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&self.to_be_bytes())
+    }
+
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::Id for DualIdFlake<TS, PID, SID, SEQ> {
+    type BaseType = u128;
+
+    fn id(&self) -> Self::BaseType {
+        DualIdFlake::id(self)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::UnixMillis for DualIdFlake<TS, PID, SID, SEQ> {
+    type Error = error::Error;
+
+    fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        DualIdFlake::unix_millis(self, epoch)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> From<DualIdFlake<TS, PID, SID, SEQ>> for u128 {
+    #[inline(always)]
+    fn from(flake: DualIdFlake<TS, PID, SID, SEQ>) -> u128 {
+        flake.id()
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> From<&DualIdFlake<TS, PID, SID, SEQ>> for u128 {
+    #[inline(always)]
+    fn from(flake: &DualIdFlake<TS, PID, SID, SEQ>) -> u128 {
+        flake.id()
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> TryFrom<u128> for DualIdFlake<TS, PID, SID, SEQ> {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: u128) -> Result<Self, Self::Error> {
+        DualIdFlake::try_from(&id)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> TryFrom<&u128> for DualIdFlake<TS, PID, SID, SEQ> {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: &u128) -> Result<Self, Self::Error> {
+        DualIdFlake::try_from(id)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::cmp::PartialEq for DualIdFlake<TS, PID, SID, SEQ> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.tsm == rhs.tsm && self.pid == rhs.pid && self.sid == rhs.sid && self.seq == rhs.seq
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::cmp::PartialEq<u128> for DualIdFlake<TS, PID, SID, SEQ> {
+    fn eq(&self, rhs: &u128) -> bool {
+        self.id() == *rhs
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::cmp::PartialOrd for DualIdFlake<TS, PID, SID, SEQ> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::cmp::Ord for DualIdFlake<TS, PID, SID, SEQ> {
+    /// compares the ids of both snowflakes which orders them by timestamp
+    /// first followed by the id segments and then the sequence
+    fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
+        self.id().cmp(&rhs.id())
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::hash::Hash for DualIdFlake<TS, PID, SID, SEQ> {
+    /// hashes the id of the snowflake so the hash will be the same as the
+    /// hash of the u128 it is stored as
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::Debug for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = self.id();
+
+        f.debug_struct("DualIdFlake")
+            .field("id", &id)
+            .field("dur", &self.dur)
+            .field("tsm", &self.tsm)
+            .field("pid", &self.pid)
+            .field("sid", &self.sid)
+            .field("seq", &self.seq)
+            .finish()
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::Display for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::LowerHex for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::UpperHex for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::UpperHex::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::Binary for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Binary::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::Octal for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Octal::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::str::FromStr for DualIdFlake<TS, PID, SID, SEQ> {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.parse::<u128>().map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::FromIdGenerator for DualIdFlake<TS, PID, SID, SEQ> {
+    type IdSegType = Segments<u128, 2>;
+    type Builder = Builder<TS, PID, SID, SEQ>;
+
+    const LAYOUT_BITS: u32 = TS as u32 + PID as u32 + SID as u32 + SEQ as u32;
+    const MAX_LAYOUT_BITS: u32 = 128;
+
+    fn valid_id(v: &Self::IdSegType) -> bool {
+        *v.primary() > 0 && *v.primary() <= Self::MAX_PRIMARY_ID && 
+        *v.secondary() > 0 && *v.secondary() <= Self::MAX_SECONDARY_ID
+    }
+
+    fn valid_epoch(e: &u64) -> bool {
+        *e as u128 <= Self::MAX_TIMESTAMP
+    }
+
+    fn builder(ids: &Self::IdSegType) -> Self::Builder {
+        let () = Self::VALID_LAYOUT;
+
+        Builder {
+            dur: Duration::new(0,0),
+            ts: 0,
+            seq: 0,
+            pid: *ids.primary(),
+            sid: *ids.secondary()
+        }
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::FlakeParts for DualIdFlake<TS, PID, SID, SEQ> {
+    fn id_segs(&self) -> Self::IdSegType {
+        Segments::from([self.pid, self.sid])
+    }
+
+    fn timestamp_ticks(&self) -> u64 {
+        u64::try_from(self.tsm).unwrap_or(u64::MAX)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::FlakeLayout for DualIdFlake<TS, PID, SID, SEQ> {
+    const LAYOUT: traits::Layout = traits::Layout {
+        base_bits: 128,
+        ts_bits: TS,
+        seg_bits: &[PID, SID],
+        seq_bits: SEQ,
+    };
+}
+
+pub struct Builder<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> {
+    dur: Duration,
+    ts: u64,
+    seq: u64,
+    pid: u128,
+    sid: u128,
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::IdBuilder for Builder<TS, PID, SID, SEQ> {
+    type Output = DualIdFlake<TS, PID, SID, SEQ>;
+
+    fn with_ts(&mut self, ts: u64) -> bool {
+        if ts as u128 > DualIdFlake::<TS, PID, SID, SEQ>::MAX_TIMESTAMP {
+            false
+        } else {
+            self.ts = ts;
+            true
+        }
+    }
+
+    fn with_seq(&mut self, seq: u64) -> bool {
+        if seq as u128 > DualIdFlake::<TS, PID, SID, SEQ>::MAX_SEQUENCE {
+            false
+        } else {
+            self.seq = seq;
+            true
+        }
+    }
+
+    fn with_dur(&mut self, dur: Duration) {
+        self.dur = dur;
+    }
+
+    fn build(self) -> Self::Output {
+        DualIdFlake {
+            dur: Some(self.dur),
+            tsm: self.ts as u128,
+            pid: self.pid,
+            sid: self.sid,
+            seq: self.seq as u128
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> ser::Serialize for DualIdFlake<TS, PID, SID, SEQ> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer
+    {
+        let id_str = self.id().to_string();
+
+        serializer.serialize_str(id_str.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+struct StrVisitor<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> {}
+
+#[cfg(feature = "serde")]
+impl<'de, const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> de::Visitor<'de> for StrVisitor<TS, PID, SID, SEQ> {
+    type Value = DualIdFlake<TS, PID, SID, SEQ>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "integer string from 0 to u128::MAX")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(num) = s.parse::<u128>() else {
+            return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+        };
+
+        let Ok(flake) = TryFrom::try_from(num) else {
+            return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+        };
+
+        Ok(flake)
+    }
+
+    fn visit_u64<E>(self, u: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(flake) = TryFrom::try_from(u as u128) else {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(u), &self));
+        };
+
+        Ok(flake)
+    }
+
+    fn visit_f64<E>(self, f: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(u) = u64::try_from(crate::serde_ext::float_to_int(f)?) else {
+            return Err(E::invalid_value(de::Unexpected::Float(f), &self));
+        };
+
+        self.visit_u64(u)
+    }
+
+    fn visit_u128<E>(self, u: u128) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(flake) = TryFrom::try_from(u) else {
+            return Err(E::invalid_value(de::Unexpected::Other("u128"), &self));
+        };
+
+        Ok(flake)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> de::Deserialize<'de> for DualIdFlake<TS, PID, SID, SEQ> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrVisitor {})
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type TestSnowflake = DualIdFlake<64, 16, 16, 32>;
+
+    #[test]
+    fn properly_calculated_consts() {
+        let max_timestamp: u128 = 0xffff_ffff_ffff_ffff;
+        let max_primary_id: u128 = 0xffff;
+        let max_secondary_id: u128 = 0xffff;
+        let max_sequence: u128 = 0xffff_ffff;
+
+        let timestamp_shift: u128 = 64;
+        let primary_id_shift: u128 = 48;
+        let secondary_id_shift: u128 = 32;
+
+        let timestamp_mask: u128 =    0xffff_ffff_ffff_ffff_0000_0000_0000_0000;
+        let primary_id_mask: u128 =   0x0000_0000_0000_0000_ffff_0000_0000_0000;
+        let secondary_id_mask: u128 = 0x0000_0000_0000_0000_0000_ffff_0000_0000;
+        let sequence_mask: u128 =     0x0000_0000_0000_0000_0000_0000_ffff_ffff;
+
+        assert_eq!(TestSnowflake::MAX_TIMESTAMP, max_timestamp, "invalid max timestamp");
+        assert_eq!(TestSnowflake::MAX_PRIMARY_ID, max_primary_id, "invalid max primary id");
+        assert_eq!(TestSnowflake::MAX_SECONDARY_ID, max_secondary_id, "invalid max secondary id");
+        assert_eq!(TestSnowflake::MAX_SEQUENCE, max_sequence, "invalid max sequence");
+
+        assert_eq!(TestSnowflake::TIMESTAMP_SHIFT, timestamp_shift, "invalid timestamp shift");
+        assert_eq!(TestSnowflake::PRIMARY_ID_SHIFT, primary_id_shift, "invalid primary id shift");
+        assert_eq!(TestSnowflake::SECONDARY_ID_SHIFT, secondary_id_shift, "invalid secondary id shift");
+
+        assert_eq!(TestSnowflake::TIMESTAMP_MASK, timestamp_mask, "invalid timestamp mask");
+        assert_eq!(TestSnowflake::PRIMARY_ID_MASK, primary_id_mask, "invalid primary id mask");
+        assert_eq!(TestSnowflake::SECONDARY_ID_MASK, secondary_id_mask, "invalid secondary id mask");
+        assert_eq!(TestSnowflake::SEQUENCE_MASK, sequence_mask, "invalid sequence mask");
+    }
+
+    #[test]
+    fn to_int_and_back() {
+        let flake = TestSnowflake::from_parts(1, 1, 1, 1).unwrap();
+
+        let to_int: u128 = (&flake).into();
+        let to_flake: TestSnowflake = (&to_int).try_into().unwrap();
+
+        assert_eq!(to_flake, flake);
+    }
+
+    #[test]
+    fn properly_shifted_integers() {
+        let flake = TestSnowflake::from_parts(1, 1, 1, 1).unwrap();
+
+        let expected: u128 = 0x0000_0000_0000_0001_0001_0001_0000_0001;
+
+        assert_eq!(
+            flake.id(),
+            expected,
+            "impropperly formatted snowflake.\n{:0128b}\n{:0128b}\n{:#?}",
+            expected,
+            flake.id(),
+            flake
+        );
+    }
+
+    #[test]
+    fn extract_helpers() {
+        for bits in crate::random_bits(1000) {
+            let id = bits;
+            let Ok(flake) = TestSnowflake::try_from(&id) else {
+                continue;
+            };
+
+            assert_eq!(TestSnowflake::timestamp_of(id), flake.timestamp());
+            assert_eq!(TestSnowflake::primary_id_of(id), flake.primary_id());
+            assert_eq!(TestSnowflake::secondary_id_of(id), flake.secondary_id());
+            assert_eq!(TestSnowflake::sequence_of(id), flake.sequence());
+        }
+    }
+
+    #[test]
+    fn be_bytes() {
+        let flakes = [
+            TestSnowflake::NIL,
+            TestSnowflake::try_from(&1).unwrap(),
+            TestSnowflake::try_from(&256).unwrap(),
+            TestSnowflake::MAX,
+        ];
+
+        for flake in flakes {
+            let mut written = Vec::new();
+            flake.write_be(&mut written).unwrap();
+
+            assert_eq!(TestSnowflake::from_be_bytes(flake.to_be_bytes()).unwrap(), flake);
+            assert_eq!(flake.to_le_bytes(), flake.id().to_le_bytes());
+            assert_eq!(written, flake.to_be_bytes());
+        }
+
+        assert!(flakes.windows(2).all(|pair| pair[0].to_be_bytes() < pair[1].to_be_bytes()));
+    }
+
+    #[test]
+    fn anonymize() {
+        let first = TestSnowflake::MAX.with_sequence(1).unwrap();
+
+        assert_ne!(first, TestSnowflake::MAX);
+        assert_eq!(first.anonymize(false), TestSnowflake::MAX.anonymize(false));
+        assert_eq!(first.anonymize(false).sequence(), 0);
+        assert_eq!(
+            TestSnowflake::first_for_timestamp(5000).unwrap().anonymize(true),
+            TestSnowflake::last_for_timestamp(5000).unwrap().anonymize(true)
+        );
+    }
+
+    #[test]
+    fn bucket_millis() {
+        let bucket = Duration::from_secs(10);
+        let first = TestSnowflake::first_for_timestamp(25000).unwrap();
+        let last = TestSnowflake::last_for_timestamp(29000).unwrap();
+        let next = TestSnowflake::first_for_timestamp(35000).unwrap();
+
+        assert_eq!(first.bucket_millis(bucket), 20_000);
+        assert_eq!(first.bucket_millis(bucket), last.bucket_millis(bucket));
+        assert_ne!(first.bucket_millis(bucket), next.bucket_millis(bucket));
+        assert_eq!(first.bucket_millis(Duration::ZERO), 25_000);
+    }
+
+    #[test]
+    fn shard() {
+        let first = TestSnowflake::first_for_timestamp(5000).unwrap();
+        let next = first.with_sequence(1).unwrap();
+
+        assert_eq!(first.shard(16), crate::shard::shard_u128(first.id(), 16));
+        assert!(next.shard(7) < 7);
+        assert_eq!(first.shard_by_primary(4), next.shard_by_primary(4));
+        assert_eq!(TestSnowflake::MAX.shard_by_primary(3), (TestSnowflake::MAX.primary_id() % 3) as u32);
+    }
+
+    #[test]
+    fn display_and_from_str() {
+        let flake = TestSnowflake::MAX.with_sequence(1).unwrap();
+        let string = flake.to_string();
+
+        assert_eq!(string, flake.id().to_string());
+        assert_eq!(string.parse::<TestSnowflake>().unwrap(), flake);
+        assert_eq!(format!("{:>50}", flake), format!("{:>50}", flake.id()));
+
+        assert!(matches!(format!(" {}", string).parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!(format!("{} ", string).parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("".parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("-1".parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!(format!("{}0", u128::MAX).parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+    }
+
+    #[test]
+    fn radix_formatting() {
+        let flake = TestSnowflake::from_parts(1, 1, 1, 1).unwrap();
+
+        assert_eq!(format!("{:x}", flake), "10001000100000001");
+        assert_eq!(format!("{:#X}", flake), "0x10001000100000001");
+        assert_eq!(format!("{:o}", flake), "2000010000040000000001");
+        assert_eq!(format!("{:#o}", flake), "0o2000010000040000000001");
+        assert_eq!(format!("{:b}", flake), "10000000000000001000000000000000100000000000000000000000000000001");
+        assert_eq!(format!("{:#0130b}", flake), "0b00000000000000000000000000000000000000000000000000000000000000010000000000000001000000000000000100000000000000000000000000000001");
+        assert_eq!(format!("{:>#12x}", flake), "0x10001000100000001");
+    }
+
+    #[test]
+    fn fmt_layout() {
+        let flake = TestSnowflake::from_parts(5, 2, 3, 4).unwrap();
+        let expected = [
+            "0000000000000000000000000000000000000000000000000000000000000101 | 0000000000000010 | 0000000000000011 | 00000000000000000000000000000100",
+            "ts                                                               | pid              | sid              | seq",
+            "5                                                                | 2                | 3                | 4",
+        ];
+
+        assert_eq!(flake.fmt_layout(), expected.join("\n"));
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_ext {
+        use super::*;
+
+        use serde::{Serialize, Deserialize};
+        use serde_json;
+
+        #[derive(Serialize, Deserialize)]
+        struct IdFlake {
+            id: TestSnowflake,
+        }
+
+        #[test]
+        fn to_string() {
+            let obj = IdFlake {
+                id: TestSnowflake::from_parts(1, 1, 1, 1).unwrap(),
+            };
+
+            match serde_json::to_string(&obj) {
+                Ok(json_string) => {
+                    assert_eq!(
+                        json_string,
+                        String::from("{\"id\":\"18447025552981229569\"}"),
+                        "invalid json string"
+                    );
+                },
+                Err(err) => {
+                    panic!("failed to create json string. {:#?}", err);
+                }
+            }
+        }
+
+        #[test]
+        fn from_string() {
+            let json_str = "{\"id\":\"18447025552981229569\"}";
+
+            match serde_json::from_str::<IdFlake>(json_str) {
+                Ok(obj) => {
+                    assert_eq!(
+                        obj.id,
+                        TestSnowflake::from_parts(1, 1, 1, 1).unwrap(),
+                        "invalid parsed id"
+                    );
+                },
+                Err(err) => {
+                    panic!("failed to parse json string. {:#?}", err);
+                }
+            }
+        }
+    }
+}
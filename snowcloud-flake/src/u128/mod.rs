@@ -0,0 +1,7 @@
+//! provides u128 based snowflakes
+
+mod single;
+mod dual;
+
+pub use single::SingleIdFlake;
+pub use dual::DualIdFlake;
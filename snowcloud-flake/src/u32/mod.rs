@@ -0,0 +1,5 @@
+//! provides u32 based snowflakes
+
+mod single;
+
+pub use single::SingleIdFlake;
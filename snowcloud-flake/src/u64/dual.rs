@@ -1,5 +1,7 @@
 use std::hash::Hasher;
-use std::time::Duration;
+use std::num::NonZeroU64;
+use std::ops::RangeInclusive;
+use std::time::{Duration, SystemTime};
 
 use snowcloud_core::traits;
 
@@ -10,6 +12,7 @@ use serde::{de, ser};
 
 use crate::error;
 use crate::Segments;
+use crate::u64::DualIdParts;
 
 /// u64 Snowflake with 2 id segments
 ///
@@ -29,8 +32,9 @@ use crate::Segments;
 /// bit values for each segment can be specified by `TS`, `PID`, `SID`, and 
 /// `SEQ`. the total amount of bits should equal 64.
 ///
-/// Note: there is currently no way to ensure that the values provided are
-/// valid. `generic_const_exprs` would help with this but is unstable currently
+/// Note: the total is checked at compile time once the snowflake is used and
+/// will fail to compile if it does not equal 64. the `relaxed-layout`
+/// feature will only require the total to be less than or equal to 64.
 ///
 /// # Timestamp
 ///
@@ -67,7 +71,7 @@ use crate::Segments;
 /// ```rust
 /// use serde::{Serialize, Deserialize};
 ///
-/// type MyFlake = snowcloud::u64::DualIdFlake<43, 4, 4, 12>;
+/// type MyFlake = snowcloud::u64::DualIdFlake<44, 4, 4, 12>;
 ///
 /// #[derive(Serialize, Deserialize)]
 /// pub struct MyStruct {
@@ -88,7 +92,7 @@ use crate::Segments;
 /// # Example Usage
 ///
 /// ```rust
-/// type MyFlake = snowcloud::u64::DualIdFlake<43, 4, 4, 12>;
+/// type MyFlake = snowcloud::u64::DualIdFlake<44, 4, 4, 12>;
 /// type MyCloud = snowcloud::Generator<MyFlake>;
 ///
 /// const START_TIME: u64 = 1679587200000;
@@ -105,7 +109,7 @@ use crate::Segments;
 ///     .expect("invalid u64 was provided");
 /// println!("{:?}", and_back);
 /// ```
-#[derive(Eq, Clone)]
+#[derive(Eq, Clone, Copy)]
 pub struct DualIdFlake<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> {
     pub(crate) dur: Option<Duration>,
     pub(crate) tsm: u64,
@@ -153,28 +157,111 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> DualIdFlake<TS,
 
     const MAX_EPOCH: u64 = (1 << TS as u64) - 1;
 
+    /// snowflake with every part set to 0
+    pub const NIL: Self = Self { dur: None, tsm: 0, pid: 0, sid: 0, seq: 0 };
+    /// smallest possible snowflake. same as `Self::NIL`
+    pub const MIN: Self = Self::NIL;
+    /// largest possible snowflake with every part set to its max value
+    pub const MAX: Self = Self {
+        dur: None,
+        tsm: Self::MAX_TIMESTAMP,
+        pid: Self::MAX_PRIMARY_ID,
+        sid: Self::MAX_SECONDARY_ID,
+        seq: Self::MAX_SEQUENCE,
+    };
+
+    /// fails to compile if the layout does not fit the available bits
+    const VALID_LAYOUT: () = assert!(
+        crate::layout_fits(
+            <Self as traits::FromIdGenerator>::LAYOUT_BITS,
+            <Self as traits::FromIdGenerator>::MAX_LAYOUT_BITS
+        ),
+        "DualIdFlake bit widths must sum to 64"
+    );
+
+    /// returns the time since the epoch that the snowflake was generated at
+    ///
+    /// only snowflakes from a generator will have this. anything created
+    /// from parts or an integer will be `None` and only the millisecond
+    /// precision of the timestamp is available
     pub fn duration(&self) -> Option<&Duration> {
         self.dur.as_ref()
     }
 
     /// returns timestamp
-    pub fn timestamp(&self) -> &u64 {
-        &self.tsm
+    pub fn timestamp(&self) -> u64 {
+        self.tsm
+    }
+
+    /// returns the unix timestamp in milliseconds of when the snowflake was
+    /// created using the given epoch
+    ///
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the result does not fit in a u64
+    pub fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        epoch.checked_add(self.tsm)
+            .ok_or(error::Error::EpochInvalid)
+    }
+
+    /// returns when the snowflake was created using the given epoch
+    ///
+    /// `None` will be returned if the time cannot be represented
+    pub fn created_at(&self, epoch_millis: u64) -> Option<SystemTime> {
+        let millis = self.unix_millis(epoch_millis).ok()?;
+
+        SystemTime::UNIX_EPOCH.checked_add(Duration::from_millis(millis))
+    }
+
+    /// returns how long ago the snowflake was created using the given epoch
+    ///
+    /// `None` will be returned if the snowflake appears to be created in the
+    /// future
+    pub fn age(&self, epoch_millis: u64) -> Option<Duration> {
+        SystemTime::now().duration_since(self.created_at(epoch_millis)?).ok()
+    }
+
+    /// checks if both snowflakes were created in the same millisecond
+    pub fn same_tick(&self, other: &Self) -> bool {
+        self.tsm == other.tsm
+    }
+
+    /// returns primary id
+    pub fn primary_id(&self) -> u64 {
+        self.pid
+    }
+
+    /// returns secondary id
+    pub fn secondary_id(&self) -> u64 {
+        self.sid
+    }
+
+    /// returns sequence
+    pub fn sequence(&self) -> u64 {
+        self.seq
+    }
+
+    /// extracts the timestamp from an id without any validation
+    #[inline]
+    pub const fn timestamp_of(id: u64) -> u64 {
+        (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT
     }
 
-    /// returns primary id reference
-    pub fn primary_id(&self) -> &u64 {
-        &self.pid
+    /// extracts the primary id from an id without any validation
+    #[inline]
+    pub const fn primary_id_of(id: u64) -> u64 {
+        (id & Self::PRIMARY_ID_MASK) >> Self::PRIMARY_ID_SHIFT
     }
 
-    /// returns secondary id reference
-    pub fn secondary_id(&self) -> &u64 {
-        &self.sid
+    /// extracts the secondary id from an id without any validation
+    #[inline]
+    pub const fn secondary_id_of(id: u64) -> u64 {
+        (id & Self::SECONDARY_ID_MASK) >> Self::SECONDARY_ID_SHIFT
     }
 
-    /// returns sequence reference
-    pub fn sequence(&self) -> &u64 {
-        &self.seq
+    /// extracts the sequence from an id without any validation
+    #[inline]
+    pub const fn sequence_of(id: u64) -> u64 {
+        id & Self::SEQUENCE_MASK
     }
 
     /// generates a Snowflake from the provided parts
@@ -184,6 +271,8 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> DualIdFlake<TS,
     /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
     /// the primary/secondary id is invalid
     pub fn from_parts(tsm: u64, pid: u64, sid: u64, seq: u64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
         if tsm > Self::MAX_TIMESTAMP {
             return Err(error::Error::EpochInvalid);
         }
@@ -203,11 +292,116 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> DualIdFlake<TS,
         Ok(Self { dur: None, tsm, pid, sid, seq })
     }
 
+    /// iterates every snowflake that can be created with the given timestamp
+    /// and ids starting with a sequence of 0
+    ///
+    /// the timestamp and ids are checked the same as
+    /// [`from_parts`](Self::from_parts) before creating the iterator
+    pub fn iter_sequence(tsm: u64, pid: u64, sid: u64) -> error::Result<impl ExactSizeIterator<Item = Self> + DoubleEndedIterator> {
+        let first = Self::from_parts(tsm, pid, sid, 0)?;
+        let max = usize::try_from(Self::MAX_SEQUENCE).unwrap_or(usize::MAX - 1);
+
+        Ok((0..max + 1).map(move |seq| Self { seq: seq as u64, ..first }))
+    }
+
+    /// generates a Snowflake from the timestamp, id segments, and sequence
+    ///
+    /// the ids can be anything that converts into the same segments given to
+    /// a generator like a single value, a tuple, or
+    /// [`Segments`](crate::Segments). the same checks as
+    /// [`from_parts`](Self::from_parts) will be performed
+    pub fn from_segments<I>(tsm: u64, ids: I, seq: u64) -> error::Result<Self>
+    where
+        I: Into<Segments<u64, 2>>
+    {
+        let ids = ids.into();
+
+        Self::from_parts(tsm, ids[0], ids[1], seq)
+    }
+
+    /// generates a Snowflake from the provided named parts
+    ///
+    /// the same checks as [`from_parts`](Self::from_parts) will be performed
+    pub fn from_parts_named(parts: DualIdParts) -> error::Result<Self> {
+        Self::from_parts(parts.timestamp, parts.primary_id, parts.secondary_id, parts.sequence)
+    }
+
+    /// generates the smallest Snowflake for the given timestamp
+    ///
+    /// every other part is set to 0. useful as the lower bound when
+    /// searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn first_for_timestamp(tsm: u64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::NIL })
+    }
+
+    /// generates the largest Snowflake for the given timestamp
+    ///
+    /// every other part is set to its max value. useful as the upper bound
+    /// when searching for ids created at a given timestamp.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the timestamp is invalid
+    pub fn last_for_timestamp(tsm: u64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
+        if tsm > Self::MAX_TIMESTAMP {
+            return Err(error::Error::EpochInvalid);
+        }
+
+        Ok(Self { tsm, ..Self::MAX })
+    }
+
+    /// returns the range of ids that were created between the given times
+    /// using the epoch in milliseconds
+    ///
+    /// `start` is inclusive and `end` is exclusive. the returned range is
+    /// inclusive so it can be used directly with something like
+    /// `WHERE id BETWEEN $1 AND $2`. a `start` before the epoch or an `end`
+    /// after the max timestamp will be clamped to what the snowflake can
+    /// represent. [`EpochInvalid`](crate::error::Error::EpochInvalid) will be
+    /// returned if none of the time range can be represented
+    pub fn id_range_for(epoch_millis: u64, start: SystemTime, end: SystemTime) -> error::Result<RangeInclusive<u64>> {
+        let (first, last) = crate::tick_range::<Self>(epoch_millis, start, end, Self::MAX_TIMESTAMP)?;
+
+        Ok(Self::first_for_timestamp(first)?.id()..=Self::last_for_timestamp(last)?.id())
+    }
+
+    /// returns the first id of every boundary between the given times using
+    /// the epoch in milliseconds
+    ///
+    /// boundaries are multiples of `step` since the unix epoch so a step of
+    /// one day will give the first id of each day in UTC. `start` is
+    /// inclusive and `end` is exclusive. boundaries that are before the epoch
+    /// or after the max timestamp are skipped and a `step` of 0 will not
+    /// produce any ids. the ids are calculated as the iterator is consumed
+    pub fn id_partition_boundaries(epoch_millis: u64, start: SystemTime, end: SystemTime, step: Duration) -> impl Iterator<Item = u64> {
+        crate::boundary_ticks::<Self>(epoch_millis, start, end, step, Self::MAX_TIMESTAMP)
+            .filter_map(|tick| Self::first_for_timestamp(tick).ok())
+            .map(|flake| flake.id())
+    }
+
     /// splits the current Snowflake into its individual parts
     pub fn into_parts(self) -> (u64, u64, u64, u64) {
         (self.tsm, self.pid, self.sid, self.seq)
     }
 
+    /// splits the current Snowflake into its individual named parts
+    pub fn into_parts_named(self) -> DualIdParts {
+        DualIdParts {
+            timestamp: self.tsm,
+            primary_id: self.pid,
+            secondary_id: self.sid,
+            sequence: self.seq,
+        }
+    }
+
     /// generates the unique id
     pub fn id(&self) -> u64 {
         (self.tsm << Self::TIMESTAMP_SHIFT) | 
@@ -216,8 +410,286 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> DualIdFlake<TS,
         self.seq
     }
 
+    /// renders the bits of the id split at each part with the name and
+    /// value of each part underneath
+    ///
+    /// useful for checking that a layout places the parts where they are
+    /// expected to be
+    pub fn fmt_layout(&self) -> String {
+        crate::layout_diagram(u128::from(self.id()), 64, &[("ts", TS), ("pid", PID), ("sid", SID), ("seq", SEQ)])
+    }
+
+    /// encodes the id as base58
+    ///
+    /// see [`encoding::base58`](crate::encoding::base58) for more information
+    pub fn to_base58(&self) -> String {
+        crate::encoding::base58::encode(self)
+    }
+
+    /// decodes a base58 string created by [`to_base58`](Self::to_base58)
+    pub fn from_base58(string: &str) -> error::Result<Self> {
+        crate::encoding::base58::decode(string)
+    }
+
+    /// encodes the id as base62
+    ///
+    /// see [`encoding::base62`](crate::encoding::base62) for more information
+    pub fn to_base62(&self) -> String {
+        crate::encoding::base62::encode(self)
+    }
+
+    /// decodes a base62 string created by [`to_base62`](Self::to_base62)
+    pub fn from_base62(string: &str) -> error::Result<Self> {
+        crate::encoding::base62::decode(string)
+    }
+
+    /// encodes the id as fixed width crockford base32
+    ///
+    /// see [`encoding::crockford`](crate::encoding::crockford) for more
+    /// information
+    pub fn to_crockford(&self) -> String {
+        crate::encoding::crockford::encode_fixed(self)
+    }
+
+    /// decodes a crockford base32 string created by
+    /// [`to_crockford`](Self::to_crockford)
+    pub fn from_crockford(string: &str) -> error::Result<Self> {
+        crate::encoding::crockford::decode(string)
+    }
+
+    /// encodes the big endian bytes of the id as unpadded base64url
+    ///
+    /// see [`encoding::base64url`](crate::encoding::base64url) for more
+    /// information
+    pub fn to_base64url(&self) -> String {
+        crate::encoding::base64url::encode(self)
+    }
+
+    /// decodes a base64url string created by
+    /// [`to_base64url`](Self::to_base64url)
+    pub fn from_base64url(string: &str) -> error::Result<Self> {
+        crate::encoding::base64url::decode(string)
+    }
+
+    /// prints the id in the given radix using lowercase letters and no
+    /// prefix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn to_string_radix(&self, radix: u32) -> String {
+        crate::encoding::to_string_radix(self.id() as u128, radix)
+    }
+
+    /// parses a snowflake from an id in the given radix
+    ///
+    /// panics if the radix is not in the range 2 to 36
+    pub fn from_str_radix(string: &str, radix: u32) -> error::Result<Self> {
+        let id = u64::from_str_radix(string, radix).map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+
+    /// parses a snowflake from an id that may start with a `0x`, `0o`, or
+    /// `0b` prefix
+    ///
+    /// strings without a prefix are parsed as decimal
+    pub fn parse_prefixed(string: &str) -> error::Result<Self> {
+        Self::try_from(&crate::encoding::parse_prefixed::<u64>(string)?)
+    }
+
+    /// returns the id if it is not zero
+    ///
+    /// only [`NIL`](Self::NIL) will return `None`
+    pub fn id_nonzero(&self) -> Option<NonZeroU64> {
+        NonZeroU64::new(self.id())
+    }
+
+    /// converts to the i64 snowflake with the same id and sequence widths
+    ///
+    /// the timestamp width can differ since the i64 snowflake has one less
+    /// bit available. [`EpochInvalid`](crate::error::Error::EpochInvalid)
+    /// will be returned if the timestamp does not fit which will happen for
+    /// any id larger than `i64::MAX`
+    pub fn to_signed<const STS: u8>(&self) -> error::Result<crate::i64::DualIdFlake<STS, PID, SID, SEQ>> {
+        let Ok(tsm) = i64::try_from(self.tsm) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        let mut flake = crate::i64::DualIdFlake::from_parts(tsm, self.pid as i64, self.sid as i64, self.seq as i64)?;
+        flake.dur = self.dur;
+
+        Ok(flake)
+    }
+
+    /// checks if every part of the snowflake is 0
+    pub fn is_nil(&self) -> bool {
+        self == &Self::NIL
+    }
+
+    /// returns the snowflake that comes after this one
+    ///
+    /// same as adding 1 to the id so the sequence will carry over into the
+    /// id segments and timestamp. `None` will be returned if this is
+    /// [`MAX`](Self::MAX)
+    pub fn successor(&self) -> Option<Self> {
+        if *self == Self::MAX {
+            return None;
+        }
+
+        Self::try_from(&(self.id() + 1)).ok()
+    }
+
+    /// returns the snowflake that comes before this one
+    ///
+    /// same as subtracting 1 from the id. `None` will be returned if this is
+    /// [`MIN`](Self::MIN)
+    pub fn predecessor(&self) -> Option<Self> {
+        if *self == Self::MIN {
+            return None;
+        }
+
+        Self::try_from(&(self.id() - 1)).ok()
+    }
+
+    /// returns the snowflake with the primary id replaced
+    ///
+    /// the duration is kept as is.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the primary id is invalid
+    pub fn with_primary_id(self, pid: u64) -> error::Result<Self> {
+        if pid > Self::MAX_PRIMARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        Ok(Self { pid, ..self })
+    }
+
+    /// returns the snowflake with the secondary id replaced
+    ///
+    /// the duration is kept as is.
+    /// [`IdSegInvalid`](crate::error::Error::IdSegInvalid) will be returned if
+    /// the secondary id is invalid
+    pub fn with_secondary_id(self, sid: u64) -> error::Result<Self> {
+        if sid > Self::MAX_SECONDARY_ID {
+            return Err(error::Error::IdSegInvalid);
+        }
+
+        Ok(Self { sid, ..self })
+    }
+
+    /// returns the snowflake with the sequence replaced
+    ///
+    /// the duration is kept as is.
+    /// [`SequenceInvalid`](crate::error::Error::SequenceInvalid) will be returned if
+    /// the sequence is invalid
+    pub fn with_sequence(self, seq: u64) -> error::Result<Self> {
+        if seq > Self::MAX_SEQUENCE {
+            return Err(error::Error::SequenceInvalid);
+        }
+
+        Ok(Self { seq, ..self })
+    }
+
+    /// returns a copy of the snowflake with the sequence set to 0
+    ///
+    /// snowflakes created in the same tick will be equal which hides the
+    /// order they were created in. the ids will also be set to 0 if
+    /// `clear_ids` is true. the result is only meant for exporting and is
+    /// not checked against the generator rules for ids
+    pub fn anonymize(&self, clear_ids: bool) -> Self {
+        if clear_ids {
+            Self { tsm: self.tsm, ..Self::NIL }
+        } else {
+            Self { tsm: self.tsm, pid: self.pid, sid: self.sid, ..Self::NIL }
+        }
+    }
+
+    /// returns the milliseconds since the epoch rounded down to the start of
+    /// the given bucket
+    ///
+    /// a bucket shorter than a millisecond will return the milliseconds
+    /// unchanged
+    pub fn bucket_millis(&self, bucket: Duration) -> u64 {
+        let millis = self.tsm;
+        let bucket = u64::try_from(bucket.as_millis()).unwrap_or(u64::MAX);
+
+        if bucket == 0 {
+            millis
+        } else {
+            millis - millis % bucket
+        }
+    }
+
+    /// returns the shard that the id belongs to out of the given number of
+    /// shards
+    ///
+    /// the full id is hashed before picking the shard so ids spread evenly
+    /// even when most of them have a small sequence. the result is stable
+    /// across versions, see [`shard`](crate::shard) for the algorithm. panics
+    /// if `shards` is 0
+    pub fn shard(&self, shards: u32) -> u32 {
+        crate::shard::shard_u64(self.id(), shards)
+    }
+
+    /// returns the shard of the primary id out of the given number of shards
+    ///
+    /// the primary id is used as is without hashing so every id with the same
+    /// primary id will be in the same shard. panics if `shards` is 0
+    pub fn shard_by_primary(&self, shards: u32) -> u32 {
+        crate::shard::shard_segment(self.pid as u128, shards)
+    }
+
+    /// returns the snowflake with the given milliseconds added to the
+    /// timestamp
+    ///
+    /// every other part is left untouched and the duration is cleared like
+    /// [`from_parts`](Self::from_parts).
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the new timestamp is less than 0 or greater than the max timestamp
+    pub fn add_millis(&self, delta: i64) -> error::Result<Self> {
+        let tsm = (self.tsm as i128).checked_add(delta as i128)
+            .and_then(|tsm| u64::try_from(tsm).ok())
+            .filter(|tsm| *tsm <= Self::MAX_TIMESTAMP)
+            .ok_or(error::Error::EpochInvalid)?;
+
+        Ok(Self { dur: None, tsm, ..*self })
+    }
+
+    /// returns the snowflake with the given milliseconds subtracted from the
+    /// timestamp
+    ///
+    /// same as [`add_millis`](Self::add_millis) with a negated delta
+    pub fn sub_millis(&self, delta: i64) -> error::Result<Self> {
+        let Some(delta) = delta.checked_neg() else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        self.add_millis(delta)
+    }
+
+    /// returns the snowflake with the timestamp set to the given time using
+    /// the epoch in milliseconds
+    ///
+    /// every other part is left untouched.
+    /// [`EpochInvalid`](crate::error::Error::EpochInvalid) will be returned if
+    /// the time is before the epoch or after the max timestamp
+    pub fn with_timestamp_offset_from(&self, epoch_millis: u64, time: SystemTime) -> error::Result<Self> {
+        let Ok(since_unix) = time.duration_since(SystemTime::UNIX_EPOCH) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        let target = since_unix.as_millis() as i128 - epoch_millis as i128;
+
+        let Ok(delta) = i64::try_from(target - (self.tsm as i128)) else {
+            return Err(error::Error::EpochInvalid);
+        };
+
+        self.add_millis(delta)
+    }
+
     /// attempts to generated a snowflake from the given u64
     pub fn try_from(id: &u64) -> error::Result<Self> {
+        let () = Self::VALID_LAYOUT;
+
         Ok(Self {
             dur: None,
             tsm: (id & Self::TIMESTAMP_MASK) >> Self::TIMESTAMP_SHIFT,
@@ -227,6 +699,29 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> DualIdFlake<TS,
         })
     }
 
+    /// returns the id as big endian bytes
+    ///
+    /// the bytes will sort in the same order as the ids which makes them
+    /// useful as keys in ordered byte stores
+    pub fn to_be_bytes(&self) -> [u8; 8] {
+        self.id().to_be_bytes()
+    }
+
+    /// returns the id as little endian bytes
+    pub fn to_le_bytes(&self) -> [u8; 8] {
+        self.id().to_le_bytes()
+    }
+
+    /// creates a snowflake from the big endian bytes of an id
+    pub fn from_be_bytes(bytes: [u8; 8]) -> error::Result<Self> {
+        Self::try_from(&u64::from_be_bytes(bytes))
+    }
+
+    /// writes the big endian bytes of the id to the given writer
+    pub fn write_be(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&self.to_be_bytes())
+    }
+
 }
 
 impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::Id for DualIdFlake<TS, PID, SID, SEQ> {
@@ -237,6 +732,14 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::Id for D
     }
 }
 
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::UnixMillis for DualIdFlake<TS, PID, SID, SEQ> {
+    type Error = error::Error;
+
+    fn unix_millis(&self, epoch: u64) -> error::Result<u64> {
+        DualIdFlake::unix_millis(self, epoch)
+    }
+}
+
 impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> From<DualIdFlake<TS, PID, SID, SEQ>> for u64 {
     #[inline(always)]
     fn from(flake: DualIdFlake<TS, PID, SID, SEQ>) -> u64 {
@@ -251,6 +754,13 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> From<&DualIdFlak
     }
 }
 
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> From<DualIdFlake<TS, PID, SID, SEQ>> for DualIdParts {
+    #[inline(always)]
+    fn from(flake: DualIdFlake<TS, PID, SID, SEQ>) -> DualIdParts {
+        flake.into_parts_named()
+    }
+}
+
 impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> TryFrom<u64> for DualIdFlake<TS, PID, SID, SEQ> {
     type Error = error::Error;
 
@@ -269,18 +779,64 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> TryFrom<&u64> fo
     }
 }
 
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> TryFrom<DualIdFlake<TS, PID, SID, SEQ>> for NonZeroU64 {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(flake: DualIdFlake<TS, PID, SID, SEQ>) -> Result<Self, Self::Error> {
+        flake.id_nonzero().ok_or(error::Error::InvalidId)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> TryFrom<NonZeroU64> for DualIdFlake<TS, PID, SID, SEQ> {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(id: NonZeroU64) -> Result<Self, Self::Error> {
+        Self::try_from(&id.get())
+    }
+}
+
+impl<const TS: u8, const STS: u8, const PID: u8, const SID: u8, const SEQ: u8> TryFrom<crate::i64::DualIdFlake<STS, PID, SID, SEQ>> for DualIdFlake<TS, PID, SID, SEQ> {
+    type Error = error::Error;
+
+    #[inline(always)]
+    fn try_from(flake: crate::i64::DualIdFlake<STS, PID, SID, SEQ>) -> Result<Self, Self::Error> {
+        flake.to_unsigned()
+    }
+}
+
 impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::cmp::PartialEq for DualIdFlake<TS, PID, SID, SEQ> {
     fn eq(&self, rhs: &Self) -> bool {
         self.tsm == rhs.tsm && self.pid == rhs.pid && self.sid == rhs.sid && self.seq == rhs.seq
     }
 }
 
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::cmp::PartialEq<u64> for DualIdFlake<TS, PID, SID, SEQ> {
+    fn eq(&self, rhs: &u64) -> bool {
+        self.id() == *rhs
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::cmp::PartialOrd for DualIdFlake<TS, PID, SID, SEQ> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::cmp::Ord for DualIdFlake<TS, PID, SID, SEQ> {
+    /// compares the ids of both snowflakes which orders them by timestamp
+    /// first followed by the id segments and then the sequence
+    fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
+        self.id().cmp(&rhs.id())
+    }
+}
+
 impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::hash::Hash for DualIdFlake<TS, PID, SID, SEQ> {
+    /// hashes the id of the snowflake so the hash will be the same as the
+    /// hash of the u64 it is stored as
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.tsm.hash(state);
-        self.pid.hash(state);
-        self.sid.hash(state);
-        self.seq.hash(state);
+        self.id().hash(state);
     }
 }
 
@@ -299,10 +855,79 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::Debug
     }
 }
 
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::Display for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::LowerHex for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::UpperHex for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::UpperHex::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::Binary for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Binary::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::fmt::Octal for DualIdFlake<TS, PID, SID, SEQ> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Octal::fmt(&self.id(), f)
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> std::str::FromStr for DualIdFlake<TS, PID, SID, SEQ> {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.parse::<u64>().map_err(error::Error::ParseInt)?;
+
+        Self::try_from(&id)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> DualIdFlake<TS, PID, SID, SEQ> {
+    /// places the id into a version 8 uuid
+    ///
+    /// see [`uuid_ext`](crate::uuid_ext) for the layout of the uuid
+    pub fn to_uuid(&self) -> uuid::Uuid {
+        crate::uuid_ext::from_id(self.id())
+    }
+
+    /// creates a snowflake from a uuid created by [`to_uuid`](Self::to_uuid)
+    ///
+    /// the uuid must be version 8 with only the bits used by the id set
+    pub fn from_uuid(uuid: uuid::Uuid) -> error::Result<Self> {
+        let id = crate::uuid_ext::to_id(&uuid).ok_or(error::Error::InvalidId)?;
+
+        Self::try_from(&id)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> From<DualIdFlake<TS, PID, SID, SEQ>> for uuid::Uuid {
+    fn from(flake: DualIdFlake<TS, PID, SID, SEQ>) -> Self {
+        flake.to_uuid()
+    }
+}
+
 impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::FromIdGenerator for DualIdFlake<TS, PID, SID, SEQ> {
     type IdSegType = Segments<u64, 2>;
     type Builder = Builder<TS, PID, SID, SEQ>;
 
+    const LAYOUT_BITS: u32 = TS as u32 + PID as u32 + SID as u32 + SEQ as u32;
+    const MAX_LAYOUT_BITS: u32 = 64;
+
     fn valid_id(v: &Self::IdSegType) -> bool {
         *v.primary() > 0 && *v.primary() <= Self::MAX_PRIMARY_ID && 
         *v.secondary() > 0 && *v.secondary() <= Self::MAX_SECONDARY_ID
@@ -313,6 +938,8 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::FromIdGe
     }
 
     fn builder(ids: &Self::IdSegType) -> Self::Builder {
+        let () = Self::VALID_LAYOUT;
+
         Builder {
             dur: Duration::new(0,0),
             ts: 0,
@@ -323,6 +950,25 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::FromIdGe
     }
 }
 
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::FlakeParts for DualIdFlake<TS, PID, SID, SEQ> {
+    fn id_segs(&self) -> Self::IdSegType {
+        Segments::from([self.pid, self.sid])
+    }
+
+    fn timestamp_ticks(&self) -> u64 {
+        self.tsm
+    }
+}
+
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::FlakeLayout for DualIdFlake<TS, PID, SID, SEQ> {
+    const LAYOUT: traits::Layout = traits::Layout {
+        base_bits: 64,
+        ts_bits: TS,
+        seg_bits: &[PID, SID],
+        seq_bits: SEQ,
+    };
+}
+
 pub struct Builder<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> {
     dur: Duration,
     ts: u64,
@@ -357,7 +1003,7 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> traits::IdBuilde
         }
     }
 
-    fn with_dur(&mut self, dur: Duration) -> () {
+    fn with_dur(&mut self, dur: Duration) {
         self.dur = dur;
     }
 
@@ -381,6 +1027,11 @@ impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> ser::Serialize f
     {
         let id = self.id();
 
+        #[cfg(feature = "serde-human-string")]
+        if serializer.is_human_readable() {
+            return serializer.collect_str(&id);
+        }
+
         serializer.serialize_u64(id)
     }
 }
@@ -400,6 +1051,10 @@ impl<'de, const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> de::Visitor
     where
         E: de::Error
     {
+        if i < 0 {
+            return Err(E::invalid_value(de::Unexpected::Signed(i), &self));
+        }
+
         let Ok(flake) = TryFrom::try_from(i as u64) else {
             return Err(E::invalid_value(de::Unexpected::Signed(i), &self));
         };
@@ -407,6 +1062,13 @@ impl<'de, const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> de::Visitor
         Ok(flake)
     }
 
+    fn visit_f64<E>(self, f: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        self.visit_i64(crate::serde_ext::float_to_int(f)?)
+    }
+
     fn visit_u64<E>(self, u: u64) -> Result<Self::Value, E>
     where
         E: de::Error
@@ -417,6 +1079,18 @@ impl<'de, const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> de::Visitor
 
         Ok(flake)
     }
+
+    #[cfg(feature = "serde-human-string")]
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error
+    {
+        let Ok(flake) = s.parse() else {
+            return Err(E::invalid_value(de::Unexpected::Str(s), &self));
+        };
+
+        Ok(flake)
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -425,19 +1099,36 @@ impl<'de, const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> de::Deseria
     where
         D: de::Deserializer<'de>,
     {
+        #[cfg(feature = "serde-human-string")]
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(NumVisitor {});
+        }
+
         deserializer.deserialize_u64(NumVisitor {})
     }
 }
 
+#[cfg(feature = "schemars")]
+impl<const TS: u8, const PID: u8, const SID: u8, const SEQ: u8> schemars::JsonSchema for DualIdFlake<TS, PID, SID, SEQ> {
+    fn schema_name() -> String {
+        format!("DualIdFlake_{}_{}_{}_{}", TS, PID, SID, SEQ)
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        crate::schemars_ext::id_schema(Self::MAX.id(), "uint64")
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::unusual_byte_groupings)]
 mod test {
     use super::*;
 
-    type TestSnowflake = DualIdFlake<43, 4, 4, 12>;
+    type TestSnowflake = DualIdFlake<44, 4, 4, 12>;
 
     #[test]
     fn properly_calculated_consts() {
-        let max_timestamp: u64 = 0b1111111111111111111111111111111111111111111;
+        let max_timestamp: u64 = 0b11111111111111111111111111111111111111111111;
         let max_primary_id: u64 = 0b1111;
         let max_secondary_id: u64 = 0b1111;
         let max_sequence: u64 = 0b111111111111;
@@ -446,10 +1137,10 @@ mod test {
         let primary_id_shift: u64 = 16;
         let secondary_id_shift: u64 = 12;
 
-        let timestamp_mask: u64 =    0b0_1111111111111111111111111111111111111111111_0000_0000_000000000000;
-        let primary_id_mask: u64 =   0b0_0000000000000000000000000000000000000000000_1111_0000_000000000000;
-        let secondary_id_mask: u64 = 0b0_0000000000000000000000000000000000000000000_0000_1111_000000000000;
-        let sequence_mask: u64 =     0b0_0000000000000000000000000000000000000000000_0000_0000_111111111111;
+        let timestamp_mask: u64 =    0b11111111111111111111111111111111111111111111_0000_0000_000000000000;
+        let primary_id_mask: u64 =   0b00000000000000000000000000000000000000000000_1111_0000_000000000000;
+        let secondary_id_mask: u64 = 0b00000000000000000000000000000000000000000000_0000_1111_000000000000;
+        let sequence_mask: u64 =     0b00000000000000000000000000000000000000000000_0000_0000_111111111111;
 
         assert_eq!(TestSnowflake::MAX_TIMESTAMP, max_timestamp, "invalid max timestamp");
         assert_eq!(TestSnowflake::MAX_PRIMARY_ID, max_primary_id, "invalid max primary id");
@@ -466,6 +1157,23 @@ mod test {
         assert_eq!(TestSnowflake::SEQUENCE_MASK, sequence_mask, "invalid sequence mask");
     }
 
+    #[test]
+    fn with_modifiers() {
+        let dur = Duration::from_millis(1000);
+        let flake = DualIdFlake { dur: Some(dur), ..TestSnowflake::from_parts(1000, 1, 1, 1).unwrap() };
+
+        let modified = flake.with_primary_id(2).unwrap()
+            .with_secondary_id(3).unwrap()
+            .with_sequence(4).unwrap();
+
+        assert_eq!(modified.into_parts(), (1000, 2, 3, 4));
+        assert_eq!(modified.duration(), Some(&dur));
+
+        assert!(matches!(flake.with_primary_id(TestSnowflake::MAX_PRIMARY_ID + 1), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(flake.with_secondary_id(TestSnowflake::MAX_SECONDARY_ID + 1), Err(error::Error::IdSegInvalid)));
+        assert!(matches!(flake.with_sequence(TestSnowflake::MAX_SEQUENCE + 1), Err(error::Error::SequenceInvalid)));
+    }
+
     #[test]
     fn to_int_and_back() {
         let flake = TestSnowflake::from_parts(1, 1, 1, 1).unwrap();
@@ -492,6 +1200,154 @@ mod test {
         );
     }
 
+    #[test]
+    fn extract_helpers() {
+        for bits in crate::random_bits(1000) {
+            let id = bits as u64;
+            let Ok(flake) = TestSnowflake::try_from(&id) else {
+                continue;
+            };
+
+            assert_eq!(TestSnowflake::timestamp_of(id), flake.timestamp());
+            assert_eq!(TestSnowflake::primary_id_of(id), flake.primary_id());
+            assert_eq!(TestSnowflake::secondary_id_of(id), flake.secondary_id());
+            assert_eq!(TestSnowflake::sequence_of(id), flake.sequence());
+        }
+    }
+
+    #[test]
+    fn be_bytes() {
+        let flakes = [
+            TestSnowflake::NIL,
+            TestSnowflake::try_from(&1).unwrap(),
+            TestSnowflake::try_from(&256).unwrap(),
+            TestSnowflake::MAX,
+        ];
+
+        for flake in flakes {
+            let mut written = Vec::new();
+            flake.write_be(&mut written).unwrap();
+
+            assert_eq!(TestSnowflake::from_be_bytes(flake.to_be_bytes()).unwrap(), flake);
+            assert_eq!(flake.to_le_bytes(), flake.id().to_le_bytes());
+            assert_eq!(written, flake.to_be_bytes());
+        }
+
+        assert!(flakes.windows(2).all(|pair| pair[0].to_be_bytes() < pair[1].to_be_bytes()));
+    }
+
+    #[test]
+    fn nonzero() {
+        assert_eq!(TestSnowflake::NIL.id_nonzero(), None);
+        assert!(matches!(NonZeroU64::try_from(TestSnowflake::NIL), Err(error::Error::InvalidId)));
+
+        let flake = TestSnowflake::MAX;
+        let id = flake.id_nonzero().unwrap();
+
+        assert_eq!(id.get(), flake.id());
+        assert_eq!(NonZeroU64::try_from(flake).unwrap(), id);
+
+        let back: TestSnowflake = id.try_into().unwrap();
+
+        assert_eq!(back, flake);
+    }
+
+    #[test]
+    fn anonymize() {
+        let first = TestSnowflake::MAX.with_sequence(1).unwrap();
+
+        assert_ne!(first, TestSnowflake::MAX);
+        assert_eq!(first.anonymize(false), TestSnowflake::MAX.anonymize(false));
+        assert_eq!(first.anonymize(false).sequence(), 0);
+        assert_eq!(
+            TestSnowflake::first_for_timestamp(5000).unwrap().anonymize(true),
+            TestSnowflake::last_for_timestamp(5000).unwrap().anonymize(true)
+        );
+    }
+
+    #[test]
+    fn bucket_millis() {
+        let bucket = Duration::from_secs(10);
+        let first = TestSnowflake::first_for_timestamp(25000).unwrap();
+        let last = TestSnowflake::last_for_timestamp(29000).unwrap();
+        let next = TestSnowflake::first_for_timestamp(35000).unwrap();
+
+        assert_eq!(first.bucket_millis(bucket), 20_000);
+        assert_eq!(first.bucket_millis(bucket), last.bucket_millis(bucket));
+        assert_ne!(first.bucket_millis(bucket), next.bucket_millis(bucket));
+        assert_eq!(first.bucket_millis(Duration::ZERO), 25_000);
+    }
+
+    #[test]
+    fn shard() {
+        let first = TestSnowflake::first_for_timestamp(5000).unwrap();
+        let next = first.with_sequence(1).unwrap();
+
+        assert_eq!(first.shard(16), crate::shard::shard_u64(first.id(), 16));
+        assert!(next.shard(7) < 7);
+        assert_eq!(first.shard_by_primary(4), next.shard_by_primary(4));
+        assert_eq!(TestSnowflake::MAX.shard_by_primary(3), (TestSnowflake::MAX.primary_id() % 3) as u32);
+    }
+
+    #[test]
+    fn iter_sequence() {
+        let mut iter = TestSnowflake::iter_sequence(10, 1, 1).unwrap();
+
+        assert_eq!(iter.len() as u64, TestSnowflake::MAX_SEQUENCE + 1);
+        assert_eq!(iter.next().unwrap(), TestSnowflake::from_parts(10, 1, 1, 0).unwrap());
+        assert_eq!(iter.next_back().unwrap(), TestSnowflake::from_parts(10, 1, 1, TestSnowflake::MAX_SEQUENCE).unwrap());
+        assert_eq!(iter.len() as u64, TestSnowflake::MAX_SEQUENCE - 1);
+
+        let reversed: Vec<u64> = TestSnowflake::iter_sequence(10, 1, 1).unwrap()
+            .rev()
+            .map(|flake| flake.sequence())
+            .collect();
+
+        assert_eq!(reversed, (0..=TestSnowflake::MAX_SEQUENCE).rev().collect::<Vec<u64>>());
+        assert!(matches!(TestSnowflake::iter_sequence(10, TestSnowflake::MAX_PRIMARY_ID + 1, 1), Err(error::Error::IdSegInvalid)));
+    }
+
+    #[test]
+    fn display_and_from_str() {
+        let flake = TestSnowflake::MAX.with_sequence(1).unwrap();
+        let string = flake.to_string();
+
+        assert_eq!(string, flake.id().to_string());
+        assert_eq!(string.parse::<TestSnowflake>().unwrap(), flake);
+        assert_eq!(format!("{:>50}", flake), format!("{:>50}", flake.id()));
+
+        assert!(matches!(format!(" {}", string).parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!(format!("{} ", string).parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("".parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!("-1".parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+        assert!(matches!(format!("{}0", u64::MAX).parse::<TestSnowflake>(), Err(error::Error::ParseInt(_))));
+    }
+
+    #[test]
+    fn radix_formatting() {
+        let flake = TestSnowflake::from_parts(1, 1, 1, 1).unwrap();
+
+        assert_eq!(format!("{:x}", flake), "111001");
+        assert_eq!(format!("{:#X}", flake), "0x111001");
+        assert_eq!(format!("{:o}", flake), "4210001");
+        assert_eq!(format!("{:#o}", flake), "0o4210001");
+        assert_eq!(format!("{:b}", flake), "100010001000000000001");
+        assert_eq!(format!("{:#066b}", flake), "0b0000000000000000000000000000000000000000000100010001000000000001");
+        assert_eq!(format!("{:>#12x}", flake), "    0x111001");
+    }
+
+    #[test]
+    fn fmt_layout() {
+        let flake = TestSnowflake::from_parts(5, 2, 3, 4).unwrap();
+        let expected = [
+            "00000000000000000000000000000000000000000101 | 0010 | 0011 | 000000000100",
+            "ts                                           | pid  | sid  | seq",
+            "5                                            | 2    | 3    | 4",
+        ];
+
+        assert_eq!(flake.fmt_layout(), expected.join("\n"));
+    }
+
     #[cfg(feature = "serde")]
     mod serde_ext {
         use super::*;
@@ -504,6 +1360,7 @@ mod test {
             id: TestSnowflake,
         }
 
+        #[cfg(not(feature = "serde-human-string"))]
         #[test]
         fn to_int() {
             let obj = IdFlake {
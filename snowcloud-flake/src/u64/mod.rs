@@ -1,7 +1,16 @@
 //! provides u64 based snowflakes;
 
 mod single;
+mod single_micros;
 mod dual;
+mod triple;
+mod parts;
 
 pub use single::SingleIdFlake;
+pub use single_micros::SingleIdFlakeMicros;
 pub use dual::DualIdFlake;
+pub use triple::TripleIdFlake;
+pub use parts::{SingleIdParts, DualIdParts, TripleIdParts};
+
+// storing ids as an `Option<NonZeroU64>` should cost nothing over a u64
+const _: () = assert!(std::mem::size_of::<Option<std::num::NonZeroU64>>() == 8);
@@ -0,0 +1,101 @@
+//! named parts of the u64 snowflakes
+//!
+//! an alternative to the tuples returned from `into_parts` so that the
+//! values cannot be mixed up
+//!
+//! ```rust
+//! use snowcloud::flake::u64::{SingleIdFlake, SingleIdParts};
+//!
+//! type MyFlake = SingleIdFlake<44, 8, 12>;
+//!
+//! let parts = SingleIdParts {
+//!     timestamp: 1,
+//!     primary_id: 2,
+//!     sequence: 3,
+//! };
+//!
+//! let flake = MyFlake::from_parts_named(parts).unwrap();
+//!
+//! assert_eq!(flake.into_parts_named(), parts);
+//! ```
+
+/// named parts of a [`SingleIdFlake`](crate::u64::SingleIdFlake)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct SingleIdParts {
+    pub timestamp: u64,
+    pub primary_id: u64,
+    pub sequence: u64,
+}
+
+/// named parts of a [`DualIdFlake`](crate::u64::DualIdFlake)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct DualIdParts {
+    pub timestamp: u64,
+    pub primary_id: u64,
+    pub secondary_id: u64,
+    pub sequence: u64,
+}
+
+/// named parts of a [`TripleIdFlake`](crate::u64::TripleIdFlake)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct TripleIdParts {
+    pub timestamp: u64,
+    pub primary_id: u64,
+    pub secondary_id: u64,
+    pub tertiary_id: u64,
+    pub sequence: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::u64::{SingleIdFlake, DualIdFlake, TripleIdFlake};
+
+    #[test]
+    fn round_trip() {
+        let single = SingleIdFlake::<44, 8, 12>::from_parts(1, 2, 3).unwrap();
+        let parts = SingleIdParts::from(single);
+
+        assert_eq!(parts, SingleIdParts { timestamp: 1, primary_id: 2, sequence: 3 });
+        assert_eq!(SingleIdFlake::<44, 8, 12>::from_parts_named(parts).unwrap(), single);
+
+        let dual = DualIdFlake::<44, 4, 4, 12>::from_parts(1, 2, 3, 4).unwrap();
+        let parts = dual.into_parts_named();
+
+        assert_eq!(parts, DualIdParts { timestamp: 1, primary_id: 2, secondary_id: 3, sequence: 4 });
+        assert_eq!(DualIdFlake::<44, 4, 4, 12>::from_parts_named(parts).unwrap(), dual);
+
+        let triple = TripleIdFlake::<44, 4, 4, 4, 8>::from_parts(1, 2, 3, 4, 5).unwrap();
+        let parts = TripleIdParts::from(triple);
+
+        assert_eq!(parts.tertiary_id, 4);
+        assert_eq!(TripleIdFlake::<44, 4, 4, 4, 8>::from_parts_named(parts).unwrap(), triple);
+    }
+
+    #[test]
+    fn invalid_parts() {
+        let parts = SingleIdParts { timestamp: 1, primary_id: 256, sequence: 3 };
+
+        assert!(matches!(
+            SingleIdFlake::<44, 8, 12>::from_parts_named(parts),
+            Err(crate::error::Error::IdSegInvalid)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_named_fields() {
+        let parts = DualIdParts { timestamp: 1, primary_id: 2, secondary_id: 3, sequence: 4 };
+        let json = serde_json::to_string(&parts).unwrap();
+
+        assert_eq!(json, r#"{"timestamp":1,"primary_id":2,"secondary_id":3,"sequence":4}"#);
+        assert_eq!(serde_json::from_str::<DualIdParts>(&json).unwrap(), parts);
+    }
+}
@@ -0,0 +1,132 @@
+//! helpers for working with the [`uuid`] crate
+//!
+//! 64 bit snowflakes can be stored as a version 8 (custom) uuid. the bits of
+//! the id fill the uuid from the most significant bit, skipping the version
+//! and variant bits, so uuids sort in the same order as the ids. the byte
+//! placement is part of the public format and will not change.
+//!
+//! | uuid bits | contents |
+//! |-----------|----------|
+//! | 127 - 80  | id bits 63 - 16 |
+//! | 79 - 76   | version, `0b1000` |
+//! | 75 - 64   | id bits 15 - 4 |
+//! | 63 - 62   | variant, `0b10` |
+//! | 61 - 58   | id bits 3 - 0 |
+//! | 57 - 0    | zero |
+//!
+//! ```rust
+//! type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+//!
+//! let flake = MyFlake::from_parts(1, 1, 1).unwrap();
+//! let uuid = flake.to_uuid();
+//!
+//! assert_eq!(uuid.to_string(), "00000000-0010-8100-8400-000000000000");
+//! assert_eq!(MyFlake::from_uuid(uuid).unwrap(), flake);
+//! ```
+
+use uuid::{Uuid, Variant};
+
+/// the version value of a custom uuid
+const VERSION: u128 = 8;
+
+/// the variant value of an RFC 9562 uuid
+const VARIANT: u128 = 0b10;
+
+/// bits of the uuid that are not set by the id
+const UNUSED_MASK: u128 = (1 << 58) - 1;
+
+/// places the id into a version 8 uuid
+pub(crate) fn from_id(id: u64) -> Uuid {
+    let id = id as u128;
+
+    Uuid::from_u128(
+        (id >> 16) << 80
+        | VERSION << 76
+        | ((id >> 4) & 0xfff) << 64
+        | VARIANT << 62
+        | (id & 0xf) << 58
+    )
+}
+
+/// retrieves the id from a version 8 uuid
+///
+/// returns `None` if the uuid is not a version 8 uuid with the RFC 9562
+/// variant or any of the unused bits are set
+pub(crate) fn to_id(uuid: &Uuid) -> Option<u64> {
+    if uuid.get_version_num() != VERSION as usize || uuid.get_variant() != Variant::RFC4122 {
+        return None;
+    }
+
+    let value = uuid.as_u128();
+
+    if value & UNUSED_MASK != 0 {
+        return None;
+    }
+
+    Some((
+        (value >> 80) << 16
+        | ((value >> 64) & 0xfff) << 4
+        | ((value >> 58) & 0xf)
+    ) as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        let expected = [
+            (0, "00000000-0000-8000-8000-000000000000"),
+            (1, "00000000-0000-8000-8400-000000000000"),
+            (1052673, "00000000-0010-8100-8400-000000000000"),
+            (1679082337000, "00000186-f11b-832e-a000-000000000000"),
+            (i64::MAX as u64, "7fffffff-ffff-8fff-bc00-000000000000"),
+            (u64::MAX, "ffffffff-ffff-8fff-bc00-000000000000"),
+        ];
+
+        for (id, string) in expected {
+            let uuid = from_id(id);
+
+            assert_eq!(uuid.to_string(), string, "placement changed for {}", id);
+            assert_eq!(uuid.get_version_num(), 8);
+            assert_eq!(uuid.get_variant(), Variant::RFC4122);
+            assert_eq!(to_id(&Uuid::parse_str(string).unwrap()), Some(id));
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut prev = None;
+
+        for bits in crate::random_bits(1000) {
+            let id = bits as u64;
+
+            assert_eq!(to_id(&from_id(id)), Some(id), "failed round trip for {}", id);
+
+            if let Some((prev_id, prev_uuid)) = prev {
+                assert_eq!(id.cmp(&prev_id), from_id(id).cmp(&prev_uuid), "order changed for {}", id);
+            }
+
+            prev = Some((id, from_id(id)));
+        }
+    }
+
+    #[test]
+    fn rejected() {
+        let invalid = [
+            // version 4
+            "00000000-0010-4100-8400-000000000000",
+            // microsoft variant
+            "00000000-0010-8100-c400-000000000000",
+            // unused bits set
+            "00000000-0010-8100-8400-000000000001",
+            "00000000-0010-8100-8600-000000000000",
+            "00000000-0000-0000-0000-000000000000",
+        ];
+
+        for string in invalid {
+            assert_eq!(to_id(&Uuid::parse_str(string).unwrap()), None, "accepted {}", string);
+        }
+    }
+}
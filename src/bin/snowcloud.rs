@@ -0,0 +1,232 @@
+//! command line tool for decoding, encoding, and generating snowflakes
+//!
+//! layouts are given as the bits of each part separated by `-` starting
+//! with the timestamp and ending with the sequence. `43-8-12` is the same
+//! layout as `snowcloud::i64::SingleIdFlake<43, 8, 12>`.
+//!
+//! ```text
+//! snowcloud decode 1052673 --layout 43-8-12 --epoch 1679587200000
+//! snowcloud encode --layout 43-8-12 --ts 1 --pid 1 --seq 1
+//! snowcloud gen --count 10 --layout 43-8-12 --epoch 1679587200000 --pid 1
+//! ```
+
+use std::io::Write;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+
+use snowcloud::display::UtcDisplay;
+use snowcloud::flake::dynamic::{DynamicFlake, Layout, Parts};
+use snowcloud::{i64, presets, wait, Generator};
+
+/// layout used when one is not given
+const DEFAULT_LAYOUT: &str = "43-8-12";
+
+type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// names of the segments in the order they appear in a layout
+const SEGMENT_NAMES: [&str; 3] = ["pid", "sid", "tid"];
+
+/// decode, encode, and generate snowflake ids
+#[derive(Parser)]
+#[command(name = "snowcloud", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// prints the parts of an id
+    Decode {
+        /// the id to decode
+        id: i64,
+
+        /// bits of each part of the id
+        #[arg(long, default_value = DEFAULT_LAYOUT)]
+        layout: Layout,
+
+        /// epoch of the id in milliseconds. prints when the id was created
+        /// in UTC when given
+        #[arg(long)]
+        epoch: Option<u64>,
+    },
+
+    /// creates an id from its parts
+    Encode {
+        /// bits of each part of the id
+        #[arg(long, default_value = DEFAULT_LAYOUT)]
+        layout: Layout,
+
+        /// timestamp of the id since the epoch
+        #[arg(long)]
+        ts: i64,
+
+        #[command(flatten)]
+        segments: Segments,
+
+        /// sequence of the id
+        #[arg(long, default_value = "0")]
+        seq: i64,
+    },
+
+    /// generates ids with a generator
+    ///
+    /// only the 43-8-12, 43-4-4-12, and 41-5-5-12 layouts are supported
+    Gen {
+        /// total ids to generate
+        #[arg(long, default_value = "1")]
+        count: u64,
+
+        /// bits of each part of the id
+        #[arg(long, default_value = DEFAULT_LAYOUT)]
+        layout: Layout,
+
+        /// epoch of the generator in milliseconds
+        #[arg(long)]
+        epoch: u64,
+
+        #[command(flatten)]
+        segments: Segments,
+    },
+}
+
+/// id segments in the order they appear in a layout
+#[derive(clap::Args)]
+struct Segments {
+    /// primary id
+    #[arg(long)]
+    pid: Option<i64>,
+
+    /// secondary id
+    #[arg(long)]
+    sid: Option<i64>,
+
+    /// tertiary id
+    #[arg(long)]
+    tid: Option<i64>,
+}
+
+impl Segments {
+    /// returns the segments required by the layout
+    fn for_layout(&self, layout: &Layout) -> Result<Vec<i64>> {
+        let given = [self.pid, self.sid, self.tid];
+        let count = layout.seg_bits().len();
+
+        if count > given.len() {
+            return Err(format!("layout {} has more segments than can be given", layout).into());
+        }
+
+        for (name, value) in SEGMENT_NAMES.iter().zip(given).skip(count) {
+            if value.is_some() {
+                return Err(format!("layout {} does not have a {} segment", layout, name).into());
+            }
+        }
+
+        SEGMENT_NAMES.iter()
+            .zip(given)
+            .take(count)
+            .map(|(name, value)| value.ok_or_else(|| format!("--{} is required for layout {}", name, layout).into()))
+            .collect()
+    }
+}
+
+/// returns the name of the segment at the given index
+fn segment_name(index: usize) -> String {
+    SEGMENT_NAMES.get(index)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("seg{}", index))
+}
+
+fn decode(out: &mut impl Write, id: i64, layout: Layout, epoch: Option<u64>) -> Result {
+    let name = layout.to_string();
+    let flake = DynamicFlake::new(Arc::new(layout), id)
+        .map_err(|err| format!("invalid id {} for layout {}: {}", id, name, err))?;
+
+    writeln!(out, "id         {}", flake.id())?;
+    writeln!(out, "layout     {}", name)?;
+    writeln!(out, "timestamp  {}", flake.timestamp())?;
+
+    if let Some(epoch) = epoch {
+        let created = epoch.checked_add(flake.timestamp() as u64)
+            .ok_or_else(|| format!("timestamp {} overflows epoch {}", flake.timestamp(), epoch))?;
+
+        writeln!(out, "created    {}", UtcDisplay(Duration::from_millis(created)))?;
+    }
+
+    for (index, segment) in flake.segments().into_iter().enumerate() {
+        writeln!(out, "{:<11}{}", segment_name(index), segment)?;
+    }
+
+    writeln!(out, "seq        {}", flake.sequence())?;
+
+    Ok(())
+}
+
+fn encode(out: &mut impl Write, layout: Layout, ts: i64, segments: &Segments, seq: i64) -> Result {
+    let parts = Parts {
+        timestamp: ts,
+        segments: segments.for_layout(&layout)?,
+        sequence: seq,
+    };
+
+    let flake = DynamicFlake::from_parts(Arc::new(layout), parts)
+        .map_err(|err| format!("invalid parts: {}", err))?;
+
+    writeln!(out, "{}", flake.id())?;
+
+    Ok(())
+}
+
+/// runs a generator for one of the supported layouts and writes each id
+macro_rules! generate {
+    ($flake:ty, $out:expr, $epoch:expr, $ids:expr, $count:expr) => {{
+        let mut cloud = Generator::<$flake>::new($epoch, $ids)
+            .map_err(|err| format!("failed to create generator: {}", err))?;
+
+        for _ in 0..$count {
+            let flake = wait::try_next_id_mut(&mut cloud, 4)
+                .map_err(|err| format!("failed to generate id: {}", err))?;
+
+            writeln!($out, "{}", flake.id())?;
+        }
+
+        Ok(())
+    }};
+}
+
+fn gen(out: &mut impl Write, count: u64, layout: Layout, epoch: u64, segments: &Segments) -> Result {
+    let ids = segments.for_layout(&layout)?;
+
+    match layout.to_string().as_str() {
+        "43-8-12" => generate!(i64::SingleIdFlake<43, 8, 12>, out, epoch, ids[0], count),
+        "43-4-4-12" => generate!(i64::DualIdFlake<43, 4, 4, 12>, out, epoch, (ids[0], ids[1]), count),
+        "41-5-5-12" => generate!(presets::TwitterFlake, out, epoch, (ids[0], ids[1]), count),
+        _ => Err(format!(
+            "layout {} is not supported for generating ids. use 43-8-12, 43-4-4-12, or 41-5-5-12",
+            layout
+        ).into()),
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut out = std::io::stdout().lock();
+
+    let result = match cli.command {
+        Command::Decode { id, layout, epoch } => decode(&mut out, id, layout, epoch),
+        Command::Encode { layout, ts, segments, seq } => encode(&mut out, layout, ts, &segments, seq),
+        Command::Gen { count, layout, epoch, segments } => gen(&mut out, count, layout, epoch, &segments),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -74,7 +74,7 @@
 //! - [`NextAvailId`](crate::traits::NextAvailId) describes an object that is
 //!   capable of returing a [`duraiton`](std::time::Duration) to the next 
 //!   available millisecond. check 
-//!   [`blocking_next_id`](crate::wait::blocking_next_id) for example 
+//!   [`try_next_id`](crate::wait::try_next_id) for example 
 //!   implementation.
 //! - [`Id`](crate::traits::Id) describes base methods for what an Id requires.
 //!   currently just handles turning a snowflake into its base type like an
@@ -119,7 +119,107 @@
 //! [`Deserialize`](serde::Deserialize) to there internal types with an 
 //! addtional option to de/serailize to a string. see 
 //! [`serde_ext`](crate::serde_ext) for additional methods of de/serialization
+//!
+//! enabling the `serde-human-string` feature will write snowflakes as
+//! decimal strings for human readable formats (json) and accept either
+//! strings or integers when reading them. binary formats are unchanged. the
+//! 128 bit snowflakes always use strings.
+//!
+//! ## Postgres
+//!
+//! the `postgres` feature implements `ToSql` and `FromSql` for the i64
+//! snowflakes as `INT8`. arrays work through the impls provided by
+//! `postgres-types` so a `Vec<Flake>` or `&[Flake]` can be used for `INT8[]`
+//! parameters and columns, `WHERE id = ANY($1)` for example.
+//!
+//! ## sqlx
+//!
+//! the `sqlx` feature implements sqlx `Type`, `Encode`, and `Decode` for the
+//! i64 and u64 snowflakes as `BIGINT`. the `sqlx-postgres`, `sqlx-mysql`,
+//! and `sqlx-sqlite` features enable the matching driver. see
+//! [`flake::sqlx_ext`] for how u64 snowflakes are stored.
+//!
+//! ## MySQL
+//!
+//! the `mysql` feature implements the `mysql_common` value conversions so
+//! snowflakes can be used as params and read from rows. i64 snowflakes map
+//! to `BIGINT` and u64 snowflakes map to `BIGINT UNSIGNED`. see
+//! [`flake::mysql_ext`].
+//!
+//! ## SeaORM
+//!
+//! the `sea-orm` feature allows i64 snowflakes to be used as entity columns
+//! and primary keys stored as `BIGINT`. primary keys need
+//! `#[sea_orm(primary_key, auto_increment = false)]`, see
+//! [`flake::sea_orm_ext`].
+//!
+//! ## ScyllaDB
+//!
+//! the `scylla` feature writes and reads snowflakes as CQL `bigint` for the
+//! scylla driver. u64 snowflakes are stored with the same bits so see
+//! [`flake::scylla_ext`] for how that affects ordering.
+//!
+//! ## MongoDB
+//!
+//! the `bson` feature converts i64 snowflakes to and from `Bson` values and
+//! provides [`flake::bson_ext::id_range_filter`] for querying ids created
+//! during a time range.
+//!
+//! ## GraphQL
+//!
+//! the `juniper` feature exposes the i64 snowflakes as a `SnowflakeID`
+//! scalar that is written as a decimal string and accepts either a string or
+//! an integer as input. see [`flake::juniper_ext`] for details.
+//!
+//! ## C FFI
+//!
+//! the `snowcloud-ffi` crate in this workspace builds a `cdylib` with
+//! `extern "C"` functions for creating ids with a thread safe generator from
+//! C or C++. the header is at `snowcloud-ffi/include/snowcloud.h`.
+//!
+//! ## CLI
+//!
+//! the `cli` feature builds the `snowcloud` binary for decoding, encoding,
+//! and generating ids from the command line. layouts are given as the bits
+//! of each part, e.g. `43-8-12`.
+//!
+//! ```text
+//! cargo install snowcloud --features cli
+//! snowcloud decode 1052673 --layout 43-8-12 --epoch 1679587200000
+//! ```
 
 pub use snowcloud_core::traits;
+pub use snowcloud_core::display;
 pub use snowcloud_flake as flake;
 pub use snowcloud_cloud as cloud;
+
+pub use snowcloud_flake::{i64, u64, u32, i128, u128, presets, Segments};
+#[cfg(feature = "serde")]
+pub use snowcloud_flake::serde_ext;
+
+pub use snowcloud_cloud::{Generator, sync, wait, error::Error};
+
+/// commonly used types and traits
+///
+/// ```rust
+/// use snowcloud::prelude::*;
+///
+/// type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+///
+/// const START_TIME: u64 = 1679587200000;
+///
+/// let cloud = MutexGenerator::<MyFlake>::new(START_TIME, 1).unwrap();
+/// let flake = wait::try_next_id(&cloud, 2).unwrap();
+///
+/// assert_eq!(flake.primary_id(), 1);
+/// assert!(flake.id() > 0);
+/// ```
+pub mod prelude {
+    pub use snowcloud_core::traits::{Id, IdGenerator, IdGeneratorMut, NextAvailId};
+    pub use snowcloud_cloud::{Generator, sync::MutexGenerator, wait};
+    pub use snowcloud_cloud::error::Error;
+}
+
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+struct ReadmeDoctests;
@@ -10,11 +10,8 @@ fn sanity_check() {
     println!("{}", gen.ids());
 
     for _ in 0..(MyFlake::MAX_SEQUENCE * 3) {
-        let Some(result) = snowcloud::cloud::wait::blocking_next_id_mut(&mut gen, 2) else {
-            panic!("ran out of attempts to get a new snowflake");
-        };
-
-        let flake = result.expect("failed to generate snowflake");
+        let flake = snowcloud::cloud::wait::try_next_id_mut(&mut gen, 2)
+            .expect("failed to generate snowflake");
 
         println!("{}", flake.id());
     }
@@ -35,11 +32,10 @@ fn threaded_sanity_check() {
 
         threads.push(std::thread::spawn(move || {
             for _ in 0..(MyFlake::MAX_SEQUENCE * 3) {
-                let Some(result) = snowcloud::cloud::wait::blocking_next_id(&local_gen, 3) else {
-                    panic!("ran out of attempts to get a new snowflake");
-                };
-
-                result.expect("failed to generate snowflake");
+                // threads compete for the same millisecond so give enough
+                // attempts for a thread that keeps losing
+                snowcloud::cloud::wait::try_next_id(&local_gen, 16)
+                    .expect("failed to generate snowflake");
             }
         }));
     }
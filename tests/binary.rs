@@ -0,0 +1,99 @@
+//! sizes of snowflakes in binary serde formats
+//!
+//! the default impls write the id as an integer so the size depends on how
+//! the format encodes integers. `fixed_bytes_id` always writes the same
+//! number of bytes.
+
+#![cfg(feature = "serde")]
+
+use bincode::Options;
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+
+use snowcloud::serde_ext::fixed_bytes_id;
+
+type I64SID = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+type I64DID = snowcloud::i64::DualIdFlake<43, 4, 4, 12>;
+type U64SID = snowcloud::u64::SingleIdFlake<44, 8, 12>;
+type U64DID = snowcloud::u64::DualIdFlake<44, 4, 4, 12>;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Default<F> {
+    id: F,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Fixed<F>
+where
+    F: snowcloud::traits::Id + TryFrom<F::BaseType>,
+    F::BaseType: snowcloud::serde_ext::BeBytes,
+{
+    #[serde(with = "fixed_bytes_id")]
+    id: F,
+}
+
+fn bincode_fixint<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(value: &T) -> usize {
+    let options = bincode::DefaultOptions::new().with_fixint_encoding();
+    let bytes = options.serialize(value).unwrap();
+
+    assert_eq!(&options.deserialize::<T>(&bytes).unwrap(), value);
+
+    bytes.len()
+}
+
+fn bincode_varint<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(value: &T) -> usize {
+    let options = bincode::DefaultOptions::new().with_varint_encoding();
+    let bytes = options.serialize(value).unwrap();
+
+    assert_eq!(&options.deserialize::<T>(&bytes).unwrap(), value);
+
+    bytes.len()
+}
+
+fn postcard<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(value: &T) -> usize {
+    let bytes = postcard::to_allocvec(value).unwrap();
+
+    assert_eq!(&postcard::from_bytes::<T>(&bytes).unwrap(), value);
+
+    bytes.len()
+}
+
+macro_rules! sizes {
+    ($name:ident, $flake:ty, $small:expr) => {
+        #[test]
+        fn $name() {
+            for flake in [<$flake>::NIL, $small, <$flake>::MAX] {
+                // fixed int encoding always uses the size of the integer
+                assert_eq!(bincode_fixint(&Default { id: flake }), 8);
+
+                // variable length encodings depend on the value
+                let varint = bincode_varint(&Default { id: flake });
+                let post = postcard(&Default { id: flake });
+
+                if flake == <$flake>::MAX {
+                    assert!(varint > 8, "{} bytes for {}", varint, flake);
+                } else {
+                    assert!(varint < 8, "{} bytes for {}", varint, flake);
+                    assert!(post < 8, "{} bytes for {}", post, flake);
+                }
+
+                assert_eq!(bincode_fixint(&Fixed { id: flake }), 8);
+                assert_eq!(bincode_varint(&Fixed { id: flake }), 8);
+                assert_eq!(postcard(&Fixed { id: flake }), 8);
+            }
+        }
+    };
+}
+
+sizes!(i64_single, I64SID, I64SID::from_parts(1, 1, 1).unwrap());
+sizes!(i64_dual, I64DID, I64DID::from_parts(1, 1, 1, 1).unwrap());
+sizes!(u64_single, U64SID, U64SID::from_parts(1, 1, 1).unwrap());
+sizes!(u64_dual, U64DID, U64DID::from_parts(1, 1, 1, 1).unwrap());
+
+#[test]
+fn fixed_bytes_are_big_endian() {
+    let flake = I64SID::from_parts(1, 1, 1).unwrap();
+    let expected = 1052673i64.to_be_bytes();
+
+    assert_eq!(postcard::to_allocvec(&Fixed { id: flake }).unwrap(), expected);
+    assert_eq!(bincode::serialize(&Fixed { id: flake }).unwrap(), expected);
+}
@@ -0,0 +1,96 @@
+//! grouping generated snowflakes into time buckets
+
+use std::collections::BTreeMap;
+
+type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+type MyCloud = snowcloud::Generator<MyFlake>;
+
+const START_TIME: u64 = 1679587200000;
+const BUCKET_MILLIS: u64 = 5;
+
+fn generate(gen: &mut MyCloud) -> MyFlake {
+    snowcloud::wait::try_next_id_mut(gen, 4).expect("failed to generate snowflake")
+}
+
+/// generates ids until the given number of buckets have passed since the
+/// first id
+fn generate_across(gen: &mut MyCloud, buckets: i64) -> Vec<MyFlake> {
+    let first = generate(gen);
+    let mut flakes = vec![first];
+
+    while flakes.last().unwrap().timestamp() < first.timestamp() + BUCKET_MILLIS as i64 * buckets {
+        flakes.push(generate(gen));
+    }
+
+    flakes
+}
+
+#[test]
+fn same_tick() {
+    let mut gen = MyCloud::new(START_TIME, 1).unwrap();
+
+    let mut flakes = Vec::with_capacity(64);
+
+    for _ in 0..flakes.capacity() {
+        flakes.push(generate(&mut gen));
+    }
+
+    for pair in flakes.windows(2) {
+        if pair[0].same_tick(&pair[1]) {
+            assert_eq!(pair[0].bucket(1).unwrap(), pair[1].bucket(1).unwrap());
+            assert_eq!(pair[0].bucket(BUCKET_MILLIS).unwrap(), pair[1].bucket(BUCKET_MILLIS).unwrap());
+        }
+    }
+}
+
+#[test]
+fn adjacent_buckets() {
+    let mut gen = MyCloud::new(START_TIME, 1).unwrap();
+    let flakes = generate_across(&mut gen, 3);
+
+    let ticks: BTreeMap<i64, u64> = flakes.iter()
+        .map(|flake| (flake.timestamp(), flake.bucket(BUCKET_MILLIS).unwrap()))
+        .collect();
+    let mut checked = 0;
+
+    for (tick, bucket) in &ticks {
+        if let Some(later) = ticks.get(&(tick + BUCKET_MILLIS as i64)) {
+            assert_eq!(*later, bucket + 1);
+
+            checked += 1;
+        }
+    }
+
+    assert!(checked > 0);
+}
+
+#[test]
+fn bucket_ranges() {
+    let mut gen = MyCloud::new(START_TIME, 1).unwrap();
+    let mut buckets: BTreeMap<u64, Vec<MyFlake>> = BTreeMap::new();
+
+    let flakes = generate_across(&mut gen, 3);
+
+    for flake in &flakes {
+        buckets.entry(flake.bucket(BUCKET_MILLIS).unwrap())
+            .or_default()
+            .push(*flake);
+    }
+
+    assert!(buckets.len() >= 3);
+
+    for (bucket, in_bucket) in &buckets {
+        let range = MyFlake::bucket_id_range(*bucket, BUCKET_MILLIS).unwrap();
+        let contained: Vec<MyFlake> = flakes.iter()
+            .filter(|flake| range.contains(&flake.id()))
+            .copied()
+            .collect();
+
+        assert_eq!(&contained, in_bucket);
+    }
+
+    assert!(matches!(
+        MyFlake::bucket_id_range(1, 0),
+        Err(snowcloud::flake::error::Error::BucketInvalid)
+    ));
+}
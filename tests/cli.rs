@@ -0,0 +1,117 @@
+//! runs the snowcloud binary against known ids
+
+#![cfg(feature = "cli")]
+
+use assert_cmd::Command;
+
+const START_TIME: u64 = 1679587200000;
+
+type MyFlake = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+
+fn snowcloud(args: &[&str]) -> Command {
+    let mut cmd = Command::cargo_bin("snowcloud").unwrap();
+    cmd.args(args);
+    cmd
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = snowcloud(args).assert().success().get_output().stdout.clone();
+
+    String::from_utf8(output).unwrap()
+}
+
+fn stderr(args: &[&str]) -> String {
+    let output = snowcloud(args).assert().failure().get_output().stderr.clone();
+
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn decode() {
+    let epoch = START_TIME.to_string();
+
+    assert_eq!(stdout(&["decode", "1052673", "--layout", "43-8-12", "--epoch", &epoch]), "\
+id         1052673
+layout     43-8-12
+timestamp  1
+created    2023-03-23T16:00:00.001Z
+pid        1
+seq        1
+");
+
+    // leap day with every other part at its max value
+    assert_eq!(stdout(&["decode", "1792279786291199999", "--epoch", "0"]), "\
+id         1792279786291199999
+layout     43-8-12
+timestamp  1709251199999
+created    2024-02-29T23:59:59.999Z
+pid        255
+seq        4095
+");
+
+    // a twitter id without an epoch only shows the raw timestamp
+    assert_eq!(stdout(&["decode", "1212092628029698048", "--layout", "41-5-5-12"]), "\
+id         1212092628029698048
+layout     41-5-5-12
+timestamp  288985402114
+pid        10
+sid        7
+seq        0
+");
+
+    let twitter = stdout(&[
+        "decode", "1212092628029698048",
+        "--layout", "41-5-5-12",
+        "--epoch", &snowcloud::presets::twitter::EPOCH_MILLIS.to_string(),
+    ]);
+
+    assert!(twitter.contains("created    2019-12-31T19:26:16.771Z\n"), "{}", twitter);
+}
+
+#[test]
+fn encode() {
+    assert_eq!(stdout(&["encode", "--ts", "1", "--pid", "1", "--seq", "1"]), "1052673\n");
+    assert_eq!(
+        stdout(&["encode", "--layout", "43-4-4-12", "--ts", "1", "--pid", "2", "--sid", "3", "--seq", "4"]),
+        format!("{}\n", snowcloud::i64::DualIdFlake::<43, 4, 4, 12>::from_parts(1, 2, 3, 4).unwrap().id())
+    );
+
+    assert!(stderr(&["encode", "--ts", "1"]).contains("--pid is required"));
+    assert!(stderr(&["encode", "--ts", "1", "--pid", "1", "--sid", "1"]).contains("does not have a sid segment"));
+    assert!(stderr(&["encode", "--ts", "1", "--pid", "256"]).contains("invalid parts"));
+}
+
+#[test]
+fn gen() {
+    let epoch = START_TIME.to_string();
+    let output = stdout(&["gen", "--count", "5000", "--layout", "43-8-12", "--epoch", &epoch, "--pid", "7"]);
+
+    let flakes: Vec<MyFlake> = output.lines()
+        .map(|line| line.parse().unwrap())
+        .collect();
+
+    assert_eq!(flakes.len(), 5000);
+
+    for pair in flakes.windows(2) {
+        assert!(pair[0] < pair[1]);
+    }
+
+    for flake in &flakes {
+        assert_eq!(flake.primary_id(), 7);
+    }
+
+    let output = stdout(&["gen", "--layout", "41-5-5-12", "--epoch", &epoch, "--pid", "1", "--sid", "2"]);
+    let flake: snowcloud::presets::TwitterFlake = output.trim().parse().unwrap();
+
+    assert_eq!((flake.primary_id(), flake.secondary_id()), (1, 2));
+
+    assert!(stderr(&["gen", "--layout", "40-10-12", "--epoch", &epoch, "--pid", "1"]).contains("not supported"));
+    assert!(stderr(&["gen", "--epoch", &epoch]).contains("--pid is required"));
+}
+
+#[test]
+fn invalid_arguments() {
+    assert!(stderr(&["decode", "--", "-5"]).contains("invalid id -5"));
+    assert!(stderr(&["decode", "1", "--layout", "43-9-12"]).contains("layout invalid"));
+    assert!(stderr(&["decode", "not-a-number"]).contains("invalid value"));
+}
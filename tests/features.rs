@@ -0,0 +1,107 @@
+//! checks that the optional modules and impls of snowcloud-flake are
+//! reachable through the facade crate when the features are enabled
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_ext_string_id() {
+    use serde_derive::{Serialize, Deserialize};
+
+    type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        #[serde(with = "snowcloud::flake::serde_ext::string_id")]
+        id: MyFlake,
+    }
+
+    let record = Record {
+        id: MyFlake::from_parts(1, 1, 1).unwrap(),
+    };
+
+    let json = serde_json::to_string(&record).unwrap();
+
+    assert_eq!(json, r#"{"id":"1052673"}"#);
+    assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn postgres_impls() {
+    use postgres_types::{ToSql, FromSql, Type};
+
+    type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+
+    let flake = MyFlake::from_parts(1, 1, 1).unwrap();
+    let mut bytes = bytes::BytesMut::new();
+
+    flake.to_sql(&Type::INT8, &mut bytes).unwrap();
+
+    assert_eq!(&bytes[..], &1052673i64.to_be_bytes());
+    assert_eq!(MyFlake::from_sql(&Type::INT8, &bytes).unwrap(), flake);
+    assert!(<MyFlake as ToSql>::accepts(&Type::INT8));
+    assert!(!<MyFlake as FromSql>::accepts(&Type::INT4));
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn postgres_arrays() {
+    use postgres_protocol::types::{array_from_sql, array_to_sql, int8_to_sql, ArrayDimension};
+    use postgres_types::{ToSql, FromSql, Type, IsNull};
+    use postgres_protocol::IsNull as ProtoIsNull;
+
+    type MyFlake = snowcloud::flake::i64::SingleIdFlake<43, 8, 12>;
+
+    let flakes = vec![
+        MyFlake::from_parts(1, 1, 1).unwrap(),
+        MyFlake::from_parts(2, 1, 1).unwrap(),
+        MyFlake::from_parts(3, 1, 1).unwrap(),
+    ];
+    let ints: Vec<i64> = flakes.iter().map(|f| f.id()).collect();
+
+    assert!(<Vec<MyFlake> as ToSql>::accepts(&Type::INT8_ARRAY));
+    assert!(<&[MyFlake] as ToSql>::accepts(&Type::INT8_ARRAY));
+    assert!(<Vec<MyFlake> as FromSql>::accepts(&Type::INT8_ARRAY));
+    assert!(!<Vec<MyFlake> as ToSql>::accepts(&Type::INT4_ARRAY));
+
+    // encode a parameter and check it against the plain i64 encoding
+    let mut bytes = bytes::BytesMut::new();
+
+    assert!(matches!(
+        flakes.to_sql(&Type::INT8_ARRAY, &mut bytes).unwrap(),
+        IsNull::No
+    ));
+
+    let array = array_from_sql(&bytes).unwrap();
+
+    assert_eq!(array.element_type(), Type::INT8.oid());
+    assert!(!array.has_nulls());
+
+    let mut expected = bytes::BytesMut::new();
+
+    ints.to_sql(&Type::INT8_ARRAY, &mut expected).unwrap();
+
+    assert_eq!(bytes, expected);
+
+    // build an INT8[] column with the wire format helpers and decode it
+    let mut column = bytes::BytesMut::new();
+
+    array_to_sql(
+        Some(ArrayDimension { len: ints.len() as i32, lower_bound: 1 }),
+        Type::INT8.oid(),
+        ints.iter(),
+        |int, buf| {
+            int8_to_sql(*int, buf);
+            Ok(ProtoIsNull::No)
+        },
+        &mut column
+    ).unwrap();
+
+    assert_eq!(Vec::<MyFlake>::from_sql(&Type::INT8_ARRAY, &column).unwrap(), flakes);
+
+    // negative values are not valid snowflakes
+    let mut invalid = bytes::BytesMut::new();
+
+    vec![-1i64].to_sql(&Type::INT8_ARRAY, &mut invalid).unwrap();
+
+    assert!(Vec::<MyFlake>::from_sql(&Type::INT8_ARRAY, &invalid).is_err());
+}
@@ -0,0 +1,100 @@
+//! using snowflakes as the primary key of a SeaORM entity with an in memory
+//! sqlite database
+
+#![cfg(feature = "sea-orm")]
+
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection, Schema};
+use sea_orm::entity::prelude::*;
+use sea_orm::ActiveValue::Set;
+
+type RecordId = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+type ParentId = snowcloud::i64::DualIdFlake<43, 4, 4, 12>;
+type RecordCloud = snowcloud::Generator<RecordId>;
+
+const START_TIME: u64 = 1679587200000;
+
+mod record {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "records")]
+    pub struct Model {
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: super::RecordId,
+        pub parent: Option<super::ParentId>,
+        pub name: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+async fn connect() -> DatabaseConnection {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    let schema = Schema::new(db.get_database_backend());
+    let stmt = db.get_database_backend()
+        .build(&schema.create_table_from_entity(record::Entity));
+
+    db.execute(stmt).await.unwrap();
+
+    db
+}
+
+#[tokio::test]
+async fn find_by_id() {
+    let db = connect().await;
+    let mut cloud = RecordCloud::new(START_TIME, 1).unwrap();
+
+    let id = cloud.next_id().unwrap();
+    let parent = ParentId::from_parts(1, 1, 1, 1).unwrap();
+
+    let inserted = record::ActiveModel {
+        id: Set(id),
+        parent: Set(Some(parent)),
+        name: Set("first".to_owned()),
+    }.insert(&db).await.unwrap();
+
+    assert_eq!(inserted.id, id);
+
+    let found = record::Entity::find_by_id(id)
+        .one(&db)
+        .await
+        .unwrap()
+        .expect("record was not found");
+
+    assert_eq!(found, record::Model {
+        id,
+        parent: Some(parent),
+        name: "first".to_owned(),
+    });
+
+    let missing = record::Entity::find_by_id(cloud.next_id().unwrap())
+        .one(&db)
+        .await
+        .unwrap();
+
+    assert!(missing.is_none());
+
+    let by_parent = record::Entity::find()
+        .filter(record::Column::Parent.eq(parent))
+        .all(&db)
+        .await
+        .unwrap();
+
+    assert_eq!(by_parent, vec![found]);
+}
+
+#[tokio::test]
+async fn invalid_id() {
+    let db = connect().await;
+
+    db.execute_unprepared("insert into records (id, name) values (-1, 'invalid')")
+        .await
+        .unwrap();
+
+    let result = record::Entity::find().one(&db).await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,84 @@
+//! binding and fetching snowflakes with sqlx using an in memory sqlite
+//! database
+
+#![cfg(feature = "sqlx")]
+
+use sqlx::{Connection, Row, SqliteConnection};
+
+type I64SID = snowcloud::i64::SingleIdFlake<43, 8, 12>;
+type I64DID = snowcloud::i64::DualIdFlake<43, 4, 4, 12>;
+type U64SID = snowcloud::u64::SingleIdFlake<44, 8, 12>;
+
+type Record = (I64SID, Option<I64DID>, U64SID);
+
+async fn connect() -> SqliteConnection {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+
+    sqlx::query("create table records (id integer primary key, parent integer, external integer not null)")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    conn
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let mut conn = connect().await;
+
+    let record: Record = (
+        I64SID::from_parts(1, 1, 1).unwrap(),
+        Some(I64DID::from_parts(1, 1, 1, 1).unwrap()),
+        U64SID::MAX,
+    );
+
+    sqlx::query("insert into records (id, parent, external) values (?, ?, ?)")
+        .bind(record.0)
+        .bind(record.1)
+        .bind(record.2)
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    let fetched: Record = sqlx::query_as("select id, parent, external from records where id = ?")
+        .bind(record.0)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(fetched, record);
+
+    let id: I64SID = sqlx::query_scalar("select id from records")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(id, record.0);
+
+    // i64 flakes are stored as their id and u64 flakes as the same bits
+    let row = sqlx::query("select id, external from records")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(row.get::<i64, _>("id"), record.0.id());
+    assert_eq!(row.get::<i64, _>("external"), record.2.id() as i64);
+}
+
+#[tokio::test]
+async fn invalid_id() {
+    let mut conn = connect().await;
+
+    sqlx::query("insert into records (id, external) values (?, ?)")
+        .bind(-1i64)
+        .bind(1i64)
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    let result = sqlx::query_scalar::<_, I64SID>("select id from records")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(result, Err(sqlx::Error::ColumnDecode { .. })));
+}